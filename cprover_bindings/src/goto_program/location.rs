@@ -20,6 +20,8 @@ pub enum Location {
         start_col: Option<u64>,
         end_line: u64,
         end_col: Option<u64>,
+        /// CBMC pragmas that apply to the code at this location, e.g. `disable:bounds-check`.
+        pragmas: &'static [&'static str],
     },
     /// Location for Statements that use Property Class and Description - Assert, Assume, Cover etc.
     Property {
@@ -71,6 +73,13 @@ pub fn end_line(&self) -> Option<u64> {
         }
     }
 
+    pub fn end_col(&self) -> Option<u64> {
+        match self {
+            Location::Loc { end_col, .. } => *end_col,
+            _ => None,
+        }
+    }
+
     /// Convert a location to a short string suitable for (e.g.) logging.
     /// Goal is to return just "file:line" as clearly as possible.
     pub fn short_string(&self) -> String {
@@ -118,6 +127,17 @@ pub fn new<T, U: Into<InternedString>, V: Into<InternedString>>(
             start_col: start_col_into,
             end_line: end_line_into,
             end_col: end_col_into,
+            pragmas: &[],
+        }
+    }
+
+    /// Add the given CBMC pragmas to a location in user code. Other locations are unchanged.
+    pub fn with_pragmas(self, pragmas: &'static [&'static str]) -> Location {
+        match self {
+            Location::Loc { file, function, start_line, start_col, end_line, end_col, .. } => {
+                Location::Loc { file, function, start_line, start_col, end_line, end_col, pragmas }
+            }
+            _ => self,
         }
     }
 
@@ -159,7 +179,7 @@ pub fn create_location_with_property<T: Into<InternedString>>(
                 comment.into(),
                 property_name.into(),
             ),
-            Location::Loc { file, function, start_line, start_col, end_line: _, end_col: _ } => {
+            Location::Loc { file, function, start_line, start_col, .. } => {
                 Location::property_location(
                     file.into(),
                     function.intern(),
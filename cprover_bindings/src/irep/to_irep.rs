@@ -361,13 +361,25 @@ fn to_irep(&self, _mm: &MachineModel) -> Irep {
                 (IrepId::Function, Irep::just_string_id(function_name.to_string())),
             ])
             .with_named_sub_option(IrepId::Line, line.map(Irep::just_int_id)),
-            Location::Loc { file, function, start_line, start_col, end_line: _, end_col: _ } => {
+            Location::Loc { file, function, start_line, start_col, pragmas, .. } => {
+                // CBMC stores each pragma as a named sub of the `pragma` irep, with an empty value.
+                let pragmas = (!pragmas.is_empty()).then(|| {
+                    Irep::just_named_sub(
+                        pragmas
+                            .iter()
+                            .map(|pragma| {
+                                (IrepId::from_string(*pragma), Irep::just_id(IrepId::EmptyString))
+                            })
+                            .collect(),
+                    )
+                });
                 Irep::just_named_sub(linear_map![
                     (IrepId::File, Irep::just_string_id(file.to_string())),
                     (IrepId::Line, Irep::just_int_id(*start_line)),
                 ])
                 .with_named_sub_option(IrepId::Column, start_col.map(Irep::just_int_id))
                 .with_named_sub_option(IrepId::Function, function.map(Irep::just_string_id))
+                .with_named_sub_option(IrepId::Pragma, pragmas)
             }
             Location::Property { file, function, line, col, property_class, comment } => {
                 Irep::just_named_sub(linear_map![
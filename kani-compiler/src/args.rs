@@ -1,6 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use kani_metadata::MmioRegion;
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 use tracing_subscriber::filter::Directive;
 
@@ -25,18 +26,33 @@ pub struct Arguments {
     /// Option name used to enable assertion reachability checks.
     #[clap(long = "assertion-reach-checks")]
     pub check_assertion_reachability: bool,
+    /// Option name used to record where each check ends in the source code. This adds a unique
+    /// identifier to the description of the checks, which the driver removes.
+    #[clap(long = "check-span-ends")]
+    pub record_check_span_ends: bool,
     /// Option name used to enable coverage checks.
     #[clap(long = "coverage-checks")]
     pub check_coverage: bool,
     /// Option name used to dump function pointer restrictions.
     #[clap(long = "restrict-vtable-fn-ptrs")]
     pub emit_vtable_restrictions: bool,
+    /// Option name used to dump the possible targets of calls through function pointers.
+    #[clap(long = "restrict-fn-ptrs")]
+    pub emit_fn_ptr_restrictions: bool,
+    /// Option used to accept crates compiled without overflow checks (`-C overflow-checks=off`),
+    /// which Kani verifies with wrapping arithmetic semantics.
+    #[clap(long = "wrapping-overflow")]
+    pub wrapping_overflow: bool,
     /// Option name used to use json pretty-print for output files.
     #[clap(long = "pretty-json-files")]
     pub output_pretty_json: bool,
     /// Option used for suppressing global ASM error.
     #[clap(long)]
     pub ignore_global_asm: bool,
+    /// Option used to drop the assumptions made in the local crate, so the driver can check
+    /// which assertions only hold because of them (`-Z assertion-contest`).
+    #[clap(long)]
+    pub ignore_user_assumptions: bool,
     #[clap(long)]
     /// Option used to write JSON symbol tables instead of GOTO binaries.
     ///
@@ -47,6 +63,16 @@ pub struct Arguments {
     pub reachability_analysis: ReachabilityType,
     #[clap(long = "enable-stubbing")]
     pub stubbing_enabled: bool,
+    /// Option used to declare an address range of memory-mapped I/O registers.
+    #[clap(long = "mmio-region")]
+    pub mmio_regions: Vec<MmioRegion>,
+    /// Option used to compile the given crates or modules with their checks disabled when
+    /// `-Z trusted-code` is enabled.
+    #[clap(long = "trusted")]
+    pub trusted_paths: Vec<String>,
+    /// Option used to bound the number of interrupts when `-Z interrupts` is enabled.
+    #[clap(long, default_value = "1")]
+    pub max_interrupts: u64,
     /// Option name used to define unstable features.
     #[clap(short = 'Z', long = "unstable")]
     pub unstable_features: Vec<String>,
@@ -11,6 +11,9 @@
 //! 4. A Kani `cover` is a CBMC `assert(!cond)`, that we treat specially in our cbmc output handler.
 //!    (We do not use cbmc's notion of a `cover`.)
 //!
+//! With `-Z assumption-checks`, each `kani::assume` is also followed by a cover-like
+//! `assumption_check` (see [GotocCtx::codegen_checked_assume]).
+//!
 //! Kani further offers a few special cases:
 //!
 //! 5. `codegen_unimplemented_{stmt,expr}` : `assert(false)` but recorded specially
@@ -21,6 +24,9 @@
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
 use cbmc::InternedString;
+use kani_metadata::SpanEnd;
+use rustc_lexer::{tokenize, LiteralKind, TokenKind};
+use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::Span;
 use std::convert::AsRef;
 use strum_macros::{AsRefStr, EnumString};
@@ -37,10 +43,22 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None TODO: Why should this exist?
     ArithmeticOverflow,
+    /// Checks added with `-Z aliasing` that fail if a mutable reference aliases another reference
+    /// passed to the same function call.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under
+    /// Kani). Only emitted when the check is requested.
+    Aliasing,
     /// The Rust `assume` instrinsic is `assert`'d by Kani, and gets this property class.
     ///
     /// SPECIAL BEHAVIOR: None? Possibly confusing to customers that a Rust assume is a Kani assert.
     Assume,
+    /// Marks an assumption created with `kani::assume!`. Encoded as `assert(false)` right before
+    /// the assumption so that a failure means the assumption is reachable.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
+    /// These checks are removed from the verification results and reported in the assumption report.
+    Assumption,
     /// See [GotocCtx::codegen_cover] below. Generally just an `assert(false)` that's not an error.
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
@@ -58,6 +76,42 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
     CodeCoverage,
+    /// See [GotocCtx::codegen_checked_assume] below. An `assert(false)` placed right after a
+    /// `kani::assume`, paired with a reachability check placed right before it.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
+    /// If the assumption is reachable but this check is not, the assumption is contradictory.
+    AssumptionCheck,
+    /// Checks added in constant-time mode (`-Z constant-time`) where a branch condition or a
+    /// memory address depends on a value created with `kani::secret()`.
+    ///
+    /// SPECIAL BEHAVIOR: These are `assert(false)` checks that fail if the offending construct is
+    /// reachable. They do not exist when running code normally (i.e. not under Kani).
+    ConstantTime,
+    /// Checks created with `kani::self_blocking_check` by the models of `kani::sync`, which fail
+    /// if the current thread would wait forever for itself, e.g. when it locks a mutex that it
+    /// already holds.
+    ///
+    /// SPECIAL BEHAVIOR: The driver reports failures of these checks as calls that block forever.
+    SelfBlocking,
+    /// Checks added with `-Z drop-checks` that fail if a value is dropped more than once.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under
+    /// Kani). Only emitted when the check is requested.
+    Drop,
+    /// Checks added with `-Z enum-validity-checks` that fail if the tag of an enum whose
+    /// discriminant is read doesn't encode any of its variants.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under
+    /// Kani). Only emitted when the check is requested.
+    #[strum(serialize = "enum-validity")]
+    EnumValidity,
+    /// Marks an expression wrapped by `kani::expect_fail!`. Encoded as `assert(true)` with the
+    /// span of the whole expression.
+    ///
+    /// SPECIAL BEHAVIOR: The driver uses these checks to find the failures that are expected.
+    /// They fail if none of the checks of the expression fails.
+    ExpectedFailure,
     /// Ordinary (Rust) assertions and panics.
     ///
     /// SPECIAL BEHAVIOR: These assertion failures should be observable during normal execution of Rust code.
@@ -72,6 +126,12 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None TODO: Why should this exist?
     FiniteCheck,
+    /// Checks added with `-Z provenance-checks` to integer-to-pointer casts, which fail if the
+    /// resulting pointer isn't derived from any live allocation.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under
+    /// Kani). Only emitted when the check is requested.
+    Provenance,
     /// Checks added by Kani compiler to determine whether a property (e.g.
     /// `PropertyClass::Assertion` or `PropertyClass:Cover`) is reachable
     ReachabilityCheck,
@@ -102,10 +162,34 @@ impl PropertyClass {
     pub fn as_str(&self) -> &str {
         self.as_ref()
     }
+
+    /// Whether the checks of this class are disabled in trusted code, i.e. whether they only fail
+    /// if the code is incorrect. The checks that report reachability, the sanity checks and the
+    /// unsupported constructs are kept, since they are about the model rather than the code.
+    pub fn is_disabled_in_trusted_code(&self) -> bool {
+        !matches!(
+            self,
+            PropertyClass::Assumption
+                | PropertyClass::Cover
+                | PropertyClass::CodeCoverage
+                | PropertyClass::AssumptionCheck
+                | PropertyClass::ExpectedFailure
+                | PropertyClass::ReachabilityCheck
+                | PropertyClass::SanityCheck
+                | PropertyClass::UnsupportedConstruct
+        )
+    }
 }
 
 impl<'tcx> GotocCtx<'tcx> {
+    /// Whether the current function is trusted, i.e. compiled with its checks disabled.
+    pub fn in_trusted_fn(&self) -> bool {
+        self.current_fn.as_ref().is_some_and(|current_fn| current_fn.is_trusted())
+    }
+
     /// Generates a CBMC assertion. Note: Does _NOT_ assume.
+    ///
+    /// In trusted code, checks are removed, so they don't restrict the executions either.
     pub fn codegen_assert(
         &self,
         cond: Expr,
@@ -113,6 +197,9 @@ pub fn codegen_assert(
         message: &str,
         loc: Location,
     ) -> Stmt {
+        if self.in_trusted_fn() && property_class.is_disabled_in_trusted_code() {
+            return Stmt::skip(loc);
+        }
         let property_name = property_class.as_str();
         Stmt::assert(cond, property_name, message, loc)
     }
@@ -123,6 +210,8 @@ pub fn codegen_assume(&self, cond: Expr, loc: Location) -> Stmt {
     }
 
     /// Generates a CBMC assertion, followed by an assumption of the same condition.
+    ///
+    /// In trusted code, checks are only assumed, since the execution stops where they fail.
     pub fn codegen_assert_assume(
         &self,
         cond: Expr,
@@ -130,6 +219,9 @@ pub fn codegen_assert_assume(
         message: &str,
         loc: Location,
     ) -> Stmt {
+        if self.in_trusted_fn() && property_class.is_disabled_in_trusted_code() {
+            return self.codegen_assume(cond, loc);
+        }
         let property_name = property_class.as_str();
         Stmt::block(
             vec![Stmt::assert(cond.clone(), property_name, message, loc), Stmt::assume(cond, loc)],
@@ -147,6 +239,35 @@ pub fn codegen_cover(&self, cond: Expr, msg: &str, span: Option<Span>) -> Stmt {
         self.codegen_assert(cond.not(), PropertyClass::Cover, msg, loc)
     }
 
+    /// Generates a CBMC assumption. If `-Z assumption-checks` is enabled, the
+    /// assumption is surrounded by a reachability check and an `assumption_check`,
+    /// which together tell whether this assumption rules out every execution that
+    /// reaches it.
+    pub fn codegen_checked_assume(&mut self, cond: Expr, msg: &str, span: Option<Span>) -> Stmt {
+        let loc = self.codegen_caller_span(&span);
+        if !self.queries.args().unstable_features.contains(&"assumption-checks".to_string()) {
+            return self.codegen_assume(cond, loc);
+        }
+        let (msg, reach_stmt) = self.codegen_reachability_check(msg.to_string(), span);
+        Stmt::block(
+            vec![
+                reach_stmt,
+                self.codegen_assume(cond, loc),
+                self.codegen_assert(Expr::bool_false(), PropertyClass::AssumptionCheck, &msg, loc),
+            ],
+            loc,
+        )
+    }
+
+    /// Whether the assumption made by the current function should be dropped. Only assumptions
+    /// in the local crate are dropped, so the ones made by the Kani library (e.g., by
+    /// `kani::any_where` or by the safety constraints assumed by a derived `Arbitrary`) still
+    /// restrict the values of the inputs.
+    pub fn ignore_assumption(&self) -> bool {
+        self.queries.args().ignore_user_assumptions
+            && self.current_fn().instance().def_id().is_local()
+    }
+
     /// Generate a cover statement for code coverage reports.
     pub fn codegen_coverage(&self, span: Span) -> Stmt {
         let loc = self.codegen_caller_span(&Some(span));
@@ -169,37 +290,47 @@ pub fn codegen_coverage(&self, span: Span) -> Stmt {
     /// meant to check whether the property is reachable. The function returns a
     /// modified version of the provided message that should be used for the
     /// property to allow the CBMC output parser to pair the property with its
-    /// reachability check.
-    /// If reachability checks are disabled, the function returns the message
-    /// unmodified and an empty (skip) statement.
+    /// reachability check, and with the end of its source span.
+    /// If reachability checks are disabled, the returned statement is empty (skip), and the
+    /// message is only modified if the end of the span is recorded (`--check-span-ends`). In
+    /// trusted code, where the properties are not checked, the message is returned unmodified.
     pub fn codegen_reachability_check(
         &mut self,
         msg: String,
         span: Option<Span>,
     ) -> (String, Stmt) {
         let loc = self.codegen_caller_span(&span);
-        if self.queries.args().check_assertion_reachability {
-            // Generate a unique ID for the assert
-            let assert_id = self.next_check_id();
-            // Also add the unique ID as a prefix to the assert message so that it can be
-            // easily paired with the reachability check
-            let msg = GotocCtx::add_prefix_to_msg(&msg, &assert_id);
-            // Generate a message for the reachability check that includes the unique ID
-            let reach_msg = assert_id;
-            // inject a reachability check, which is a (non-blocking)
-            // assert(false) whose failure indicates that this line is reachable.
-            // The property class (`PropertyClass:ReachabilityCheck`) is used by
-            // the CBMC output parser to distinguish those checks from others.
-            let check = self.codegen_assert(
-                Expr::bool_false(),
-                PropertyClass::ReachabilityCheck,
-                &reach_msg,
-                loc,
-            );
-            (msg, check)
-        } else {
-            (msg, Stmt::skip(loc))
+        let args = self.queries.args();
+        let check_reachability = args.check_assertion_reachability;
+        if !(check_reachability || args.record_check_span_ends) || self.in_trusted_fn() {
+            return (msg, Stmt::skip(loc));
         }
+        // Generate a unique ID for the assert
+        let assert_id = self.next_check_id();
+        // Record where the property ends, since only its start is reported by CBMC.
+        if let (Some(line), Some(column)) = (loc.end_line(), loc.end_col()) {
+            let end = SpanEnd { line: line as usize, column: column as usize };
+            self.check_span_ends.insert(assert_id.clone(), end);
+        }
+        // Also add the unique ID as a prefix to the assert message so that it can be
+        // easily paired with the reachability check
+        let msg = GotocCtx::add_prefix_to_msg(&msg, &assert_id);
+        if !check_reachability {
+            return (msg, Stmt::skip(loc));
+        }
+        // Generate a message for the reachability check that includes the unique ID
+        let reach_msg = assert_id;
+        // inject a reachability check, which is a (non-blocking)
+        // assert(false) whose failure indicates that this line is reachable.
+        // The property class (`PropertyClass:ReachabilityCheck`) is used by
+        // the CBMC output parser to distinguish those checks from others.
+        let check = self.codegen_assert(
+            Expr::bool_false(),
+            PropertyClass::ReachabilityCheck,
+            &reach_msg,
+            loc,
+        );
+        (msg, check)
     }
 
     /// A shorthand for generating a CBMC assert-assume(false)
@@ -224,15 +355,61 @@ pub fn codegen_fatal_error(
     }
 
     /// Kani hooks function calls to `panic` and calls this intead.
+    ///
+    /// Panics are reported in trusted code too, otherwise the executions where the trusted code
+    /// panics would be silently discarded.
     pub fn codegen_panic(&self, span: Option<Span>, fargs: Vec<Expr>) -> Stmt {
+        let msg = self.panic_message(&fargs, span);
+        let loc = self.codegen_caller_span(&span);
+        let property_name = PropertyClass::Assertion.as_str();
+        Stmt::block(
+            vec![
+                Stmt::assert(Expr::bool_false(), property_name, &msg, loc),
+                Stmt::assume(Expr::bool_false(), loc),
+            ],
+            loc,
+        )
+    }
+
+    /// Generate the assertion for a panic that unwinds the stack instead of aborting.
+    /// The caller is responsible for starting the unwinding. Like [Self::codegen_panic], the
+    /// assertion is kept in trusted code.
+    pub fn codegen_unwinding_panic(&self, span: Option<Span>, fargs: &[Expr]) -> Stmt {
+        let msg = self.panic_message(fargs, span);
+        let loc = self.codegen_caller_span(&span);
+        Stmt::assert(Expr::bool_false(), PropertyClass::Assertion.as_str(), &msg, loc)
+    }
+
+    fn panic_message(&self, fargs: &[Expr], span: Option<Span>) -> String {
         // CBMC requires that the argument to the assertion must be a string constant.
-        // If there is one in the MIR, use it; otherwise, explain that we can't.
+        // If there is one in the MIR, use it; otherwise, fall back to the format string of the
+        // message, or explain that we can't.
         assert!(!fargs.is_empty(), "Panic requires a string message");
-        let msg = self.extract_const_message(&fargs[0]).unwrap_or(String::from(
-            "This is a placeholder message; Kani doesn't support message formatted at runtime",
-        ));
+        self.extract_const_message(&fargs[0]).or_else(|| self.panic_format_string(span?)).unwrap_or(
+            String::from(
+                "This is a placeholder message; Kani doesn't support message formatted at runtime",
+            ),
+        )
+    }
 
-        self.codegen_fatal_error(PropertyClass::Assertion, &msg, span)
+    /// Recover the format string of a panic message that is formatted at runtime, e.g.
+    /// `unreachable!("unexpected value {}", x)`, from the source code of the macro call that
+    /// created it.
+    ///
+    /// The panic macro may be called by other macros, e.g. a user macro that calls `panic!`, so
+    /// we walk the macro backtrace from the innermost expansion until we find the call to a panic
+    /// macro whose arguments start with a format string. The outermost call site would be the
+    /// call to the user macro instead.
+    fn panic_format_string(&self, span: Span) -> Option<String> {
+        let source_map = self.tcx.sess.source_map();
+        span.macro_backtrace()
+            .filter(|expn| {
+                matches!(expn.kind, ExpnKind::Macro(MacroKind::Bang, name)
+                    if PANIC_MACROS.contains(&name.as_str()))
+            })
+            .find_map(|expn| {
+                format_string_literal(&source_map.span_to_snippet(expn.call_site).ok()?)
+            })
     }
 
     /// Kani does not currently support all MIR constructs.
@@ -330,3 +507,81 @@ pub fn codegen_sanity(&self, cond: Expr, message: &str, loc: Location) -> Stmt {
         self.codegen_assert_assume(cond, PropertyClass::SanityCheck, &assert_msg, loc)
     }
 }
+
+/// The macros whose message is given by a format string and its arguments.
+const PANIC_MACROS: [&str; 10] = [
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "unreachable",
+    "todo",
+    "unimplemented",
+];
+
+/// Find the format string in the source code of a macro call, i.e., the first string literal
+/// passed as an argument to the macro. Literals nested inside other arguments are ignored, e.g.
+/// the format string of `assert!(s == "a", "unexpected {s}")` is `unexpected {s}`.
+fn format_string_literal(macro_call: &str) -> Option<String> {
+    let mut depth = 0;
+    let mut starts_argument = false;
+    let mut offset = 0;
+    for token in tokenize(macro_call) {
+        let text = &macro_call[offset..offset + token.len as usize];
+        offset += token.len as usize;
+        match token.kind {
+            TokenKind::Whitespace
+            | TokenKind::LineComment { .. }
+            | TokenKind::BlockComment { .. } => {
+                continue;
+            }
+            TokenKind::OpenParen | TokenKind::OpenBrace | TokenKind::OpenBracket => {
+                depth += 1;
+                starts_argument = depth == 1;
+                continue;
+            }
+            TokenKind::CloseParen | TokenKind::CloseBrace | TokenKind::CloseBracket => {
+                depth -= 1;
+            }
+            TokenKind::Comma => {
+                starts_argument = depth == 1;
+                continue;
+            }
+            TokenKind::Literal { kind: LiteralKind::Str { terminated: true }, .. }
+                if starts_argument =>
+            {
+                return Some(text[1..text.len() - 1].to_string());
+            }
+            _ => {}
+        }
+        starts_argument = false;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_string_literal;
+
+    #[test]
+    fn check_format_string_literal() {
+        assert_eq!(
+            format_string_literal(r#"unreachable!("unexpected value {}", x)"#).as_deref(),
+            Some("unexpected value {}")
+        );
+        assert_eq!(
+            format_string_literal(r#"assert!(s == "a", "unexpected {s}")"#).as_deref(),
+            Some("unexpected {s}")
+        );
+        assert_eq!(
+            format_string_literal(r#"core::panic!( /* "comment" */ "{}: {}", f("a"), b)"#)
+                .as_deref(),
+            Some("{}: {}")
+        );
+        assert_eq!(format_string_literal(r#"assert!(is_valid(x, "a"))"#), None);
+        assert_eq!(format_string_literal("todo!()"), None);
+    }
+}
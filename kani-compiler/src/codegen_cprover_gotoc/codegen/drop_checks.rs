@@ -0,0 +1,130 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the instrumentation used by `-Z drop-checks` to detect values that are
+//! dropped more than once.
+//!
+//! For every type with drop glue, we track one object chosen non-deterministically: a global
+//! variable holds its address and another one records whether it has been dropped. The drop glue
+//! of the type checks that the tracked object hasn't been dropped already before marking it as
+//! dropped. Since the tracked address is non-deterministic, the check covers every object of that
+//! type.
+//!
+//! Assigning a new value to the tracked object, either directly or through a `MaybeUninit` or a
+//! `ManuallyDrop` wrapper, clears the dropped state. Memory that is re-initialized by a raw
+//! memory copy (e.g. `ptr::copy`) is not tracked, and may lead to spurious failures.
+//!
+//! The drops of early-return paths, and of unwind paths with `-Z unwind`, are checked like any
+//! other drop. Values that are never dropped (leaks) and the order in which values are dropped
+//! are not checked.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use rustc_middle::mir::Place;
+use rustc_middle::ty::{self, Ty};
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether we should check that values are not dropped more than once.
+    pub fn drop_checks_enabled(&self) -> bool {
+        self.queries.args().unstable_features.contains(&"drop-checks".to_string())
+    }
+
+    /// Whether objects of the given type are tracked by the drop checks.
+    /// Zero-sized and unsized types are not tracked.
+    fn is_drop_tracked(&self, ty: Ty<'tcx>) -> bool {
+        ty.is_sized(self.tcx, ty::ParamEnv::reveal_all())
+            && ty.needs_drop(self.tcx, ty::ParamEnv::reveal_all())
+            && !self.is_zst(ty)
+    }
+
+    /// The global variables that hold the address of the object tracked for the given type and
+    /// whether that object has been dropped.
+    fn drop_tracking_vars(&mut self, ty: Ty<'tcx>) -> (Expr, Expr) {
+        let mangled = self.ty_mangled_name(ty);
+        let ptr_typ = self.codegen_ty(ty).to_pointer();
+        let tracked = self.ensure_global_var(
+            format!("__kani_drop_tracked_{mangled}"),
+            false,
+            ptr_typ.clone(),
+            Location::none(),
+            |_, var| Some(var.assign(Expr::nondet(ptr_typ), Location::none())),
+        );
+        let dropped = self.ensure_global_var(
+            format!("__kani_drop_dropped_{mangled}"),
+            false,
+            Type::bool(),
+            Location::none(),
+            |_, var| Some(var.assign(Expr::bool_false(), Location::none())),
+        );
+        (tracked, dropped)
+    }
+
+    /// Generate the check at the entry of the drop glue of `ty`, where `ptr` points to the object
+    /// being dropped.
+    pub fn codegen_drop_check(&mut self, ty: Ty<'tcx>, ptr: Expr, loc: Location) -> Option<Stmt> {
+        if !self.drop_checks_enabled() || !self.is_drop_tracked(ty) {
+            return None;
+        }
+        let (tracked, dropped) = self.drop_tracking_vars(ty);
+        let ptr = ptr.cast_to(tracked.typ().clone());
+        let check = self.codegen_assert(
+            dropped.clone().not(),
+            PropertyClass::Drop,
+            "value is dropped more than once",
+            loc,
+        );
+        let mark = dropped.assign(Expr::bool_true(), loc);
+        Some(Stmt::if_then_else(ptr.eq(tracked), Stmt::block(vec![check, mark], loc), None, loc))
+    }
+
+    /// Clear the dropped state of the object stored in `place` after a new value was assigned to
+    /// it.
+    pub fn codegen_drop_state_reset(&mut self, place: &Place<'tcx>, loc: Location) -> Option<Stmt> {
+        if !self.drop_checks_enabled() {
+            return None;
+        }
+        let place_ty = self.place_ty(place);
+        let tracked_tys = self.drop_reinit_types(place_ty);
+        if tracked_tys.is_empty() {
+            return None;
+        }
+        let addr = self.codegen_place(place).ok()?.goto_expr.address_of();
+        let resets = tracked_tys
+            .into_iter()
+            .map(|ty| {
+                let (tracked, dropped) = self.drop_tracking_vars(ty);
+                let ptr = addr.clone().cast_to(tracked.typ().clone());
+                Stmt::if_then_else(
+                    ptr.eq(tracked),
+                    dropped.assign(Expr::bool_false(), loc),
+                    None,
+                    loc,
+                )
+            })
+            .collect();
+        Some(Stmt::block(resets, loc))
+    }
+
+    /// The tracked types whose object is re-initialized when a value of type `ty` is written.
+    /// This includes `ty` itself and the types wrapped by `MaybeUninit` and `ManuallyDrop`.
+    fn drop_reinit_types(&self, ty: Ty<'tcx>) -> Vec<Ty<'tcx>> {
+        let mut tys = vec![];
+        let mut curr = Some(ty);
+        while let Some(ty) = curr {
+            if self.is_drop_tracked(ty) {
+                tys.push(ty);
+            }
+            curr = match ty.kind() {
+                ty::Adt(def, args)
+                    if Some(def.did()) == self.tcx.lang_items().maybe_uninit()
+                        || Some(def.did()) == self.tcx.lang_items().manually_drop() =>
+                {
+                    Some(args.type_at(0))
+                }
+                _ => None,
+            };
+        }
+        tys
+    }
+}
@@ -3,7 +3,7 @@
 //! This module implements foreign function handling.
 //!
 //! Kani currently only support CBMC built-in functions that are declared in the `cprover_bindings`
-//! crate, and allocation functions defined in `kani_lib.c`.
+//! crate, and allocation and C library functions defined in `kani_lib.c`.
 //!
 //! All other functions will be replaced by an unimplemented check, due to current issues with
 //! linking and usability unless unstable C-FFI support is enabled.
@@ -34,6 +34,14 @@
             "__rust_realloc".into(),
         ])
     };
+
+    /// The list of C library functions that are modeled inside `kani_lib.c`.
+    static ref MODELED_LIBC_FNS: HashSet<InternedString> = {
+        HashSet::from([
+            // Used by `std::time::Instant::now()` and `std::time::SystemTime::now()`.
+            "clock_gettime".into(),
+        ])
+    };
 }
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -51,9 +59,11 @@ pub fn codegen_foreign_fn(&mut self, instance: Instance<'tcx>) -> &Symbol {
             // Symbol has been added (either a built-in CBMC function or a Rust allocation function).
             self.symbol_table.lookup(fn_name).unwrap()
         } else if RUST_ALLOC_FNS.contains(&fn_name)
+            || MODELED_LIBC_FNS.contains(&fn_name)
             || (self.is_cffi_enabled() && kani_middle::fn_abi(self.tcx, instance).conv == Conv::C)
         {
-            // Add a Rust alloc lib function as is declared by core.
+            // Add a Rust alloc lib function as is declared by core, or a C library function that
+            // is modeled in `kani_lib.c`.
             // When C-FFI feature is enabled, we just trust the rust declaration.
             // TODO: Add proper casting and clashing definitions check.
             // https://github.com/model-checking/kani/issues/1350
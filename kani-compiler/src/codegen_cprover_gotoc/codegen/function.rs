@@ -4,11 +4,12 @@
 //! This file contains functions related to codegenning MIR functions into gotoc
 
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::trusted::full_def_path;
 use cbmc::goto_program::{Expr, Stmt, Symbol};
 use cbmc::InternString;
 use rustc_middle::mir::traversal::reverse_postorder;
-use rustc_middle::mir::{Body, HasLocalDecls, Local};
-use rustc_middle::ty::{self, Instance};
+use rustc_middle::mir::{Body, HasLocalDecls, Local, Place};
+use rustc_middle::ty::{self, Instance, InstanceDef};
 use std::collections::BTreeMap;
 use std::iter::FromIterator;
 use tracing::{debug, debug_span};
@@ -77,9 +78,13 @@ pub fn codegen_function(&mut self, instance: Instance<'tcx>) {
         } else {
             assert!(old_sym.is_function());
             let mir = self.current_fn().mir();
+            if self.current_fn().is_trusted() {
+                self.trusted_fns.insert(full_def_path(self.tcx, instance.def_id()));
+            }
             self.print_instance(instance, mir);
             self.codegen_function_prelude();
             self.codegen_declare_variables();
+            self.codegen_drop_glue_prelude(instance);
 
             reverse_postorder(mir).for_each(|(bb, bbd)| self.codegen_block(bb, bbd));
 
@@ -100,6 +105,20 @@ fn codegen_function_prelude(&mut self) {
         }
     }
 
+    /// Check that the object dropped by a drop glue function hasn't been dropped already.
+    /// See [GotocCtx::codegen_drop_check] for more details.
+    fn codegen_drop_glue_prelude(&mut self, instance: Instance<'tcx>) {
+        if let InstanceDef::DropGlue(_, Some(ty)) = instance.def {
+            let mir = self.current_fn().mir();
+            let loc = self.codegen_span(&mir.span);
+            // The only argument of a drop glue function is a pointer to the object being dropped.
+            let Ok(ptr) = self.codegen_place(&Place::from(Local::from_usize(1))) else { return };
+            if let Some(check) = self.codegen_drop_check(ty, ptr.goto_expr, loc) {
+                self.current_fn_mut().push_onto_block(check);
+            }
+        }
+    }
+
     /// MIR functions have a `spread_arg` field that specifies whether the
     /// final argument to the function is "spread" at the LLVM/codegen level
     /// from a tuple into its individual components. (Used for the "rust-
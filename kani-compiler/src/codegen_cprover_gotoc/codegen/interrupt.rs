@@ -0,0 +1,145 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the model of interrupts used when `-Z interrupts` is enabled.
+//!
+//! Interrupt handlers are registered with `kani::interrupt::attach`, which stores them in a global
+//! table. Before every statement of the crate under verification that reads or writes memory
+//! through a pointer, we add an interrupt point where one of the registered handlers may run.
+//! The total number of interrupts is bounded by `--max-interrupts`, which defaults to 1.
+//!
+//! Functions from other crates, including the standard library, don't have interrupt points, so
+//! their execution is treated as if interrupts were disabled.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::{Place, ProjectionElem, Rvalue};
+
+/// The maximum number of interrupt handlers that can be attached.
+const MAX_INTERRUPT_HANDLERS: u64 = 8;
+/// The global table that holds the attached interrupt handlers.
+const INTERRUPT_HANDLERS: &str = "__kani_interrupt_handlers";
+/// The global variable that holds the number of attached interrupt handlers.
+const INTERRUPT_COUNT: &str = "__kani_interrupt_count";
+/// The global variable that holds how many more interrupts may happen.
+const INTERRUPT_BUDGET: &str = "__kani_interrupt_budget";
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether we should model interrupts.
+    pub fn interrupts_enabled(&self) -> bool {
+        self.queries.args().unstable_features.contains(&"interrupts".to_string())
+    }
+
+    fn interrupt_handler_type() -> Type {
+        Type::code(vec![], Type::empty()).to_pointer()
+    }
+
+    fn interrupt_handlers(&mut self) -> Expr {
+        let typ = Self::interrupt_handler_type().array_of(MAX_INTERRUPT_HANDLERS);
+        self.ensure_global_var(INTERRUPT_HANDLERS, false, typ, Location::none(), |_, _| None)
+    }
+
+    fn interrupt_count(&mut self) -> Expr {
+        self.ensure_global_var(
+            INTERRUPT_COUNT,
+            false,
+            Type::size_t(),
+            Location::none(),
+            |_, var| Some(var.assign(Expr::int_constant(0, Type::size_t()), Location::none())),
+        )
+    }
+
+    fn interrupt_budget(&mut self) -> Expr {
+        let max = self.queries.args().max_interrupts;
+        self.ensure_global_var(
+            INTERRUPT_BUDGET,
+            false,
+            Type::size_t(),
+            Location::none(),
+            |_, var| Some(var.assign(Expr::int_constant(max, Type::size_t()), Location::none())),
+        )
+    }
+
+    /// Register `handler` so it can be invoked at any interrupt point.
+    pub fn codegen_attach_interrupt(&mut self, handler: Expr, loc: Location) -> Stmt {
+        let handlers = self.interrupt_handlers();
+        let count = self.interrupt_count();
+        let check = self.codegen_assert_assume(
+            count.clone().lt(Expr::int_constant(MAX_INTERRUPT_HANDLERS, Type::size_t())),
+            PropertyClass::UnsupportedConstruct,
+            &format!(
+                "kani::interrupt::attach: attaching more than {MAX_INTERRUPT_HANDLERS} \
+                interrupt handlers is not supported"
+            ),
+            loc,
+        );
+        let store = handlers
+            .index_array(count.clone())
+            .assign(handler.cast_to(Self::interrupt_handler_type()), loc);
+        let increment =
+            count.clone().assign(count.plus(Expr::int_constant(1, Type::size_t())), loc);
+        Stmt::block(vec![check, store, increment], loc)
+    }
+
+    /// Generate an interrupt point before a statement that executes the given assignment, if
+    /// it accesses memory through a pointer.
+    pub fn codegen_interrupt_point(
+        &mut self,
+        place: &Place<'tcx>,
+        rvalue: &Rvalue<'tcx>,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !self.interrupts_enabled() || !self.current_fn().instance().def_id().is_local() {
+            return None;
+        }
+        let mut visitor = DerefVisitor { found: is_deref(place) };
+        visitor.visit_rvalue(rvalue, rustc_middle::mir::Location::START);
+        if !visitor.found {
+            return None;
+        }
+
+        let handlers = self.interrupt_handlers();
+        let count = self.interrupt_count();
+        let budget = self.interrupt_budget();
+        let zero = Expr::int_constant(0, Type::size_t());
+        let should_interrupt = budget
+            .clone()
+            .gt(zero.clone())
+            .and(count.clone().gt(zero))
+            .and(Expr::nondet(Type::bool()));
+        let (idx, decl) =
+            self.decl_temp_variable(Type::size_t(), Some(Expr::nondet(Type::size_t())), loc);
+        let interrupt = Stmt::block(
+            vec![
+                budget.clone().assign(budget.sub(Expr::int_constant(1, Type::size_t())), loc),
+                decl,
+                Stmt::assume(idx.clone().lt(count), loc),
+                handlers.index_array(idx).dereference().call(vec![]).as_stmt(loc),
+            ],
+            loc,
+        );
+        Some(Stmt::if_then_else(should_interrupt, interrupt, None, loc))
+    }
+}
+
+fn is_deref(place: &Place) -> bool {
+    place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Deref))
+}
+
+/// Check whether an rvalue reads memory through a pointer.
+struct DerefVisitor {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for DerefVisitor {
+    fn visit_place(
+        &mut self,
+        place: &Place<'tcx>,
+        _context: PlaceContext,
+        _location: rustc_middle::mir::Location,
+    ) {
+        self.found |= is_deref(place);
+    }
+}
@@ -8,6 +8,7 @@
 use cbmc::goto_program::{
     ArithmeticOverflowResult, BinaryOperator, BuiltinFn, Expr, Location, Stmt, Type,
 };
+use cbmc::MachineModel;
 use rustc_middle::mir::{BasicBlock, Operand, Place};
 use rustc_middle::ty::layout::{LayoutOf, ValidityRequirement};
 use rustc_middle::ty::{self, Ty};
@@ -425,7 +426,18 @@ macro_rules! unstable_codegen {
             "cttz_nonzero" => codegen_count_intrinsic!(cttz, false),
             "discriminant_value" => {
                 let ty = instance.args.type_at(0);
-                let e = self.codegen_get_discriminant(fargs.remove(0).dereference(), ty, ret_ty);
+                let place = fargs.remove(0).dereference();
+                let e = self.codegen_get_discriminant(place.clone(), ty, ret_ty);
+                let e = if self
+                    .queries
+                    .args()
+                    .unstable_features
+                    .contains(&"enum-validity-checks".to_string())
+                {
+                    self.codegen_enum_validity_check(place, ty, e, loc)
+                } else {
+                    e
+                };
                 self.codegen_expr_to_place(p, e)
             }
             "exact_div" => self.codegen_exact_div(fargs, p, loc),
@@ -511,6 +523,7 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             "simd_and" => codegen_intrinsic_binop!(bitand),
+            "simd_cast" => self.codegen_simd_cast(fargs, p, farg_types, ret_ty, span),
             // TODO: `simd_rem` doesn't check for overflow cases for floating point operands.
             // <https://github.com/model-checking/kani/pull/2645>
             "simd_div" | "simd_rem" => {
@@ -539,6 +552,9 @@ macro_rules! unstable_codegen {
                 self.codegen_simd_cmp(Expr::vector_neq, fargs, p, span, farg_types, ret_ty)
             }
             "simd_or" => codegen_intrinsic_binop!(bitor),
+            "simd_saturating_add" => self.codegen_simd_saturating(Expr::saturating_add, fargs, p),
+            "simd_saturating_sub" => self.codegen_simd_saturating(Expr::saturating_sub, fargs, p),
+            "simd_select" => self.codegen_simd_select(fargs, p),
             "simd_shl" | "simd_shr" => {
                 self.codegen_simd_shift_with_distance_check(fargs, intrinsic, p, loc)
             }
@@ -1622,6 +1638,90 @@ fn codegen_simd_op_with_overflow<F: FnOnce(Expr, Expr) -> Expr, G: Fn(Expr, Expr
         Stmt::block(vec![check_stmt, expr_place], loc)
     }
 
+    /// `simd_saturating_add` and `simd_saturating_sub` apply the scalar saturating operation to
+    /// each pair of lanes.
+    fn codegen_simd_saturating<F: Fn(Expr, Expr, &MachineModel) -> Expr>(
+        &mut self,
+        op_fun: F,
+        mut fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+    ) -> Stmt {
+        let a = fargs.remove(0);
+        let b = fargs.remove(0);
+        let typ = a.typ().clone();
+        let elems = (0..typ.len().unwrap())
+            .map(|i| {
+                let index = Expr::int_constant(i, Type::ssize_t());
+                let v_a = a.clone().index_array(index.clone());
+                let v_b = b.clone().index_array(index);
+                op_fun(v_a, v_b, self.symbol_table.machine_model())
+            })
+            .collect();
+        self.codegen_expr_to_place(p, Expr::vector_expr(typ, elems))
+    }
+
+    /// `simd_cast(vector)` converts each lane of `vector` with an `as` cast to the element type
+    /// of the return vector, which must have the same number of lanes.
+    ///
+    /// TODO: Casting a float to an integer that cannot represent its value is UB, but we don't
+    /// check for it.
+    fn codegen_simd_cast(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        rust_arg_types: &[Ty<'tcx>],
+        rust_ret_type: Ty<'tcx>,
+        span: Option<Span>,
+    ) -> Stmt {
+        let vec = fargs.remove(0);
+        let ret_typ = self.codegen_ty(rust_ret_type);
+        let len = vec.typ().len().unwrap();
+        if len != ret_typ.len().unwrap() {
+            let err_msg = format!(
+                "expected return type with length {} (same as input type `{}`), \
+                found `{}` with length {}",
+                len,
+                rust_arg_types[0],
+                rust_ret_type,
+                ret_typ.len().unwrap()
+            );
+            self.tcx.sess.span_err(span.unwrap(), err_msg);
+        }
+        self.tcx.sess.abort_if_errors();
+
+        let base_type = ret_typ.base_type().unwrap().clone();
+        let elems = (0..len)
+            .map(|i| {
+                let index = Expr::int_constant(i, Type::ssize_t());
+                vec.clone().index_array(index).cast_to(base_type.clone())
+            })
+            .collect();
+        self.codegen_expr_to_place(p, Expr::vector_expr(ret_typ, elems))
+    }
+
+    /// `simd_select(mask, if_true, if_false)` picks each lane from `if_true` if the
+    /// corresponding lane of `mask` is set, and from `if_false` otherwise.
+    ///
+    /// The lanes of `mask` must be either all zeros or all ones, so we only compare them to zero.
+    fn codegen_simd_select(&mut self, mut fargs: Vec<Expr>, p: &Place<'tcx>) -> Stmt {
+        let mask = fargs.remove(0);
+        let if_true = fargs.remove(0);
+        let if_false = fargs.remove(0);
+        let typ = if_true.typ().clone();
+        let mask_base_type = mask.typ().base_type().unwrap().clone();
+        let elems = (0..typ.len().unwrap())
+            .map(|i| {
+                let index = Expr::int_constant(i, Type::ssize_t());
+                let m = mask.clone().index_array(index.clone());
+                m.neq(mask_base_type.zero()).ternary(
+                    if_true.clone().index_array(index.clone()),
+                    if_false.clone().index_array(index),
+                )
+            })
+            .collect();
+        self.codegen_expr_to_place(p, Expr::vector_expr(typ, elems))
+    }
+
     /// Intrinsics which encode a SIMD bitshift.
     /// Also checks for valid shift distance. Shifts on an integer of type T are UB if shift
     /// distance < 0 or >= T::BITS.
@@ -1778,6 +1878,9 @@ fn codegen_intrinsic_simd_shuffle(
     /// TODO: Add a check for the condition:
     ///  * `src` must point to a properly initialized value of type `T`
     /// See <https://github.com/model-checking/kani/issues/920> for more details
+    ///
+    /// With `-Z mmio`, loads from the regions given by `--mmio-region` return a
+    /// non-deterministic value instead of reading the memory.
     fn codegen_volatile_load(
         &mut self,
         mut fargs: Vec<Expr>,
@@ -1794,17 +1897,49 @@ fn codegen_volatile_load(
             "`src` must be properly aligned",
             loc,
         );
-        let expr = src.dereference();
+        let expr = src.clone().dereference();
         let res_stmt = self.codegen_expr_to_place(p, expr);
+        let res_stmt = match self.codegen_in_mmio_region(src, src_typ) {
+            Some(in_region) => {
+                let nondet = Expr::nondet(self.codegen_ty(self.place_ty(p)));
+                let mmio_stmt = self.codegen_expr_to_place(p, nondet);
+                Stmt::if_then_else(in_region, mmio_stmt, Some(res_stmt), loc)
+            }
+            None => res_stmt,
+        };
         Stmt::block(vec![align_check, res_stmt], loc)
     }
 
+    /// Generate an expression that checks whether the access through the pointer `ptr` falls
+    /// inside one of the memory-mapped I/O regions given by `--mmio-region`.
+    ///
+    /// Returns `None` if `-Z mmio` is not enabled or if no region can contain the access.
+    fn codegen_in_mmio_region(&self, ptr: Expr, ptr_typ: Ty<'tcx>) -> Option<Expr> {
+        let args = self.queries.args();
+        if !args.unstable_features.contains(&"mmio".to_string()) {
+            return None;
+        }
+        let size = self.layout_of(pointee_type(ptr_typ).unwrap()).size.bytes();
+        let addr = ptr.cast_to(Type::size_t());
+        args.mmio_regions
+            .iter()
+            .filter(|region| region.len >= size)
+            .map(|region| {
+                let base = Expr::int_constant(region.base, Type::size_t());
+                let max_offset = Expr::int_constant(region.len - size, Type::size_t());
+                addr.clone().ge(base.clone()).and(addr.clone().sub(base).le(max_offset))
+            })
+            .reduce(|acc, in_region| acc.or(in_region))
+    }
+
     /// A volatile write of a memory location:
     /// <https://doc.rust-lang.org/std/ptr/fn.write_volatile.html>
     ///
     /// Undefined behavior if any of these conditions are violated:
     ///  * `dst` must be valid for writes (done by `--pointer-check`)
     ///  * `dst` must be properly aligned (done by `align_check` below)
+    ///
+    /// With `-Z mmio`, stores to the regions given by `--mmio-region` are ignored.
     fn codegen_volatile_store(
         &mut self,
         mut fargs: Vec<Expr>,
@@ -1821,7 +1956,11 @@ fn codegen_volatile_store(
             "`dst` must be properly aligned",
             loc,
         );
-        let expr = dst.dereference().assign(src, loc);
+        let expr = dst.clone().dereference().assign(src, loc);
+        let expr = match self.codegen_in_mmio_region(dst, dst_typ) {
+            Some(in_region) => Stmt::if_then_else(in_region.not(), expr, None, loc),
+            None => expr,
+        };
         Stmt::block(vec![align_check, expr], loc)
     }
 
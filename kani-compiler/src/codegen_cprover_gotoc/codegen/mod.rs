@@ -6,8 +6,10 @@
 
 mod assert;
 mod block;
+mod drop_checks;
 mod foreign_function;
 mod function;
+mod interrupt;
 mod intrinsic;
 mod operand;
 mod place;
@@ -15,6 +17,7 @@
 mod span;
 mod statement;
 mod static_var;
+mod unwind;
 
 // Visible for all codegen module.
 pub(super) mod typ;
@@ -461,7 +461,13 @@ fn codegen_alloc_pointer(
         let base_addr = match self.tcx.global_alloc(alloc_id) {
             GlobalAlloc::Function(instance) => {
                 // We want to return the function pointer (not to be confused with function item)
-                self.codegen_func_expr(instance, span).address_of()
+                let func_expr = self.codegen_func_expr(instance, span).address_of();
+                let instance_ty = instance.ty(self.tcx, ty::ParamEnv::reveal_all());
+                if instance_ty.is_fn() {
+                    let fn_ptr_ty = Ty::new_fn_ptr(self.tcx, instance_ty.fn_sig(self.tcx));
+                    self.add_fn_ptr_target(fn_ptr_ty, instance);
+                }
+                func_expr
             }
             GlobalAlloc::Static(def_id) => self.codegen_static_pointer(def_id, false),
             GlobalAlloc::Memory(alloc) => {
@@ -688,14 +688,23 @@ pub fn codegen_rvalue(&mut self, rv: &Rvalue<'tcx>, loc: Location) -> Expr {
                 | CastKind::IntToFloat
                 | CastKind::FnPtrToPtr
                 | CastKind::PtrToPtr
-                | CastKind::PointerExposeAddress
-                | CastKind::PointerFromExposedAddress,
+                | CastKind::PointerExposeAddress,
                 e,
                 t,
             ) => {
                 let t = self.monomorphize(*t);
                 self.codegen_misc_cast(e, t)
             }
+            Rvalue::Cast(CastKind::PointerFromExposedAddress, e, t) => {
+                let t = self.monomorphize(*t);
+                let ptr = self.codegen_misc_cast(e, t);
+                if self.queries.args().unstable_features.contains(&"provenance-checks".to_string())
+                {
+                    self.codegen_provenance_check(ptr, loc)
+                } else {
+                    ptr
+                }
+            }
             Rvalue::Cast(CastKind::DynStar, _, _) => {
                 let ty = self.codegen_ty(res_ty);
                 self.codegen_unimplemented_expr(
@@ -757,7 +766,17 @@ pub fn codegen_rvalue(&mut self, rv: &Rvalue<'tcx>, loc: Location) -> Expr {
                 let place =
                     unwrap_or_return_codegen_unimplemented!(self, self.codegen_place(p)).goto_expr;
                 let pt = self.place_ty(p);
-                self.codegen_get_discriminant(place, pt, res_ty)
+                let discr = self.codegen_get_discriminant(place.clone(), pt, res_ty);
+                if self
+                    .queries
+                    .args()
+                    .unstable_features
+                    .contains(&"enum-validity-checks".to_string())
+                {
+                    self.codegen_enum_validity_check(place, pt, discr, loc)
+                } else {
+                    discr
+                }
             }
             Rvalue::Aggregate(ref k, operands) => {
                 self.codegen_rvalue_aggregate(k, operands, res_ty, loc)
@@ -863,6 +882,71 @@ pub fn codegen_get_discriminant(&mut self, e: Expr, ty: Ty<'tcx>, res_ty: Ty<'tc
         }
     }
 
+    /// Check that the tag of the enum `e` encodes one of its variants before returning its
+    /// discriminant `discr`. Unsafe code, e.g. a `transmute` or a write through a pointer, can
+    /// create an enum whose tag is invalid, which is undefined behavior.
+    ///
+    /// With the direct encoding, the tag must be the discriminant of a variant. With the niche
+    /// encoding, the tag is stored in a field of the untagged variant, and it must be within the
+    /// valid range of the tag, which includes the niche values and the valid values of the field.
+    pub fn codegen_enum_validity_check(
+        &mut self,
+        e: Expr,
+        ty: Ty<'tcx>,
+        discr: Expr,
+        loc: Location,
+    ) -> Expr {
+        let layout = self.layout_of(ty);
+        let Variants::Multiple { tag, tag_encoding, .. } = &layout.variants else { return discr };
+        let bits = tag.size(self).bits();
+        let tag_type = Type::unsigned_int(bits);
+        let mask = |val: u128| if bits == 128 { val } else { val & ((1u128 << bits) - 1) };
+        let is_valid = match tag_encoding {
+            TagEncoding::Direct => {
+                let tag_val = self.codegen_discriminant_field(e, ty).cast_to(tag_type.clone());
+                let discriminants: Vec<u128> = if let Some(adt_def) = ty.ty_adt_def() {
+                    adt_def.discriminants(self.tcx).map(|(_, discr)| discr.val).collect()
+                } else {
+                    let ty::Coroutine(def_id, args, _) = ty.kind() else {
+                        unreachable!("direct tag encoding for unexpected type `{ty}`")
+                    };
+                    let args = args.as_coroutine();
+                    args.discriminants(*def_id, self.tcx).map(|(_, discr)| discr.val).collect()
+                };
+                discriminants
+                    .into_iter()
+                    .map(|val| tag_val.clone().eq(Expr::int_constant(mask(val), tag_type.clone())))
+                    .reduce(|a, b| a.or(b))
+                    .unwrap_or_else(Expr::bool_false)
+            }
+            TagEncoding::Niche { .. } => {
+                let offset = match &layout.fields {
+                    FieldsShape::Arbitrary { offsets, .. } => offsets[0usize.into()],
+                    _ => unreachable!("niche encoding must have arbitrary fields"),
+                };
+                let tag_val = self.codegen_get_niche(e, offset, tag_type.clone());
+                // The valid range may wrap around, so shift it to start at zero.
+                let valid_range = tag.valid_range(self);
+                let relative_end = mask(valid_range.end.wrapping_sub(valid_range.start));
+                if relative_end == mask(u128::MAX) {
+                    // Every value of the tag is valid.
+                    return discr;
+                }
+                tag_val
+                    .sub(Expr::int_constant(valid_range.start, tag_type.clone()))
+                    .le(Expr::int_constant(relative_end, tag_type))
+            }
+        };
+        let typ = discr.typ().clone();
+        let check = self.codegen_assert(
+            is_valid,
+            PropertyClass::EnumValidity,
+            "enum validity: the tag does not encode any variant of the enum",
+            loc,
+        );
+        Expr::statement_expression(vec![check, discr.as_stmt(loc)], typ)
+    }
+
     /// Extract the niche value from `v`. This value should be of type `niche_ty` and located
     /// at byte offset `offset`
     pub fn codegen_get_niche(&self, v: Expr, offset: Size, niche_ty: Type) -> Expr {
@@ -913,6 +997,22 @@ fn codegen_fat_ptr_to_thin_ptr_cast(&mut self, src: &Operand<'tcx>, dst_t: Ty<'t
         src_goto_expr.member("data", &self.symbol_table).cast_to(dst_goto_typ)
     }
 
+    /// Check that a pointer created from an integer is either null or points to a live
+    /// allocation, i.e., that the integer was derived from an existing pointer.
+    fn codegen_provenance_check(&mut self, ptr: Expr, loc: Location) -> Expr {
+        let typ = ptr.typ().clone();
+        let (var, decl) = self.decl_temp_variable(typ.clone(), Some(ptr), loc);
+        let is_null = var.clone().eq(typ.null());
+        let is_valid = var.clone().r_ok(Expr::int_constant(0, Type::size_t()));
+        let check = self.codegen_assert(
+            is_null.or(is_valid),
+            PropertyClass::Provenance,
+            "int-to-pointer cast: pointer is not derived from any live allocation",
+            loc,
+        );
+        Expr::statement_expression(vec![decl, check, var.as_stmt(loc)], typ)
+    }
+
     /// This handles all kinds of casts, except a limited subset that are instead
     /// handled by [`Self::codegen_pointer_cast`].
     fn codegen_misc_cast(&mut self, src: &Operand<'tcx>, dst_t: Ty<'tcx>) -> Expr {
@@ -1010,7 +1110,9 @@ fn codegen_pointer_cast(
                             .unwrap();
                     // We need to handle this case in a special way because `codegen_operand` compiles FnDefs to dummy structs.
                     // (cf. the function documentation)
-                    self.codegen_func_expr(instance, None).address_of()
+                    let func_expr = self.codegen_func_expr(instance, None).address_of();
+                    self.add_fn_ptr_target(t, instance);
+                    func_expr
                 }
                 _ => unreachable!(),
             },
@@ -1021,7 +1123,9 @@ fn codegen_pointer_cast(
                         Instance::resolve_closure(self.tcx, *def_id, args, ty::ClosureKind::FnOnce)
                             .expect("failed to normalize and resolve closure during codegen")
                             .polymorphize(self.tcx);
-                    self.codegen_func_expr(instance, None).address_of()
+                    let func_expr = self.codegen_func_expr(instance, None).address_of();
+                    self.add_fn_ptr_target(t, instance);
+                    func_expr
                 } else {
                     unreachable!("{:?} cannot be cast to a fn ptr", operand)
                 }
@@ -1363,9 +1467,10 @@ fn codegen_vtable(&mut self, src_mir_type: Ty<'tcx>, dst_mir_type: Ty<'tcx>) ->
                         VtblEntry::MetadataSize => Some(vt_size.clone()),
                         VtblEntry::MetadataAlign => Some(vt_align.clone()),
                         VtblEntry::Vacant => None,
-                        // TODO: trait upcasting
-                        // https://github.com/model-checking/kani/issues/358
-                        VtblEntry::TraitVPtr(_trait_ref) => None,
+                        VtblEntry::TraitVPtr(trait_ref) => {
+                            let super_ty = ctx.supertrait_object_ty(*trait_ref);
+                            Some(ctx.codegen_vtable(src_mir_type, super_ty).address_of())
+                        }
                         VtblEntry::Method(instance) => {
                             Some(ctx.codegen_vtable_method_field(*instance, trait_type, idx))
                         }
@@ -1423,13 +1528,29 @@ fn codegen_cast_to_fat_pointer(
                 };
                 slice_fat_ptr(fat_ptr_type, dst_data_expr, dst_goto_len, &self.symbol_table)
             }
-            (ty::Dynamic(..), ty::Dynamic(..)) => {
+            (ty::Dynamic(src_data, ..), ty::Dynamic(dst_data, ..)) => {
                 // Cast between fat pointers. Cast the data and the source
+                let vptr_slot = if src_data.principal_def_id() != dst_data.principal_def_id() {
+                    // Trait upcasting. The vtable of the supertrait may be stored in the
+                    // source vtable, otherwise the source vtable starts with it.
+                    self.tcx.vtable_trait_upcasting_coercion_new_vptr_slot((
+                        metadata_src_type,
+                        metadata_dst_type,
+                    ))
+                } else {
+                    None
+                };
                 let src_data = src_goto_expr.to_owned().member("data", &self.symbol_table);
                 let dst_data = src_data.cast_to(dst_data_type);
 
                 // Retrieve the vtable and cast the vtable type.
                 let src_vtable = src_goto_expr.member("vtable", &self.symbol_table);
+                let src_vtable = if let Some(idx) = vptr_slot {
+                    let field = self.vtable_vptr_field_name(idx);
+                    src_vtable.dereference().member(field, &self.symbol_table)
+                } else {
+                    src_vtable
+                };
                 let vtable_name = self.vtable_name(metadata_dst_type);
                 let vtable_ty = Type::struct_tag(vtable_name).to_pointer();
                 let dst_vtable = src_vtable.cast_to(vtable_ty);
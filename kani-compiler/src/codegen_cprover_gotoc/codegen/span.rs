@@ -3,22 +3,26 @@
 
 //! MIR Span related functions
 
+use crate::kani_middle::trusted::TRUSTED_CODE_PRAGMAS;
 use crate::{codegen_cprover_gotoc::GotocCtx, kani_middle::SourceLocation};
 use cbmc::goto_program::Location;
 use rustc_middle::mir::{Local, VarDebugInfo, VarDebugInfoContents};
 use rustc_span::Span;
 
 impl<'tcx> GotocCtx<'tcx> {
+    /// The location of the given span. The checks that CBMC adds to the code of a trusted
+    /// function are disabled by the pragmas of its locations.
     pub fn codegen_span(&self, sp: &Span) -> Location {
         let loc = SourceLocation::new(self.tcx, sp);
-        Location::new(
+        let location = Location::new(
             loc.filename,
             self.current_fn.as_ref().map(|x| x.readable_name().to_string()),
             loc.start_line,
             Some(loc.start_col),
             loc.end_line,
             Some(loc.end_col),
-        )
+        );
+        if self.in_trusted_fn() { location.with_pragmas(TRUSTED_CODE_PRAGMAS) } else { location }
     }
 
     /// Get the location of the caller. This will attempt to reach the macro caller.
@@ -3,14 +3,15 @@
 use super::typ::TypeExt;
 use super::typ::FN_RETURN_VOID_VAR_NAME;
 use super::PropertyClass;
+use crate::codegen_cprover_gotoc::overrides::is_panic_fn;
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
 use rustc_middle::mir::{
-    AssertKind, BasicBlock, NonDivergingIntrinsic, Operand, Place, Statement, StatementKind,
-    SwitchTargets, Terminator, TerminatorKind,
+    AssertKind, BasicBlock, NonDivergingIntrinsic, Operand, Place, Rvalue, Statement,
+    StatementKind, SwitchTargets, Terminator, TerminatorKind, UnwindAction,
 };
 use rustc_middle::ty;
 use rustc_middle::ty::layout::LayoutOf;
@@ -35,7 +36,7 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
                 let lty = self.place_ty(l);
                 let rty = self.rvalue_ty(r);
                 // we ignore assignment for all zero size types
-                if self.is_zst(lty) {
+                let assign = if self.is_zst(lty) {
                     Stmt::skip(location)
                 } else if lty.is_fn_ptr() && rty.is_fn() && !rty.is_fn_ptr() {
                     // implicit address of a function pointer, e.g.
@@ -52,6 +53,18 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
                     unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(l))
                         .goto_expr
                         .assign(self.codegen_rvalue(r, location), location)
+                };
+                let assign = match self.codegen_drop_state_reset(l, location) {
+                    Some(reset) => Stmt::block(vec![assign, reset], location),
+                    None => assign,
+                };
+                let assign = match self.codegen_interrupt_point(l, r, location) {
+                    Some(interrupt) => Stmt::block(vec![interrupt, assign], location),
+                    None => assign,
+                };
+                match self.codegen_secret_address_check(l, r, location) {
+                    Some(check) => Stmt::block(vec![check, assign], location),
+                    None => assign,
                 }
             }
             StatementKind::Deinit(place) => self.codegen_deinit(place, location),
@@ -114,6 +127,10 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
             TerminatorKind::SwitchInt { discr, targets } => {
                 self.codegen_switch_int(discr, targets, loc)
             }
+            TerminatorKind::UnwindResume if self.unwind_model() => self.codegen_unwind_resume(loc),
+            TerminatorKind::UnwindTerminate(reason) if self.unwind_model() => {
+                self.codegen_unwind_action(&UnwindAction::Terminate(*reason), loc)
+            }
             // The following two use `codegen_mimic_unimplemented`
             // because we don't want to raise the warning during compilation.
             // These operations will normally be codegen'd but normally be unreachable
@@ -128,34 +145,19 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
                 loc,
                 "https://github.com/model-checking/kani/issues/692",
             ),
-            TerminatorKind::Return => {
-                let rty = self.current_fn().sig().skip_binder().output();
-                if rty.is_unit() {
-                    self.codegen_ret_unit()
-                } else {
-                    let p = Place::from(mir::RETURN_PLACE);
-                    let v =
-                        unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(&p))
-                            .goto_expr;
-                    if self.place_ty(&p).is_bool() {
-                        v.cast_to(Type::c_bool()).ret(loc)
-                    } else {
-                        v.ret(loc)
-                    }
-                }
-            }
+            TerminatorKind::Return => self.codegen_return(loc),
             TerminatorKind::Unreachable => self.codegen_assert_assume_false(
                 PropertyClass::Unreachable,
                 "unreachable code",
                 loc,
             ),
-            TerminatorKind::Drop { place, target, unwind: _, replace: _ } => {
-                self.codegen_drop(place, target, loc)
+            TerminatorKind::Drop { place, target, unwind, replace: _ } => {
+                self.codegen_drop(place, target, unwind, loc)
             }
-            TerminatorKind::Call { func, args, destination, target, .. } => {
-                self.codegen_funcall(func, args, destination, target, term.source_info.span)
+            TerminatorKind::Call { func, args, destination, target, unwind, .. } => {
+                self.codegen_funcall(func, args, destination, target, unwind, term.source_info.span)
             }
-            TerminatorKind::Assert { cond, expected, msg, target, .. } => {
+            TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
                 let cond = {
                     let r = self.codegen_operand(cond);
                     if *expected { r } else { Expr::not(r) }
@@ -178,17 +180,32 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
                 let (msg_str, reach_stmt) =
                     self.codegen_reachability_check(msg.to_owned(), Some(term.source_info.span));
 
+                let check = if self.unwind_model() {
+                    // A failed assertion panics, so start unwinding instead of aborting.
+                    let cond = cond.cast_to(Type::bool());
+                    let unwind = self.codegen_start_unwind(unwind, loc);
+                    Stmt::block(
+                        vec![
+                            self.codegen_assert(
+                                cond.clone(),
+                                PropertyClass::Assertion,
+                                &msg_str,
+                                loc,
+                            ),
+                            Stmt::if_then_else(cond.not(), unwind, None, loc),
+                        ],
+                        loc,
+                    )
+                } else {
+                    self.codegen_assert_assume(
+                        cond.cast_to(Type::bool()),
+                        PropertyClass::Assertion,
+                        &msg_str,
+                        loc,
+                    )
+                };
                 Stmt::block(
-                    vec![
-                        reach_stmt,
-                        self.codegen_assert_assume(
-                            cond.cast_to(Type::bool()),
-                            PropertyClass::Assertion,
-                            &msg_str,
-                            loc,
-                        ),
-                        Stmt::goto(self.current_fn().find_label(target), loc),
-                    ],
+                    vec![reach_stmt, check, Stmt::goto(self.current_fn().find_label(target), loc)],
                     loc,
                 )
             }
@@ -286,7 +303,26 @@ fn codegen_deinit(&mut self, place: &Place<'tcx>, loc: Location) -> Stmt {
         }
     }
 
-    /// A special case handler to codegen `return ();`
+    /// Generates Goto-C for a MIR [TerminatorKind::Return], which returns the value stored in
+    /// the return place. Functions that return `()` are handled by [Self::codegen_ret_unit].
+    pub(crate) fn codegen_return(&mut self, loc: Location) -> Stmt {
+        let rty = self.current_fn().sig().skip_binder().output();
+        if rty.is_unit() {
+            self.codegen_ret_unit()
+        } else {
+            let p = Place::from(mir::RETURN_PLACE);
+            let v = unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(&p))
+                .goto_expr;
+            if self.place_ty(&p).is_bool() {
+                v.cast_to(Type::c_bool()).ret(loc)
+            } else {
+                v.ret(loc)
+            }
+        }
+    }
+
+    /// A special case handler to codegen `return ();`, which returns the global unit variable
+    /// instead of the return place.
     fn codegen_ret_unit(&mut self) -> Stmt {
         let is_file_local = false;
         let ty = self.codegen_ty_unit();
@@ -305,9 +341,15 @@ fn codegen_ret_unit(&mut self) -> Stmt {
     ///
     /// The generated code should invoke the appropriate `drop` function on `place`, then goto `target`.
     ///
-    /// TODO: this function doesn't handle unwinding which begins if the destructor panics
-    /// <https://github.com/model-checking/kani/issues/221>
-    fn codegen_drop(&mut self, place: &Place<'tcx>, target: &BasicBlock, loc: Location) -> Stmt {
+    /// When unwinding is modeled (`-Z unwind`), the generated code follows the `unwind` action
+    /// if the destructor panics.
+    fn codegen_drop(
+        &mut self,
+        place: &Place<'tcx>,
+        target: &BasicBlock,
+        unwind: &UnwindAction,
+        loc: Location,
+    ) -> Stmt {
         let place_ty = self.place_ty(place);
         let drop_instance = Instance::resolve_drop_in_place(self.tcx, place_ty);
         debug!(?place_ty, ?drop_instance, "codegen_drop");
@@ -364,7 +406,11 @@ fn codegen_drop(&mut self, place: &Place<'tcx>, target: &BasicBlock, loc: Locati
             ),
         };
         let goto_target = Stmt::goto(self.current_fn().find_label(target), loc);
-        let block = vec![drop_implementation, goto_target];
+        let block = if self.unwind_model() {
+            vec![drop_implementation, self.codegen_check_unwind(unwind, loc), goto_target]
+        } else {
+            vec![drop_implementation, goto_target]
+        };
         Stmt::block(block, loc)
     }
 
@@ -377,6 +423,54 @@ fn codegen_switch_int(
         discr: &Operand<'tcx>,
         targets: &SwitchTargets,
         loc: Location,
+    ) -> Stmt {
+        let switch = self.codegen_switch_int_inner(discr, targets, loc);
+        match self.codegen_secret_branch_check(discr, loc) {
+            Some(check) => Stmt::block(vec![check, switch], loc),
+            None => switch,
+        }
+    }
+
+    /// In constant-time mode, generate a check that fails if this branch condition
+    /// depends on a secret value.
+    fn codegen_secret_branch_check(&self, discr: &Operand<'tcx>, loc: Location) -> Option<Stmt> {
+        let taint = self.secret_taint.as_ref()?;
+        taint.is_operand_tainted(self.current_fn().instance(), discr).then(|| {
+            self.codegen_assert(
+                Expr::bool_false(),
+                PropertyClass::ConstantTime,
+                "branch condition depends on a secret value",
+                loc,
+            )
+        })
+    }
+
+    /// In constant-time mode, generate a check that fails if this assignment accesses
+    /// memory at an address that depends on a secret value.
+    fn codegen_secret_address_check(
+        &self,
+        place: &Place<'tcx>,
+        rvalue: &Rvalue<'tcx>,
+        loc: Location,
+    ) -> Option<Stmt> {
+        let taint = self.secret_taint.as_ref()?;
+        let instance = self.current_fn().instance();
+        (taint.has_secret_index(instance, place) || taint.has_secret_address(instance, rvalue))
+            .then(|| {
+                self.codegen_assert(
+                    Expr::bool_false(),
+                    PropertyClass::ConstantTime,
+                    "memory address depends on a secret value",
+                    loc,
+                )
+            })
+    }
+
+    fn codegen_switch_int_inner(
+        &mut self,
+        discr: &Operand<'tcx>,
+        targets: &SwitchTargets,
+        loc: Location,
     ) -> Stmt {
         let v = self.codegen_operand(discr);
         let switch_ty = v.typ().clone();
@@ -454,7 +548,21 @@ fn codegen_untupled_args(
 
     /// Because function calls terminate basic blocks, to "end" a function call, we
     /// must jump to the next basic block.
-    fn codegen_end_call(&self, target: Option<&BasicBlock>, loc: Location) -> Stmt {
+    fn codegen_end_call(
+        &mut self,
+        target: Option<&BasicBlock>,
+        unwind: &UnwindAction,
+        loc: Location,
+    ) -> Stmt {
+        if self.unwind_model() {
+            let check = self.codegen_check_unwind(unwind, loc);
+            let end = self.codegen_end_call_inner(target, loc);
+            return Stmt::block(vec![check, end], loc);
+        }
+        self.codegen_end_call_inner(target, loc)
+    }
+
+    fn codegen_end_call_inner(&self, target: Option<&BasicBlock>, loc: Location) -> Stmt {
         if let Some(next_bb) = target {
             Stmt::goto(self.current_fn().find_label(next_bb), loc)
         } else {
@@ -491,6 +599,56 @@ pub(crate) fn codegen_funcall_args(
         fargs
     }
 
+    /// In aliasing mode (`-Z aliasing`), generate checks that no mutable reference passed to a
+    /// function call overlaps with another reference passed to the same call.
+    ///
+    /// This approximates the retag performed by Stacked Borrows and Tree Borrows on function
+    /// entry: a `&mut` argument must be the only way to access its pointee for the duration of
+    /// the call. Unlike Miri, the check is symbolic, so it covers every possible address.
+    /// References to unsized and zero-sized types are ignored.
+    fn codegen_aliasing_checks(&mut self, args: &[Operand<'tcx>], loc: Location) -> Vec<Stmt> {
+        if !self.queries.args().unstable_features.contains(&"aliasing".to_string()) {
+            return vec![];
+        }
+        let refs: Vec<(Expr, u64, bool)> = args
+            .iter()
+            .filter_map(|arg| {
+                let arg_ty = self.operand_ty(arg);
+                let ty::Ref(_, pointee, mutbl) = arg_ty.kind() else { return None };
+                if !pointee.is_sized(self.tcx, ty::ParamEnv::reveal_all()) || self.is_zst(*pointee)
+                {
+                    return None;
+                }
+                let size = self.layout_of(*pointee).size.bytes();
+                let ptr = self.codegen_operand(arg).cast_to(Type::unsigned_int(8).to_pointer());
+                Some((ptr, size, mutbl.is_mut()))
+            })
+            .collect();
+        let mut checks = vec![];
+        for (idx, (first, first_size, first_mut)) in refs.iter().enumerate() {
+            for (second, second_size, second_mut) in &refs[idx + 1..] {
+                if !first_mut && !second_mut {
+                    continue;
+                }
+                let first_end = first.clone().plus(Expr::int_constant(*first_size, Type::size_t()));
+                let second_end =
+                    second.clone().plus(Expr::int_constant(*second_size, Type::size_t()));
+                let overlap = first
+                    .clone()
+                    .same_object(second.clone())
+                    .and(first.clone().lt(second_end))
+                    .and(second.clone().lt(first_end));
+                checks.push(self.codegen_assert(
+                    overlap.not(),
+                    PropertyClass::Aliasing,
+                    "mutable reference aliases another reference passed to the same call",
+                    loc,
+                ));
+            }
+        }
+        checks
+    }
+
     /// Generates Goto-C for a MIR [TerminatorKind::Call] statement.
     ///
     /// This calls either:
@@ -509,6 +667,7 @@ fn codegen_funcall(
         args: &[Operand<'tcx>],
         destination: &Place<'tcx>,
         target: &Option<BasicBlock>,
+        unwind: &UnwindAction,
         span: Span,
     ) -> Stmt {
         debug!(?func, ?args, ?destination, ?span, "codegen_funcall");
@@ -531,11 +690,18 @@ fn codegen_funcall(
                     self.codegen_untupled_args(instance, &mut fargs, args.last());
                 }
 
+                if self.unwind_model() && is_panic_fn(self.tcx, instance) {
+                    let panic = self.codegen_unwinding_panic(Some(span), &fargs);
+                    let unwind = self.codegen_start_unwind(unwind, loc);
+                    return Stmt::block(vec![panic, unwind], loc);
+                }
+
                 if let Some(hk) = self.hooks.hook_applies(self.tcx, instance) {
                     return hk.handle(self, instance, fargs, *destination, *target, Some(span));
                 }
 
-                let mut stmts: Vec<Stmt> = match instance.def {
+                let mut stmts = self.codegen_aliasing_checks(args, loc);
+                stmts.extend(match instance.def {
                     // Here an empty drop glue is invoked; we just ignore it.
                     InstanceDef::DropGlue(_, None) => {
                         return Stmt::goto(self.current_fn().find_label(&target.unwrap()), loc);
@@ -571,22 +737,28 @@ fn codegen_funcall(
                         ]
                     }
                     InstanceDef::ThreadLocalShim(_) => todo!(),
-                };
-                stmts.push(self.codegen_end_call(target.as_ref(), loc));
+                });
+                stmts.extend(self.codegen_drop_state_reset(destination, loc));
+                stmts.push(self.codegen_end_call(target.as_ref(), unwind, loc));
                 Stmt::block(stmts, loc)
             }
             // Function call through a pointer
             ty::FnPtr(_) => {
                 let func_expr = self.codegen_operand(func).dereference();
                 // Actually generate the function call and return.
-                Stmt::block(
-                    vec![
-                        self.codegen_expr_to_place(destination, func_expr.call(fargs))
-                            .with_location(loc),
-                        Stmt::goto(self.current_fn().find_label(&target.unwrap()), loc),
-                    ],
-                    loc,
-                )
+                let mut stmts = self.codegen_aliasing_checks(args, loc);
+                let call_stmt = self
+                    .codegen_expr_to_place(destination, func_expr.call(fargs))
+                    .with_location(loc);
+                let call_stmt = if self.vtable_ctx.emit_fn_ptr_restrictions {
+                    self.fn_ptr_call_with_restricted_fn_ptr(funct, call_stmt)
+                } else {
+                    call_stmt
+                };
+                stmts.push(call_stmt);
+                stmts.extend(self.codegen_drop_state_reset(destination, loc));
+                stmts.push(self.codegen_end_call(target.as_ref(), unwind, loc));
+                Stmt::block(stmts, loc)
             }
             x => unreachable!("Function call where the function was of unexpected type: {:?}", x),
         }
@@ -641,7 +813,7 @@ fn codegen_virtual_funcall(
         );
 
         let vtable_ref = trait_fat_ptr.to_owned().member("vtable", &self.symbol_table);
-        let vtable = vtable_ref.dereference();
+        let vtable = vtable_ref.clone().dereference();
         let fn_ptr = vtable.member(vtable_field_name, &self.symbol_table);
         trace!(fn_typ=?fn_ptr.typ(), "codegen_virtual_funcall");
 
@@ -671,6 +843,18 @@ fn codegen_virtual_funcall(
             data_ptr
         };
 
+        // The vtable pointer comes from the trait object metadata, which unsafe code may forge.
+        let vtable_is_valid = vtable_ref.clone().r_ok(Expr::int_constant(
+            vtable_ref.typ().base_type().unwrap().sizeof(&self.symbol_table),
+            Type::size_t(),
+        ));
+        let assert_vtable = self.codegen_assert_assume(
+            vtable_is_valid,
+            PropertyClass::SafetyCheck,
+            "dynamic dispatch through a valid vtable",
+            loc,
+        );
+
         // For soundness, add an assertion that the vtable function call is not null.
         // Otherwise, CBMC might treat this as an assume(0) and later user-added assertions
         // could become unreachable.
@@ -686,6 +870,7 @@ fn codegen_virtual_funcall(
         } else {
             call_stmt
         };
+        ret_stmts.push(assert_vtable);
         ret_stmts.push(assert_nonnull);
         ret_stmts.push(call_stmt);
         ret_stmts
@@ -595,9 +595,14 @@ fn trait_vtable_field_types(&mut self, t: ty::Ty<'tcx>) -> Vec<DatatypeComponent
                         VtblEntry::Method(instance) => {
                             Some(self.trait_method_vtable_field_type(instance, idx))
                         }
-                        // TODO: trait upcasting
-                        // https://github.com/model-checking/kani/issues/358
-                        VtblEntry::TraitVPtr(..) => None,
+                        VtblEntry::TraitVPtr(trait_ref) => {
+                            let super_ty = self.supertrait_object_ty(trait_ref);
+                            let vptr_type = self.codegen_trait_vtable_type(super_ty).to_pointer();
+                            Some(DatatypeComponent::field(
+                                self.vtable_vptr_field_name(idx),
+                                vptr_type,
+                            ))
+                        }
                         VtblEntry::MetadataDropInPlace
                         | VtblEntry::MetadataSize
                         | VtblEntry::MetadataAlign
@@ -614,6 +619,24 @@ fn trait_vtable_field_types(&mut self, t: ty::Ty<'tcx>) -> Vec<DatatypeComponent
         }
     }
 
+    /// Build the trait object type `dyn Super` for a supertrait entry of a vtable.
+    ///
+    /// Vtables of traits with more than one supertrait store a pointer to the vtable of each
+    /// supertrait that doesn't share its prefix. These pointers are used for trait upcasting.
+    pub fn supertrait_object_ty(&self, trait_ref: ty::PolyTraitRef<'tcx>) -> Ty<'tcx> {
+        let predicate = trait_ref.map_bound(|trait_ref| {
+            ty::ExistentialPredicate::Trait(ty::ExistentialTraitRef::erase_self_ty(
+                self.tcx, trait_ref,
+            ))
+        });
+        Ty::new_dynamic(
+            self.tcx,
+            self.tcx.mk_poly_existential_predicates(&[predicate]),
+            self.tcx.lifetimes.re_erased,
+            ty::Dyn,
+        )
+    }
+
     /// Gives the name for a trait, i.e., `dyn T`. This does not work for `&dyn T`.
     pub fn normalized_trait_name(&self, t: Ty<'tcx>) -> String {
         assert!(t.is_trait(), "Type {t} must be a trait type (a dynamic type)");
@@ -622,11 +645,6 @@ pub fn normalized_trait_name(&self, t: Ty<'tcx>) -> String {
 
     /// Gives the vtable name for a type.
     /// In some cases, we have &T, in other cases T, so normalize.
-    ///
-    /// TODO: to handle trait upcasting, this will need to use a
-    /// poly existential trait type as a part of the key as well.
-    /// See compiler/rustc_middle/src/ty/vtable.rs
-    /// <https://github.com/model-checking/kani/issues/358>
     pub fn vtable_name(&self, t: Ty<'tcx>) -> String {
         format!("{}::vtable", self.normalized_trait_name(t))
     }
@@ -0,0 +1,83 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the model of stack unwinding used when `-Z unwind` is enabled.
+//!
+//! In this mode, the crate is compiled with `-C panic=unwind`, so its MIR contains cleanup
+//! blocks that drop the live values of a function when a panic goes through it. We model
+//! unwinding with a global flag that is set while the stack is being unwound:
+//!
+//! - A panic sets the flag and follows the unwind action of the call that panicked.
+//! - After every call or drop, if the flag is set, we follow the unwind action of that terminator.
+//! - Entering a cleanup block clears the flag, and `UnwindResume` sets it again before returning
+//!   to the caller.
+//!
+//! The standard library shipped with Kani is compiled with `-C panic=abort`, so its functions
+//! don't have cleanup blocks. Their calls are marked with `UnwindAction::Unreachable`, which we
+//! treat as if the panic was propagated to the caller without running any destructor.
+
+use super::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use rustc_middle::mir::UnwindAction;
+
+/// The global variable that is set while the stack is being unwound.
+const UNWINDING_FLAG: &str = "__kani_unwinding";
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether we should model stack unwinding instead of aborting on panic.
+    pub fn unwind_model(&self) -> bool {
+        self.queries.args().unstable_features.contains(&"unwind".to_string())
+    }
+
+    fn unwinding_flag(&mut self) -> Expr {
+        self.ensure_global_var(UNWINDING_FLAG, false, Type::bool(), Location::none(), |_, var| {
+            Some(var.assign(Expr::bool_false(), Location::none()))
+        })
+    }
+
+    /// Start unwinding the stack from a panic in a terminator with the given unwind action.
+    pub fn codegen_start_unwind(&mut self, unwind: &UnwindAction, loc: Location) -> Stmt {
+        let flag = self.unwinding_flag();
+        Stmt::block(
+            vec![flag.assign(Expr::bool_true(), loc), self.codegen_unwind_action(unwind, loc)],
+            loc,
+        )
+    }
+
+    /// Resume unwinding at the end of a cleanup block by returning to the caller.
+    pub fn codegen_unwind_resume(&mut self, loc: Location) -> Stmt {
+        let flag = self.unwinding_flag();
+        Stmt::block(vec![flag.assign(Expr::bool_true(), loc), self.codegen_return(loc)], loc)
+    }
+
+    /// Follow the unwind action of a call or a drop if the callee started unwinding.
+    pub fn codegen_check_unwind(&mut self, unwind: &UnwindAction, loc: Location) -> Stmt {
+        let flag = self.unwinding_flag();
+        let action = self.codegen_unwind_action(unwind, loc);
+        Stmt::if_then_else(flag, action, None, loc)
+    }
+
+    /// Continue unwinding according to the given action: run the cleanup block, return to the
+    /// caller, or abort the execution if this function cannot unwind.
+    pub fn codegen_unwind_action(&mut self, unwind: &UnwindAction, loc: Location) -> Stmt {
+        match unwind {
+            UnwindAction::Cleanup(bb) => {
+                let flag = self.unwinding_flag();
+                Stmt::block(
+                    vec![
+                        flag.assign(Expr::bool_false(), loc),
+                        Stmt::goto(self.current_fn().find_label(bb), loc),
+                    ],
+                    loc,
+                )
+            }
+            UnwindAction::Continue | UnwindAction::Unreachable => self.codegen_return(loc),
+            UnwindAction::Terminate(_) => self.codegen_assert_assume_false(
+                PropertyClass::Assertion,
+                "panic in a function that cannot unwind",
+                loc,
+            ),
+        }
+    }
+}
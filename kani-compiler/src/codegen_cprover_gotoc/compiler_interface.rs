@@ -7,11 +7,17 @@
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::kani_middle::analysis;
 use crate::kani_middle::attributes::is_test_harness_description;
+use crate::kani_middle::complexity;
+use crate::kani_middle::const_fn;
+use crate::kani_middle::harness_lints;
 use crate::kani_middle::metadata::gen_test_metadata;
 use crate::kani_middle::provide;
 use crate::kani_middle::reachability::{
     collect_reachable_items, filter_const_crate_items, filter_crate_items,
 };
+use crate::kani_middle::secrets::SecretTaint;
+use crate::kani_middle::send_sync;
+use crate::kani_middle::unsafe_code::{reached_unsafe, unsafe_functions};
 use crate::kani_middle::{check_reachable_items, dump_mir_items};
 use crate::kani_queries::QueryDb;
 use cbmc::goto_program::Location;
@@ -31,7 +37,7 @@
 use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::temp_dir::MaybeTempDir;
 use rustc_errors::{ErrorGuaranteed, DEFAULT_LOCALE_RESOURCE};
-use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::definitions::DefPathHash;
 use rustc_metadata::fs::{emit_wrapper_file, METADATA_FILENAME};
 use rustc_metadata::EncodedMetadata;
@@ -83,16 +89,29 @@ fn codegen_items<'tcx>(
         symtab_goto: &Path,
         machine_model: &MachineModel,
     ) -> (GotocCtx<'tcx>, Vec<MonoItem<'tcx>>) {
-        let items = with_timer(
-            || collect_reachable_items(tcx, starting_items),
-            "codegen reachability analysis",
-        );
-        dump_mir_items(tcx, &items, &symtab_goto.with_extension("kani.mir"));
+        let items = reachable_items(tcx, starting_items, symtab_goto);
+        self.codegen_reachable_items(tcx, items, symtab_goto, machine_model)
+    }
 
+    /// Generate code for the given items, which must include every item reachable from them.
+    fn codegen_reachable_items<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        items: Vec<MonoItem<'tcx>>,
+        symtab_goto: &Path,
+        machine_model: &MachineModel,
+    ) -> (GotocCtx<'tcx>, Vec<MonoItem<'tcx>>) {
         // Follow rustc naming convention (cx is abbrev for context).
         // https://rustc-dev-guide.rust-lang.org/conventions.html#naming-conventions
         let mut gcx = GotocCtx::new(tcx, (*self.queries.lock().unwrap()).clone(), machine_model);
         check_reachable_items(gcx.tcx, &gcx.queries, &items);
+        if gcx.queries.args().unstable_features.contains(&"complexity-lints".to_string()) {
+            with_timer(|| complexity::lint_items(tcx, &items), "complexity lints");
+        }
+        if gcx.queries.args().unstable_features.contains(&"constant-time".to_string()) {
+            gcx.secret_taint =
+                Some(with_timer(|| SecretTaint::analyze(tcx, &items), "secret taint analysis"));
+        }
 
         with_timer(
             || {
@@ -157,11 +176,12 @@ fn codegen_items<'tcx>(
             BTreeMap::from_iter(gcx.type_map.iter().map(|(k, v)| (*k, v.to_string().into())));
 
         // Get the vtable function pointer restrictions if requested
-        let vtable_restrictions = if gcx.vtable_ctx.emit_vtable_restrictions {
-            Some(gcx.vtable_ctx.get_virtual_function_restrictions())
-        } else {
-            None
-        };
+        let vtable_restrictions =
+            if gcx.vtable_ctx.emit_vtable_restrictions || gcx.vtable_ctx.emit_fn_ptr_restrictions {
+                Some(gcx.vtable_ctx.get_virtual_function_restrictions())
+            } else {
+                None
+            };
 
         // No output should be generated if user selected no_codegen.
         if !tcx.sess.opts.unstable_opts.no_codegen && tcx.sess.opts.output_types.should_codegen() {
@@ -184,6 +204,39 @@ fn codegen_items<'tcx>(
     }
 }
 
+/// Collect the items reachable from the given starting points, and dump their MIR if requested.
+fn reachable_items<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    starting_items: &[MonoItem<'tcx>],
+    symtab_goto: &Path,
+) -> Vec<MonoItem<'tcx>> {
+    let items = with_timer(
+        || collect_reachable_items(tcx, starting_items),
+        "codegen reachability analysis",
+    );
+    dump_mir_items(tcx, &items, &symtab_goto.with_extension("kani.mir"));
+    items
+}
+
+/// Return a function that is stubbed in this compilation session, but not by the given harness,
+/// if the harness reaches it.
+fn reached_foreign_stub(
+    tcx: TyCtxt,
+    items: &[MonoItem],
+    queries: &QueryDb,
+    harness: &DefPathHash,
+) -> Option<DefId> {
+    let foreign = queries.foreign_stubs.get(harness).filter(|stubs| !stubs.is_empty())?;
+    items.iter().find_map(|item| {
+        let def_id = match item {
+            MonoItem::Fn(instance) => instance.def_id(),
+            MonoItem::Static(def_id) => *def_id,
+            MonoItem::GlobalAsm(_) => return None,
+        };
+        foreign.contains(&tcx.def_path_hash(def_id)).then_some(def_id)
+    })
+}
+
 impl CodegenBackend for GotocCodegenBackend {
     fn metadata_loader(&self) -> Box<MetadataLoaderDyn> {
         Box::new(rustc_codegen_ssa::back::metadata::DefaultMetadataLoader)
@@ -213,7 +266,7 @@ fn codegen_crate(
         // Queries shouldn't change today once codegen starts.
         let queries = self.queries.lock().unwrap().clone();
         check_target(tcx.sess);
-        check_options(tcx.sess);
+        check_options(tcx.sess, &queries);
 
         // Codegen all items that need to be processed according to the selected reachability mode:
         //
@@ -223,6 +276,21 @@ fn codegen_crate(
         // - None: Don't generate code. This is used to compile dependencies.
         let base_filename = tcx.output_filenames(()).output_path(OutputType::Object);
         let reachability = queries.args().reachability_analysis;
+        if reachability != ReachabilityType::None
+            && queries.args().unstable_features.contains(&"send-sync-lints".to_string())
+        {
+            send_sync::lint_impls(tcx);
+        }
+        if reachability != ReachabilityType::None
+            && queries.args().unstable_features.contains(&"harness-lints".to_string())
+        {
+            harness_lints::lint_harnesses(tcx);
+        }
+        if reachability != ReachabilityType::None
+            && queries.args().unstable_features.contains(&"const-fn-parity".to_string())
+        {
+            const_fn::lint_const_fns(tcx);
+        }
         let mut results = GotoCodegenResults::new(tcx, reachability);
         match reachability {
             ReachabilityType::Harnesses => {
@@ -232,11 +300,47 @@ fn codegen_crate(
                 items.extend(harnesses);
                 let harnesses =
                     filter_crate_items(tcx, |_, def_id| items.contains(&tcx.def_path_hash(def_id)));
-                for harness in harnesses {
-                    let model_path =
-                        queries.harness_model_path(&tcx.def_path_hash(harness.def_id())).unwrap();
-                    let (gcx, items) =
-                        self.codegen_items(tcx, &[harness], model_path, &results.machine_model);
+                let total = harnesses.len();
+                let unsafe_fns = unsafe_functions(tcx);
+                for (idx, harness) in harnesses.into_iter().enumerate() {
+                    let def_path = tcx.def_path_hash(harness.def_id());
+                    let model_path = queries.harness_model_path(&def_path).unwrap();
+                    let start = Instant::now();
+                    let items = reachable_items(tcx, &[harness], model_path);
+                    // This compilation session may apply stubs that were requested by other
+                    // harnesses. Such harnesses are compiled again in a session of their own.
+                    if let Some(stub) = reached_foreign_stub(tcx, &items, &queries, &def_path) {
+                        info!(
+                            "Deferred harness `{}`, which reaches `{}`, stubbed by another harness",
+                            tcx.def_path_str(harness.def_id()),
+                            tcx.def_path_str(stub)
+                        );
+                        self.queries.lock().unwrap().deferred_harnesses.push(def_path);
+                        continue;
+                    }
+                    let (gcx, items) = self.codegen_reachable_items(
+                        tcx,
+                        items,
+                        model_path,
+                        &results.machine_model,
+                    );
+                    let elapsed = start.elapsed();
+                    info!(
+                        "Generated code for harness `{}` ({}/{total}): {} items in {}s",
+                        tcx.def_path_str(harness.def_id()),
+                        idx + 1,
+                        items.len(),
+                        elapsed.as_secs_f32()
+                    );
+                    let mut queries = self.queries.lock().unwrap();
+                    queries.codegen_times.insert(def_path, elapsed);
+                    queries.nondet_stats.insert(def_path, gcx.nondet_stats.clone());
+                    queries.check_span_ends.insert(def_path, gcx.check_span_ends.clone());
+                    queries
+                        .reached_unsafe
+                        .insert(def_path, reached_unsafe(tcx, &unsafe_fns, &items));
+                    queries.trusted_code.insert(def_path, gcx.trusted_fns.clone());
+                    drop(queries);
                     results.extend(gcx, items, None);
                 }
             }
@@ -258,8 +362,10 @@ fn codegen_crate(
                 // We will be able to remove this once we optimize all calls to CBMC utilities.
                 // https://github.com/model-checking/kani/issues/1971
                 let model_path = base_filename.with_extension(ArtifactType::SymTabGoto);
+                let start = Instant::now();
                 let (gcx, items) =
                     self.codegen_items(tcx, &harnesses, &model_path, &results.machine_model);
+                report_crate_progress(tcx, &items, start);
                 results.extend(gcx, items, None);
 
                 for (test_fn, test_desc) in harnesses.iter().zip(descriptions.iter()) {
@@ -283,8 +389,10 @@ fn codegen_crate(
                         || entry_fn == Some(def_id)
                 });
                 let model_path = base_filename.with_extension(ArtifactType::SymTabGoto);
+                let start = Instant::now();
                 let (gcx, items) =
                     self.codegen_items(tcx, &local_reachable, &model_path, &results.machine_model);
+                report_crate_progress(tcx, &items, start);
                 results.extend(gcx, items, None);
             }
         }
@@ -390,16 +498,18 @@ fn check_target(session: &Session) {
     let is_x86_64_darwin_target = session.target.llvm_target.starts_with("x86_64-apple-");
     // looking for `arm64-apple-*`
     let is_arm64_darwin_target = session.target.llvm_target.starts_with("arm64-apple-");
+    let is_x86_64_windows_target = session.target.llvm_target == "x86_64-pc-windows-msvc";
 
     if !is_x86_64_linux_target
         && !is_arm64_linux_target
         && !is_x86_64_darwin_target
         && !is_arm64_darwin_target
+        && !is_x86_64_windows_target
     {
         let err_msg = format!(
             "Kani requires the target platform to be `x86_64-unknown-linux-gnu`, \
-            `aarch64-unknown-linux-gnu`, `x86_64-apple-*` or `arm64-apple-*`, but \
-            it is {}",
+            `aarch64-unknown-linux-gnu`, `x86_64-apple-*`, `arm64-apple-*` or \
+            `x86_64-pc-windows-msvc`, but it is {}",
             &session.target.llvm_target
         );
         session.err(err_msg);
@@ -408,7 +518,7 @@ fn check_target(session: &Session) {
     session.abort_if_errors();
 }
 
-fn check_options(session: &Session) {
+fn check_options(session: &Session, queries: &QueryDb) {
     // The requirements for `min_global_align` and `endian` are needed to build
     // a valid CBMC machine model in function `machine_model_from_session` from
     // src/kani-compiler/src/codegen_cprover_gotoc/context/goto_ctx.rs
@@ -427,13 +537,16 @@ fn check_options(session: &Session) {
         session.err("Kani requires the target architecture option `endian` to be `little`.");
     }
 
-    if !session.overflow_checks() {
+    if !session.overflow_checks() && !queries.args().wrapping_overflow {
         session.err("Kani requires overflow checks in order to provide a sound analysis.");
     }
 
-    if session.panic_strategy() != PanicStrategy::Abort {
+    if session.panic_strategy() != PanicStrategy::Abort
+        && !queries.args().unstable_features.contains(&"unwind".to_string())
+    {
         session.err(
-            "Kani can only handle abort panic strategy (-C panic=abort). See for more details \
+            "Kani can only handle abort panic strategy (-C panic=abort) unless stack unwinding \
+        is enabled with `-Z unwind`. See for more details \
         https://github.com/model-checking/kani/issues/692",
         );
     }
@@ -564,6 +677,7 @@ pub fn generate_metadata(&self) -> KaniMetadata {
             proof_harnesses: proofs,
             unsupported_features,
             test_harnesses: tests,
+            unsafe_items: vec![],
         }
     }
 
@@ -620,8 +734,8 @@ fn print_report(&self, tcx: TyCtxt<'tcx>) {
 
 /// Builds a machine model which is required by CBMC
 fn new_machine_model(sess: &Session) -> MachineModel {
-    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`
-    // or `aarch64-apple-darwin` platform. We check the target platform in function
+    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`,
+    // `aarch64-apple-darwin` or `x86_64-pc-windows-msvc` platform. We check the target platform in function
     // `check_target` from src/kani-compiler/src/codegen_cprover_gotoc/compiler_interface.rs
     // and error if it is not any of the ones we expect.
     let architecture = &sess.target.arch;
@@ -654,13 +768,16 @@ fn new_machine_model(sess: &Session) -> MachineModel {
             let double_width = 64;
             let float_width = 32;
             let int_width = 32;
-            let long_double_width = 128;
-            let long_int_width = 64;
+            // Windows uses the LLP64 data model, where `long` is 32 bits wide, and MSVC uses
+            // 64 bits for `long double` and a 16 bits unsigned `wchar_t`.
+            let is_windows = os.as_ref() == "windows";
+            let long_double_width = if is_windows { 64 } else { 128 };
+            let long_int_width = if is_windows { 32 } else { 64 };
             let long_long_int_width = 64;
             let short_int_width = 16;
             let single_width = 32;
-            let wchar_t_is_unsigned = false;
-            let wchar_t_width = 32;
+            let wchar_t_is_unsigned = is_windows;
+            let wchar_t_width = if is_windows { 16 } else { 32 };
 
             MachineModel {
                 architecture: architecture.to_string(),
@@ -738,6 +855,16 @@ fn new_machine_model(sess: &Session) -> MachineModel {
     }
 }
 
+/// Log how many items were generated for the local crate and how long it took.
+fn report_crate_progress(tcx: TyCtxt, items: &[MonoItem], start: Instant) {
+    info!(
+        "Generated code for crate `{}`: {} items in {}s",
+        tcx.crate_name(LOCAL_CRATE),
+        items.len(),
+        start.elapsed().as_secs_f32()
+    );
+}
+
 /// Execute the provided function and measure the clock time it took for its execution.
 /// Log the time with the given description.
 pub fn with_timer<T, F>(func: F, description: &str) -> T
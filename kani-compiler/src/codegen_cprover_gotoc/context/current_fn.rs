@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::trusted::is_trusted;
 use cbmc::goto_program::Stmt;
 use rustc_middle::mir::BasicBlock;
 use rustc_middle::mir::Body;
@@ -31,6 +32,8 @@ pub struct CurrentFnCtx<'tcx> {
     sig: PolyFnSig<'tcx>,
     /// A counter to enable creating temporary variables
     temp_var_counter: u64,
+    /// Whether the function is trusted, i.e. compiled with its checks disabled
+    trusted: bool,
 }
 
 /// Constructor
@@ -47,6 +50,7 @@ pub fn new(instance: Instance<'tcx>, gcx: &GotocCtx<'tcx>, labels: Vec<String>)
             readable_name: gcx.readable_instance_name(instance),
             sig: gcx.fn_sig_of_instance(instance),
             temp_var_counter: 0,
+            trusted: is_trusted(gcx.tcx, instance.def_id(), &gcx.queries.args().trusted_paths),
         }
     }
 }
@@ -117,6 +121,11 @@ pub fn is_std(&self) -> bool {
         self.krate == "std" || self.krate == "core"
     }
 
+    /// Whether the checks of this function are disabled because the user trusts it.
+    pub fn is_trusted(&self) -> bool {
+        self.trusted
+    }
+
     pub fn find_label(&self, bb: &BasicBlock) -> String {
         self.labels[bb.index()].clone()
     }
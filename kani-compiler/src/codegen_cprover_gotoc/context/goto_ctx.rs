@@ -18,11 +18,12 @@
 use crate::codegen_cprover_gotoc::overrides::{fn_hooks, GotocHooks};
 use crate::codegen_cprover_gotoc::utils::full_crate_name;
 use crate::codegen_cprover_gotoc::UnsupportedConstructs;
+use crate::kani_middle::secrets::SecretTaint;
 use crate::kani_queries::QueryDb;
 use cbmc::goto_program::{DatatypeComponent, Expr, Location, Stmt, Symbol, SymbolTable, Type};
 use cbmc::utils::aggr_tag;
 use cbmc::{InternedString, MachineModel};
-use kani_metadata::HarnessMetadata;
+use kani_metadata::{HarnessMetadata, NondetStats, SpanEnd};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::mir::interpret::Allocation;
 use rustc_middle::span_bug;
@@ -34,6 +35,7 @@
 use rustc_span::source_map::{respan, Span};
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
+use std::collections::{BTreeMap, BTreeSet};
 
 pub struct GotocCtx<'tcx> {
     /// the typing context
@@ -67,6 +69,14 @@ pub struct GotocCtx<'tcx> {
     /// We collect them and print one warning at the end if not empty instead of printing one
     /// warning at each occurrence.
     pub concurrent_constructs: UnsupportedConstructs,
+    /// The result of the secret taint analysis. Only available in constant-time mode.
+    pub secret_taint: Option<SecretTaint<'tcx>>,
+    /// The nondeterministic values created by `kani::any()` in the generated code.
+    pub nondet_stats: NondetStats,
+    /// Where the checks that have a unique identifier end in the source code.
+    pub check_span_ends: BTreeMap<String, SpanEnd>,
+    /// The trusted functions in the generated code, which are compiled without checks.
+    pub trusted_fns: BTreeSet<String>,
 }
 
 /// Constructor
@@ -79,6 +89,7 @@ pub fn new(
         let fhks = fn_hooks();
         let symbol_table = SymbolTable::new(machine_model.clone());
         let emit_vtable_restrictions = queries.args().emit_vtable_restrictions;
+        let emit_fn_ptr_restrictions = queries.args().emit_fn_ptr_restrictions;
         GotocCtx {
             tcx,
             queries,
@@ -87,7 +98,7 @@ pub fn new(
             full_crate_name: full_crate_name(tcx),
             global_var_count: 0,
             alloc_map: FxHashMap::default(),
-            vtable_ctx: VtableCtx::new(emit_vtable_restrictions),
+            vtable_ctx: VtableCtx::new(emit_vtable_restrictions, emit_fn_ptr_restrictions),
             current_fn: None,
             type_map: FxHashMap::default(),
             str_literals: FxHashMap::default(),
@@ -96,6 +107,10 @@ pub fn new(
             global_checks_count: 0,
             unsupported_constructs: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
+            secret_taint: None,
+            nondet_stats: NondetStats::default(),
+            check_span_ends: BTreeMap::new(),
+            trusted_fns: BTreeSet::new(),
         }
     }
 }
@@ -13,14 +13,22 @@
 /// performance compared to heuristics that consider a wider set of possible
 /// function pointer targets.
 ///
+/// Calls through plain function pointers are handled similarly: every function
+/// whose address is taken is a possible target of the calls through function
+/// pointers of the same type.
+///
 /// For the current CBMC implementation of function restrictions, see:
 ///     http://cprover.diffblue.com/md__home_travis_build_diffblue_cbmc_doc_architectural_restrict-function-pointer.html
 use crate::codegen_cprover_gotoc::codegen::typ::pointee_type;
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::goto_program::{Stmt, Type};
 use cbmc::InternedString;
-use kani_metadata::{CallSite, PossibleMethodEntry, TraitDefinedMethod, VtableCtxResults};
+use kani_metadata::{
+    CallSite, FnPtrCallSite, PossibleFnPtrEntry, PossibleMethodEntry, TraitDefinedMethod,
+    VtableCtxResults,
+};
 use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::ty::{self, Instance, Ty};
 use tracing::debug;
 
 /// This structure represents data about the vtable that we construct
@@ -30,24 +38,40 @@ pub struct VtableCtx {
     // Option to actually enable restrictions
     pub emit_vtable_restrictions: bool,
 
+    // Option to enable restrictions of calls through function pointers
+    pub emit_fn_ptr_restrictions: bool,
+
     // Map: (normalized trait name, method index) -> possible implementations
     possible_methods: FxHashMap<TraitDefinedMethod, Vec<InternedString>>,
 
     // All sites where a virtual call takes place
     call_sites: Vec<CallSite>,
 
+    // Map: function pointer type -> functions whose address is taken with that type
+    possible_fn_ptrs: FxHashMap<InternedString, Vec<InternedString>>,
+
+    // All sites where a call through a function pointer takes place
+    fn_ptr_call_sites: Vec<FnPtrCallSite>,
+
     // Internal tracing of index needed for call site wrappers
     call_site_global_idx: usize,
 }
 
 /// Constructor
 impl VtableCtx {
-    pub fn new(emit_vtable_restrictions: bool) -> Self {
-        debug!("Restricting vtable function pointers? {:?}", emit_vtable_restrictions);
+    pub fn new(emit_vtable_restrictions: bool, emit_fn_ptr_restrictions: bool) -> Self {
+        debug!(
+            ?emit_vtable_restrictions,
+            ?emit_fn_ptr_restrictions,
+            "Restricting vtable function pointers?"
+        );
         Self {
             emit_vtable_restrictions,
+            emit_fn_ptr_restrictions,
             possible_methods: FxHashMap::default(),
             call_sites: Vec::new(),
+            possible_fn_ptrs: FxHashMap::default(),
+            fn_ptr_call_sites: Vec::new(),
             call_site_global_idx: 0,
         }
     }
@@ -72,6 +96,15 @@ pub fn add_possible_method(
         }
     }
 
+    /// Add a possible target for calls through a function pointer type.
+    pub fn add_possible_fn_ptr(&mut self, fn_ptr_type: InternedString, imp: InternedString) {
+        assert!(self.emit_fn_ptr_restrictions);
+        let possibilities = self.possible_fn_ptrs.entry(fn_ptr_type).or_default();
+        if !possibilities.contains(&imp) {
+            possibilities.push(imp);
+        }
+    }
+
     /// The vtable index for drop
     pub fn drop_index() -> usize {
         rustc_middle::ty::COMMON_VTABLE_ENTRIES_DROPINPLACE
@@ -81,7 +114,7 @@ pub fn drop_index() -> usize {
 /// Internal tracking helpers
 impl VtableCtx {
     fn get_call_site_global_idx(&mut self) -> usize {
-        assert!(self.emit_vtable_restrictions);
+        assert!(self.emit_vtable_restrictions || self.emit_fn_ptr_restrictions);
         self.call_site_global_idx += 1;
         self.call_site_global_idx
     }
@@ -102,6 +135,17 @@ fn add_call_site(
         };
         self.call_sites.push(site);
     }
+
+    /// Add a given call site for a function pointer
+    fn add_fn_ptr_call_site(
+        &mut self,
+        fn_ptr_type: InternedString,
+        function_name: InternedString,
+        label: InternedString,
+    ) {
+        assert!(self.emit_fn_ptr_restrictions);
+        self.fn_ptr_call_sites.push(FnPtrCallSite { fn_ptr_type, function_name, label });
+    }
 }
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -132,6 +176,36 @@ pub fn virtual_call_with_restricted_fn_ptr(
         );
         body.with_label(label)
     }
+
+    /// Create a label to the call site of a function pointer
+    pub fn fn_ptr_call_with_restricted_fn_ptr(&mut self, fn_ptr_ty: Ty<'tcx>, body: Stmt) -> Stmt {
+        assert!(self.vtable_ctx.emit_fn_ptr_restrictions);
+
+        let label: InternedString =
+            format!("restricted_call_label_{}", self.vtable_ctx.get_call_site_global_idx()).into();
+        let fn_ptr_type = self.fn_ptr_restriction_key(fn_ptr_ty);
+        self.vtable_ctx.add_fn_ptr_call_site(fn_ptr_type, self.current_fn().name().into(), label);
+        body.with_label(label)
+    }
+
+    /// Record that `instance` may be called through function pointers of type `fn_ptr_ty`.
+    pub fn add_fn_ptr_target(&mut self, fn_ptr_ty: Ty<'tcx>, instance: Instance<'tcx>) {
+        if self.vtable_ctx.emit_fn_ptr_restrictions {
+            let fn_ptr_type = self.fn_ptr_restriction_key(fn_ptr_ty);
+            let fn_name = self.symbol_name(instance);
+            self.vtable_ctx.add_possible_fn_ptr(fn_ptr_type, fn_name.into());
+        }
+    }
+
+    /// The canonical name of a function pointer type used to match call sites with their
+    /// possible targets. `unsafe fn` and `fn` pointers with the same signature share a key,
+    /// since coercing one into the other doesn't change the function being pointed to.
+    fn fn_ptr_restriction_key(&self, fn_ptr_ty: Ty<'tcx>) -> InternedString {
+        let sig = fn_ptr_ty.fn_sig(self.tcx);
+        let sig = sig.map_bound(|sig| ty::FnSig { unsafety: rustc_hir::Unsafety::Normal, ..sig });
+        let fn_ptr_ty = self.tcx.erase_regions(Ty::new_fn_ptr(self.tcx, sig));
+        self.ty_mangled_name(fn_ptr_ty)
+    }
 }
 
 /// Write out information per crate. We need to later aggregate the information
@@ -139,7 +213,7 @@ pub fn virtual_call_with_restricted_fn_ptr(
 impl VtableCtx {
     /// Write out (1) all call sites and (2) possible concrete methods to JSON.
     pub fn get_virtual_function_restrictions(&mut self) -> VtableCtxResults {
-        assert!(self.emit_vtable_restrictions);
+        assert!(self.emit_vtable_restrictions || self.emit_fn_ptr_restrictions);
 
         VtableCtxResults {
             call_sites: self.call_sites.clone(),
@@ -148,6 +222,12 @@ pub fn get_virtual_function_restrictions(&mut self) -> VtableCtxResults {
                 .drain()
                 .map(|(k, v)| PossibleMethodEntry { trait_method: k, possibilities: v })
                 .collect(),
+            fn_ptr_call_sites: self.fn_ptr_call_sites.clone(),
+            possible_fn_ptrs: self
+                .possible_fn_ptrs
+                .drain()
+                .map(|(k, v)| PossibleFnPtrEntry { fn_ptr_type: k, possibilities: v })
+                .collect(),
         }
     }
 }
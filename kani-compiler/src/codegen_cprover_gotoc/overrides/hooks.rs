@@ -12,7 +12,9 @@
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Type};
+use kani_metadata::SpanEnd;
 use rustc_middle::mir::{BasicBlock, Place};
+use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{Instance, TyCtxt};
 use rustc_span::Span;
@@ -88,6 +90,64 @@ fn handle(
     }
 }
 
+/// A hook for `kani::expect_fail`, which backs the `kani::expect_fail!` macro.
+/// The function takes two arguments: the value of the wrapped expression and the class of the
+/// checks that are expected to fail (&'static str), which is empty for any class.
+/// The hook returns the value unchanged, and emits an `expected_failure` property with the span
+/// of the whole macro call, so the driver can find the checks of the expression.
+struct ExpectFail;
+impl<'tcx> GotocHook<'tcx> for ExpectFail {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniExpectFail")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let value = fargs.remove(0);
+        let class = tcx.extract_const_message(&fargs.remove(0)).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+
+        // The driver needs the end of the span to know which checks belong to the expression,
+        // so it is recorded even if reachability checks are disabled.
+        let check_id = tcx.next_check_id();
+        if let (Some(line), Some(column)) = (caller_loc.end_line(), caller_loc.end_col()) {
+            let end = SpanEnd { line: line as usize, column: column as usize };
+            tcx.check_span_ends.insert(check_id.clone(), end);
+        }
+        let msg = if class.is_empty() {
+            "expected failure".to_string()
+        } else {
+            format!("expected failure of class `{class}`")
+        };
+        let msg = GotocCtx::add_prefix_to_msg(&msg, &check_id);
+
+        Stmt::block(
+            vec![
+                tcx.codegen_assert(
+                    Expr::bool_true(),
+                    PropertyClass::ExpectedFailure,
+                    &msg,
+                    caller_loc,
+                ),
+                unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
+                    .goto_expr
+                    .assign(value, caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
 struct Assume;
 impl<'tcx> GotocHook<'tcx> for Assume {
     fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
@@ -107,10 +167,13 @@ fn handle(
         let cond = fargs.remove(0).cast_to(Type::bool());
         let target = target.unwrap();
         let loc = tcx.codegen_span_option(span);
+        if tcx.ignore_assumption() {
+            return Stmt::goto(tcx.current_fn().find_label(&target), loc);
+        }
 
         Stmt::block(
             vec![
-                tcx.codegen_assume(cond, loc),
+                tcx.codegen_checked_assume(cond, "assumption", span),
                 Stmt::goto(tcx.current_fn().find_label(&target), loc),
             ],
             loc,
@@ -118,10 +181,55 @@ fn handle(
     }
 }
 
-struct Assert;
+/// A hook for `kani::assume_with_message`, which backs the `kani::assume!` macro.
+/// Besides the assumption itself, we emit an `assumption` check before it so the
+/// driver can report whether the assumption is reachable.
+struct AssumeWithMessage;
+impl<'tcx> GotocHook<'tcx> for AssumeWithMessage {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniAssumeWithMessage")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let msg = fargs.remove(0);
+        let msg = tcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+        if tcx.ignore_assumption() {
+            return Stmt::goto(tcx.current_fn().find_label(&target), caller_loc);
+        }
+
+        Stmt::block(
+            vec![
+                tcx.codegen_assert(Expr::bool_false(), PropertyClass::Assumption, &msg, caller_loc),
+                tcx.codegen_checked_assume(cond, &msg, span),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// Hook for `kani::assert`, and for the checks of the `kani` library that have their own
+/// property class.
+struct Assert {
+    diagnostic_item: &'static str,
+    property_class: PropertyClass,
+}
+
 impl<'tcx> GotocHook<'tcx> for Assert {
     fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
-        matches_function(tcx, instance, "KaniAssert")
+        matches_function(tcx, instance, self.diagnostic_item)
     }
 
     fn handle(
@@ -150,7 +258,7 @@ fn handle(
             vec![
                 reach_stmt,
                 decl,
-                tcx.codegen_assert_assume(tmp, PropertyClass::Assertion, &msg, caller_loc),
+                tcx.codegen_assert_assume(tmp, self.property_class.clone(), &msg, caller_loc),
                 Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
             ],
             caller_loc,
@@ -184,6 +292,10 @@ fn handle(
             let pe =
                 unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
                     .goto_expr;
+            let bits = tcx.layout_of(pt).size.bits();
+            let ty = tcx.ty_pretty_name(pt).to_string();
+            // This is only called once per type, by the instance of `any_raw_internal` for it.
+            tcx.nondet_stats.record(&ty, bits);
             Stmt::block(
                 vec![
                     pe.assign(tcx.codegen_ty(pt).nondet(), loc),
@@ -197,14 +309,19 @@ fn handle(
 
 struct Panic;
 
+/// Whether the given instance is one of the functions that start a panic.
+pub fn is_panic_fn(tcx: TyCtxt, instance: Instance) -> bool {
+    let def_id = instance.def.def_id();
+    Some(def_id) == tcx.lang_items().panic_fn()
+        || tcx.has_attr(def_id, rustc_span::sym::rustc_const_panic_str)
+        || Some(def_id) == tcx.lang_items().panic_fmt()
+        || Some(def_id) == tcx.lang_items().begin_panic_fn()
+        || matches_function(tcx, instance, "KaniPanic")
+}
+
 impl<'tcx> GotocHook<'tcx> for Panic {
     fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
-        let def_id = instance.def.def_id();
-        Some(def_id) == tcx.lang_items().panic_fn()
-            || tcx.has_attr(def_id, rustc_span::sym::rustc_const_panic_str)
-            || Some(def_id) == tcx.lang_items().panic_fmt()
-            || Some(def_id) == tcx.lang_items().begin_panic_fn()
-            || matches_function(tcx, instance, "KaniPanic")
+        is_panic_fn(tcx, instance)
     }
 
     fn handle(
@@ -368,17 +485,59 @@ fn handle(
     }
 }
 
+/// A hook for `kani::interrupt::attach`, which registers an interrupt handler that may be
+/// invoked at any interrupt point when `-Z interrupts` is enabled.
+struct AttachInterrupt;
+
+impl<'tcx> GotocHook<'tcx> for AttachInterrupt {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniAttachInterrupt")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let handler = fargs.remove(0);
+        let target = target.unwrap();
+        let loc = tcx.codegen_span_option(span);
+        Stmt::block(
+            vec![
+                tcx.codegen_attach_interrupt(handler, loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
+    }
+}
+
 pub fn fn_hooks<'tcx>() -> GotocHooks<'tcx> {
     GotocHooks {
         hooks: vec![
             Rc::new(Panic),
             Rc::new(Assume),
-            Rc::new(Assert),
+            Rc::new(AssumeWithMessage),
+            Rc::new(Assert {
+                diagnostic_item: "KaniAssert",
+                property_class: PropertyClass::Assertion,
+            }),
+            Rc::new(Assert {
+                diagnostic_item: "KaniSelfBlockingCheck",
+                property_class: PropertyClass::SelfBlocking,
+            }),
             Rc::new(Cover),
+            Rc::new(ExpectFail),
             Rc::new(Nondet),
             Rc::new(RustAlloc),
             Rc::new(MemCmp),
             Rc::new(UntrackedDeref),
+            Rc::new(AttachInterrupt),
         ],
     }
 }
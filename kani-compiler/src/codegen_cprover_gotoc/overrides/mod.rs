@@ -8,4 +8,4 @@
 
 mod hooks;
 
-pub use hooks::{fn_hooks, GotocHooks};
+pub use hooks::{fn_hooks, is_panic_fn, GotocHooks};
@@ -92,6 +92,11 @@ pub fn vtable_field_name(&self, _def_id: DefId, idx: usize) -> InternedString {
         idx.to_string().into()
     }
 
+    /// The name of the vtable field that points to the vtable of a supertrait.
+    pub fn vtable_vptr_field_name(&self, idx: usize) -> InternedString {
+        format!("{idx}_vptr").into()
+    }
+
     /// Add a prefix of the form:
     /// \[`<prefix>`\]
     /// to the provided message
@@ -0,0 +1,390 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Verify the examples of the documentation that are fenced with ```` ```kani ````
+//! (`-Z doc-harnesses`).
+//!
+//! The examples are read from the doc attributes of the items of the crate, i.e. from the `///`
+//! and `//!` comments as parsed by the compiler. A first compilation of the crate generates a
+//! harness per example in a separate source file, next to the other artifacts of the crate. The
+//! crate is then compiled again with that file declared as the `kani_doc_harnesses` module of the
+//! crate root, so failures in an example point to the generated file.
+//!
+//! The examples of the items of a module are generated in a module with the same path, e.g. the
+//! examples of `foo::bar` are in `kani_doc_harnesses::foo`. They can use the items of the crate
+//! through its name, as doc tests do, and the items of their module directly if it can be named
+//! from the crate root. An example becomes a harness named `line_<N>`, where `N` is the line of its
+//! opening fence, unless it declares its own harnesses with `#[kani::proof]`.
+//!
+//! Like in doc tests, lines that start with `# ` are verified but hidden from the documentation.
+
+use crate::args::{Arguments, ReachabilityType};
+use rustc_ast::util::comments::beautify_doc_string;
+use rustc_ast::AttrStyle;
+use rustc_data_structures::sync::Lrc;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{LocalDefId, CRATE_DEF_ID, LOCAL_CRATE};
+use rustc_hir::definitions::DefPathData;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OutputType;
+use rustc_span::source_map::{FileLoader, RealFileLoader};
+use rustc_span::symbol::Ident;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the unstable feature that enables the verification of doc examples.
+const DOC_HARNESSES_FEATURE: &str = "doc-harnesses";
+
+/// The name of the module that contains the harnesses generated for the examples of the crate.
+const DOC_HARNESSES_MODULE: &str = "kani_doc_harnesses";
+
+/// Whether the doc examples of the crate being compiled should be verified. Only the crates
+/// whose harnesses are verified are affected, so the examples of dependencies are ignored.
+pub fn is_enabled(args: &Arguments) -> bool {
+    args.reachability_analysis == ReachabilityType::Harnesses
+        && args.unstable_features.contains(&DOC_HARNESSES_FEATURE.to_string())
+}
+
+/// Generate the file of the module with the harnesses of the doc examples of the crate.
+///
+/// Return the path of the file, or `None` if the crate has no examples to verify.
+pub fn generate_module(tcx: TyCtxt) -> Option<PathBuf> {
+    let mut root = ExampleModule::default();
+    let crate_items = tcx.hir_crate_items(());
+    for def_id in std::iter::once(CRATE_DEF_ID).chain(crate_items.definitions()) {
+        for DocComment { style, file, lines } in doc_comments(tcx, def_id) {
+            let examples = doc_examples(lines.iter().map(|(line, doc)| (*line, doc.as_str())));
+            if examples.is_empty() {
+                continue;
+            }
+            // Inner doc comments (`//!`) document the module that contains them.
+            let module = if style == AttrStyle::Inner && tcx.def_kind(def_id) == DefKind::Mod {
+                def_id
+            } else {
+                tcx.parent_module_from_def_id(def_id).to_local_def_id()
+            };
+            let Some(path) = module_path(tcx, module) else {
+                tcx.sess.span_warn(
+                    tcx.def_span(def_id),
+                    "the doc examples of a module declared in a function are not verified",
+                );
+                continue;
+            };
+            let example_module = root.submodule(&path);
+            example_module.is_nameable = is_nameable(tcx, module);
+            example_module.examples.extend(examples.into_iter().map(|ex| (file.clone(), ex)));
+        }
+    }
+    if root.is_empty() {
+        return None;
+    }
+    let mut source = String::from(
+        "// Harnesses generated by Kani for the doc examples of the crate (`-Z doc-harnesses`).\n\
+        #![allow(dead_code, unused_imports, non_snake_case)]\n",
+    );
+    root.render(tcx.crate_name(LOCAL_CRATE).as_str(), &mut vec![], &mut source);
+    let mut file = tcx.output_filenames(()).output_path(OutputType::Object);
+    file.set_extension(format!("{DOC_HARNESSES_MODULE}.rs"));
+    if let Err(err) = std::fs::write(&file, source) {
+        tcx.sess.err(format!(
+            "failed to write the harnesses of the doc examples to `{}`: {err}",
+            file.display()
+        ));
+        return None;
+    }
+    Some(file)
+}
+
+/// The documentation of an item written with one style of doc comments.
+struct DocComment {
+    style: AttrStyle,
+    /// The source file that contains the comment.
+    file: String,
+    /// The lines of the comment, with their line number.
+    lines: Vec<(usize, String)>,
+}
+
+/// The doc comments of an item. The outer (`///`) and inner (`//!`) comments of a module are
+/// separate pieces of documentation, possibly in different files.
+fn doc_comments(tcx: TyCtxt, def_id: LocalDefId) -> Vec<DocComment> {
+    let mut comments: Vec<DocComment> = vec![];
+    let source_map = tcx.sess.source_map();
+    for attr in tcx.get_attrs_unchecked(def_id.to_def_id()) {
+        let Some((doc, kind)) = attr.doc_str_and_comment_kind() else {
+            continue;
+        };
+        let start = source_map.lookup_char_pos(attr.span.lo());
+        let comment = match comments.iter_mut().position(|comment| comment.style == attr.style) {
+            Some(idx) => &mut comments[idx],
+            None => {
+                let file = start.file.name.prefer_local().to_string();
+                comments.push(DocComment { style: attr.style, file, lines: vec![] });
+                comments.last_mut().unwrap()
+            }
+        };
+        for (idx, line) in beautify_doc_string(doc, kind).as_str().lines().enumerate() {
+            // Line comments keep the space that follows `///`, so strip it.
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            comment.lines.push((start.line + idx, line.to_string()));
+        }
+    }
+    comments
+}
+
+/// The path of a module from the crate root, or `None` if the module is declared in the body of
+/// a function, where its items cannot be named.
+fn module_path(tcx: TyCtxt, module: LocalDefId) -> Option<Vec<String>> {
+    tcx.def_path(module.to_def_id())
+        .data
+        .iter()
+        .map(|segment| match segment.data {
+            DefPathData::TypeNs(name) => Some(Ident::with_dummy_span(name).to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether the items of a module can be imported from the module of the generated harnesses,
+/// i.e. whether the module and all its parents are visible from the crate root.
+fn is_nameable(tcx: TyCtxt, mut module: LocalDefId) -> bool {
+    while module != CRATE_DEF_ID {
+        if !tcx.visibility(module).is_accessible_from(CRATE_DEF_ID, tcx) {
+            return false;
+        }
+        module = tcx.local_parent(module);
+    }
+    true
+}
+
+/// Load the source files of the crate, with the module of the harnesses generated for its doc
+/// examples declared in the crate root.
+pub struct DocHarnessLoader {
+    crate_root: Option<PathBuf>,
+    module: PathBuf,
+}
+
+impl DocHarnessLoader {
+    pub fn new(crate_root: &Path, module: PathBuf) -> Self {
+        DocHarnessLoader { crate_root: crate_root.canonicalize().ok(), module }
+    }
+
+    fn is_crate_root(&self, path: &Path) -> bool {
+        self.crate_root.is_some() && path.canonicalize().ok() == self.crate_root
+    }
+}
+
+impl FileLoader for DocHarnessLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        RealFileLoader.file_exists(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        let mut source = RealFileLoader.read_file(path)?;
+        // The declaration is appended, so the spans of the original code don't change.
+        if self.is_crate_root(path) {
+            let module = self.module.to_string_lossy();
+            write!(source, "\n#[path = {module:?}]\nmod {DOC_HARNESSES_MODULE};\n").unwrap();
+        }
+        Ok(source)
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        RealFileLoader.read_binary_file(path)
+    }
+}
+
+/// A ```` ```kani ```` code block of the documentation.
+#[derive(Debug, PartialEq, Eq)]
+struct DocExample {
+    /// The line of the opening fence.
+    line: usize,
+    /// Whether the fence is marked with `should_panic`, e.g. ```` ```kani,should_panic ````.
+    should_panic: bool,
+    code: String,
+}
+
+/// The state of the code block that is being read.
+enum Fence {
+    None,
+    /// A code block that is not verified, e.g. ```` ```rust ````.
+    Other,
+    Kani(DocExample),
+}
+
+/// Extract the ```` ```kani ```` code blocks from the lines of a doc comment.
+fn doc_examples<'a>(lines: impl IntoIterator<Item = (usize, &'a str)>) -> Vec<DocExample> {
+    let mut examples = vec![];
+    let mut fence = Fence::None;
+    for (line, doc) in lines {
+        match (doc.trim_start().strip_prefix("```"), &mut fence) {
+            (Some(_), Fence::Kani(_)) => {
+                let Fence::Kani(example) = std::mem::replace(&mut fence, Fence::None) else {
+                    unreachable!()
+                };
+                examples.push(example);
+            }
+            (Some(_), Fence::Other) => fence = Fence::None,
+            (Some(info), Fence::None) => {
+                fence = if has_token(info, "kani") {
+                    let should_panic = has_token(info, "should_panic");
+                    Fence::Kani(DocExample { line, should_panic, code: String::new() })
+                } else {
+                    Fence::Other
+                }
+            }
+            (None, Fence::Kani(example)) => {
+                example.code.push_str(unhide(doc));
+                example.code.push('\n');
+            }
+            (None, _) => {}
+        }
+    }
+    // A code block that isn't closed ends with the doc comment.
+    if let Fence::Kani(example) = fence {
+        examples.push(example);
+    }
+    examples
+}
+
+/// Whether the info string of a fence, e.g. `kani,should_panic`, contains the given token.
+fn has_token(info: &str, token: &str) -> bool {
+    info.split(|c: char| c == ',' || c.is_whitespace()).any(|t| t == token)
+}
+
+/// Remove the `#` marker of the lines that are hidden from the documentation.
+fn unhide(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    if trimmed == "#" { "" } else { trimmed.strip_prefix("# ").unwrap_or(line) }
+}
+
+/// The examples of a module of the crate, with the examples of its submodules.
+#[derive(Default)]
+struct ExampleModule {
+    /// The examples of the module, with the source file they are written in.
+    examples: Vec<(String, DocExample)>,
+    /// Whether the items of the module can be imported by its examples.
+    is_nameable: bool,
+    submodules: BTreeMap<String, ExampleModule>,
+}
+
+impl ExampleModule {
+    fn submodule(&mut self, path: &[String]) -> &mut ExampleModule {
+        path.iter().fold(self, |module, name| module.submodules.entry(name.clone()).or_default())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.examples.is_empty() && self.submodules.values().all(ExampleModule::is_empty)
+    }
+
+    /// Generate the items of the module at `path`, with a harness per example.
+    fn render(&self, crate_name: &str, path: &mut Vec<String>, out: &mut String) {
+        if self.is_nameable {
+            let prefix: String = path.iter().flat_map(|name| [name.as_str(), "::"]).collect();
+            writeln!(out, "use crate::{prefix}*;").unwrap();
+        }
+        writeln!(out, "use crate as {crate_name};").unwrap();
+        for (file, DocExample { line, should_panic, code }) in &self.examples {
+            writeln!(out, "\n// The example at {file}:{line}.").unwrap();
+            if code.contains("#[kani::proof") {
+                write!(out, "mod line_{line} {{\nuse super::*;\n{code}}}\n").unwrap();
+            } else {
+                let should_panic = if *should_panic { "#[kani::should_panic]\n" } else { "" };
+                write!(out, "#[kani::proof]\n{should_panic}fn line_{line}() {{\n{code}}}\n")
+                    .unwrap();
+            }
+        }
+        for (name, submodule) in &self.submodules {
+            writeln!(out, "\nmod {name} {{").unwrap();
+            path.push(name.clone());
+            submodule.render(crate_name, path, out);
+            path.pop();
+            out.push_str("}\n");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_doc_examples() {
+        let doc = [
+            "Adds one.",
+            "",
+            "```",
+            "assert_eq!(add_one(1), 2);",
+            "```",
+            "",
+            "```kani,should_panic",
+            "# use foo::add_one;",
+            "#",
+            "add_one(u8::MAX);",
+            "```",
+            "```kani",
+            "#[kani::proof]",
+            "fn unclosed() {}",
+        ];
+        let examples = doc_examples(doc.iter().enumerate().map(|(idx, line)| (idx + 10, *line)));
+        assert_eq!(
+            examples,
+            vec![
+                DocExample {
+                    line: 16,
+                    should_panic: true,
+                    code: "use foo::add_one;\n\nadd_one(u8::MAX);\n".to_string()
+                },
+                DocExample {
+                    line: 21,
+                    should_panic: false,
+                    code: "#[kani::proof]\nfn unclosed() {}\n".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_render() {
+        let example = |line, code: &str| DocExample {
+            line,
+            should_panic: line == 12,
+            code: code.to_string(),
+        };
+        let mut root = ExampleModule {
+            examples: vec![("src/lib.rs".to_string(), example(2, "let x: u8 = kani::any();\n"))],
+            is_nameable: true,
+            ..Default::default()
+        };
+        root.submodule(&["foo".to_string(), "bar".to_string()]).examples.extend([
+            ("src/foo/bar.rs".to_string(), example(12, "foo::add_one(u8::MAX);\n")),
+            ("src/foo/bar.rs".to_string(), example(24, "#[kani::proof]\nfn check() {}\n")),
+        ]);
+        assert!(!root.is_empty());
+        let mut module = String::new();
+        root.render("foo", &mut vec![], &mut module);
+        assert!(module.starts_with("use crate::*;\nuse crate as foo;\n"));
+        assert!(module.contains(
+            "// The example at src/lib.rs:2.\n#[kani::proof]\nfn line_2() {\nlet x: u8 = kani::any();\n}\n"
+        ));
+        // `foo` only has a submodule with examples, and `foo::bar` cannot be named.
+        assert!(
+            module.contains("\nmod foo {\nuse crate as foo;\n\nmod bar {\nuse crate as foo;\n")
+        );
+        assert!(!module.contains("use crate::foo::bar::*;"));
+        assert!(module.contains("#[kani::proof]\n#[kani::should_panic]\nfn line_12() {"));
+        assert!(module.contains("mod line_24 {\nuse super::*;\n#[kani::proof]\nfn check() {}\n}"));
+        assert!(!module.contains("fn line_24()"));
+        assert!(ExampleModule::default().is_empty());
+    }
+
+    #[test]
+    fn check_has_token() {
+        assert!(has_token("kani", "kani"));
+        assert!(has_token("rust,kani", "kani"));
+        assert!(has_token(" kani should_panic", "should_panic"));
+        assert!(!has_token("", "kani"));
+        assert!(!has_token("rust", "kani"));
+        assert!(!has_token("kani_ignore", "kani"));
+    }
+}
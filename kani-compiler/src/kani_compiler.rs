@@ -13,27 +13,34 @@
 //! If no stubs need to be applied, the compiler will proceed to generate goto code, and it won't
 //! need any extra runs. However, if stubs are required, we will have to restart the rustc driver
 //! in order to apply the stubs. For the subsequent runs, we add the stub configuration to
-//! `-C llvm-args`.
+//! `-C llvm-args`. Harnesses whose stubs don't conflict share the same run, so the number of runs
+//! is usually much smaller than the number of different sets of stubs.
+//!
+//! With `-Z doc-harnesses`, the first run only generates the harnesses of the doc examples, and
+//! the crate is compiled again with them before the harnesses are collected.
 
 use crate::args::{Arguments, ReachabilityType};
 #[cfg(feature = "cprover")]
 use crate::codegen_cprover_gotoc::GotocCodegenBackend;
+use crate::doc_harnesses::{self, DocHarnessLoader};
 use crate::kani_middle::attributes::is_proof_harness;
 use crate::kani_middle::check_crate_items;
 use crate::kani_middle::metadata::gen_proof_metadata;
 use crate::kani_middle::reachability::filter_crate_items;
-use crate::kani_middle::stubbing::{self, harness_stub_map};
+use crate::kani_middle::stubbing::{self, default_stubs, harness_stub_map};
+use crate::kani_middle::trusted::check_trusted_harnesses;
+use crate::kani_middle::unsafe_code::collect_unsafe_items;
 use crate::kani_queries::QueryDb;
 use crate::session::init_session;
 use clap::Parser;
-use kani_metadata::{ArtifactType, HarnessMetadata, KaniMetadata};
+use kani_metadata::{ArtifactType, HarnessMetadata, KaniMetadata, UnsafeItem};
 use rustc_codegen_ssa::traits::CodegenBackend;
 use rustc_driver::{Callbacks, Compilation, RunCompiler};
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_hir::definitions::DefPathHash;
 use rustc_interface::Config;
 use rustc_middle::ty::TyCtxt;
-use rustc_session::config::{ErrorOutputType, OutputType};
+use rustc_session::config::{ErrorOutputType, Input, OutputType};
 use rustc_span::ErrorGuaranteed;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
@@ -72,6 +79,10 @@ fn backend(queries: Arc<Mutex<QueryDb>>) -> Box<CodegenBackend> {
 /// A set of stubs.
 type Stubs = BTreeMap<DefPathHash, DefPathHash>;
 
+/// A group of harnesses that are compiled in the same compilation session, with the stubs that
+/// the session applies.
+type StubGroup = (Stubs, Vec<HarnessId>);
+
 #[derive(Clone, Debug)]
 struct HarnessInfo {
     pub metadata: HarnessMetadata,
@@ -85,6 +96,8 @@ struct CrateInfo {
     pub name: String,
     /// The metadata output path that shall be generated as part of the crate compilation.
     pub output_path: PathBuf,
+    /// The `unsafe` blocks and functions written in the crate.
+    pub unsafe_items: Vec<UnsafeItem>,
 }
 
 /// Represents the current compilation stage.
@@ -95,8 +108,11 @@ struct CrateInfo {
 /// - We always start in the [CompilationStage::Init].
 /// - After [CompilationStage::Init] we transition to either
 ///   - [CompilationStage::CodegenNoStubs] on a regular crate compilation, this will follow Init.
+///   - [CompilationStage::DocHarnesses] when the harnesses of the doc examples were generated.
 ///   - [CompilationStage::CompilationSkipped], running the compiler to gather information, such as
 ///     `--version` will skip code generation completely, and there is no work to be done.
+/// - [CompilationStage::DocHarnesses] compiles the crate again right after Init, and we transition
+///   to [CompilationStage::CodegenNoStubs] like Init does.
 /// - After the [CompilationStage::CodegenNoStubs], we transition to either
 ///   - [CompilationStage::CodegenWithStubs] when there is at least one harness with stubs.
 ///   - [CompilationStage::Done] where there is no harness left to process.
@@ -112,7 +128,8 @@ struct CrateInfo {
 /// I.e.:
 /// ```dot
 /// graph CompilationStage {
-///   Init -> {CodegenNoStubs, CompilationSkipped}
+///   Init -> {CodegenNoStubs, DocHarnesses, CompilationSkipped}
+///   DocHarnesses -> CodegenNoStubs
 ///   CodegenNoStubs -> {CodegenStubs, Done}
 ///   // Loop up to N harnesses times.
 ///   CodegenStubs -> {CodegenStubs, Done}
@@ -128,19 +145,25 @@ enum CompilationStage {
     Init,
     /// State where the compiler ran but didn't actually compile anything (e.g.: --version).
     CompilationSkipped,
+    /// Stage where the crate is compiled again with the harnesses generated for its doc examples
+    /// (`-Z doc-harnesses`). The harnesses are collected in this stage instead of Init.
+    DocHarnesses,
     /// Stage where the compiler will perform codegen of all harnesses that don't use stub.
     CodegenNoStubs {
         target_harnesses: Vec<HarnessId>,
-        next_harnesses: Vec<Vec<HarnessId>>,
+        next_harnesses: Vec<StubGroup>,
         all_harnesses: HashMap<HarnessId, HarnessInfo>,
         crate_info: CrateInfo,
     },
     /// Stage where the compiler will codegen harnesses that use stub, one group at a time.
-    /// The harnesses at this stage are grouped according to the stubs they are using. For now,
-    /// we ensure they have the exact same set of stubs.
+    /// The harnesses at this stage are grouped according to the stubs they are using. Groups
+    /// whose stubs don't conflict share a stage, which applies the stubs of all of them. A
+    /// harness that reaches a function that it doesn't stub itself, but that is stubbed in the
+    /// stage, is deferred to a later stage that only applies its own stubs.
     CodegenWithStubs {
         target_harnesses: Vec<HarnessId>,
-        next_harnesses: Vec<Vec<HarnessId>>,
+        stubs: Stubs,
+        next_harnesses: Vec<StubGroup>,
         all_harnesses: HashMap<HarnessId, HarnessInfo>,
         crate_info: CrateInfo,
     },
@@ -167,12 +190,14 @@ struct KaniCompiler {
     pub queries: Arc<Mutex<QueryDb>>,
     /// The state which the compiler is at.
     stage: CompilationStage,
+    /// The file of the module with the harnesses of the doc examples, once it's generated.
+    doc_harnesses: Option<PathBuf>,
 }
 
 impl KaniCompiler {
     /// Create a new [KaniCompiler] instance.
     pub fn new() -> KaniCompiler {
-        KaniCompiler { queries: QueryDb::new(), stage: CompilationStage::Init }
+        KaniCompiler { queries: QueryDb::new(), stage: CompilationStage::Init, doc_harnesses: None }
     }
 
     /// Compile the current crate with the given arguments.
@@ -185,15 +210,19 @@ pub fn run(&mut self, orig_args: Vec<String>) -> Result<(), ErrorGuaranteed> {
             match &self.stage {
                 CompilationStage::Init => {
                     self.run_compilation_session(&orig_args)?;
+                    if matches!(self.stage, CompilationStage::DocHarnesses) {
+                        self.run_compilation_session(&orig_args)?;
+                    }
+                }
+                CompilationStage::DocHarnesses => {
+                    unreachable!("This stage should always run right after Init");
                 }
                 CompilationStage::CodegenNoStubs { .. } => {
                     unreachable!("This stage should always run in the same session as Init");
                 }
-                CompilationStage::CodegenWithStubs { target_harnesses, all_harnesses, .. } => {
+                CompilationStage::CodegenWithStubs { target_harnesses, stubs, .. } => {
                     assert!(!target_harnesses.is_empty(), "expected at least one target harness");
-                    let target_harness = &target_harnesses[0];
-                    let extra_arg =
-                        stubbing::mk_rustc_arg(&all_harnesses[&target_harness].stub_map);
+                    let extra_arg = stubbing::mk_rustc_arg(stubs);
                     let mut args = orig_args.clone();
                     args.push(extra_arg);
                     self.run_compilation_session(&args)?;
@@ -225,7 +254,7 @@ pub fn run(&mut self, orig_args: Vec<String>) -> Result<(), ErrorGuaranteed> {
     /// Set up the next compilation stage after a `rustc` run.
     fn next_stage(&mut self) {
         self.stage = match &mut self.stage {
-            CompilationStage::Init => {
+            CompilationStage::Init | CompilationStage::DocHarnesses => {
                 // This may occur when user passes arguments like --version or --help.
                 CompilationStage::Done { metadata: None }
             }
@@ -238,10 +267,49 @@ fn next_stage(&mut self) {
                 crate_info,
                 ..
             } => {
-                if let Some(target_harnesses) = next_harnesses.pop() {
+                // Record the codegen time, the nondeterministic values, the check spans and the
+                // unsafe and trusted code reached by the harnesses compiled in the previous stage.
+                let mut queries = self.queries.lock().unwrap();
+                let codegen_times = mem::take(&mut queries.codegen_times);
+                let nondet_stats = mem::take(&mut queries.nondet_stats);
+                let check_span_ends = mem::take(&mut queries.check_span_ends);
+                let reached_unsafe = mem::take(&mut queries.reached_unsafe);
+                let trusted_code = mem::take(&mut queries.trusted_code);
+                let deferred = mem::take(&mut queries.deferred_harnesses);
+                drop(queries);
+                for (harness, time) in codegen_times {
+                    if let Some(info) = all_harnesses.get_mut(&harness) {
+                        info.metadata.codegen_time = Some(time);
+                    }
+                }
+                for (harness, stats) in nondet_stats {
+                    if let Some(info) = all_harnesses.get_mut(&harness) {
+                        info.metadata.nondet_stats = Some(stats);
+                    }
+                }
+                for (harness, span_ends) in check_span_ends {
+                    if let Some(info) = all_harnesses.get_mut(&harness) {
+                        info.metadata.check_span_ends = span_ends;
+                    }
+                }
+                for (harness, reached) in reached_unsafe {
+                    if let Some(info) = all_harnesses.get_mut(&harness) {
+                        info.metadata.reached_unsafe = reached;
+                    }
+                }
+                for (harness, trusted) in trusted_code {
+                    if let Some(info) = all_harnesses.get_mut(&harness) {
+                        info.metadata.trusted_code = trusted;
+                    }
+                }
+                // Deferred harnesses are compiled with their own stubs only, so they cannot be
+                // deferred again.
+                next_harnesses.extend(exact_stub_groups(deferred, all_harnesses));
+                if let Some((stubs, target_harnesses)) = next_harnesses.pop() {
                     assert!(!target_harnesses.is_empty(), "expected at least one target harness");
                     CompilationStage::CodegenWithStubs {
                         target_harnesses,
+                        stubs,
                         next_harnesses: mem::take(next_harnesses),
                         all_harnesses: mem::take(all_harnesses),
                         crate_info: crate_info.clone(),
@@ -275,18 +343,21 @@ fn process_harnesses(&self, tcx: TyCtxt) -> CompilationStage {
         let crate_info = CrateInfo {
             name: tcx.crate_name(LOCAL_CRATE).as_str().into(),
             output_path: metadata_output_path(tcx),
+            unsafe_items: collect_unsafe_items(tcx),
         };
         if self.queries.lock().unwrap().args().reachability_analysis == ReachabilityType::Harnesses
         {
             let base_filename = tcx.output_filenames(()).output_path(OutputType::Object);
             let harnesses = filter_crate_items(tcx, |_, def_id| is_proof_harness(tcx, def_id));
+            let default_stubs =
+                default_stubs(&self.queries.lock().unwrap().args().unstable_features);
             let all_harnesses = harnesses
                 .into_iter()
                 .map(|harness| {
                     let def_id = harness.def_id();
                     let def_path = tcx.def_path_hash(def_id);
                     let metadata = gen_proof_metadata(tcx, def_id, &base_filename);
-                    let stub_map = harness_stub_map(tcx, def_id, &metadata);
+                    let stub_map = harness_stub_map(tcx, def_id, &metadata, &default_stubs);
                     (def_path, HarnessInfo { metadata, stub_map })
                 })
                 .collect::<HashMap<_, _>>();
@@ -306,7 +377,7 @@ fn process_harnesses(&self, tcx: TyCtxt) -> CompilationStage {
             // Even if no_stubs is empty we still need to store rustc metadata.
             CompilationStage::CodegenNoStubs {
                 target_harnesses: no_stubs,
-                next_harnesses: group_by_stubs(with_stubs, &all_harnesses),
+                next_harnesses: merge_stub_groups(exact_stub_groups(with_stubs, &all_harnesses)),
                 all_harnesses,
                 crate_info,
             }
@@ -325,30 +396,53 @@ fn process_harnesses(&self, tcx: TyCtxt) -> CompilationStage {
     fn prepare_codegen(&mut self) -> Compilation {
         debug!(stage=?self.stage, "prepare_codegen");
         match &self.stage {
-            CompilationStage::CodegenNoStubs { target_harnesses, all_harnesses, .. }
-            | CompilationStage::CodegenWithStubs { target_harnesses, all_harnesses, .. } => {
-                debug!(
-                    harnesses=?target_harnesses
-                        .iter()
-                        .map(|h| &all_harnesses[h].metadata.pretty_name)
-                        .collect::<Vec<_>>(),
-                        "prepare_codegen"
-                );
-                let queries = &mut (*self.queries.lock().unwrap());
-                queries.harnesses_info = target_harnesses
-                    .iter()
-                    .map(|harness| {
-                        (*harness, all_harnesses[harness].metadata.goto_file.clone().unwrap())
-                    })
-                    .collect();
+            CompilationStage::CodegenNoStubs { target_harnesses, all_harnesses, .. } => {
+                self.set_target_harnesses(target_harnesses, all_harnesses, &Stubs::default());
+                Compilation::Continue
+            }
+            CompilationStage::CodegenWithStubs {
+                target_harnesses, all_harnesses, stubs, ..
+            } => {
+                self.set_target_harnesses(target_harnesses, all_harnesses, stubs);
                 Compilation::Continue
             }
             CompilationStage::Init
+            | CompilationStage::DocHarnesses
             | CompilationStage::Done { .. }
             | CompilationStage::CompilationSkipped => unreachable!(),
         }
     }
 
+    /// Configure the queries to generate code for the given harnesses, in a compilation session
+    /// that applies the given stubs.
+    fn set_target_harnesses(
+        &self,
+        target_harnesses: &[HarnessId],
+        all_harnesses: &HashMap<HarnessId, HarnessInfo>,
+        stubs: &Stubs,
+    ) {
+        debug!(
+            harnesses=?target_harnesses
+                .iter()
+                .map(|h| &all_harnesses[h].metadata.pretty_name)
+                .collect::<Vec<_>>(),
+            ?stubs,
+            "set_target_harnesses"
+        );
+        let queries = &mut (*self.queries.lock().unwrap());
+        queries.harnesses_info = target_harnesses
+            .iter()
+            .map(|harness| (*harness, all_harnesses[harness].metadata.goto_file.clone().unwrap()))
+            .collect();
+        queries.foreign_stubs = target_harnesses
+            .iter()
+            .map(|harness| {
+                let own = &all_harnesses[harness].stub_map;
+                (*harness, stubs.keys().filter(|orig| !own.contains_key(orig)).cloned().collect())
+            })
+            .collect();
+    }
+
     /// Write the metadata to a file
     fn store_metadata(&self, metadata: &KaniMetadata, filename: &Path) {
         debug!(?filename, "write_metadata");
@@ -374,6 +468,47 @@ fn group_by_stubs(
     per_stubs.into_values().collect()
 }
 
+/// Group the harnesses by their stubs, and compile each group with its own stubs only.
+fn exact_stub_groups(
+    harnesses: Vec<HarnessId>,
+    all_harnesses: &HashMap<HarnessId, HarnessInfo>,
+) -> Vec<StubGroup> {
+    group_by_stubs(harnesses, all_harnesses)
+        .into_iter()
+        .map(|group| (all_harnesses[&group[0]].stub_map.clone(), group))
+        .collect()
+}
+
+/// Whether two sets of stubs conflict, i.e., whether they replace the same function by
+/// different stubs, or one replaces a function that the other uses as a stub.
+fn stubs_conflict(lhs: &Stubs, rhs: &Stubs) -> bool {
+    let one_way = |a: &Stubs, b: &Stubs| {
+        a.iter().any(|(orig, stub)| {
+            b.get(orig).is_some_and(|other| other != stub)
+                || (b.contains_key(stub) && !a.contains_key(stub))
+        })
+    };
+    one_way(lhs, rhs) || one_way(rhs, lhs)
+}
+
+/// Merge the groups of harnesses whose stubs don't conflict, so they can be compiled in the same
+/// compilation session instead of paying for a full compiler run per group.
+fn merge_stub_groups(groups: Vec<StubGroup>) -> Vec<StubGroup> {
+    let mut merged: Vec<StubGroup> = vec![];
+    for (stubs, harnesses) in groups {
+        let compatible =
+            merged.iter_mut().find(|(merged_stubs, _)| !stubs_conflict(merged_stubs, &stubs));
+        match compatible {
+            Some((merged_stubs, merged_harnesses)) => {
+                merged_stubs.extend(stubs);
+                merged_harnesses.extend(harnesses);
+            }
+            None => merged.push((stubs, harnesses)),
+        }
+    }
+    merged
+}
+
 /// Use default function implementations.
 impl Callbacks for KaniCompiler {
     /// Configure the [KaniCompiler] `self` object during the [CompilationStage::Init].
@@ -390,6 +525,11 @@ fn config(&mut self, config: &mut Config) {
 
             debug!(?queries, "config end");
         }
+        // The file loader is needed by every run of the compiler after the harnesses of the doc
+        // examples are generated, since each one reads the source files again.
+        if let (Some(module), Input::File(crate_root)) = (&self.doc_harnesses, &config.input) {
+            config.file_loader = Some(Box::new(DocHarnessLoader::new(crate_root, module.clone())));
+        }
     }
 
     /// During the initialization state, we collect the crate harnesses and prepare for codegen.
@@ -398,9 +538,20 @@ fn after_analysis<'tcx>(
         _compiler: &rustc_interface::interface::Compiler,
         rustc_queries: &'tcx rustc_interface::Queries<'tcx>,
     ) -> Compilation {
-        if self.stage.is_init() {
+        if self.stage.is_init() && doc_harnesses::is_enabled(self.queries.lock().unwrap().args()) {
+            let module = rustc_queries.global_ctxt().unwrap().enter(doc_harnesses::generate_module);
+            if module.is_some() {
+                self.doc_harnesses = module;
+                self.stage = CompilationStage::DocHarnesses;
+                return Compilation::Stop;
+            }
+        }
+        if matches!(self.stage, CompilationStage::Init | CompilationStage::DocHarnesses) {
             self.stage = rustc_queries.global_ctxt().unwrap().enter(|tcx| {
-                check_crate_items(tcx, self.queries.lock().unwrap().args().ignore_global_asm);
+                let queries = self.queries.lock().unwrap();
+                check_trusted_harnesses(tcx, &queries.args().trusted_paths);
+                check_crate_items(tcx, queries.args().ignore_global_asm);
+                drop(queries);
                 self.process_harnesses(tcx)
             });
         }
@@ -424,6 +575,7 @@ fn generate_metadata(
         proof_harnesses,
         unsupported_features: vec![],
         test_harnesses,
+        unsafe_items: crate_info.unsafe_items.clone(),
     }
 }
 
@@ -440,7 +592,7 @@ mod tests {
     use kani_metadata::{HarnessAttributes, HarnessMetadata};
     use rustc_data_structures::fingerprint::Fingerprint;
     use rustc_hir::definitions::DefPathHash;
-    use std::collections::HashMap;
+    use std::collections::{BTreeSet, HashMap};
 
     fn mock_next_id() -> HarnessId {
         static mut COUNTER: u64 = 0;
@@ -459,6 +611,12 @@ fn mock_metadata(name: String, krate: String) -> HarnessMetadata {
             original_end_line: 20,
             goto_file: None,
             attributes: HarnessAttributes::default(),
+            doc: None,
+            codegen_time: None,
+            nondet_stats: None,
+            check_span_ends: BTreeMap::new(),
+            reached_unsafe: BTreeSet::new(),
+            trusted_code: BTreeSet::new(),
         }
     }
 
@@ -504,11 +662,40 @@ fn test_group_by_stubs_works() {
         assert!(grouped.contains(&vec![harness_2]));
     }
 
+    #[test]
+    fn test_merge_stub_groups() {
+        let harness_1 = mock_next_id();
+        let harness_2 = mock_next_id();
+        let harness_3 = mock_next_id();
+        let harness_4 = mock_next_id();
+
+        let (orig_1, orig_2, stub_1, stub_2) =
+            (mock_next_id(), mock_next_id(), mock_next_id(), mock_next_id());
+        let groups = vec![
+            (Stubs::from([(orig_1, stub_1)]), vec![harness_1]),
+            (Stubs::from([(orig_2, stub_2)]), vec![harness_2]),
+            // Replaces the same function by a different stub.
+            (Stubs::from([(orig_1, stub_2)]), vec![harness_3]),
+            // Replaces a function that other harnesses use as a stub.
+            (Stubs::from([(stub_1, orig_1)]), vec![harness_4]),
+        ];
+        let merged = merge_stub_groups(groups);
+        assert_eq!(
+            merged,
+            vec![
+                (Stubs::from([(orig_1, stub_1), (orig_2, stub_2)]), vec![harness_1, harness_2]),
+                (Stubs::from([(orig_1, stub_2)]), vec![harness_3]),
+                (Stubs::from([(stub_1, orig_1)]), vec![harness_4]),
+            ]
+        );
+    }
+
     #[test]
     fn test_generate_metadata() {
         // Mock inputs.
         let name = "my_crate".to_string();
-        let crate_info = CrateInfo { name: name.clone(), output_path: PathBuf::default() };
+        let crate_info =
+            CrateInfo { name: name.clone(), output_path: PathBuf::default(), unsafe_items: vec![] };
 
         let mut info = mock_info_with_stubs(Stubs::default());
         info.metadata.attributes.proof = true;
@@ -528,7 +715,8 @@ fn test_generate_metadata() {
     fn test_generate_empty_metadata() {
         // Mock inputs.
         let name = "my_crate".to_string();
-        let crate_info = CrateInfo { name: name.clone(), output_path: PathBuf::default() };
+        let crate_info =
+            CrateInfo { name: name.clone(), output_path: PathBuf::default(), unsafe_items: vec![] };
         let all_harnesses = HashMap::new();
 
         // Call generate metadata.
@@ -543,7 +731,11 @@ fn test_generate_empty_metadata() {
     fn test_generate_metadata_with_multiple_harness() {
         // Mock inputs.
         let krate = "my_crate".to_string();
-        let crate_info = CrateInfo { name: krate.clone(), output_path: PathBuf::default() };
+        let crate_info = CrateInfo {
+            name: krate.clone(),
+            output_path: PathBuf::default(),
+            unsafe_items: vec![],
+        };
 
         let harnesses = ["h1", "h2", "h3"];
         let infos = harnesses.map(|harness| {
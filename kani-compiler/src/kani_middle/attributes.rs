@@ -3,6 +3,7 @@
 //! This module contains code for processing Rust attributes (like `kani::proof`).
 
 use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use kani_metadata::{CbmcSolver, HarnessAttributes, Stub};
 use rustc_ast::{
@@ -13,7 +14,7 @@
 use rustc_hir::{def::DefKind, def_id::DefId};
 use rustc_middle::ty::{Instance, TyCtxt, TyKind};
 use rustc_session::Session;
-use rustc_span::{Span, Symbol};
+use rustc_span::{FileName, Span, Symbol};
 use std::str::FromStr;
 use strum_macros::{AsRefStr, EnumString};
 
@@ -24,10 +25,14 @@
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, PartialEq, Eq, PartialOrd, Ord)]
 #[strum(serialize_all = "snake_case")]
 enum KaniAttributeKind {
+    /// A file with the lines that the output of a harness must contain.
+    ExpectOutput,
     Proof,
     ShouldPanic,
     Solver,
     Stub,
+    /// The verification timeout of a harness, given with `#[kani::proof(timeout = <seconds>)]`.
+    Timeout,
     /// Attribute used to mark unstable APIs.
     Unstable,
     Unwind,
@@ -55,11 +60,13 @@ impl KaniAttributeKind {
     pub fn is_harness_only(self) -> bool {
         match self {
             KaniAttributeKind::Proof
+            | KaniAttributeKind::ExpectOutput
             | KaniAttributeKind::ShouldPanic
             | KaniAttributeKind::Solver
             | KaniAttributeKind::Stub
             | KaniAttributeKind::ProofForContract
             | KaniAttributeKind::StubVerified
+            | KaniAttributeKind::Timeout
             | KaniAttributeKind::Unwind => true,
             KaniAttributeKind::Unstable
             | KaniAttributeKind::ReplacedWith
@@ -247,6 +254,12 @@ pub(super) fn check_attributes(&self) {
                         parse_solver(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::ExpectOutput => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_expected_output(self.tcx, self.item, attr);
+                    })
+                }
                 KaniAttributeKind::Stub => {
                     parse_stubs(self.tcx, self.item, attrs);
                 }
@@ -256,6 +269,12 @@ pub(super) fn check_attributes(&self) {
                         parse_unwind(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::Timeout => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_timeout(self.tcx, attr);
+                    })
+                }
                 KaniAttributeKind::Proof => {
                     if self.map.contains_key(&KaniAttributeKind::ProofForContract) {
                         local_error(
@@ -274,7 +293,14 @@ pub(super) fn check_attributes(&self) {
                             "`proof` and `proof_for_contract` may not be used on the same function.".to_string(),
                         );
                     }
+                    if self.map.contains_key(&KaniAttributeKind::ShouldPanic) {
+                        local_error(
+                            "`should_panic` may not be used on a `proof_for_contract` harness"
+                                .to_string(),
+                        );
+                    }
                     expect_single(self.tcx, kind, &attrs);
+                    self.check_proof_for_contract_stubs(attrs[0]);
                 }
                 KaniAttributeKind::StubVerified => {
                     expect_single(self.tcx, kind, &attrs);
@@ -374,12 +400,19 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                 KaniAttributeKind::Solver => {
                     harness.solver = parse_solver(self.tcx, attributes[0]);
                 }
+                KaniAttributeKind::ExpectOutput => {
+                    harness.expected_output =
+                        parse_expected_output(self.tcx, self.item, attributes[0]);
+                }
                 KaniAttributeKind::Stub => {
                     harness.stubs.extend_from_slice(&parse_stubs(self.tcx, self.item, attributes));
                 }
                 KaniAttributeKind::Unwind => {
                     harness.unwind_value = parse_unwind(self.tcx, attributes[0])
                 }
+                KaniAttributeKind::Timeout => {
+                    harness.timeout = parse_timeout(self.tcx, attributes[0])
+                }
                 KaniAttributeKind::Proof => harness.proof = true,
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(&mut harness),
                 KaniAttributeKind::StubVerified => self.handle_stub_verified(&mut harness),
@@ -419,6 +452,7 @@ fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
             .emit();
             return;
         };
+        harness.for_contract = Some(self.tcx.def_path_str(id));
         harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
     }
 
@@ -462,6 +496,40 @@ fn item_name(&self) -> Symbol {
         self.tcx.item_name(self.item)
     }
 
+    /// Check that a contract harness doesn't stub the function whose contract it checks, with
+    /// either `stub` or `stub_verified`.
+    ///
+    /// The `proof_for_contract` macro reports the stubs that follow it, but only the compiler sees
+    /// the ones written before it. Paths that cannot be resolved are reported elsewhere.
+    fn check_proof_for_contract_stubs(&self, proof_for_contract: &Attribute) {
+        let resolve = |name: &str| self.resolve_sibling(name).ok();
+        let Some(target) = proof_for_contract.value_str().and_then(|name| resolve(name.as_str()))
+        else {
+            return;
+        };
+        let stubs = self.map.get(&KaniAttributeKind::Stub).into_iter().flatten().filter(|attr| {
+            let original = parse_paths(attr).ok().and_then(|paths| paths.first().cloned());
+            original.is_some_and(|original| resolve(&original) == Some(target))
+        });
+        let verified_stubs =
+            self.map.get(&KaniAttributeKind::StubVerified).into_iter().flatten().filter(|attr| {
+                attr.value_str().is_some_and(|name| resolve(name.as_str()) == Some(target))
+            });
+        for attr in stubs.chain(verified_stubs) {
+            self.tcx
+                .sess
+                .struct_span_err(
+                    attr.span,
+                    "the target of `proof_for_contract` cannot be stubbed by its harness",
+                )
+                .help(format!(
+                    "remove this attribute to check the contract of `{}`",
+                    self.tcx.def_path_str(target)
+                ))
+                .emit();
+        }
+    }
+
     /// Check that if this item is tagged with a proof_attribute, it is a valid harness.
     fn check_proof_attribute(&self, proof_attribute: &Attribute) {
         let span = proof_attribute.span;
@@ -680,6 +748,20 @@ fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     }
 }
 
+/// Return the timeout in seconds from the given attribute.
+fn parse_timeout(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
+    match parse_integer(attr).and_then(|timeout| u32::try_from(timeout).ok()) {
+        Some(timeout) if timeout > 0 => Some(timeout),
+        _ => {
+            tcx.sess.span_err(
+                attr.span,
+                "invalid argument for `timeout`, expected a positive number of seconds",
+            );
+            None
+        }
+    }
+}
+
 fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
     let current_module = tcx.parent_module_from_def_id(harness.expect_local());
     let check_resolve = |attr: &Attribute, name: &str| {
@@ -771,6 +853,42 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     }
 }
 
+/// Return the path of the file given to `#[kani::expect_output]`.
+///
+/// Relative paths are resolved against the directory of the source file that declares the
+/// harness, like the paths given to `include_str!`.
+fn parse_expected_output(tcx: TyCtxt, harness: DefId, attr: &Attribute) -> Option<PathBuf> {
+    let file = match attr.meta_item_list().as_deref() {
+        Some([arg]) => arg.lit().filter(|lit| lit.kind.is_str()).map(|lit| lit.symbol.to_string()),
+        _ => None,
+    };
+    let Some(file) = file else {
+        tcx.sess.span_err(
+            attr.span,
+            "invalid argument for `expect_output` attribute, expected the path of a file \
+            as a string literal",
+        );
+        return None;
+    };
+    let source_file =
+        tcx.sess.source_map().span_to_filename(tcx.def_span(harness).source_callsite());
+    let source_dir = match &source_file {
+        FileName::Real(name) => name.local_path().and_then(Path::parent),
+        _ => None,
+    };
+    let path = source_dir.unwrap_or(Path::new("")).join(file);
+    match path.canonicalize() {
+        Ok(path) => Some(path),
+        Err(err) => {
+            tcx.sess.span_err(
+                attr.span,
+                format!("failed to find expected output file `{}`: {err}", path.display()),
+            );
+            None
+        }
+    }
+}
+
 /// Extracts the integer value argument from the attribute provided
 /// For example, `unwind(8)` return `Some(8)`
 fn parse_integer(attr: &Attribute) -> Option<u128> {
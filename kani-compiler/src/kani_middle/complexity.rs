@@ -0,0 +1,175 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Lints for code that is known to be expensive to verify (`-Z complexity-lints`).
+//!
+//! The lints warn about calls from the local crate into library functions that transitively
+//! reach formatting or hashing machinery, and about calls that allocate memory inside a loop.
+//! Each warning points at the call in the local crate, since this is where users can stub or
+//! bound the expensive code.
+//!
+//! # Performance Impact
+//!
+//! This analysis builds the call graph of every reachable function and iterates over it until
+//! it reaches a fixed point. It should only be run when the lints are enabled.
+
+use crate::kani_middle::resolve::resolve_fn;
+use rustc_hir::def_id::{DefId, CRATE_DEF_ID};
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::{BasicBlock, Body, TerminatorKind};
+use rustc_middle::ty::{EarlyBinder, Instance, InstanceDef, ParamEnv, TyCtxt, TyKind};
+use rustc_span::Span;
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+
+/// The kinds of code that are known to be expensive to verify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Expensive {
+    Formatting,
+    Hashing,
+    Allocation,
+}
+
+impl Expensive {
+    const ALL: [Expensive; 3] = [Expensive::Formatting, Expensive::Hashing, Expensive::Allocation];
+
+    /// The library functions that identify this kind of code.
+    fn paths(self) -> &'static [&'static str] {
+        match self {
+            Expensive::Formatting => &[
+                "core::fmt::write",
+                "core::fmt::Formatter::pad",
+                "core::fmt::Formatter::pad_integral",
+            ],
+            Expensive::Hashing => &[
+                "core::hash::SipHasher13::new_with_keys",
+                "std::collections::hash_map::RandomState::new",
+            ],
+            Expensive::Allocation => {
+                &["alloc::alloc::alloc", "alloc::alloc::alloc_zeroed", "alloc::alloc::realloc"]
+            }
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Expensive::Formatting => {
+                "formatting values is expensive to verify, consider stubbing this call or \
+                only making it when `kani::cfg!(playback)` is true"
+            }
+            Expensive::Hashing => {
+                "hashing is expensive to verify, consider stubbing the hasher or using a \
+                collection that doesn't hash its keys, e.g. `BTreeMap`"
+            }
+            Expensive::Allocation => {
+                "each iteration of the loop may allocate memory, consider bounding the loop or \
+                reserving the memory before it"
+            }
+        }
+    }
+}
+
+/// A call in the body of a function.
+struct Call<'tcx> {
+    block: BasicBlock,
+    callee: Instance<'tcx>,
+    span: Span,
+}
+
+/// Warn about the calls in the local crate that reach code that is known to be expensive to
+/// verify. Only the given items are analyzed.
+pub fn lint_items<'tcx>(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) {
+    let targets: HashMap<DefId, Expensive> = Expensive::ALL
+        .into_iter()
+        .flat_map(|kind| kind.paths().iter().map(move |path| (kind, path)))
+        .filter_map(|(kind, path)| Some((resolve_fn(tcx, CRATE_DEF_ID, path).ok()?, kind)))
+        .collect();
+    debug!(?targets, "lint_items");
+
+    let calls: Vec<(Instance<'tcx>, Vec<Call<'tcx>>)> = items
+        .iter()
+        .filter_map(|item| if let MonoItem::Fn(instance) = item { Some(*instance) } else { None })
+        .map(|instance| (instance, collect_calls(tcx, instance)))
+        .collect();
+
+    // Map each function that reaches expensive code to the expensive function it reaches.
+    let mut reaches: HashMap<(Instance<'tcx>, Expensive), DefId> = calls
+        .iter()
+        .filter_map(|(instance, _)| {
+            let def_id = instance.def_id();
+            targets.get(&def_id).map(|kind| ((*instance, *kind), def_id))
+        })
+        .collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (caller, caller_calls) in &calls {
+            for call in caller_calls {
+                for kind in Expensive::ALL {
+                    if let Some(witness) = reaches.get(&(call.callee, kind)).copied() {
+                        changed |= reaches.insert((*caller, kind), witness).is_none();
+                    }
+                }
+            }
+        }
+    }
+
+    for (caller, caller_calls) in calls.iter().filter(|(caller, _)| caller.def_id().is_local()) {
+        let body = tcx.instance_mir(caller.def);
+        for call in caller_calls.iter().filter(|call| !call.callee.def_id().is_local()) {
+            for kind in Expensive::ALL {
+                let Some(witness) = reaches.get(&(call.callee, kind)) else { continue };
+                let callee = tcx.def_path_str(call.callee.def_id());
+                let msg = match kind {
+                    Expensive::Allocation if is_in_loop(body, call.block) => {
+                        format!("call to `{callee}` allocates memory inside a loop")
+                    }
+                    Expensive::Allocation => continue,
+                    Expensive::Formatting | Expensive::Hashing => format!(
+                        "call to `{callee}` reaches `{}`, which is expensive to verify",
+                        tcx.def_path_str(*witness)
+                    ),
+                };
+                tcx.sess.struct_span_warn(call.span, msg).help(kind.help()).emit();
+            }
+        }
+    }
+}
+
+/// Collect the calls to statically known functions in the body of the given instance.
+fn collect_calls<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Vec<Call<'tcx>> {
+    let body = tcx.instance_mir(instance.def);
+    body.basic_blocks
+        .iter_enumerated()
+        .filter_map(|(block, data)| {
+            let terminator = data.terminator();
+            let TerminatorKind::Call { func, .. } = &terminator.kind else { return None };
+            let fn_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                ParamEnv::reveal_all(),
+                EarlyBinder::bind(func.ty(body, tcx)),
+            );
+            let TyKind::FnDef(def_id, args) = *fn_ty.kind() else { return None };
+            let callee = Instance::resolve(tcx, ParamEnv::reveal_all(), def_id, args).ok()??;
+            (!matches!(callee.def, InstanceDef::Virtual(..))).then_some(Call {
+                block,
+                callee,
+                span: terminator.source_info.span,
+            })
+        })
+        .collect()
+}
+
+/// Whether the given block is part of a cycle in the control flow graph of the body.
+fn is_in_loop(body: &Body, block: BasicBlock) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack: Vec<_> = body.basic_blocks[block].terminator().successors().collect();
+    while let Some(next) = stack.pop() {
+        if next == block {
+            return true;
+        }
+        if visited.insert(next) {
+            stack.extend(body.basic_blocks[next].terminator().successors());
+        }
+    }
+    false
+}
@@ -0,0 +1,146 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Lints for the operations of a `const fn` whose result may differ between compile-time
+//! evaluation (CTFE) and runtime (`-Z const-fn-parity`).
+//!
+//! Kani verifies the runtime semantics of a `const fn`, i.e. the code that is executed when a
+//! harness calls it. The values of constants, e.g. lookup tables, are computed by CTFE instead,
+//! and the interpreter only agrees with the runtime for most operations:
+//!  * Floating-point arithmetic that produces a NaN may produce a different NaN, i.e. with
+//!    another sign or payload, at compile time than at runtime.
+//!  * A pointer cannot be transmuted to an integer at compile time, so the evaluation of a
+//!    constant fails where the runtime succeeds. Casts are already rejected by the compiler.
+//!
+//! The lints warn about these operations in the local `const fn`s, using the body that CTFE
+//! evaluates. Floating-point operations are common in `const fn`s that are only called at
+//! runtime, so they are only reported in the `const fn`s that the initializer of a local constant
+//! or static calls, directly or through other `const fn`s. The values themselves are compared by the harnesses that `#[kani::const_parity]`
+//! generates for the constants it marks, which are enabled by the same option.
+
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::BodyOwnerKind;
+use rustc_middle::mir::visit::Visitor as MirVisitor;
+use rustc_middle::mir::{BinOp, Body, CastKind, Location, Rvalue, TerminatorKind};
+use rustc_middle::ty::{Ty, TyCtxt};
+use std::collections::HashSet;
+use tracing::debug;
+
+/// Warn about the operations of the local `const fn`s whose result may differ between CTFE and
+/// runtime.
+pub fn lint_const_fns(tcx: TyCtxt) {
+    let evaluated = const_evaluated_fns(tcx);
+    for def_id in tcx.hir().body_owners() {
+        if !tcx.is_const_fn_raw(def_id.to_def_id()) {
+            continue;
+        }
+        debug!(?def_id, "lint_const_fns");
+        let body = tcx.mir_for_ctfe(def_id);
+        let lint_floats = evaluated.contains(&def_id);
+        ConstFnLinter { tcx, def_id, body, lint_floats }.visit_body(body);
+    }
+}
+
+/// The local `const fn`s that are called by the initializer of a local constant or static,
+/// directly or through other `const fn`s, i.e. the ones that are evaluated at compile time.
+fn const_evaluated_fns(tcx: TyCtxt) -> HashSet<LocalDefId> {
+    let mut pending: Vec<LocalDefId> = tcx
+        .hir()
+        .body_owners()
+        .filter(|def_id| {
+            matches!(
+                tcx.hir().body_owner_kind(*def_id),
+                BodyOwnerKind::Const { .. } | BodyOwnerKind::Static(_)
+            )
+        })
+        .collect();
+    let mut evaluated = HashSet::new();
+    while let Some(def_id) = pending.pop() {
+        for data in tcx.mir_for_ctfe(def_id).basic_blocks.iter() {
+            let TerminatorKind::Call { func, .. } = &data.terminator().kind else { continue };
+            let Some((callee, _)) = func.const_fn_def() else { continue };
+            let Some(callee) = callee.as_local() else { continue };
+            if tcx.is_const_fn_raw(callee.to_def_id()) && evaluated.insert(callee) {
+                pending.push(callee);
+            }
+        }
+    }
+    evaluated
+}
+
+struct ConstFnLinter<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    body: &'a Body<'tcx>,
+    /// Whether to warn about floating-point operations, i.e. whether the function is evaluated
+    /// at compile time by a local constant.
+    lint_floats: bool,
+}
+
+impl<'a, 'tcx> ConstFnLinter<'a, 'tcx> {
+    fn warn(&self, location: Location, msg: String, help: &str) {
+        let span = self.body.source_info(location).span;
+        self.tcx
+            .sess
+            .struct_span_warn(span, msg)
+            .span_note(
+                self.tcx.def_span(self.def_id),
+                "the function may be evaluated at compile time",
+            )
+            .help(help.to_string())
+            .emit();
+    }
+
+    fn warn_float(&self, location: Location, operation: &str) {
+        let name = self.tcx.def_path_str(self.def_id.to_def_id());
+        self.warn(
+            location,
+            format!("{operation} in `const fn {name}` may produce a different NaN at compile time than at runtime"),
+            "Kani verifies the result at runtime, mark the constants computed by this function with \
+            `#[kani::const_parity]` to check that they have the same value at runtime",
+        );
+    }
+
+    fn warn_pointer_to_int(&self, location: Location) {
+        let name = self.tcx.def_path_str(self.def_id.to_def_id());
+        self.warn(
+            location,
+            format!(
+                "`const fn {name}` converts a pointer to an integer, which fails at compile time"
+            ),
+            "Kani verifies the result at runtime, where the conversion succeeds, check that this \
+            function is not used to compute constants",
+        );
+    }
+}
+
+/// Whether a transmute from `src` to `dst` turns a pointer into an integer.
+fn is_pointer_to_int(src: Ty, dst: Ty) -> bool {
+    (src.is_unsafe_ptr() || src.is_ref() || src.is_fn_ptr()) && dst.is_integral()
+}
+
+impl<'a, 'tcx> MirVisitor<'tcx> for ConstFnLinter<'a, 'tcx> {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        match rvalue {
+            Rvalue::BinaryOp(op, operands)
+                if self.lint_floats
+                    && operands.0.ty(self.body, self.tcx).is_floating_point()
+                    && matches!(
+                        op,
+                        BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem
+                    ) =>
+            {
+                self.warn_float(location, "floating-point arithmetic")
+            }
+            Rvalue::Cast(CastKind::FloatToFloat, ..) if self.lint_floats => {
+                self.warn_float(location, "floating-point conversion")
+            }
+            Rvalue::Cast(CastKind::Transmute, operand, ty)
+                if is_pointer_to_int(operand.ty(self.body, self.tcx), *ty) =>
+            {
+                self.warn_pointer_to_int(location)
+            }
+            _ => {}
+        }
+        self.super_rvalue(rvalue, location)
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Lints for common mistakes in the body of proof harnesses (`-Z harness-lints`).
+//!
+//! The lints catch harnesses that were copied and only partially adapted. They warn about
+//! symbolic values, e.g. `let x: u8 = kani::any()`, whose value is never used: it neither
+//! constrains the harness nor reaches any check, so it doesn't contribute to the proof.
+//!
+//! Unlike rustc's `unused_variables` lint, the analysis follows the value through the MIR of the
+//! harness: a value that is only copied or moved to other variables that are never read, or that
+//! is only bound with `let _ = x`, is reported as well.
+
+use crate::kani_middle::attributes::is_proof_harness;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{
+    MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor as MirVisitor,
+};
+use rustc_middle::mir::{
+    Body, Local, Location, Operand, Rvalue, Statement, StatementKind, TerminatorKind,
+    VarDebugInfoContents,
+};
+use rustc_middle::ty::{InstanceDef, TyCtxt};
+use rustc_span::{Span, Symbol};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+
+/// The functions that create symbolic values.
+const ANY_FNS: [&str; 3] = ["KaniAny", "KaniAnyWhere", "KaniArbitraryAny"];
+
+/// Warn about the unused symbolic values of the local harnesses.
+pub fn lint_harnesses(tcx: TyCtxt) {
+    let any_fns: HashSet<DefId> =
+        ANY_FNS.iter().filter_map(|name| tcx.get_diagnostic_item(Symbol::intern(name))).collect();
+    for def_id in tcx.hir().body_owners() {
+        let def_id = def_id.to_def_id();
+        if !is_proof_harness(tcx, def_id) {
+            continue;
+        }
+        debug!(?def_id, "lint_harnesses");
+        let body = tcx.instance_mir(InstanceDef::Item(def_id));
+        let mut uses = ValueUses::default();
+        uses.visit_body(body);
+        for (local, span) in symbolic_values(body, &any_fns) {
+            if !uses.is_used(local) {
+                warn_unused(tcx, span, uses.name(body, local));
+            }
+        }
+    }
+}
+
+/// The locals that receive a symbolic value, with the span of the call that creates it.
+fn symbolic_values(body: &Body, any_fns: &HashSet<DefId>) -> Vec<(Local, Span)> {
+    body.basic_blocks
+        .iter()
+        .filter_map(|data| {
+            let terminator = data.terminator();
+            let TerminatorKind::Call { func, destination, .. } = &terminator.kind else {
+                return None;
+            };
+            let (callee, _) = func.const_fn_def()?;
+            let local = destination.as_local()?;
+            any_fns.contains(&callee).then_some((local, terminator.source_info.span))
+        })
+        .collect()
+}
+
+fn warn_unused(tcx: TyCtxt, span: Span, name: Option<Symbol>) {
+    let msg = match name {
+        Some(name) => format!("symbolic value `{name}` is never used"),
+        None => "symbolic value is discarded".to_string(),
+    };
+    tcx.sess
+        .struct_span_warn(span, msg)
+        .help(
+            "a symbolic value that isn't constrained nor used by any check, even through a copy, \
+            doesn't contribute to the proof, consider removing it or passing it to the code under \
+            verification",
+        )
+        .emit();
+}
+
+/// How the values of the locals of a body are used.
+#[derive(Default)]
+struct ValueUses {
+    /// The locals whose value is read, other than to be copied or moved to another local.
+    read: HashSet<Local>,
+    /// The locals that are copied or moved as a whole to other locals.
+    copies: HashMap<Local, Vec<Local>>,
+}
+
+impl ValueUses {
+    /// Whether the value of `local` is read, directly or through the locals it is copied to.
+    fn is_used(&self, local: Local) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![local];
+        while let Some(local) = pending.pop() {
+            if self.read.contains(&local) {
+                return true;
+            }
+            if visited.insert(local) {
+                pending.extend(self.copies.get(&local).into_iter().flatten());
+            }
+        }
+        false
+    }
+
+    /// The name of the user variable that holds the value of `local`, if any.
+    fn name(&self, body: &Body, local: Local) -> Option<Symbol> {
+        let mut visited = HashSet::new();
+        let mut pending = vec![local];
+        while let Some(local) = pending.pop() {
+            let name = body.var_debug_info.iter().find_map(|info| match &info.value {
+                VarDebugInfoContents::Place(place) if place.as_local() == Some(local) => {
+                    Some(info.name)
+                }
+                _ => None,
+            });
+            if name.is_some() {
+                return name;
+            }
+            if visited.insert(local) {
+                pending.extend(self.copies.get(&local).into_iter().flatten());
+            }
+        }
+        None
+    }
+}
+
+impl<'tcx> MirVisitor<'tcx> for ValueUses {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        if let StatementKind::Assign(box (
+            dest,
+            Rvalue::Use(Operand::Copy(src) | Operand::Move(src)),
+        )) = &statement.kind
+        {
+            if let (Some(dest), Some(src)) = (dest.as_local(), src.as_local()) {
+                self.copies.entry(src).or_default().push(dest);
+                return;
+            }
+        }
+        self.super_statement(statement, location)
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _location: Location) {
+        let is_read = match context {
+            // E.g. `let _ = x`, which doesn't read `x`.
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::PlaceMention) => false,
+            PlaceContext::NonMutatingUse(_) => true,
+            // Writing a new value, or dropping the old one, doesn't read the value.
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                | MutatingUseContext::Call
+                | MutatingUseContext::Drop
+                | MutatingUseContext::Deinit
+                | MutatingUseContext::SetDiscriminant,
+            ) => false,
+            // E.g. a mutable borrow, through which the value can be read.
+            PlaceContext::MutatingUse(_) => true,
+            PlaceContext::NonUse(_) => false,
+        };
+        if is_read {
+            self.read.insert(local);
+        }
+    }
+}
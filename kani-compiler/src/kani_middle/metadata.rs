@@ -3,10 +3,12 @@
 //! This module handles Kani metadata generation. For example, generating HarnessMetadata for a
 //! given function.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use crate::kani_middle::attributes::test_harness_name;
 use kani_metadata::{ArtifactType, HarnessAttributes, HarnessMetadata};
+use rustc_ast::util::comments::beautify_doc_string;
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::{Instance, InstanceDef, TyCtxt};
 
@@ -39,6 +41,12 @@ pub fn gen_proof_metadata(tcx: TyCtxt, def_id: DefId, base_name: &Path) -> Harne
         attributes,
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        doc: harness_doc(tcx, def_id),
+        codegen_time: None,
+        nondet_stats: None,
+        check_span_ends: BTreeMap::new(),
+        reached_unsafe: BTreeSet::new(),
+        trusted_code: BTreeSet::new(),
     }
 }
 
@@ -67,5 +75,32 @@ pub fn gen_test_metadata<'tcx>(
         attributes: HarnessAttributes::default(),
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        doc: harness_doc(tcx, test_fn.def_id()),
+        codegen_time: None,
+        nondet_stats: None,
+        check_span_ends: BTreeMap::new(),
+        reached_unsafe: BTreeSet::new(),
+        trusted_code: BTreeSet::new(),
     }
 }
+
+/// Collect the documentation comments (`///` and `/** */`) attached to a harness.
+///
+/// Return `None` if the harness is not documented.
+fn harness_doc(tcx: TyCtxt, def_id: DefId) -> Option<String> {
+    let lines: Vec<String> = tcx
+        .get_attrs_unchecked(def_id)
+        .iter()
+        .filter_map(|attr| attr.doc_str_and_comment_kind())
+        .flat_map(|(doc, kind)| {
+            // Line comments keep the space that follows `///`, so strip it.
+            let doc = beautify_doc_string(doc, kind).to_string();
+            doc.lines()
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let doc = lines.join("\n");
+    let doc = doc.trim();
+    (!doc.is_empty()).then(|| doc.to_string())
+}
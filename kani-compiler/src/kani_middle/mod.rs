@@ -30,12 +30,19 @@
 pub mod analysis;
 pub mod attributes;
 pub mod coercion;
+pub mod complexity;
+pub mod const_fn;
+pub mod harness_lints;
 mod intrinsics;
 pub mod metadata;
 pub mod provide;
 pub mod reachability;
 pub mod resolve;
+pub mod secrets;
+pub mod send_sync;
 pub mod stubbing;
+pub mod trusted;
+pub mod unsafe_code;
 
 /// Check that all crate items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
@@ -15,8 +15,10 @@
 
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId, LocalModDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
-use rustc_hir::{ItemKind, UseKind};
-use rustc_middle::ty::TyCtxt;
+use rustc_hir::{ItemKind, PrimTy, UseKind};
+use rustc_middle::ty::fast_reject::SimplifiedType;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::Symbol;
 use tracing::debug;
 
 /// Attempts to resolve a simple path (in the form of a string) to a function / method `DefId`.
@@ -29,7 +31,8 @@ pub fn resolve_fn<'tcx>(
     current_module: LocalDefId,
     path_str: &str,
 ) -> Result<DefId, ResolveError<'tcx>> {
-    let result = resolve_path(tcx, current_module, path_str);
+    let result = resolve_primitive_method(tcx, path_str)
+        .unwrap_or_else(|| resolve_path(tcx, current_module, path_str));
     match result {
         Ok(def_id) => {
             let def_kind = tcx.def_kind(def_id);
@@ -75,6 +78,37 @@ fn resolve_path<'tcx>(
     })
 }
 
+/// Attempts to resolve a path to an inherent method of a primitive type, e.g. `u32::to_le`.
+/// Returns `None` if the path doesn't start with the name of a primitive type.
+///
+/// The inherent `impl` blocks of primitive types are defined in `core`, and cannot be reached
+/// from a module, so they are looked up by type instead.
+fn resolve_primitive_method<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    path_str: &str,
+) -> Option<Result<DefId, ResolveError<'tcx>>> {
+    let (prim_name, name) = path_str.split_once("::")?;
+    let prim_ty = PrimTy::from_name(Symbol::intern(prim_name.trim()))?;
+    let name = name.trim();
+    let simplified = match prim_ty {
+        PrimTy::Int(int_ty) => SimplifiedType::Int(ty::int_ty(int_ty)),
+        PrimTy::Uint(uint_ty) => SimplifiedType::Uint(ty::uint_ty(uint_ty)),
+        PrimTy::Float(float_ty) => SimplifiedType::Float(ty::float_ty(float_ty)),
+        PrimTy::Str => SimplifiedType::Str,
+        PrimTy::Bool => SimplifiedType::Bool,
+        PrimTy::Char => SimplifiedType::Char,
+    };
+    let found = tcx
+        .incoherent_impls(simplified)
+        .iter()
+        .flat_map(|impl_id| tcx.associated_item_def_ids(impl_id))
+        .copied()
+        .find(|item| tcx.item_name(*item).as_str() == name);
+    Some(found.ok_or_else(|| ResolveError::InvalidPath {
+        msg: format!("unable to find `{name}` in the inherent methods of `{prim_name}`"),
+    }))
+}
+
 /// Provide information about where the resolution failed.
 /// Todo: Add error message.
 pub enum ResolveError<'tcx> {
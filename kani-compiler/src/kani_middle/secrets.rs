@@ -0,0 +1,216 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Taint analysis used by the constant-time mode (`-Z constant-time`).
+//!
+//! Values returned by `kani::secret()` are sources of taint. Taint flows through
+//! assignments, references, and function calls, and it is tracked at the
+//! granularity of MIR locals. The analysis is context insensitive: a parameter is
+//! tainted in every call to an instance as soon as one caller passes it a secret.
+//!
+//! Memory is not tracked precisely. When a secret is stored through a pointer, the
+//! pointer itself is considered tainted, so reads through it are tainted too.
+//!
+//! # Performance Impact
+//!
+//! This analysis iterates over the body of every reachable function until it reaches
+//! a fixed point. It should only be run when the constant-time mode is enabled.
+
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::visit::Visitor as MirVisitor;
+use rustc_middle::mir::{
+    BinOp, Body, Local, Location, Operand, Place, ProjectionElem, Rvalue, StatementKind,
+    TerminatorKind, RETURN_PLACE,
+};
+use rustc_middle::ty::{EarlyBinder, Instance, InstanceDef, ParamEnv, TyCtxt, TyKind};
+use rustc_span::Symbol;
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+
+/// The result of the taint analysis: the locals of each function instance that may
+/// hold a value derived from a `kani::secret()`.
+#[derive(Debug, Default)]
+pub struct SecretTaint<'tcx> {
+    tainted: HashMap<Instance<'tcx>, HashSet<Local>>,
+}
+
+impl<'tcx> SecretTaint<'tcx> {
+    /// Run the analysis over all the given items.
+    pub fn analyze(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) -> Self {
+        let instances: Vec<_> = items
+            .iter()
+            .filter_map(
+                |item| if let MonoItem::Fn(instance) = item { Some(*instance) } else { None },
+            )
+            .collect();
+        let bodies: HashSet<_> = instances.iter().copied().collect();
+        let mut taint = SecretTaint::default();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for instance in &instances {
+                changed |= taint.propagate(tcx, *instance, &bodies);
+            }
+        }
+        debug!(tainted=?taint.tainted, "SecretTaint::analyze");
+        taint
+    }
+
+    /// Whether the given local of the given instance may hold a secret value.
+    pub fn is_local_tainted(&self, instance: Instance<'tcx>, local: Local) -> bool {
+        self.tainted.get(&instance).map_or(false, |locals| locals.contains(&local))
+    }
+
+    /// Whether reading the given operand may yield a secret value.
+    pub fn is_operand_tainted(&self, instance: Instance<'tcx>, operand: &Operand<'tcx>) -> bool {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.is_place_tainted(instance, place),
+            Operand::Constant(_) => false,
+        }
+    }
+
+    /// Whether reading the given place may yield a secret value. This includes places
+    /// that are indexed by a secret value.
+    pub fn is_place_tainted(&self, instance: Instance<'tcx>, place: &Place<'tcx>) -> bool {
+        self.is_local_tainted(instance, place.local)
+            || place.projection.iter().any(|elem| match elem {
+                ProjectionElem::Index(idx) => self.is_local_tainted(instance, idx),
+                _ => false,
+            })
+    }
+
+    /// Whether the given place computes a memory address from a secret value, i.e., whether it
+    /// is indexed by a secret.
+    pub fn has_secret_index(&self, instance: Instance<'tcx>, place: &Place<'tcx>) -> bool {
+        place.projection.iter().any(|elem| match elem {
+            ProjectionElem::Index(idx) => self.is_local_tainted(instance, idx),
+            _ => false,
+        })
+    }
+
+    /// Whether the given rvalue computes a memory address from a secret value.
+    pub fn has_secret_address(&self, instance: Instance<'tcx>, rvalue: &Rvalue<'tcx>) -> bool {
+        let mut places = PlaceCollector::default();
+        places.visit_rvalue(rvalue, Location::START);
+        places.places.iter().any(|place| self.has_secret_index(instance, place))
+            || matches!(rvalue, Rvalue::BinaryOp(BinOp::Offset, box (_, offset))
+                if self.is_operand_tainted(instance, offset))
+    }
+
+    fn taint(&mut self, instance: Instance<'tcx>, local: Local) -> bool {
+        self.tainted.entry(instance).or_default().insert(local)
+    }
+
+    fn is_rvalue_tainted(&self, instance: Instance<'tcx>, rvalue: &Rvalue<'tcx>) -> bool {
+        let mut places = PlaceCollector::default();
+        places.visit_rvalue(rvalue, Location::START);
+        places.places.iter().any(|place| self.is_place_tainted(instance, place))
+    }
+
+    /// Propagate taint through the body of one instance. Return whether anything changed.
+    fn propagate(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        instance: Instance<'tcx>,
+        bodies: &HashSet<Instance<'tcx>>,
+    ) -> bool {
+        let body = tcx.instance_mir(instance.def);
+        let mut changed = false;
+        for bbd in body.basic_blocks.iter() {
+            for stmt in &bbd.statements {
+                if let StatementKind::Assign(box (place, rvalue)) = &stmt.kind {
+                    if self.is_rvalue_tainted(instance, rvalue) {
+                        changed |= self.taint(instance, place.local);
+                    }
+                }
+            }
+            if let TerminatorKind::Call { func, args, destination, .. } = &bbd.terminator().kind {
+                changed |=
+                    self.propagate_call(tcx, instance, body, func, args, destination, bodies);
+            }
+        }
+        changed
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn propagate_call(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        caller: Instance<'tcx>,
+        body: &Body<'tcx>,
+        func: &Operand<'tcx>,
+        args: &[Operand<'tcx>],
+        destination: &Place<'tcx>,
+        bodies: &HashSet<Instance<'tcx>>,
+    ) -> bool {
+        let tainted_args: Vec<bool> =
+            args.iter().map(|arg| self.is_operand_tainted(caller, arg)).collect();
+        let fn_ty = caller.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            ParamEnv::reveal_all(),
+            EarlyBinder::bind(func.ty(body, tcx)),
+        );
+        let callee = if let TyKind::FnDef(def_id, args) = *fn_ty.kind() {
+            Instance::resolve(tcx, ParamEnv::reveal_all(), def_id, args).ok().flatten()
+        } else {
+            None
+        };
+        let mut changed = false;
+        match callee {
+            Some(callee) if is_secret_source(tcx, callee) => {
+                changed |= self.taint(caller, destination.local);
+            }
+            Some(callee)
+                if bodies.contains(&callee) && !matches!(callee.def, InstanceDef::Virtual(..)) =>
+            {
+                let callee_body = tcx.instance_mir(callee.def);
+                if callee_body.spread_arg.is_some() {
+                    // Arguments are packed in a tuple, so taint all of them.
+                    if tainted_args.contains(&true) {
+                        for idx in 1..=callee_body.arg_count {
+                            changed |= self.taint(callee, Local::from_usize(idx));
+                        }
+                    }
+                } else {
+                    for (idx, tainted) in tainted_args.iter().enumerate() {
+                        if *tainted && idx < callee_body.arg_count {
+                            changed |= self.taint(callee, Local::from_usize(idx + 1));
+                        }
+                    }
+                }
+                if self.is_local_tainted(callee, RETURN_PLACE) {
+                    changed |= self.taint(caller, destination.local);
+                }
+            }
+            _ => {
+                // We don't have a body to analyze, so assume the result depends on the arguments.
+                if tainted_args.contains(&true) {
+                    changed |= self.taint(caller, destination.local);
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Whether the given instance is `kani::secret`.
+fn is_secret_source(tcx: TyCtxt, instance: Instance) -> bool {
+    let secret_sym = Symbol::intern("KaniSecret");
+    tcx.all_diagnostic_items(()).name_to_id.get(&secret_sym) == Some(&instance.def_id())
+}
+
+/// Collect every place that is read by an rvalue, including the places used as indices.
+#[derive(Default)]
+struct PlaceCollector<'tcx> {
+    places: Vec<Place<'tcx>>,
+}
+
+impl<'tcx> MirVisitor<'tcx> for PlaceCollector<'tcx> {
+    fn visit_place(
+        &mut self,
+        place: &Place<'tcx>,
+        _context: rustc_middle::mir::visit::PlaceContext,
+        _location: Location,
+    ) {
+        self.places.push(*place);
+    }
+}
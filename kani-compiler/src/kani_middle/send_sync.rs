@@ -0,0 +1,96 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Lints for the assumptions made by `unsafe impl Send` and `unsafe impl Sync` (`-Z send-sync-lints`).
+//!
+//! An `unsafe impl Send for T` asserts that every field of `T` can be moved to another thread,
+//! and an `unsafe impl Sync for T` asserts that every field of `T` can be accessed through a
+//! shared reference from several threads at once. The compiler cannot check these assertions for
+//! fields whose type doesn't implement the trait, so these are the fields that the impl makes
+//! assumptions about. The lints warn about each of them, using the bounds of the impl, since a
+//! missing bound on a type parameter is a common source of unsoundness.
+//!
+//! These lints are an inventory of the assumptions to review, not a verification of the impls:
+//! Kani doesn't check the assumptions, nor generate harnesses for them. Checking them requires
+//! running several threads that share the value, and Kani verifies a single thread.
+
+use rustc_hir::def_id::DefId;
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_middle::ty::{ImplPolarity, Ty, TyCtxt, TyKind};
+use rustc_span::Symbol;
+use rustc_trait_selection::traits::type_known_to_meet_bound_modulo_regions;
+use tracing::debug;
+
+/// Warn about the fields of local types that don't satisfy the `Send` or `Sync` impl that is
+/// declared unsafely for them.
+pub fn lint_impls(tcx: TyCtxt) {
+    let traits = [tcx.get_diagnostic_item(rustc_span::sym::Send), tcx.lang_items().sync_trait()];
+    for trait_id in traits.into_iter().flatten() {
+        for impl_id in tcx.all_local_trait_impls(()).get(&trait_id).into_iter().flatten() {
+            let impl_id = impl_id.to_def_id();
+            // `Send` and `Sync` are unsafe traits, so every positive impl is an `unsafe impl`.
+            if tcx.impl_polarity(impl_id) == ImplPolarity::Positive {
+                lint_impl(tcx, trait_id, impl_id);
+            }
+        }
+    }
+}
+
+fn lint_impl(tcx: TyCtxt, trait_id: DefId, impl_id: DefId) {
+    let self_ty = tcx.type_of(impl_id).instantiate_identity();
+    let TyKind::Adt(adt_def, args) = self_ty.kind() else { return };
+    let param_env = tcx.param_env(impl_id);
+    let infcx = tcx.infer_ctxt().build();
+    let trait_name = tcx.item_name(trait_id);
+    let is_sync = tcx.lang_items().sync_trait() == Some(trait_id);
+    debug!(?impl_id, ?self_ty, "lint_impl");
+    for field in adt_def.all_fields() {
+        let field_ty = field.ty(tcx, args);
+        // Markers, e.g. `PhantomData<*const ()>`, don't hold any data that could be accessed.
+        if field_ty.is_phantom_data()
+            || type_known_to_meet_bound_modulo_regions(&infcx, param_env, field_ty, trait_id)
+        {
+            continue;
+        }
+        let msg = format!(
+            "`unsafe impl {trait_name} for {self_ty}` assumes that field `{}` of type `{field_ty}` {}",
+            field.name,
+            if is_sync {
+                "can be accessed through a shared reference from several threads"
+            } else {
+                "can be moved to another thread"
+            }
+        );
+        tcx.sess
+            .struct_span_warn(tcx.def_span(field.did), msg)
+            .span_note(tcx.def_span(impl_id), "the assumption is made by this impl")
+            .note("Kani doesn't verify this assumption, since it cannot find data races")
+            .help(help(field_ty, trait_name, is_sync))
+            .emit();
+    }
+}
+
+/// Explain how to review the assumption made about a field of the given type.
+fn help(field_ty: Ty, trait_name: Symbol, is_sync: bool) -> String {
+    if let TyKind::Param(param) = field_ty.kind() {
+        format!("add a `{param}: {trait_name}` bound to the impl")
+    } else if field_ty
+        .walk()
+        .any(|arg| arg.as_type().is_some_and(|ty| matches!(ty.kind(), TyKind::Param(_))))
+    {
+        format!(
+            "check whether the type parameters of `{field_ty}` need a `{trait_name}` bound in the impl"
+        )
+    } else if field_ty.is_unsafe_ptr() && is_sync {
+        "check that every access through the pointer from a shared reference is synchronized"
+            .to_string()
+    } else if field_ty.is_unsafe_ptr() {
+        "check that the pointed-to data is owned by this value, or that every access to it is \
+        synchronized"
+            .to_string()
+    } else {
+        format!(
+            "`{field_ty}` is not `{trait_name}`, check that the code that uses this field \
+            synchronizes its accesses"
+        )
+    }
+}
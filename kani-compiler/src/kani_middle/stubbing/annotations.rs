@@ -63,3 +63,16 @@ pub fn update_stub_mapping(
         }
     }
 }
+
+/// Like [`update_stub_mapping`], but keeps the existing stub if the function/method is already
+/// mapped. Used for the stubs that Kani applies by default, so user stubs take precedence.
+pub fn update_default_stub_mapping(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    stub: &Stub,
+    stub_pairs: &mut BTreeMap<DefPathHash, DefPathHash>,
+) {
+    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, harness, stub) {
+        stub_pairs.entry(tcx.def_path_hash(orig_id)).or_insert(tcx.def_path_hash(stub_id));
+    }
+}
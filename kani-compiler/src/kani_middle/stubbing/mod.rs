@@ -7,24 +7,68 @@
 
 use std::collections::BTreeMap;
 
-use kani_metadata::HarnessMetadata;
+use kani_metadata::{HarnessMetadata, Stub};
 use rustc_hir::def_id::DefId;
 use rustc_hir::definitions::DefPathHash;
 use rustc_middle::ty::TyCtxt;
 pub use transform::*;
 
-use self::annotations::update_stub_mapping;
+use self::annotations::{update_default_stub_mapping, update_stub_mapping};
 
-/// Collects the stubs from the harnesses in a crate.
+/// The stubs applied to every harness when the formatting abstraction (`-Z fmt-abstraction`) is
+/// enabled. They replace the formatting machinery by a model that doesn't write anything.
+const FMT_STUBS: [(&str, &str); 1] = [("core::fmt::write", "kani::fmt::write")];
+
+/// The integer types whose byte-order conversions are stubbed with `-Z endian-portability`.
+const BYTE_ORDER_TYPES: [&str; 12] =
+    ["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+/// The conversions that depend on the byte order of the target. The other conversions, e.g.
+/// `to_le_bytes`, are implemented with these ones.
+const BYTE_ORDER_FNS: [&str; 6] =
+    ["to_ne_bytes", "from_ne_bytes", "to_le", "from_le", "to_be", "from_be"];
+
+/// The stubs applied to every harness for the given unstable features, which replace library
+/// functions by models.
+pub fn default_stubs(unstable_features: &[String]) -> Vec<Stub> {
+    let enabled = |feature: &str| unstable_features.iter().any(|enabled| enabled == feature);
+    let mut stubs = vec![];
+    if enabled("fmt-abstraction") {
+        stubs.extend(FMT_STUBS.map(|(original, replacement)| Stub {
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+        }));
+    }
+    if enabled("endian-portability") {
+        stubs.push(Stub {
+            original: "kani::endian::Endian::target".to_string(),
+            replacement: "kani::endian::models::any_target".to_string(),
+        });
+        for ty in BYTE_ORDER_TYPES {
+            stubs.extend(BYTE_ORDER_FNS.map(|name| Stub {
+                original: format!("{ty}::{name}"),
+                replacement: format!("kani::endian::models::{ty}::{name}"),
+            }));
+        }
+    }
+    stubs
+}
+
+/// Collects the stubs from the harnesses in a crate. The `default_stubs` are added too, unless
+/// the harness already stubs the same functions.
 pub fn harness_stub_map(
     tcx: TyCtxt,
     harness: DefId,
     metadata: &HarnessMetadata,
+    default_stubs: &[Stub],
 ) -> BTreeMap<DefPathHash, DefPathHash> {
     let attrs = &metadata.attributes;
     let mut stub_pairs = BTreeMap::default();
     for stubs in &attrs.stubs {
         update_stub_mapping(tcx, harness.expect_local(), stubs, &mut stub_pairs);
     }
+    for stub in default_stubs {
+        update_default_stub_mapping(tcx, harness.expect_local(), stub, &mut stub_pairs);
+    }
     stub_pairs
 }
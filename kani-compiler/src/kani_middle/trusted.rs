@@ -0,0 +1,71 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Find the code that the user trusts, i.e. the functions inside the crates and modules given to
+//! `--trusted <PATH>` (`-Z trusted-code`).
+//!
+//! Trusted code is compiled with its checks disabled: the properties that Kani adds to it are
+//! removed, except for the ones that are followed by an assumption, e.g. a bounds check, which
+//! become that assumption. The checks that CBMC would add to it are disabled with pragmas. Panics
+//! are still reported, since assuming they don't happen would silently discard every execution
+//! that reaches them. This is meant
+//! for code that the user doesn't own, e.g. vendored dependencies, to reduce the number of checks
+//! and the load on the solver. The trusted functions reached by each harness are recorded in its
+//! metadata, so the driver can report them as unchecked.
+
+use crate::kani_middle::attributes::is_proof_harness;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+
+/// The CBMC checks that are disabled in trusted code. They cover all the checks that the driver
+/// may enable. Unwinding assertions are kept, since they tell whether the result is complete.
+pub const TRUSTED_CODE_PRAGMAS: &[&str] = &[
+    "disable:bounds-check",
+    "disable:pointer-check",
+    "disable:div-by-zero-check",
+    "disable:float-overflow-check",
+    "disable:nan-check",
+    "disable:pointer-overflow-check",
+    "disable:pointer-primitive-check",
+    "disable:signed-overflow-check",
+    "disable:unsigned-overflow-check",
+    "disable:undefined-shift-check",
+    "disable:conversion-check",
+    "disable:memory-leak-check",
+];
+
+/// The path of the given item, starting with the name of its crate, e.g.: `my_crate::module::f`.
+/// Unlike `def_path_str`, this is the same for items of the local crate and of dependencies.
+pub fn full_def_path(tcx: TyCtxt, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate_verbose())
+}
+
+/// Whether the given item is inside one of the trusted paths, i.e. whether its path is equal to
+/// one of them or starts with one of them followed by `::`.
+pub fn is_trusted(tcx: TyCtxt, def_id: DefId, trusted_paths: &[String]) -> bool {
+    !trusted_paths.is_empty() && {
+        let path = full_def_path(tcx, def_id);
+        trusted_paths.iter().any(|trusted| {
+            path.strip_prefix(trusted.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+}
+
+/// Reject the local harnesses inside of a trusted path, since their own checks would be disabled.
+pub fn check_trusted_harnesses(tcx: TyCtxt, trusted_paths: &[String]) {
+    for def_id in tcx.hir().body_owners() {
+        let def_id = def_id.to_def_id();
+        if is_proof_harness(tcx, def_id) && is_trusted(tcx, def_id, trusted_paths) {
+            tcx.sess
+                .struct_span_err(
+                    tcx.def_span(def_id),
+                    format!(
+                        "the harness `{}` is inside of a trusted path, which disables its checks",
+                        full_def_path(tcx, def_id)
+                    ),
+                )
+                .help("use a `--trusted` path that only contains the code under verification")
+                .emit();
+        }
+    }
+}
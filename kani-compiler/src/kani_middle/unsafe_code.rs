@@ -0,0 +1,109 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Collect the `unsafe` code written in the local crate, and which of it each harness reaches.
+//!
+//! This information is stored in the crate metadata, and used by the driver to build the
+//! inventory printed by `cargo kani unsafe-inventory`. Reachability is tracked at the granularity
+//! of functions: an `unsafe` block is reachable from a harness if the function that contains it
+//! is.
+
+use crate::kani_middle::SourceLocation;
+use kani_metadata::{UnsafeItem, UnsafeKind};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, FnKind, Visitor};
+use rustc_hir::{Block, BlockCheckMode, BodyId, FnDecl, UnsafeSource, Unsafety};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+use std::collections::{BTreeSet, HashSet};
+
+/// Collect the `unsafe` blocks and functions written in the local crate.
+pub fn collect_unsafe_items(tcx: TyCtxt) -> Vec<UnsafeItem> {
+    let mut collector = UnsafeCollector { tcx, items: vec![] };
+    tcx.hir().visit_all_item_likes_in_crate(&mut collector);
+    collector
+        .items
+        .into_iter()
+        .map(|(owner, kind, span)| {
+            let location = SourceLocation::new(tcx, &span);
+            UnsafeItem {
+                kind,
+                function: tcx.def_path_str(owner.to_def_id()),
+                filename: location.filename,
+                start_line: location.start_line,
+                end_line: location.end_line,
+            }
+        })
+        .collect()
+}
+
+/// The functions of the local crate that contain `unsafe` code.
+pub fn unsafe_functions(tcx: TyCtxt) -> HashSet<LocalDefId> {
+    let mut collector = UnsafeCollector { tcx, items: vec![] };
+    tcx.hir().visit_all_item_likes_in_crate(&mut collector);
+    collector.items.into_iter().map(|(owner, ..)| owner).collect()
+}
+
+/// The names of the functions that contain `unsafe` code among the given reachable items. The
+/// names match the `function` of the items returned by [collect_unsafe_items].
+pub fn reached_unsafe<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    unsafe_fns: &HashSet<LocalDefId>,
+    items: &[MonoItem<'tcx>],
+) -> BTreeSet<String> {
+    items
+        .iter()
+        .filter_map(|item| if let MonoItem::Fn(instance) = item { Some(instance) } else { None })
+        .filter_map(|instance| instance.def_id().as_local())
+        .filter(|def_id| unsafe_fns.contains(def_id))
+        .map(|def_id| tcx.def_path_str(def_id.to_def_id()))
+        .collect()
+}
+
+/// Visit the bodies of the local crate, and record each `unsafe` block and function written by
+/// the user, along with the function that contains it.
+struct UnsafeCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    items: Vec<(LocalDefId, UnsafeKind, Span)>,
+}
+
+impl<'tcx> UnsafeCollector<'tcx> {
+    fn record(&mut self, owner: LocalDefId, kind: UnsafeKind, span: Span) {
+        // Skip code generated by macros of other crates, which the user doesn't control.
+        if !in_external_macro(self.tcx.sess, span) {
+            self.items.push((owner, kind, span));
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UnsafeCollector<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_fn(
+        &mut self,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'tcx>,
+        body: BodyId,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if kind.header().is_some_and(|header| header.unsafety == Unsafety::Unsafe) {
+            self.record(def_id, UnsafeKind::Function, span);
+        }
+        intravisit::walk_fn(self, kind, decl, body, def_id)
+    }
+
+    fn visit_block(&mut self, block: &'tcx Block<'tcx>) {
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided) {
+            let owner = self.tcx.hir().enclosing_body_owner(block.hir_id);
+            self.record(owner, UnsafeKind::Block, block.span);
+        }
+        intravisit::walk_block(self, block)
+    }
+}
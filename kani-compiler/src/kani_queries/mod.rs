@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Define the communication between KaniCompiler and the codegen implementation.
 
+use kani_metadata::{NondetStats, SpanEnd};
 use rustc_hir::definitions::DefPathHash;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use crate::args::Arguments;
@@ -17,6 +19,24 @@ pub struct QueryDb {
     args: Option<Arguments>,
     /// Information about all target harnesses.
     pub harnesses_info: HashMap<DefPathHash, PathBuf>,
+    /// How long it took to generate the model of each harness in this compilation stage.
+    pub codegen_times: HashMap<DefPathHash, Duration>,
+    /// The nondeterministic values introduced by the model of each harness in this compilation
+    /// stage.
+    pub nondet_stats: HashMap<DefPathHash, NondetStats>,
+    /// Where the checks of the model of each harness in this compilation stage end.
+    pub check_span_ends: HashMap<DefPathHash, BTreeMap<String, SpanEnd>>,
+    /// The functions with `unsafe` code reached by each harness in this compilation stage.
+    pub reached_unsafe: HashMap<DefPathHash, BTreeSet<String>>,
+    /// The trusted functions, compiled without checks, reached by each harness in this
+    /// compilation stage.
+    pub trusted_code: HashMap<DefPathHash, BTreeSet<String>>,
+    /// The functions stubbed in this compilation stage that each target harness doesn't stub
+    /// itself. Harnesses that reach any of them cannot be compiled in this stage.
+    pub foreign_stubs: HashMap<DefPathHash, Vec<DefPathHash>>,
+    /// The target harnesses that were not compiled in this compilation stage because they reach
+    /// a function stubbed by another harness.
+    pub deferred_harnesses: Vec<DefPathHash>,
 }
 
 impl QueryDb {
@@ -22,18 +22,22 @@
 extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_index;
+extern crate rustc_infer;
 extern crate rustc_interface;
+extern crate rustc_lexer;
 extern crate rustc_metadata;
 extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 extern crate rustc_target;
+extern crate rustc_trait_selection;
 // We can't add this directly as a dependency because we need the version to match rustc
 extern crate tempfile;
 
 mod args;
 #[cfg(feature = "cprover")]
 mod codegen_cprover_gotoc;
+mod doc_harnesses;
 mod kani_compiler;
 mod kani_middle;
 mod kani_queries;
@@ -110,6 +110,30 @@ pub struct CargoTargetArgs {
     /// Check only the package's library unit tests.
     #[arg(long)]
     pub lib: bool,
+
+    /// Check only the specified integration test target.
+    #[arg(long)]
+    pub test: Vec<String>,
+
+    /// Check only the specified bench target.
+    #[arg(long)]
+    pub bench: Vec<String>,
+
+    /// Check all bench targets.
+    #[arg(long)]
+    pub benches: bool,
+
+    /// Check only the specified example.
+    #[arg(long)]
+    pub example: Vec<String>,
+
+    /// Check all examples.
+    #[arg(long)]
+    pub examples: bool,
+
+    /// Check all targets, i.e. the library, binaries, integration tests, benches and examples.
+    #[arg(long)]
+    pub all_targets: bool,
 }
 
 impl CargoTargetArgs {
@@ -129,19 +153,62 @@ pub fn to_cargo_args(&self) -> Vec<OsString> {
             cargo_args.push("--lib".into());
         }
 
+        cargo_args.extend(self.test.iter().map(|test| format!("--test={test}").into()));
+        cargo_args.extend(self.bench.iter().map(|bench| format!("--bench={bench}").into()));
+        if self.benches {
+            cargo_args.push("--benches".into());
+        }
+
+        cargo_args.extend(self.example.iter().map(|example| format!("--example={example}").into()));
+        if self.examples {
+            cargo_args.push("--examples".into());
+        }
+
+        if self.all_targets {
+            cargo_args.push("--all-targets".into());
+        }
+
         cargo_args
     }
 
+    /// Whether any target was selected. If not, the default targets are checked, i.e. the
+    /// library, the binaries, and the integration tests if `--tests` is given.
+    fn has_selection(&self) -> bool {
+        self.lib
+            || self.bins
+            || !self.bin.is_empty()
+            || !self.test.is_empty()
+            || self.benches
+            || !self.bench.is_empty()
+            || self.examples
+            || !self.example.is_empty()
+            || self.all_targets
+    }
+
     pub fn include_bin(&self, name: &String) -> bool {
-        self.bins || (self.bin.is_empty() && !self.lib) || self.bin.contains(name)
+        self.all_targets || self.bins || self.bin.contains(name) || !self.has_selection()
     }
 
     pub fn include_lib(&self) -> bool {
-        self.lib || (!self.bins && self.bin.is_empty())
+        self.all_targets || self.lib || !self.has_selection()
     }
 
+    /// Whether the integration tests are included when `--tests` is given.
     pub fn include_tests(&self) -> bool {
-        !self.lib && !self.bins && self.bin.is_empty()
+        !self.has_selection()
+    }
+
+    /// Whether the given integration test was selected, independently of `--tests`.
+    pub fn include_test(&self, name: &String) -> bool {
+        self.all_targets || self.test.contains(name)
+    }
+
+    pub fn include_bench(&self, name: &String) -> bool {
+        self.all_targets || self.benches || self.bench.contains(name)
+    }
+
+    pub fn include_example(&self, name: &String) -> bool {
+        self.all_targets || self.examples || self.example.contains(name)
     }
 }
 
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the daemon subcommand
+
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Build the package once, and then verify its harnesses on request over a local socket.
+#[derive(Debug, Parser)]
+pub struct DaemonArgs {
+    /// The path of the socket to listen on. Defaults to `<target-dir>/kani/daemon.sock`. Only a
+    /// socket is replaced if the path already exists.
+    #[arg(long)]
+    pub socket: Option<PathBuf>,
+}
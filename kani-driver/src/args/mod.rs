@@ -5,17 +5,23 @@
 pub mod assess_args;
 pub mod cargo;
 pub mod common;
+pub mod daemon_args;
 pub mod playback_args;
+pub mod results_args;
+pub mod stats_args;
+pub mod trends_args;
+pub mod unsafe_inventory_args;
 
 pub use assess_args::*;
 
 use self::common::*;
 use crate::args::cargo::CargoTargetArgs;
-use crate::util::warning;
+use crate::util::{message, warning};
 use cargo::CargoCommonArgs;
 use clap::builder::{PossibleValue, TypedValueParser};
 use clap::{error::ContextKind, error::ContextValue, error::Error, error::ErrorKind, ValueEnum};
-use kani_metadata::CbmcSolver;
+use kani_metadata::{CbmcSolver, MmioRegion};
+use regex::Regex;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -74,6 +80,12 @@ pub struct StandaloneArgs {
     #[arg(required = true)]
     pub input: Option<PathBuf>,
 
+    /// Link a pre-built dependency to the file to verify, e.g. `--extern foo=libfoo.rlib`, like
+    /// the rustc option of the same name. The dependency must have been built by Kani, e.g. with
+    /// `kani foo.rs --only-codegen --keep-temps`
+    #[arg(long = "extern", value_name = "NAME=PATH", value_parser = ExternCrate::parse)]
+    pub externs: Vec<ExternCrate>,
+
     #[command(flatten)]
     pub verify_opts: VerificationArgs,
 
@@ -87,6 +99,16 @@ pub struct StandaloneArgs {
 pub enum StandaloneSubcommand {
     /// Execute concrete playback testcases of a local crate.
     Playback(Box<playback_args::KaniPlaybackArgs>),
+
+    /// Inspect the results of the last verification run.
+    Results(Box<results_args::ResultsArgs>),
+
+    /// Show how failures, undetermined checks and verification time evolve across runs.
+    Trends(Box<trends_args::TrendsArgs>),
+
+    /// Summarize the verification runs of the last days: verification and solver time, most
+    /// failing property classes and slowest-growing harnesses.
+    Stats(Box<stats_args::StatsArgs>),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -112,6 +134,31 @@ pub enum CargoKaniSubcommand {
 
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
+
+    /// Inspect the results of the last verification run.
+    Results(Box<results_args::ResultsArgs>),
+
+    /// Show how failures, undetermined checks and verification time evolve across runs.
+    Trends(Box<trends_args::TrendsArgs>),
+
+    /// Summarize the verification runs of the last days: verification and solver time, most
+    /// failing property classes and slowest-growing harnesses.
+    Stats(Box<stats_args::StatsArgs>),
+
+    /// Check that every type deriving `Arbitrary` can be created with `kani::any()`, i.e., that
+    /// its safety constraints can be satisfied.
+    CheckArbitrary,
+
+    /// Check that `kani::any()` only produces safe values for every type deriving both
+    /// `Arbitrary` and `Invariant`.
+    CheckInvariants,
+
+    /// Keep the package built and verify its harnesses on request, e.g., from an editor.
+    Daemon(Box<daemon_args::DaemonArgs>),
+
+    /// List the `unsafe` blocks and functions of the package, whether they are reachable from a
+    /// harness, and the status of their pointer and overflow checks.
+    UnsafeInventory(Box<unsafe_inventory_args::UnsafeInventoryArgs>),
 }
 
 // Common arguments for invoking Kani for verification purpose. This gets put into KaniContext,
@@ -138,10 +185,15 @@ pub struct VerificationArgs {
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
     /// Keep temporary files generated throughout Kani process. This is already the default
-    /// behavior for `cargo-kani`.
-    #[arg(long, hide_short_help = true)]
+    /// behavior for `cargo-kani`. This is equivalent to `--cleanup=never`.
+    #[arg(long, hide_short_help = true, conflicts_with = "cleanup")]
     pub keep_temps: bool,
 
+    /// When to delete the temporary files generated throughout Kani process.
+    /// Defaults to `always`.
+    #[arg(long, ignore_case = true, value_enum)]
+    pub cleanup: Option<CleanupPolicy>,
+
     /// Generate C file equivalent to inputted program.
     /// This feature is unstable and it requires `--enable-unstable` to be used
     #[arg(long, hide_short_help = true, requires("enable_unstable"),
@@ -153,6 +205,7 @@ pub struct VerificationArgs {
     pub target_dir: Option<PathBuf>,
 
     /// Force Kani to rebuild all packages before the verification.
+    /// This also ignores the project cached with `-Z project-cache`.
     #[arg(long)]
     pub force_build: bool,
 
@@ -160,9 +213,83 @@ pub struct VerificationArgs {
     #[arg(long, default_value = "regular", ignore_case = true, value_enum)]
     pub output_format: OutputFormat,
 
+    /// Which traces are kept in the results file (`kani-results.json`): `summary` keeps none,
+    /// `failures` keeps the traces of failed checks, and `full` keeps every trace produced by
+    /// CBMC. By default, the traces requested by other options, e.g. `--visualize`, are kept.
+    /// This doesn't affect the terminal output, except for `--output-format json`.
+    #[arg(long, ignore_case = true, value_enum)]
+    pub output_detail: Option<OutputDetail>,
+
+    /// Write the failed and undetermined checks to this file in SARIF 2.1.0 format, e.g. to
+    /// upload them to GitHub code scanning or to view them in an IDE.
+    #[arg(long, value_name = "FILE")]
+    pub sarif: Option<PathBuf>,
+
+    /// Write the results to this file in JUnit XML format, e.g. to show them as test results in
+    /// CI. Each harness is a test case, and each failed check a failure with its trace.
+    #[arg(long, value_name = "FILE")]
+    pub junit_report: Option<PathBuf>,
+
+    /// Write badges of the results to this directory, in SVG and shields.io JSON format, e.g. to
+    /// show the number of passing harnesses and the proof coverage in a README.
+    #[arg(long, value_name = "DIR")]
+    pub badge_dir: Option<PathBuf>,
+
+    /// Write the line coverage of the harnesses to this file in lcov format, e.g. to upload it to
+    /// Codecov alongside the test coverage. A line is hit once for each harness that covers it.
+    /// This option requires `--coverage`.
+    #[arg(long, value_name = "FILE", requires("coverage"))]
+    pub lcov: Option<PathBuf>,
+
+    /// Write an HTML report of the results to this directory, with a page for each harness that
+    /// shows the source code and the trace of its failed checks. Unlike `--visualize`, this
+    /// doesn't require `cbmc-viewer`.
+    #[arg(long, value_name = "DIR", conflicts_with("visualize"))]
+    pub report_dir: Option<PathBuf>,
+
+    /// Open a terminal browser once all harnesses are checked, to inspect the failed checks of
+    /// each harness with their source and trace, and to re-run individual harnesses.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with_all(&["visualize", "concrete_playback", "quiet"])
+    )]
+    pub interactive: bool,
+
+    /// Print the trace of every failed check, keeping only the steps that match all the given
+    /// filters: `user-crate` (steps in the code being verified), `visible` (no hidden steps) or
+    /// `var=<PATTERN>` (assignments to variables whose name matches the regular expression).
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("enable_unstable"),
+        conflicts_with("visualize"),
+        value_name = "FILTER",
+        value_parser = TraceFilter::parse
+    )]
+    pub trace_filter: Vec<TraceFilter>,
+
+    /// Print the trace of every failed check, truncated to the given number of steps.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"), conflicts_with("visualize"))]
+    pub trace_max_steps: Option<usize>,
+
+    /// Print the values returned by the `kani::any()` calls that make each check fail.
+    #[arg(long, conflicts_with("visualize"))]
+    pub show_counterexample: bool,
+
     #[command(flatten)]
     pub checks: CheckArgs,
 
+    /// How integer arithmetic overflow should be modeled.
+    /// `trap` reports overflow as a failed check, like debug builds do.
+    /// `wrap` uses two's-complement wrapping, like `-C overflow-checks=off` does in release builds.
+    #[arg(long, default_value = "trap", ignore_case = true, value_enum)]
+    pub overflow_semantics: OverflowSemantics,
+
     /// Entry point for verification (symbol name).
     /// This is an unstable feature. Consider using --harness instead
     #[arg(long, hide = true, requires("enable_unstable"))]
@@ -182,6 +309,12 @@ pub struct VerificationArgs {
     #[arg(long, requires("harnesses"))]
     pub exact: bool,
 
+    /// Only verify the `proof_for_contract` harnesses that check the contract of the given
+    /// function, e.g. `my_mod::div`. This option can be provided multiple times. This feature
+    /// is unstable and it requires `-Z function-contracts`
+    #[arg(long = "contract", conflicts_with = "function", value_name = "FUNCTION")]
+    pub contracts: Vec<String>,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -207,6 +340,10 @@ pub struct VerificationArgs {
     /// Specify the value used for loop unwinding for the specified harness in CBMC
     #[arg(long, requires("harnesses"))]
     pub unwind: Option<u32>,
+    /// Stop the verification of a harness after the given number of seconds and report it as
+    /// timed out. Overridden by the harness `#[kani::proof(timeout = <seconds>)]` option.
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u32).range(1..))]
+    pub harness_timeout: Option<u32>,
     /// Specify the CBMC solver to use. Overrides the harness `solver` attribute.
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
@@ -222,8 +359,9 @@ pub struct VerificationArgs {
     // consumes everything
     pub cbmc_args: Vec<OsString>,
 
-    /// Number of parallel jobs, defaults to 1
-    #[arg(short, long, hide = true, requires("enable_unstable"))]
+    /// Number of harnesses to verify in parallel, defaults to 1. With `-j` and no number, Kani
+    /// uses one job per CPU core.
+    #[arg(short, long)]
     pub jobs: Option<Option<usize>>,
 
     /// Enable extra pointer checks such as invalid pointers in relation operations and pointer
@@ -240,9 +378,34 @@ pub struct VerificationArgs {
     /// Disable restricting the targets of virtual table function pointer calls
     #[arg(long, hide_short_help = true)]
     pub no_restrict_vtable: bool,
+    /// Restrict the targets of calls through function pointers to the functions whose address is
+    /// taken with a matching type.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub restrict_fn_ptrs: bool,
+    /// Treat the given address range as memory-mapped I/O registers, given as `<BASE>:<LEN>`.
+    /// Volatile reads from the range return non-deterministic values and volatile writes to it are
+    /// ignored. This option can be repeated. This feature is unstable and it requires `-Z mmio`
+    #[arg(long = "mmio-region", value_name = "BASE:LEN", hide_short_help = true)]
+    pub mmio_regions: Vec<MmioRegion>,
+    /// Compile the crate or module at the given path with its checks disabled, e.g. for vendored
+    /// code that is trusted. The path starts with the crate name, e.g. `my_crate::vendor`. Panics
+    /// are still reported, and the harnesses must be outside of the trusted paths. The
+    /// trusted functions reached by each harness are listed in its results as unchecked. This
+    /// option can be repeated. This feature is unstable and it requires `-Z trusted-code`
+    #[arg(long = "trusted", value_name = "PATH", hide_short_help = true)]
+    pub trusted_paths: Vec<String>,
+    /// Maximum number of interrupts that may happen during the execution of a harness.
+    /// Defaults to 1. This feature is unstable and it requires `-Z interrupts`
+    #[arg(long, hide_short_help = true)]
+    pub max_interrupts: Option<u32>,
     /// Turn off assertion reachability checks
     #[arg(long)]
     pub no_assertion_reach_checks: bool,
+    /// Fail verification of harnesses that don't have any check, e.g. because all of their code
+    /// was found to be unreachable
+    #[arg(long)]
+    pub fail_on_empty_harness: bool,
 
     /// Do not error out for crates containing `global_asm!`.
     /// This option may impact the soundness of the analysis and may cause false proofs and/or counterexamples
@@ -289,10 +452,22 @@ pub struct VerificationArgs {
     )]
     enable_stubbing: bool,
 
-    /// Enable Kani coverage output alongside verification result
+    /// Enable Kani coverage output alongside verification result. If a harness has no coverage
+    /// checks, the reachability of its other checks is reported instead.
     #[arg(long, hide_short_help = true)]
     pub coverage: bool,
 
+    /// Write an evidence report of the verification run to this directory, in JSON and Markdown
+    /// format. The report includes the results, the tool versions and the configuration used.
+    /// This feature is unstable and it requires `-Z evidence`.
+    #[arg(long, value_name = "DIR", hide_short_help = true)]
+    pub evidence_dir: Option<PathBuf>,
+
+    /// Sign the evidence report with this SSH private key, using `ssh-keygen -Y sign`. The
+    /// signature is written to `evidence.json.sig`.
+    #[arg(long, value_name = "KEY", requires("evidence_dir"), hide_short_help = true)]
+    pub evidence_signing_key: Option<PathBuf>,
+
     /// Arguments to pass down to Cargo
     #[command(flatten)]
     pub cargo: CargoCommonArgs,
@@ -334,6 +509,16 @@ pub fn jobs(&self) -> Option<usize> {
         }
     }
 
+    /// When the temporary files generated by this session should be deleted.
+    pub fn cleanup_policy(&self) -> CleanupPolicy {
+        if self.keep_temps { CleanupPolicy::Never } else { self.cleanup.unwrap_or_default() }
+    }
+
+    /// Whether the traces of failed checks should be printed.
+    pub fn show_traces(&self) -> bool {
+        !self.trace_filter.is_empty() || self.trace_max_steps.is_some()
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
@@ -344,6 +529,8 @@ pub fn is_stubbing_enabled(&self) -> bool {
         self.enable_stubbing
             || self.common_args.unstable_features.contains(UnstableFeature::Stubbing)
             || self.is_function_contracts_enabled()
+            || self.common_args.unstable_features.contains(UnstableFeature::FmtAbstraction)
+            || self.common_args.unstable_features.contains(UnstableFeature::EndianPortability)
     }
 }
 
@@ -355,11 +542,87 @@ pub enum ConcretePlaybackMode {
     InPlace,
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum CleanupPolicy {
+    /// Delete temporary files once Kani finishes.
+    #[default]
+    Always,
+    /// Only delete temporary files if every harness was verified successfully.
+    OnSuccess,
+    /// Keep all temporary files.
+    Never,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OverflowSemantics {
+    /// Integer overflow is a panic that Kani reports as a failed `arithmetic_overflow` check.
+    Trap,
+    /// Integer overflow wraps around, and no overflow check is emitted.
+    Wrap,
+}
+
+/// A filter over the steps of the traces printed for failed checks.
+#[derive(Clone, Debug)]
+pub enum TraceFilter {
+    /// Only keep steps located in the code being verified.
+    UserCrate,
+    /// Drop the steps that CBMC marks as hidden.
+    Visible,
+    /// Only keep assignments to variables whose name matches the pattern.
+    Var(Regex),
+}
+
+impl TraceFilter {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "user-crate" => Ok(TraceFilter::UserCrate),
+            "visible" => Ok(TraceFilter::Visible),
+            _ => {
+                let pattern = value.strip_prefix("var=").ok_or_else(|| {
+                    format!("expected `user-crate`, `visible` or `var=<PATTERN>`, found `{value}`")
+                })?;
+                Regex::new(pattern).map(TraceFilter::Var).map_err(|err| err.to_string())
+            }
+        }
+    }
+}
+
+/// A dependency of a standalone crate given with `--extern <NAME>=<PATH>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternCrate {
+    /// The name used to refer to the dependency in the code.
+    pub name: String,
+    /// The path to the compiled dependency, e.g. `libfoo.rlib`.
+    pub path: PathBuf,
+}
+
+impl ExternCrate {
+    fn parse(value: &str) -> Result<Self, String> {
+        let (name, path) = value
+            .split_once('=')
+            .ok_or_else(|| format!("expected `<NAME>=<PATH>`, found `{value}`"))?;
+        let is_ident = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            return Err(format!("`{name}` is not a valid crate name"));
+        }
+        Ok(ExternCrate { name: name.to_string(), path: PathBuf::from(path) })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Regular,
     Terse,
     Old,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputDetail {
+    Summary,
+    Failures,
+    Full,
 }
 
 #[derive(Debug, clap::Args)]
@@ -437,6 +700,12 @@ fn check_no_cargo_opt(is_set: bool, name: &str) -> Result<(), Error> {
 impl ValidateArgs for StandaloneArgs {
     fn validate(&self) -> Result<(), Error> {
         self.verify_opts.validate()?;
+        match &self.command {
+            Some(StandaloneSubcommand::Results(results)) => results.validate()?,
+            Some(StandaloneSubcommand::Trends(trends)) => trends.validate()?,
+            Some(StandaloneSubcommand::Stats(stats)) => stats.validate()?,
+            _ => {}
+        }
         // Cargo target arguments.
         check_no_cargo_opt(self.verify_opts.target.bins, "--bins")?;
         check_no_cargo_opt(self.verify_opts.target.lib, "--lib")?;
@@ -449,6 +718,18 @@ fn validate(&self) -> Result<(), Error> {
         check_no_cargo_opt(!self.verify_opts.cargo.exclude.is_empty(), "--exclude")?;
         check_no_cargo_opt(self.verify_opts.cargo.workspace, "--workspace")?;
         check_no_cargo_opt(self.verify_opts.cargo.manifest_path.is_some(), "--manifest-path")?;
+        for dependency in &self.externs {
+            if !dependency.path.is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid argument: `--extern {}={}` doesn't point to a file.",
+                        dependency.name,
+                        dependency.path.display()
+                    ),
+                ));
+            }
+        }
         if let Some(input) = &self.input {
             if !input.is_file() {
                 return Err(Error::raw(
@@ -479,6 +760,13 @@ fn validate(&self) -> Result<(), Error> {
             // Assess doesn't implement validation yet.
             CargoKaniSubcommand::Assess(_) => Ok(()),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
+            CargoKaniSubcommand::Results(results) => results.validate(),
+            CargoKaniSubcommand::Trends(trends) => trends.validate(),
+            CargoKaniSubcommand::Stats(stats) => stats.validate(),
+            CargoKaniSubcommand::CheckArbitrary
+            | CargoKaniSubcommand::CheckInvariants
+            | CargoKaniSubcommand::Daemon(_)
+            | CargoKaniSubcommand::UnsafeInventory(_) => Ok(()),
         }
     }
 }
@@ -514,7 +802,7 @@ fn validate(&self) -> Result<(), Error> {
                 String::new()
             };
 
-            println!(
+            message!(
                 "Using concrete playback with --randomize-layout.\n\
                 The produced tests will have to be played with the same rustc arguments:\n\
                 -Z randomize-layout{random_seed}"
@@ -564,6 +852,26 @@ fn validate(&self) -> Result<(), Error> {
                 --output-format=old.",
             ));
         }
+        if self.interactive && self.output_format == OutputFormat::Old {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --interactive isn't compatible with --output-format=old.",
+            ));
+        }
+        if self.show_traces() && self.output_format == OutputFormat::Old {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --trace-filter and --trace-max-steps aren't compatible with \
+                --output-format=old.",
+            ));
+        }
+        if self.show_counterexample && self.output_format == OutputFormat::Old {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --show-counterexample isn't compatible with \
+                --output-format=old.",
+            ));
+        }
         if self.concrete_playback.is_some() && self.jobs() != Some(1) {
             // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
             return Err(Error::raw(
@@ -571,12 +879,12 @@ fn validate(&self) -> Result<(), Error> {
                 "Conflicting options: --concrete-playback isn't compatible with --jobs.",
             ));
         }
-        if self.jobs.is_some() && self.output_format != OutputFormat::Terse {
-            // More verbose output formats make it hard to interpret output right now when run in parallel.
-            // This can be removed when we change up how results are printed.
+        if self.jobs.is_some() && self.output_format == OutputFormat::Old {
+            // CBMC prints directly to the terminal with the old output format, so the output of
+            // harnesses that run in parallel would be interleaved.
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
-                "Conflicting options: --jobs requires `--output-format=terse`",
+                "Conflicting options: --jobs isn't compatible with `--output-format=old`",
             ));
         }
         if let Some(out_dir) = &self.target_dir {
@@ -623,6 +931,51 @@ fn validate(&self) -> Result<(), Error> {
             }
         }
 
+        if self.common_args.unstable_features.contains(UnstableFeature::AssumptionChecks)
+            && !self.assertion_reach_checks()
+        {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "`-Z assumption-checks` relies on reachability checks and cannot be used with \
+            `--no-assertion-reach-checks` or `--visualize`.",
+            ));
+        }
+
+        if !self.mmio_regions.is_empty()
+            && !self.common_args.unstable_features.contains(UnstableFeature::Mmio)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--mmio-region` argument is unstable and requires `-Z mmio` to be used.",
+            ));
+        }
+
+        if !self.contracts.is_empty() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--contract` argument is unstable and requires `-Z function-contracts` to be used.",
+            ));
+        }
+
+        if !self.trusted_paths.is_empty()
+            && !self.common_args.unstable_features.contains(UnstableFeature::TrustedCode)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--trusted` argument is unstable and requires `-Z trusted-code` to be used.",
+            ));
+        }
+
+        if self.max_interrupts.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::Interrupts)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--max-interrupts` argument is unstable and requires `-Z interrupts` to be \
+                used.",
+            ));
+        }
+
         if self.coverage
             && !self.common_args.unstable_features.contains(UnstableFeature::LineCoverage)
         {
@@ -633,6 +986,15 @@ fn validate(&self) -> Result<(), Error> {
             ));
         }
 
+        if self.evidence_dir.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::Evidence)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--evidence-dir` argument is unstable and requires `-Z evidence` to be used.",
+            ));
+        }
+
         Ok(())
     }
 }
@@ -721,6 +1083,31 @@ fn check_arg_parsing() {
         // no assertion: the above might fail if it fails to allow 0 args to cbmc-args
     }
 
+    #[test]
+    fn check_extern_crates() {
+        let args = StandaloneArgs::try_parse_from([
+            "kani",
+            "file.rs",
+            "--extern",
+            "foo=libfoo.rlib",
+            "--extern",
+            "bar_2=deps/libbar_2.rlib",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.externs,
+            [
+                ExternCrate { name: "foo".into(), path: "libfoo.rlib".into() },
+                ExternCrate { name: "bar_2".into(), path: "deps/libbar_2.rlib".into() },
+            ]
+        );
+        for invalid in ["libfoo.rlib", "foo-bar=libfoo.rlib", "=libfoo.rlib"] {
+            assert!(
+                StandaloneArgs::try_parse_from(["kani", "file.rs", "--extern", invalid]).is_err()
+            );
+        }
+    }
+
     /// Ensure users can pass multiple harnesses options and that the value is accumulated.
     #[test]
     fn check_multiple_harnesses() {
@@ -820,6 +1207,112 @@ fn check_restrict_vtable_unstable() {
         check_unstable_flag!("--restrict-vtable", restrict_vtable);
     }
 
+    #[test]
+    fn check_restrict_fn_ptrs_unstable() {
+        check_unstable_flag!("--restrict-fn-ptrs", restrict_fn_ptrs);
+    }
+
+    #[test]
+    fn check_mmio_region_unstable() {
+        expect_validation_error(
+            "kani file.rs --mmio-region 0x4000_0000:0x100",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z mmio --mmio-region 0x4000_0000:0x100 --mmio-region 16:4"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(
+            args.verify_opts.mmio_regions,
+            vec![MmioRegion { base: 0x4000_0000, len: 0x100 }, MmioRegion { base: 16, len: 4 }]
+        );
+        assert!(
+            StandaloneArgs::try_parse_from("kani file.rs --mmio-region 16".split(' ')).is_err()
+        );
+    }
+
+    #[test]
+    fn check_contract_unstable() {
+        expect_validation_error("kani file.rs --contract div", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z function-contracts --contract div --contract gcd".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.contracts, vec!["div", "gcd"]);
+    }
+
+    #[test]
+    fn check_lcov_requires_coverage() {
+        let res =
+            StandaloneArgs::try_parse_from("kani file.rs --lcov kani.info".split_whitespace());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z line-coverage --coverage --lcov kani.info".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.lcov, Some(PathBuf::from("kani.info")));
+    }
+
+    #[test]
+    fn check_trusted_unstable() {
+        expect_validation_error(
+            "kani file.rs --trusted my_crate",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z trusted-code --trusted my_crate::vendor --trusted dep"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.trusted_paths, vec!["my_crate::vendor", "dep"]);
+    }
+
+    #[test]
+    fn check_max_interrupts_unstable() {
+        expect_validation_error(
+            "kani file.rs --max-interrupts 2",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z interrupts --max-interrupts 2".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.max_interrupts, Some(2));
+    }
+
+    #[test]
+    fn check_jobs_output_formats() {
+        let args = StandaloneArgs::try_parse_from("kani file.rs -j 4".split_whitespace()).unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.jobs(), Some(4));
+        let args = StandaloneArgs::try_parse_from("kani file.rs -j".split_whitespace()).unwrap();
+        assert_eq!(args.verify_opts.jobs(), None);
+        expect_validation_error(
+            "kani file.rs -j 4 --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_evidence_dir_unstable() {
+        expect_validation_error(
+            "kani file.rs --evidence-dir evidence",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z evidence --evidence-dir evidence".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.evidence_dir, Some(PathBuf::from("evidence")));
+    }
+
     #[test]
     fn check_restrict_cbmc_args() {
         check_opt!("--cbmc-args --json-ui", true, cbmc_args, vec!["--json-ui"]);
@@ -863,6 +1356,87 @@ fn check_concrete_playback_conflicts() {
         );
     }
 
+    #[test]
+    fn check_overflow_semantics() {
+        let parse = |args: &str| {
+            StandaloneArgs::try_parse_from(args.split_whitespace())
+                .unwrap()
+                .verify_opts
+                .overflow_semantics
+        };
+        assert_eq!(parse("kani test.rs"), OverflowSemantics::Trap);
+        assert_eq!(parse("kani test.rs --overflow-semantics wrap"), OverflowSemantics::Wrap);
+        assert_eq!(parse("kani test.rs --overflow-semantics=TRAP"), OverflowSemantics::Trap);
+        assert!(
+            StandaloneArgs::try_parse_from(["kani", "test.rs", "--overflow-semantics", "saturate"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn check_cleanup_policy() {
+        let parse = |args: &str| {
+            StandaloneArgs::try_parse_from(args.split_whitespace())
+                .unwrap()
+                .verify_opts
+                .cleanup_policy()
+        };
+        assert_eq!(parse("kani test.rs"), CleanupPolicy::Always);
+        assert_eq!(parse("kani test.rs --cleanup on-success"), CleanupPolicy::OnSuccess);
+        assert_eq!(parse("kani test.rs --cleanup=never"), CleanupPolicy::Never);
+        assert_eq!(parse("kani test.rs --keep-temps"), CleanupPolicy::Never);
+        let err = StandaloneArgs::try_parse_from(
+            "kani test.rs --keep-temps --cleanup always".split_whitespace(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_trace_filter() {
+        let parse = |args: &str| StandaloneArgs::try_parse_from(args.split_whitespace());
+        let args = parse(
+            "kani test.rs --enable-unstable --trace-filter user-crate \
+            --trace-filter var=^x --trace-max-steps 10",
+        )
+        .unwrap()
+        .verify_opts;
+        assert!(args.show_traces());
+        assert!(matches!(args.trace_filter[0], TraceFilter::UserCrate));
+        assert!(
+            matches!(&args.trace_filter[1], TraceFilter::Var(pattern) if pattern.as_str() == "^x")
+        );
+        assert_eq!(args.trace_max_steps, Some(10));
+        assert!(!parse("kani test.rs").unwrap().verify_opts.show_traces());
+        assert_eq!(
+            parse("kani test.rs --enable-unstable --trace-filter hidden").unwrap_err().kind(),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            parse("kani test.rs --trace-filter visible").unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        expect_validation_error(
+            "kani test.rs --enable-unstable --trace-max-steps 5 --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+        expect_validation_error(
+            "kani test.rs --show-counterexample --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_assumption_checks_conflicts() {
+        expect_validation_error(
+            "kani -Z assumption-checks --no-assertion-reach-checks test.rs",
+            ErrorKind::ArgumentConflict,
+        );
+        let args =
+            StandaloneArgs::try_parse_from("kani -Z assumption-checks test.rs".split(' ')).unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+    }
+
     #[test]
     fn check_enable_stubbing() {
         check_unstable_flag!("--enable-stubbing --harness foo", enable_stubbing);
@@ -889,6 +1463,30 @@ fn parse(args: &[&str]) -> Vec<String> {
         assert_eq!(parse(&["kani", "--features", "a b", "-Fc"]), ["a", "b", "c"]);
     }
 
+    #[test]
+    fn check_target_selection() {
+        fn parse(args: &str) -> CargoTargetArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap().verify_opts.target
+        }
+        let name = |name: &str| name.to_string();
+
+        // By default, only the library, the binaries and the tests with `--tests` are included.
+        let default = parse("kani");
+        assert!(default.include_lib() && default.include_bin(&name("a")));
+        assert!(default.include_tests() && !default.include_test(&name("a")));
+        assert!(!default.include_bench(&name("a")) && !default.include_example(&name("a")));
+
+        let examples = parse("kani --example a --benches");
+        assert!(!examples.include_lib() && !examples.include_bin(&name("a")));
+        assert!(!examples.include_tests());
+        assert!(examples.include_example(&name("a")) && !examples.include_example(&name("b")));
+        assert!(examples.include_bench(&name("b")));
+
+        let all = parse("kani --all-targets");
+        assert!(all.include_lib() && all.include_bin(&name("a")) && all.include_test(&name("a")));
+        assert!(all.include_bench(&name("a")) && all.include_example(&name("a")));
+    }
+
     #[test]
     fn check_kani_playback() {
         let input = "kani playback file.rs -- dummy".split_whitespace();
@@ -41,6 +41,12 @@ pub struct PlaybackArgs {
     #[arg(long)]
     pub only_codegen: bool,
 
+    /// Before reporting a failing test, shrink its concrete values to the simplest values that
+    /// still fail with the same panic: numbers toward zero, enum variants toward the first one,
+    /// and collections toward empty.
+    #[arg(long)]
+    pub shrink: bool,
+
     // TODO: We should make this a common option to all subcommands.
     /// Control the subcommand output.
     #[arg(long, default_value = "human")]
@@ -130,6 +136,14 @@ fn check_parse_format_works() {
         assert_eq!(args.playback.message_format, MessageFormat::Json)
     }
 
+    #[test]
+    fn check_parse_shrink_works() {
+        let input = "playback -Z concrete-playback --shrink".split_whitespace();
+        let args = CargoPlaybackArgs::try_parse_from(input).unwrap();
+        args.validate().unwrap();
+        assert!(args.playback.shrink);
+    }
+
     #[test]
     fn check_kani_parse_test_works() {
         let input = "playback -Z concrete-playback input.rs -- TEST_NAME".split_whitespace();
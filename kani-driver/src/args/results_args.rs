@@ -0,0 +1,110 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the results subcommand
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Inspect the results of the last verification run without running it again.
+#[derive(Debug, Parser)]
+pub struct ResultsArgs {
+    #[command(subcommand)]
+    pub command: ResultsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ResultsSubcommand {
+    /// Print the checks that match all the given filters.
+    Query(QueryArgs),
+}
+
+/// Query subcommand arguments.
+#[derive(Debug, clap::Args)]
+pub struct QueryArgs {
+    /// The results file to query. Defaults to the `kani-results.json` file written by the last
+    /// verification run: `<target-dir>/kani/kani-results.json` for `cargo kani`, and the file in
+    /// the current directory for `kani`. Use this option for runs of `kani` with `--target-dir`.
+    #[arg(long)]
+    pub results: Option<PathBuf>,
+
+    /// Only include checks of harnesses whose name contains this filter.
+    #[arg(long)]
+    pub harness: Option<String>,
+
+    /// Only include checks located in functions of the given module, e.g.: `parser` or
+    /// `my_crate::parser`.
+    #[arg(long)]
+    pub module: Option<String>,
+
+    /// Only include checks with the given status. This option can be provided multiple times,
+    /// which will include checks with any of the given statuses.
+    #[arg(long, ignore_case = true, value_parser = [
+        "failure", "success", "undetermined", "unreachable", "satisfied", "unsatisfiable",
+        "reachable", "covered", "uncovered"
+    ])]
+    pub status: Vec<String>,
+
+    /// Only include checks whose class contains this filter, e.g.: `overflow`.
+    #[arg(long)]
+    pub class: Option<String>,
+
+    /// How to print the matching checks.
+    #[arg(long, default_value = "table", ignore_case = true, value_enum)]
+    pub format: QueryFormat,
+}
+
+/// Output formats available for the query and unsafe-inventory subcommands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum QueryFormat {
+    /// Print a table with one entry per row.
+    Table,
+    /// Print the entries in JSON format.
+    Json,
+}
+
+impl ValidateArgs for ResultsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        match &self.command {
+            ResultsSubcommand::Query(query) => query.validate(),
+        }
+    }
+}
+
+impl ValidateArgs for QueryArgs {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(results) = &self.results {
+            if !results.is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid argument: `--results` argument `{}` is not a regular file.",
+                        results.display()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_query_args() {
+        let args = ResultsArgs::try_parse_from(
+            "results query --status FAILURE --status undetermined --class overflow --format json"
+                .split_whitespace(),
+        )
+        .unwrap();
+        let ResultsSubcommand::Query(query) = args.command;
+        assert_eq!(query.status, ["FAILURE", "undetermined"]);
+        assert_eq!(query.class.as_deref(), Some("overflow"));
+        assert_eq!(query.format, QueryFormat::Json);
+        assert!(query.results.is_none());
+        assert!(ResultsArgs::try_parse_from(["results", "query", "--status", "broken"]).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the stats subcommand
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Summarize the verification runs of the last days, e.g. to find which harnesses to optimize.
+#[derive(Debug, Parser)]
+pub struct StatsArgs {
+    /// The history file to read. Defaults to the `kani-history.jsonl` file written next to
+    /// `kani-results.json` by every verification run.
+    #[arg(long)]
+    pub history: Option<PathBuf>,
+
+    /// Only include the runs of the given number of last days.
+    #[arg(long, default_value = "7", value_parser = clap::value_parser!(u32).range(1..))]
+    pub days: u32,
+
+    /// The number of property classes and harnesses to show in each ranking.
+    #[arg(long, default_value = "5", value_parser = clap::value_parser!(u32).range(1..))]
+    pub top: u32,
+
+    /// How to print the statistics.
+    #[arg(long, default_value = "text", ignore_case = true, value_enum)]
+    pub format: StatsFormat,
+}
+
+/// Output formats available for the stats subcommand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    /// Print the statistics in a human-readable format.
+    Text,
+    /// Print the statistics in JSON format.
+    Json,
+}
+
+impl ValidateArgs for StatsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(history) = &self.history {
+            if !history.is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid argument: `--history` argument `{}` is not a regular file.",
+                        history.display()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_stats_args() {
+        let args =
+            StatsArgs::try_parse_from("stats --days 30 --top 3 --format JSON".split_whitespace())
+                .unwrap();
+        assert_eq!(args.days, 30);
+        assert_eq!(args.top, 3);
+        assert_eq!(args.format, StatsFormat::Json);
+        assert!(args.validate().is_ok());
+        let args = StatsArgs::try_parse_from(["stats"]).unwrap();
+        assert_eq!((args.days, args.top, args.format), (7, 5, StatsFormat::Text));
+        assert!(StatsArgs::try_parse_from("stats --days 0".split_whitespace()).is_err());
+        let args =
+            StatsArgs::try_parse_from("stats --history missing.jsonl".split_whitespace()).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+}
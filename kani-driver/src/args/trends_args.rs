@@ -0,0 +1,76 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the trends subcommand
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Show how the failures, undetermined checks and verification time of the previous runs evolve.
+#[derive(Debug, Parser)]
+pub struct TrendsArgs {
+    /// The history file to read. Defaults to the `kani-history.jsonl` file written next to
+    /// `kani-results.json` by every verification run.
+    #[arg(long)]
+    pub history: Option<PathBuf>,
+
+    /// Only include the given number of most recent runs.
+    #[arg(long, default_value = "30", value_parser = clap::value_parser!(u32).range(1..))]
+    pub last: u32,
+
+    /// Only include classes of checks that contain this filter, e.g.: `overflow`.
+    #[arg(long)]
+    pub class: Option<String>,
+
+    /// How to print the trends.
+    #[arg(long, default_value = "sparkline", ignore_case = true, value_enum)]
+    pub format: TrendsFormat,
+}
+
+/// Output formats available for the trends subcommand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TrendsFormat {
+    /// Print a sparkline for each metric.
+    Sparkline,
+    /// Print the selected runs in JSON format.
+    Json,
+}
+
+impl ValidateArgs for TrendsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(history) = &self.history {
+            if !history.is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid argument: `--history` argument `{}` is not a regular file.",
+                        history.display()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_trends_args() {
+        let args = TrendsArgs::try_parse_from(
+            "trends --last 5 --class overflow --format JSON".split_whitespace(),
+        )
+        .unwrap();
+        assert_eq!(args.last, 5);
+        assert_eq!(args.class.as_deref(), Some("overflow"));
+        assert_eq!(args.format, TrendsFormat::Json);
+        assert!(args.validate().is_ok());
+        assert!(TrendsArgs::try_parse_from("trends --last 0".split_whitespace()).is_err());
+        let args = TrendsArgs::try_parse_from("trends --history missing.jsonl".split_whitespace())
+            .unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+}
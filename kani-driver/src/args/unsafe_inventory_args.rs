@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the unsafe-inventory subcommand
+
+use crate::args::results_args::QueryFormat;
+use clap::Parser;
+
+/// Verify the package, and list its `unsafe` code along with the harnesses that reach it and the
+/// status of the related checks.
+#[derive(Debug, Parser)]
+pub struct UnsafeInventoryArgs {
+    /// How to print the inventory.
+    #[arg(long, default_value = "table", ignore_case = true, value_enum)]
+    pub format: QueryFormat,
+}
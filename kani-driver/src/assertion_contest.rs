@@ -0,0 +1,217 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `-Z assertion-contest`, which classifies the assertions of the harnesses that
+//! passed verification according to whether they still hold once the assumptions made by the
+//! code under verification are dropped:
+//!
+//! - Assertions that can fail without the assumptions are "protected by assumptions", i.e., the
+//!   proof relies on the preconditions of the harness.
+//! - Assertions that still hold are "unconditionally true", which may mean that they are not
+//!   checking anything interesting about the inputs of the harness.
+//!
+//! Assumptions made by the Kani library, e.g., by `kani::any_where` or by the safety constraints
+//! of a type, are kept, since dropping them could create invalid values.
+
+use crate::args::OutputFormat;
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util::message;
+use anyhow::Result;
+use console::style;
+use std::collections::HashMap;
+
+/// How an assertion that holds in the original harness behaves without the user assumptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Classification {
+    /// The assertion fails once the assumptions are dropped.
+    ProtectedByAssumptions,
+    /// The assertion holds even without the assumptions.
+    UnconditionallyTrue,
+    /// The outcome of the assertion without the assumptions is unknown.
+    Undetermined,
+}
+
+/// Re-run the harnesses that passed verification without the user assumptions, and report how
+/// each of their assertions is classified. `build` is used to rebuild the project with the
+/// assumptions dropped.
+pub(crate) fn contest_assertions(
+    session: &mut KaniSession,
+    results: &[HarnessResult],
+    build: impl FnOnce(&KaniSession) -> Result<Project>,
+) -> Result<()> {
+    let passed: Vec<_> = results
+        .iter()
+        .filter(|result| result.result.status == VerificationStatus::Success)
+        .collect();
+    if passed.is_empty() {
+        message!("\nAssertion contest: no harness passed verification, nothing to contest.");
+        return Ok(());
+    }
+
+    // Only the harnesses that passed are verified again, without generating any output of
+    // their own. Restore the original options afterwards, since they are still needed to
+    // print the final summary.
+    let harnesses = passed.iter().map(|result| result.harness.pretty_name.clone()).collect();
+    let saved = (
+        std::mem::replace(&mut session.args.harnesses, harnesses),
+        std::mem::replace(&mut session.args.exact, true),
+        std::mem::replace(&mut session.args.output_format, OutputFormat::Terse),
+        std::mem::replace(&mut session.args.common_args.quiet, true),
+        session.args.concrete_playback.take(),
+        std::mem::replace(&mut session.args.visualize, false),
+    );
+    session.ignore_user_assumptions = true;
+    let contest = run_without_assumptions(session, build);
+    session.ignore_user_assumptions = false;
+    (
+        session.args.harnesses,
+        session.args.exact,
+        session.args.output_format,
+        session.args.common_args.quiet,
+        session.args.concrete_playback,
+        session.args.visualize,
+    ) = saved;
+    let contest = contest?;
+
+    message!("\nAssertion contest results:");
+    for result in passed {
+        let name = &result.harness.pretty_name;
+        let Ok(original) = &result.result.results else { continue };
+        let contested = contest.get(name).and_then(|properties| properties.as_deref());
+        let assertions = classify_assertions(original, contested);
+        if assertions.is_empty() {
+            message!(" - `{name}`: no assertions to contest");
+            continue;
+        }
+        message!(" - `{name}`:");
+        for (assertion, classification) in assertions {
+            let label = match classification {
+                Classification::ProtectedByAssumptions => style("PROTECTED BY ASSUMPTIONS").green(),
+                Classification::UnconditionallyTrue => style("UNCONDITIONALLY TRUE").yellow(),
+                Classification::Undetermined => style("UNDETERMINED").yellow(),
+            };
+            message!(
+                "   - {label}: \"{}\" at {}",
+                assertion.description,
+                assertion.source_location
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Rebuild the project without the user assumptions and verify the harnesses selected by the
+/// session. Returns the properties of each harness, which are `None` if verification did not
+/// complete.
+fn run_without_assumptions(
+    session: &KaniSession,
+    build: impl FnOnce(&KaniSession) -> Result<Project>,
+) -> Result<HashMap<String, Option<Vec<Property>>>> {
+    let project = build(session)?;
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let runner = HarnessRunner { sess: session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+    Ok(results
+        .into_iter()
+        .map(|result| (result.harness.pretty_name.clone(), result.result.results.ok()))
+        .collect())
+}
+
+/// Whether a property is an assertion written in the code under verification.
+fn is_user_assertion(property: &Property) -> bool {
+    property.property_class() == "assertion" && property.source_location.is_in_current_dir()
+}
+
+/// Classify the user assertions that hold in `original` according to their status in
+/// `contested`, i.e., once the user assumptions have been dropped. Assertions are matched by
+/// their description and location, since the property identifiers can change when the
+/// assumptions are dropped.
+fn classify_assertions<'a>(
+    original: &'a [Property],
+    contested: Option<&[Property]>,
+) -> Vec<(&'a Property, Classification)> {
+    original
+        .iter()
+        .filter(|property| is_user_assertion(property) && property.status == CheckStatus::Success)
+        .map(|assertion| {
+            let status = contested.and_then(|properties| {
+                properties
+                    .iter()
+                    .find(|property| {
+                        property.description == assertion.description
+                            && property.source_location.to_string()
+                                == assertion.source_location.to_string()
+                    })
+                    .map(|property| property.status)
+            });
+            let classification = match status {
+                Some(CheckStatus::Failure) => Classification::ProtectedByAssumptions,
+                Some(CheckStatus::Success) => Classification::UnconditionallyTrue,
+                _ => Classification::Undetermined,
+            };
+            (assertion, classification)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+
+    fn assertion(description: &str, line: &str, status: CheckStatus) -> Property {
+        Property {
+            description: description.to_string(),
+            property_id: PropertyId {
+                fn_name: Some("check".to_string()),
+                class: "assertion".to_string(),
+                id: 1,
+            },
+            source_location: SourceLocation {
+                column: None,
+                end_column: None,
+                end_line: None,
+                file: Some("src/lib.rs".to_string()),
+                function: Some("check".to_string()),
+                line: Some(line.to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_classify_assertions() {
+        let original = [
+            assertion("x > 0", "10", CheckStatus::Success),
+            assertion("x < 100", "11", CheckStatus::Success),
+            assertion("x != 5", "12", CheckStatus::Success),
+            assertion("x == 0", "13", CheckStatus::Failure),
+        ];
+        let contested = [
+            assertion("x > 0", "10", CheckStatus::Failure),
+            assertion("x < 100", "11", CheckStatus::Success),
+            assertion("x == 0", "13", CheckStatus::Failure),
+        ];
+        let classifications: Vec<_> = classify_assertions(&original, Some(&contested))
+            .into_iter()
+            .map(|(assertion, classification)| (assertion.description.as_str(), classification))
+            .collect();
+        assert_eq!(
+            classifications,
+            [
+                ("x > 0", Classification::ProtectedByAssumptions),
+                ("x < 100", Classification::UnconditionallyTrue),
+                ("x != 5", Classification::Undetermined),
+            ]
+        );
+
+        let undetermined = classify_assertions(&original, None);
+        assert!(undetermined.iter().all(|(_, c)| *c == Classification::Undetermined));
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the status badges written with `--badge-dir <DIR>`.
+//!
+//! Projects can show these badges in their README to surface the result of verification:
+//!
+//! - `proofs.svg`: the number of harnesses that passed verification, e.g. `proofs | 12/12 passing`.
+//! - `coverage.svg`: the percentage of source lines covered by the harnesses, if coverage was
+//!   enabled with `--coverage`. A line is covered if some harness fully covers it, i.e. the
+//!   lines are counted like in the `--lcov` report.
+//!
+//! Each badge is also written as a JSON file that follows the
+//! [endpoint](https://shields.io/badges/endpoint-badge) schema of shields.io, so a badge can be
+//! rendered by shields.io from the file published by a CI pipeline.
+
+use crate::coverage::line_hits;
+use crate::harness_runner::HarnessResult;
+use crate::results;
+use crate::util::escape_xml;
+use anyhow::{Context, Result};
+use kani_results::{HarnessResults, VerificationStatus};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A badge in the shields.io endpoint format.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Badge {
+    schema_version: u32,
+    label: String,
+    message: String,
+    color: Color,
+}
+
+/// The colors of the badges, named like shields.io does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Color {
+    BrightGreen,
+    Green,
+    Yellow,
+    Orange,
+    Red,
+}
+
+impl Color {
+    fn hex(self) -> &'static str {
+        match self {
+            Color::BrightGreen => "#4c1",
+            Color::Green => "#97ca00",
+            Color::Yellow => "#dfb317",
+            Color::Orange => "#fe7d37",
+            Color::Red => "#e05d44",
+        }
+    }
+}
+
+/// Write the badges of the given results to `dir`. The coverage badge is only written if
+/// `coverage` is enabled.
+pub(crate) fn write_badges(
+    harness_results: &[HarnessResult],
+    dir: &Path,
+    coverage: bool,
+) -> Result<()> {
+    let results: Vec<_> = harness_results.iter().map(results::harness_results).collect();
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create badge directory `{}`", dir.display()))?;
+    let mut badges = vec![("proofs", proofs_badge(&results))];
+    if let Some(badge) = coverage.then(|| coverage_badge(&results)).flatten() {
+        badges.push(("coverage", badge));
+    }
+    for (name, badge) in badges {
+        let json = dir.join(format!("{name}.json"));
+        std::fs::write(&json, serde_json::to_string_pretty(&badge)?)
+            .with_context(|| format!("Failed to write badge `{}`", json.display()))?;
+        let svg = dir.join(format!("{name}.svg"));
+        std::fs::write(&svg, render_svg(&badge))
+            .with_context(|| format!("Failed to write badge `{}`", svg.display()))?;
+    }
+    Ok(())
+}
+
+/// The number of harnesses that passed verification.
+fn proofs_badge(results: &[HarnessResults]) -> Badge {
+    let passing =
+        results.iter().filter(|result| result.status == VerificationStatus::Success).count();
+    Badge {
+        schema_version: 1,
+        label: "proofs".to_string(),
+        message: format!("{passing}/{} passing", results.len()),
+        color: if passing == results.len() { Color::BrightGreen } else { Color::Red },
+    }
+}
+
+/// The percentage of the lines in the coverage results that some harness fully covers, or `None`
+/// if there are no coverage results.
+fn coverage_badge(results: &[HarnessResults]) -> Option<Badge> {
+    let hits: Vec<usize> =
+        line_hits(results).into_values().flat_map(BTreeMap::into_values).collect();
+    if hits.is_empty() {
+        return None;
+    }
+    // Round down, so 100% means that every line is covered.
+    let covered = hits.iter().filter(|hits| **hits > 0).count();
+    let percentage = covered * 100 / hits.len();
+    let color = match percentage {
+        90.. => Color::BrightGreen,
+        75..=89 => Color::Green,
+        60..=74 => Color::Yellow,
+        40..=59 => Color::Orange,
+        _ => Color::Red,
+    };
+    Some(Badge {
+        schema_version: 1,
+        label: "proof coverage".to_string(),
+        message: format!("{percentage}%"),
+        color,
+    })
+}
+
+/// Render a badge in the flat style of shields.io. The width of the text is estimated, since
+/// the font used to display the badge isn't known.
+fn render_svg(badge: &Badge) -> String {
+    let text_width = |text: &str| text.chars().count() * 7 + 10;
+    let label_width = text_width(&badge.label);
+    let message_width = text_width(&badge.message);
+    let width = label_width + message_width;
+    let (label, message) = (escape_xml(&badge.label), escape_xml(&badge.message));
+    let label_x = label_width / 2;
+    let message_x = label_width + message_width / 2;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        color = badge.color.hex()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::mock;
+    use kani_results::CheckStatus;
+
+    #[test]
+    fn check_badges() {
+        let results = vec![
+            mock::harness(
+                VerificationStatus::Success,
+                vec![
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 2),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/main.rs", 1),
+                    mock::coverage_check(CheckStatus::Covered, "src/main.rs", 1),
+                ],
+            ),
+            mock::harness(
+                VerificationStatus::Failure,
+                vec![
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 2),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 3),
+                ],
+            ),
+        ];
+        let proofs = proofs_badge(&results);
+        assert_eq!(proofs.message, "1/2 passing");
+        assert_eq!(proofs.color, Color::Red);
+        // Lines 1 and 2 of `src/lib.rs` are fully covered by some harness, out of 4 lines. Line 1
+        // of `src/main.rs` is only partially covered, so it doesn't count, like in lcov reports.
+        let coverage = coverage_badge(&results).unwrap();
+        assert_eq!(coverage.message, "50%");
+        assert_eq!(coverage.color, Color::Orange);
+        assert_eq!(
+            serde_json::to_string(&coverage).unwrap(),
+            r#"{"schemaVersion":1,"label":"proof coverage","message":"50%","color":"orange"}"#
+        );
+        let svg = render_svg(&coverage);
+        assert!(svg.contains("aria-label=\"proof coverage: 50%\""));
+        assert!(svg.contains("fill=\"#fe7d37\""));
+
+        let passing = vec![mock::harness(VerificationStatus::Success, vec![])];
+        assert_eq!(proofs_badge(&passing).color, Color::BrightGreen);
+        assert_eq!(coverage_badge(&passing), None);
+    }
+}
@@ -5,6 +5,7 @@
 use crate::call_single_file::to_rustc_arg;
 use crate::project::Artifact;
 use crate::session::KaniSession;
+use crate::util::{message, FileLock};
 use crate::{session, util};
 use anyhow::{bail, Context, Result};
 use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
@@ -20,9 +21,11 @@
 use tracing::{debug, trace};
 
 //---- Crate types identifier used by cargo.
+const CRATE_TYPE_BENCH: &str = "bench";
 const CRATE_TYPE_BIN: &str = "bin";
 const CRATE_TYPE_CDYLIB: &str = "cdylib";
 const CRATE_TYPE_DYLIB: &str = "dylib";
+const CRATE_TYPE_EXAMPLE: &str = "example";
 const CRATE_TYPE_LIB: &str = "lib";
 const CRATE_TYPE_PROC_MACRO: &str = "proc-macro";
 const CRATE_TYPE_RLIB: &str = "rlib";
@@ -40,22 +43,33 @@ pub struct CargoOutputs {
     pub cargo_metadata: Metadata,
     /// For build `keep_going` mode, we collect the targets that we failed to compile.
     pub failed_targets: Option<Vec<String>>,
+    /// The diagnostics of the compiler other than errors, e.g. warnings, so they can be shown
+    /// again when the project is loaded from the cache.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Lock on the build outputs. It should be held until the session has made its own copy of
+    /// the artifacts it needs, so other sessions cannot overwrite them in the meantime.
+    pub lock: FileLock,
 }
 
 impl KaniSession {
+    /// The directory where Kani stores the outputs of a cargo build, i.e. `<target-dir>/kani`.
+    pub fn cargo_target_dir(&self, metadata: &Metadata) -> PathBuf {
+        self.args
+            .target_dir
+            .clone()
+            .unwrap_or_else(|| metadata.target_directory.clone().into())
+            .join("kani")
+    }
+
     /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
     pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         let build_target = env!("TARGET"); // see build.rs
         let metadata = self.cargo_metadata(build_target)?;
-        let target_dir = self
-            .args
-            .target_dir
-            .as_ref()
-            .unwrap_or(&metadata.target_directory.clone().into())
-            .clone()
-            .join("kani");
+        let target_dir = self.cargo_target_dir(&metadata);
         let outdir = target_dir.join(build_target).join("debug/deps");
 
+        // The lock lives outside of the build directory since `--force-build` deletes it.
+        let lock = FileLock::exclusive(&target_dir.with_extension("lock"))?;
         if self.args.force_build && target_dir.exists() {
             fs::remove_dir_all(&target_dir)?;
         }
@@ -107,6 +121,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         let packages = packages_to_verify(&self.args, &metadata)?;
         let mut artifacts = vec![];
         let mut failed_targets = vec![];
+        let mut diagnostics = vec![];
         for package in packages {
             for verification_target in package_targets(&self.args, package) {
                 let mut cmd = Command::new("cargo");
@@ -121,7 +136,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
                     .env("CARGO_ENCODED_RUSTFLAGS", rustc_args.join(OsStr::new("\x1f")))
                     .env("CARGO_TERM_PROGRESS_WHEN", "never");
 
-                match self.run_cargo(cmd, verification_target.target()) {
+                match self.run_cargo(cmd, verification_target.target(), &mut diagnostics) {
                     Err(err) => {
                         if keep_going {
                             let target_str = format!("{verification_target}");
@@ -147,10 +162,13 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             metadata: artifacts,
             cargo_metadata: metadata,
             failed_targets: keep_going.then_some(failed_targets),
+            diagnostics,
+            lock,
         })
     }
 
-    fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
+    /// Retrieve the cargo metadata of the package under verification for the given target.
+    pub fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
         let mut cmd = MetadataCommand::new();
 
         // restrict metadata command to host platform. References:
@@ -179,8 +197,14 @@ fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
     }
 
     /// Run cargo and collect any error found.
-    /// We also collect the metadata file generated during compilation if any.
-    fn run_cargo(&self, cargo_cmd: Command, target: &Target) -> Result<Option<Artifact>> {
+    /// We also collect the metadata file generated during compilation if any, and the diagnostics
+    /// other than errors.
+    fn run_cargo(
+        &self,
+        cargo_cmd: Command,
+        target: &Target,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<Option<Artifact>> {
         let support_color = std::io::stdout().is_terminal();
         let mut artifact = None;
         if let Some(mut cargo_process) = self.run_piped(cargo_cmd)? {
@@ -209,6 +233,7 @@ fn run_cargo(&self, cargo_cmd: Command, target: &Target) -> Result<Option<Artifa
                             if !self.args.common_args.quiet {
                                 print_msg(&msg.message, support_color)?;
                             }
+                            diagnostics.push(msg.message);
                         }
                     },
                     Message::CompilerArtifact(rustc_artifact) => {
@@ -225,14 +250,14 @@ fn run_cargo(&self, cargo_cmd: Command, target: &Target) -> Result<Option<Artifa
                     }
                     Message::TextLine(msg) => {
                         if !self.args.common_args.quiet {
-                            println!("{msg}");
+                            message!("{msg}");
                         }
                     }
 
                     // Non-exhaustive enum.
                     _ => {
                         if !self.args.common_args.quiet {
-                            println!("{message:?}");
+                            message!("{message:?}");
                         }
                     }
                 }
@@ -266,11 +291,16 @@ pub fn cargo_config_args() -> Vec<OsString> {
 }
 
 /// Print the compiler message following the coloring schema.
-fn print_msg(diagnostic: &Diagnostic, use_rendered: bool) -> Result<()> {
-    if use_rendered {
-        print!("{diagnostic}");
+pub fn print_msg(diagnostic: &Diagnostic, use_rendered: bool) -> Result<()> {
+    let rendered = if use_rendered {
+        diagnostic.to_string()
+    } else {
+        console::strip_ansi_codes(diagnostic.rendered.as_ref().unwrap()).to_string()
+    };
+    if util::messages_to_stderr() {
+        eprint!("{rendered}");
     } else {
-        print!("{}", console::strip_ansi_codes(diagnostic.rendered.as_ref().unwrap()));
+        print!("{rendered}");
     }
     Ok(())
 }
@@ -380,7 +410,9 @@ fn map_kani_artifact(rustc_artifact: cargo_metadata::Artifact) -> Option<Artifac
 /// Possible verification targets.
 #[derive(Debug)]
 enum VerificationTarget {
+    Bench(Target),
     Bin(Target),
+    Example(Target),
     Lib(Target),
     Test(Target),
 }
@@ -391,6 +423,12 @@ fn to_args(&self) -> Vec<String> {
         match self {
             VerificationTarget::Test(target) => vec![String::from("--test"), target.name.clone()],
             VerificationTarget::Bin(target) => vec![String::from("--bin"), target.name.clone()],
+            VerificationTarget::Bench(target) => {
+                vec![String::from("--bench"), target.name.clone()]
+            }
+            VerificationTarget::Example(target) => {
+                vec![String::from("--example"), target.name.clone()]
+            }
             VerificationTarget::Lib(_) => vec![String::from("--lib")],
         }
     }
@@ -398,7 +436,9 @@ fn to_args(&self) -> Vec<String> {
     fn target(&self) -> &Target {
         match self {
             VerificationTarget::Test(target)
+            | VerificationTarget::Bench(target)
             | VerificationTarget::Bin(target)
+            | VerificationTarget::Example(target)
             | VerificationTarget::Lib(target) => target,
         }
     }
@@ -409,6 +449,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             VerificationTarget::Test(target) => write!(f, "test `{}`", target.name),
             VerificationTarget::Bin(target) => write!(f, "binary `{}`", target.name),
+            VerificationTarget::Bench(target) => write!(f, "bench `{}`", target.name),
+            VerificationTarget::Example(target) => write!(f, "example `{}`", target.name),
             VerificationTarget::Lib(target) => write!(f, "lib `{}`", target.name),
         }
     }
@@ -417,6 +459,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 /// Extract the targets inside a package.
 ///
 /// If `--tests` is given, the list of targets will include any integration tests.
+/// Integration tests, benches and examples are also included if they are selected explicitly,
+/// e.g. with `--test <NAME>`, `--benches`, `--example <NAME>` or `--all-targets`.
 ///
 /// We use the `target.kind` as documented here. Note that `kind` for library will
 /// match the `crate-type`, despite them not being explicitly listed in the documentation:
@@ -427,6 +471,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 /// <https://docs.rs/cargo_metadata/0.15.0/cargo_metadata/struct.Target.html#structfield.crate_types>
 fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<VerificationTarget> {
     let mut ignored_tests = vec![];
+    let mut ignored_other = vec![];
     let mut ignored_unsupported = vec![];
     let mut verification_targets = vec![];
     for target in &package.targets {
@@ -456,7 +501,9 @@ fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<Verificati
                 }
                 CRATE_TYPE_TEST => {
                     // Test target.
-                    if args.target.include_tests() {
+                    if args.target.include_test(&target.name) {
+                        verification_targets.push(VerificationTarget::Test(target.clone()));
+                    } else if args.target.include_tests() {
                         if args.tests {
                             verification_targets.push(VerificationTarget::Test(target.clone()));
                         } else {
@@ -464,13 +511,27 @@ fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<Verificati
                         }
                     }
                 }
+                CRATE_TYPE_BENCH => {
+                    if args.target.include_bench(&target.name) {
+                        verification_targets.push(VerificationTarget::Bench(target.clone()));
+                    } else {
+                        ignored_other.push(target.name.as_str());
+                    }
+                }
+                CRATE_TYPE_EXAMPLE => {
+                    if args.target.include_example(&target.name) {
+                        verification_targets.push(VerificationTarget::Example(target.clone()));
+                    } else {
+                        ignored_other.push(target.name.as_str());
+                    }
+                }
                 _ => {
                     ignored_unsupported.push(target.name.as_str());
                 }
             }
         }
         match (supported_lib, unsupported_lib) {
-            (true, true) => println!(
+            (true, true) => message!(
                 "warning: Skipped verification of `{}` due to unsupported crate-type: \
                         `proc-macro`.",
                 target.name,
@@ -483,11 +544,20 @@ fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<Verificati
     if args.common_args.verbose {
         // Print targets that were skipped only on verbose mode.
         if !ignored_tests.is_empty() {
-            println!("Skipped the following test targets: '{}'.", ignored_tests.join("', '"));
-            println!("    -> Use '--tests' to verify harnesses inside a 'test' crate.");
+            message!("Skipped the following test targets: '{}'.", ignored_tests.join("', '"));
+            message!("    -> Use '--tests' to verify harnesses inside a 'test' crate.");
+        }
+        if !ignored_other.is_empty() {
+            message!(
+                "Skipped the following bench and example targets: '{}'.",
+                ignored_other.join("', '")
+            );
+            message!(
+                "    -> Use '--benches', '--examples' or '--all-targets' to verify their harnesses."
+            );
         }
         if !ignored_unsupported.is_empty() {
-            println!(
+            message!(
                 "Skipped the following unsupported targets: '{}'.",
                 ignored_unsupported.join("', '")
             );
@@ -2,24 +2,38 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{bail, Result};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{CbmcSolver, HarnessMetadata, NondetStats};
+use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::fmt::Write;
 use std::path::Path;
 use std::process::Command;
 use std::time::{Duration, Instant};
 
-use crate::args::{OutputFormat, VerificationArgs};
+use crate::args::{OutputDetail, OutputFormat, VerificationArgs};
 use crate::cbmc_output_parser::{
-    extract_results, process_cbmc_output, CheckStatus, ParserItem, Property, VerificationOutput,
+    extract_results, process_cbmc_output, CheckStatus, ParserItem, PhaseEvent, Property,
+    VerificationOutput,
+};
+use crate::cbmc_property_renderer::{
+    format_coverage, format_result, has_no_checks, kani_cbmc_output_filter, HarnessOutput,
 };
-use crate::cbmc_property_renderer::{format_coverage, format_result, kani_cbmc_output_filter};
 use crate::session::KaniSession;
 
 /// We will use Cadical by default since it performed better than MiniSAT in our analysis.
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
 static DEFAULT_SOLVER: CbmcSolver = CbmcSolver::Cadical;
 
+/// Harnesses whose nondeterministic values add up to more bits than this are flagged, since large
+/// inputs are a common cause of slow verification.
+const LARGE_NONDET_BITS: u64 = 32 * 1024;
+
+/// The number of inputs listed when verification doesn't complete.
+const MAX_LISTED_INPUTS: usize = 3;
+
+/// Exit statuses above this value indicate that the process was killed by a signal.
+const SIGNAL_EXIT_STATUS: i32 = 128;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VerificationStatus {
     Success,
@@ -55,13 +69,26 @@ pub struct VerificationResult {
     pub results: Result<Vec<Property>, i32>,
     /// The runtime duration of this CBMC invocation.
     pub runtime: Duration,
+    /// How long the compiler took to generate the model that was verified, if known.
+    pub codegen_time: Option<Duration>,
+    /// The nondeterministic values introduced by the model that was verified, if known.
+    pub nondet_stats: Option<NondetStats>,
+    /// The trusted functions reached by the harness, which were compiled without checks.
+    pub trusted_code: BTreeSet<String>,
     /// Whether concrete playback generated a test
     pub generated_concrete_test: bool,
+    /// The timeout after which the verification was stopped, if it expired.
+    pub timeout: Option<Duration>,
 }
 
 impl KaniSession {
     /// Verify a goto binary that's been prepared with goto-instrument
-    pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
+    pub fn run_cbmc(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        output: &mut HarnessOutput,
+    ) -> Result<VerificationResult> {
         let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
 
         // TODO get cbmc path from self
@@ -70,7 +97,8 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
 
         let start_time = Instant::now();
 
-        let verification_results = if self.args.output_format == crate::args::OutputFormat::Old {
+        let mut verification_results = if self.args.output_format == crate::args::OutputFormat::Old
+        {
             if self.run_terminal(cmd).is_err() {
                 VerificationResult::mock_failure()
             } else {
@@ -84,17 +112,35 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
             // Spawn the CBMC process and process its output below
             let cbmc_process_opt = self.run_piped(cmd)?;
             let cbmc_process = cbmc_process_opt.ok_or(anyhow::Error::msg("Failed to run cbmc"))?;
-            let output = process_cbmc_output(cbmc_process, |i| {
+            let timeout = resolve_timeout(&self.args, harness);
+            let output = process_cbmc_output(cbmc_process, timeout, |i| {
                 kani_cbmc_output_filter(
                     i,
                     self.args.extra_pointer_checks,
                     self.args.common_args.quiet,
                     &self.args.output_format,
+                    &harness.check_span_ends,
+                    output,
                 )
             })?;
 
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            let timed_out = output.timed_out;
+            let mut result =
+                VerificationResult::from(output, harness.attributes.should_panic, start_time);
+            if timed_out {
+                result.status = VerificationStatus::Failure;
+                result.timeout = timeout;
+            }
+            if self.args.fail_on_empty_harness
+                && matches!(&result.results, Ok(properties) if has_no_checks(properties))
+            {
+                result.status = VerificationStatus::Failure;
+            }
+            result
         };
+        verification_results.codegen_time = harness.codegen_time;
+        verification_results.nondet_stats = harness.nondet_stats.clone();
+        verification_results.trusted_code = harness.trusted_code.clone();
 
         Ok(verification_results)
     }
@@ -148,7 +194,14 @@ pub fn cbmc_flags(
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        if self.args.concrete_playback.is_some()
+            || self.args.show_traces()
+            || self.args.show_counterexample
+            || self.args.interactive
+            || self.args.junit_report.is_some()
+            || self.args.report_dir.is_some()
+            || self.args.output_detail.is_some_and(|detail| detail != OutputDetail::Summary)
+        {
             args.push("--trace".into());
         }
 
@@ -204,16 +257,7 @@ pub fn handle_solver_args(
         harness_solver: &Option<CbmcSolver>,
         args: &mut Vec<OsString>,
     ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
-            // `--solver` option takes precedence over attributes
-            solver
-        } else if let Some(solver) = harness_solver {
-            solver
-        } else {
-            &DEFAULT_SOLVER
-        };
-
-        match solver {
+        match resolve_solver(&self.args, harness_solver) {
             CbmcSolver::Cadical => {
                 args.push("--sat-solver".into());
                 args.push("cadical".into());
@@ -265,7 +309,11 @@ fn from(
                 messages: Some(items),
                 results: Ok(results),
                 runtime,
+                codegen_time: None,
+                nondet_stats: None,
+                trusted_code: BTreeSet::new(),
                 generated_concrete_test: false,
+                timeout: None,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -275,7 +323,11 @@ fn from(
                 messages: Some(items),
                 results: Err(output.process_status),
                 runtime,
+                codegen_time: None,
+                nondet_stats: None,
+                trusted_code: BTreeSet::new(),
                 generated_concrete_test: false,
+                timeout: None,
             }
         }
     }
@@ -287,10 +339,32 @@ pub fn mock_success() -> VerificationResult {
             messages: None,
             results: Ok(vec![]),
             runtime: Duration::from_secs(0),
+            codegen_time: None,
+            nondet_stats: None,
+            trusted_code: BTreeSet::new(),
             generated_concrete_test: false,
+            timeout: None,
         }
     }
 
+    /// The time spent by the SAT/SMT solver, as reported by the `Runtime Solver` status messages
+    /// of CBMC.
+    pub fn solver_time(&self) -> Duration {
+        let seconds = self
+            .messages
+            .iter()
+            .flatten()
+            .filter_map(|item| match item {
+                ParserItem::Message {
+                    phase: Some(PhaseEvent::Runtime { step, seconds, .. }),
+                    ..
+                } if step == "Solver" => Some(*seconds),
+                _ => None,
+            })
+            .sum();
+        Duration::from_secs_f64(seconds)
+    }
+
     fn mock_failure() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Failure,
@@ -301,7 +375,11 @@ fn mock_failure() -> VerificationResult {
             // so again use something weird:
             results: Err(42),
             runtime: Duration::from_secs(0),
+            codegen_time: None,
+            nondet_stats: None,
+            trusted_code: BTreeSet::new(),
             generated_concrete_test: false,
+            timeout: None,
         }
     }
 
@@ -322,14 +400,39 @@ pub fn render(
                 } else {
                     format_result(results, status, should_panic, failed_properties, show_checks)
                 };
+                if let Some(codegen_time) = self.codegen_time {
+                    writeln!(result, "Codegen Time: {}s", codegen_time.as_secs_f32()).unwrap();
+                }
+                if let Some(stats) = &self.nondet_stats {
+                    result.push_str(&format_nondet_stats(stats));
+                }
+                if !self.trusted_code.is_empty() {
+                    result.push_str(&format_trusted_code(&self.trusted_code));
+                }
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
                 result
             }
             Err(exit_status) => {
-                let verification_result = console::style("FAILED").red();
-                format!(
-                    "\nCBMC failed with status {exit_status}\nVERIFICATION:- {verification_result}\n",
-                )
+                let mut result = if let Some(timeout) = self.timeout {
+                    let verification_result = console::style("TIMEOUT").red();
+                    format!(
+                        "\nCBMC timed out after {}s\nVERIFICATION:- {verification_result}\n",
+                        timeout.as_secs()
+                    )
+                } else {
+                    let verification_result = console::style("FAILED").red();
+                    format!(
+                        "\nCBMC failed with status {exit_status}\nVERIFICATION:- {verification_result}\n",
+                    )
+                };
+                // CBMC was killed before it completed, e.g. by a timeout or by running out of
+                // memory. Large inputs are a common cause, so list the largest ones.
+                if *exit_status > SIGNAL_EXIT_STATUS {
+                    if let Some(stats) = &self.nondet_stats {
+                        result.push_str(&format_largest_inputs(stats));
+                    }
+                }
+                result
             }
         }
     }
@@ -395,6 +498,103 @@ pub fn resolve_unwind_value(
     args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
 }
 
+/// Resolve the verification timeout of a harness from its attribute and the `--harness-timeout`
+/// option.
+pub fn resolve_timeout(
+    args: &VerificationArgs,
+    harness_metadata: &HarnessMetadata,
+) -> Option<Duration> {
+    // The harness attribute takes precedence over the option.
+    harness_metadata
+        .attributes
+        .timeout
+        .or(args.harness_timeout)
+        .map(|timeout| Duration::from_secs(timeout.into()))
+}
+
+/// Resolve the solver used for a harness from the `--solver` option and the harness attribute.
+pub fn resolve_solver<'a>(
+    args: &'a VerificationArgs,
+    harness_solver: &'a Option<CbmcSolver>,
+) -> &'a CbmcSolver {
+    // `--solver` option takes precedence over attributes
+    args.solver.as_ref().or(harness_solver.as_ref()).unwrap_or(&DEFAULT_SOLVER)
+}
+
+/// Format the list of trusted functions reached by a harness, whose checks were disabled.
+fn format_trusted_code(trusted_code: &BTreeSet<String>) -> String {
+    let count = trusted_code.len();
+    let functions = if count == 1 { "function" } else { "functions" };
+    let mut result = format!("Trusted Code (not checked): {count} {functions}\n");
+    for function in trusted_code {
+        writeln!(result, " - {function}").unwrap();
+    }
+    result
+}
+
+/// Format the number of types of nondeterministic values of a harness and their size, with a
+/// warning if they are large enough to likely slow down verification.
+fn format_nondet_stats(stats: &NondetStats) -> String {
+    let types = if stats.count == 1 { "type" } else { "types" };
+    let mut result = format!(
+        "Nondeterministic Values: {} {types} ({} bytes)\n",
+        stats.count,
+        stats.total_bits.div_ceil(8)
+    );
+    if stats.total_bits > LARGE_NONDET_BITS {
+        writeln!(
+            result,
+            "{} The harness has more than {} bytes of nondeterministic inputs, which can make \
+            verification slow. Consider bounding the size of its inputs.",
+            console::style("warning:").bold().yellow(),
+            LARGE_NONDET_BITS / 8
+        )
+        .unwrap();
+    }
+    result
+}
+
+/// List the largest nondeterministic inputs of a harness whose verification did not complete, with
+/// how each of them can be bounded. The inputs are ranked by the size of their type only, since
+/// Kani doesn't know which of them slowed down verification.
+pub fn format_largest_inputs(stats: &NondetStats) -> String {
+    let inputs = stats.largest_inputs(MAX_LISTED_INPUTS);
+    if inputs.is_empty() {
+        return String::new();
+    }
+    let mut result = String::from(
+        "Verification did not complete. Kani doesn't know which inputs slowed it down, but these \
+        are the largest nondeterministic inputs of the harness, ranked by size:\n",
+    );
+    for input in inputs {
+        writeln!(
+            result,
+            " - `{}` ({} bytes): {}",
+            input.ty,
+            input.total_bits.div_ceil(8),
+            bounding_hint(&input.ty)
+        )
+        .unwrap();
+    }
+    result
+}
+
+/// How an input of the given type can be bounded.
+fn bounding_hint(ty: &str) -> String {
+    if let Some(elem) = ty.strip_prefix('[').and_then(|ty| Some(ty.rsplit_once(';')?.0)) {
+        format!(
+            "its length can be bounded with a smaller array or with \
+            `kani::vec::any_vec::<{elem}, MAX_LENGTH>()`"
+        )
+    } else if ty.starts_with(['u', 'i']) && ty[1..].chars().all(|c| c.is_ascii_alphanumeric()) {
+        "its value can be bounded with `kani::assume`, which matters most if it is used as a \
+        length or a loop bound"
+            .to_string()
+    } else {
+        "its fields can be restricted with `kani::assume` or `#[safety_constraint]`".to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::args;
@@ -434,4 +634,72 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_resolve_timeout() {
+        let harness_none = mock_proof_harness("check_one", None, None, None);
+        let mut harness_some = mock_proof_harness("check_one", None, None, None);
+        harness_some.attributes.timeout = Some(30);
+
+        fn resolve(args: &str, harness: &HarnessMetadata) -> Option<Duration> {
+            let args = args::StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+            resolve_timeout(&args.verify_opts, harness)
+        }
+
+        assert_eq!(resolve("kani x.rs", &harness_none), None);
+        assert_eq!(
+            resolve("kani x.rs --harness-timeout 10", &harness_none),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(resolve("kani x.rs", &harness_some), Some(Duration::from_secs(30)));
+        assert_eq!(
+            resolve("kani x.rs --harness-timeout 10", &harness_some),
+            Some(Duration::from_secs(30))
+        );
+        assert!(
+            args::StandaloneArgs::try_parse_from(["kani", "x.rs", "--harness-timeout", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn check_format_nondet_stats() {
+        let small = NondetStats { count: 3, total_bits: 65, inputs: vec![] };
+        assert_eq!(format_nondet_stats(&small), "Nondeterministic Values: 3 types (9 bytes)\n");
+        let large = NondetStats { count: 1, total_bits: LARGE_NONDET_BITS + 8, inputs: vec![] };
+        assert!(format_nondet_stats(&large).contains("Consider bounding the size of its inputs."));
+    }
+
+    #[test]
+    fn check_format_trusted_code() {
+        let trusted = BTreeSet::from(["dep::f".to_string(), "my_crate::vendor::g".to_string()]);
+        assert_eq!(
+            format_trusted_code(&trusted),
+            "Trusted Code (not checked): 2 functions\n - dep::f\n - my_crate::vendor::g\n"
+        );
+        assert_eq!(
+            format_trusted_code(&BTreeSet::from(["dep::f".to_string()])),
+            "Trusted Code (not checked): 1 function\n - dep::f\n"
+        );
+    }
+
+    #[test]
+    fn check_format_largest_inputs() {
+        let mut stats = NondetStats::default();
+        stats.record("[u8; 1024]", 8192);
+        stats.record("usize", 64);
+        stats.record("bool", 8);
+        stats.record("Point", 64);
+        stats.record("()", 0);
+        let inputs = format_largest_inputs(&stats);
+        let lines: Vec<_> = inputs.lines().skip(1).collect();
+        assert_eq!(lines.len(), MAX_LISTED_INPUTS);
+        assert!(lines[0].starts_with(" - `[u8; 1024]` (1024 bytes)"));
+        assert!(lines[0].contains("`kani::vec::any_vec::<u8, MAX_LENGTH>()`"));
+        assert!(lines[1].starts_with(" - `Point` (8 bytes)"));
+        assert!(lines[1].contains("`#[safety_constraint]`"));
+        assert!(lines[2].starts_with(" - `usize` (8 bytes)"));
+        assert!(lines[2].contains("`kani::assume`"));
+        assert_eq!(format_largest_inputs(&NondetStats::default()), "");
+    }
 }
@@ -8,7 +8,7 @@
 use std::process::Command;
 
 use crate::session::KaniSession;
-use crate::util::{alter_extension, warning};
+use crate::util::{alter_extension, message, warning};
 
 impl KaniSession {
     /// Run CBMC appropriately to produce 3 output XML files, then run cbmc-viewer on them to produce a report.
@@ -56,13 +56,13 @@ pub fn run_visualize(
 
         // Let the user know
         if !self.args.common_args.quiet {
-            println!("Report written to: {}/html/index.html", report_dir.to_string_lossy());
+            message!("Report written to: {}/html/index.html", report_dir.to_string_lossy());
             warning("coverage information has been disabled for `--visualize` reports");
             // If using VS Code with Remote-SSH, suggest an option for remote viewing:
             if std::env::var("VSCODE_IPC_HOOK_CLI").is_ok()
                 && std::env::var("SSH_CONNECTION").is_ok()
             {
-                println!(
+                message!(
                     "VS Code automatically forwards ports for locally hosted servers. To view the report remotely,\nTry:  python3 -m http.server --directory {}/html",
                     report_dir.to_string_lossy()
                 );
@@ -11,7 +11,8 @@
 use crate::metadata::collect_and_link_function_pointer_restrictions;
 use crate::project::Project;
 use crate::session::KaniSession;
-use crate::util::alter_extension;
+use crate::util::{alter_extension, message};
+use kani_metadata::artifact::convert_type;
 use kani_metadata::{ArtifactType, HarnessMetadata};
 
 impl KaniSession {
@@ -47,21 +48,25 @@ pub fn instrument_model(
         self.rewrite_back_edges(output)?;
 
         if self.args.gen_c {
-            let c_outfile = alter_extension(output, "c");
+            // Name the C files after the shared model, not the copy owned by this session.
+            let model = harness.goto_file.as_deref().map_or(output.to_path_buf(), |model| {
+                convert_type(model, ArtifactType::SymTabGoto, ArtifactType::Goto)
+            });
+            let c_outfile = alter_extension(&model, "c");
             // We don't put the C file into temporaries to be deleted.
 
             self.gen_c(output, &c_outfile)?;
 
             if !self.args.common_args.quiet {
-                println!("Generated C code written to {}", c_outfile.to_string_lossy());
+                message!("Generated C code written to {}", c_outfile.to_string_lossy());
             }
 
-            let c_demangled = alter_extension(output, "demangled.c");
+            let c_demangled = alter_extension(&model, "demangled.c");
             let prett_name_map =
                 project.get_harness_artifact(&harness, ArtifactType::PrettyNameMap).unwrap();
             self.demangle_c(prett_name_map, &c_outfile, &c_demangled)?;
             if !self.args.common_args.quiet {
-                println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
+                message!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
             }
         }
 
@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::util::warning;
+use crate::util::{message, warning};
 use anyhow::Result;
 use kani_metadata::HarnessMetadata;
 use std::ffi::OsString;
@@ -22,7 +22,7 @@ pub fn synthesize_loop_contracts(
         harness_metadata: &HarnessMetadata,
     ) -> Result<()> {
         if !self.args.common_args.quiet {
-            println!("Running loop contract synthesizer.");
+            message!("Running loop contract synthesizer.");
             warning("This process may not terminate.");
             warning(
                 "Loop-contracts synthesizer is not compatible with unwinding bounds. Unwind bounds will be ignored.",
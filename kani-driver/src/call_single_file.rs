@@ -6,7 +6,9 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::args::{ExternCrate, OverflowSemantics};
 use crate::session::{lib_folder, KaniSession};
+use kani_metadata::UnstableFeature;
 
 impl KaniSession {
     /// Used by `kani` and not `cargo-kani` to process a single Rust file into a `.symtab.json`
@@ -16,6 +18,7 @@ pub fn compile_single_rust_file(
         file: &Path,
         crate_name: &String,
         outdir: &Path,
+        externs: &[ExternCrate],
     ) -> Result<()> {
         let mut kani_args = self.kani_compiler_flags();
         kani_args.push(format!("--reachability={}", self.reachability_mode()));
@@ -27,6 +30,27 @@ pub fn compile_single_rust_file(
         rustc_args.push("--crate-name".into());
         rustc_args.push(crate_name.into());
 
+        // The dependencies of a pre-built crate are usually next to it, so let rustc look for
+        // them there.
+        let mut dependency_dirs = vec![];
+        for dependency in externs {
+            let mut arg = OsString::from(format!("{}=", dependency.name));
+            arg.push(&dependency.path);
+            rustc_args.push("--extern".into());
+            rustc_args.push(arg);
+            let dir = dependency.path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            let dir = dir.unwrap_or(Path::new("."));
+            if !dependency_dirs.contains(&dir) {
+                dependency_dirs.push(dir);
+            }
+        }
+        for dir in dependency_dirs {
+            let mut arg = OsString::from("dependency=");
+            arg.push(dir);
+            rustc_args.push("-L".into());
+            rustc_args.push(arg);
+        }
+
         if self.args.tests {
             // e.g. `tests/kani/Options/check_tests.rs` will fail because it already has it
             // so this is a hacky workaround
@@ -80,12 +104,25 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
         if self.args.restrict_vtable() {
             flags.push("--restrict-vtable-fn-ptrs".into());
         }
+        if self.args.restrict_fn_ptrs {
+            flags.push("--restrict-fn-ptrs".into());
+        }
+        if self.args.overflow_semantics == OverflowSemantics::Wrap {
+            flags.push("--wrapping-overflow".into());
+        }
         if self.args.assertion_reach_checks() {
             flags.push("--assertion-reach-checks".into());
         }
+        // The reports of `--visualize` would show the identifiers that the checks need for this.
+        if !self.args.visualize {
+            flags.push("--check-span-ends".into());
+        }
         if self.args.ignore_global_asm {
             flags.push("--ignore-global-asm".into());
         }
+        if self.ignore_user_assumptions {
+            flags.push("--ignore-user-assumptions".into());
+        }
 
         // Users activate it via the command line switch
         if self.args.write_json_symtab {
@@ -100,6 +137,18 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--coverage-checks".into());
         }
 
+        for region in &self.args.mmio_regions {
+            flags.push(format!("--mmio-region={region}"));
+        }
+
+        for path in &self.args.trusted_paths {
+            flags.push(format!("--trusted={path}"));
+        }
+
+        if let Some(max_interrupts) = self.args.max_interrupts {
+            flags.push(format!("--max-interrupts={max_interrupts}"));
+        }
+
         flags.extend(self.args.common_args.unstable_features.as_arguments().map(str::to_string));
 
         // This argument will select the Kani flavour of the compiler. It will be removed before
@@ -113,18 +162,21 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
     pub fn kani_rustc_flags(&self) -> Vec<OsString> {
         let lib_path = lib_folder().unwrap();
         let mut flags: Vec<_> = base_rustc_flags(lib_path);
-        // We only use panic abort strategy for verification since we cannot handle unwind logic.
-        flags.extend_from_slice(
-            &[
-                "-C",
-                "panic=abort",
-                "-C",
-                "symbol-mangling-version=v0",
-                "-Z",
-                "panic_abort_tests=yes",
-            ]
-            .map(OsString::from),
-        );
+        // Overflow checks are enabled by the base flags. Override them if the user asked for
+        // wrapping semantics, so rustc doesn't generate any overflow check.
+        if self.args.overflow_semantics == OverflowSemantics::Wrap {
+            flags.extend_from_slice(&["-C", "overflow-checks=off"].map(OsString::from));
+        }
+        // We use the panic abort strategy for verification unless the user asked us to model
+        // stack unwinding.
+        if self.args.common_args.unstable_features.contains(UnstableFeature::Unwind) {
+            flags.extend_from_slice(&["-C", "panic=unwind"].map(OsString::from));
+        } else {
+            flags.extend_from_slice(
+                &["-C", "panic=abort", "-Z", "panic_abort_tests=yes"].map(OsString::from),
+            );
+        }
+        flags.extend_from_slice(&["-C", "symbol-mangling-version=v0"].map(OsString::from));
 
         if let Some(seed_opt) = self.args.randomize_layout {
             flags.push("-Z".into());
@@ -135,6 +187,10 @@ pub fn kani_rustc_flags(&self) -> Vec<OsString> {
             }
         }
 
+        for cfg in &self.extra_cfgs {
+            flags.push(format!("--cfg={cfg}").into());
+        }
+
         // This argument will select the Kani flavour of the compiler. It will be removed before
         // rustc driver is invoked.
         flags.push("--kani-compiler".into());
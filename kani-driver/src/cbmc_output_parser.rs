@@ -16,8 +16,8 @@
 //! ]
 //! ```
 //!
-//! The parser included in this file reads from buffered input line by line, and
-//! determines if an item can be processed after reading certain lines.
+//! The parser included in this file reads from buffered input, and returns each
+//! item as soon as it's complete, regardless of how CBMC splits it into lines.
 //!
 //! The rest of code in this file is related to result postprocessing.
 
@@ -28,14 +28,17 @@
 use pathdiff::diff_paths;
 use rustc_demangle::demangle;
 use serde::{Deserialize, Deserializer};
+use tracing::debug;
 
+use std::collections::VecDeque;
 use std::env;
 use std::io::{BufRead, BufReader};
-use std::os::unix::process::ExitStatusExt;
-use std::path::PathBuf;
-use std::process::{Child, ChildStdout};
-
-const RESULT_ITEM_PREFIX: &str = "  {\n    \"result\":";
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
 /// A parser item is a top-level unit of output from the CBMC json format.
 /// See the parser for more information on how they are processed.
@@ -49,6 +52,9 @@ pub enum ParserItem {
     Message {
         message_text: String,
         message_type: String,
+        /// The phase event described by a status message, set by the parser.
+        #[serde(skip)]
+        phase: Option<PhaseEvent>,
     },
     Result {
         result: Vec<Property>,
@@ -95,8 +101,12 @@ pub struct PropertyId {
 }
 
 impl Property {
+    const ASSUMPTION_PROPERTY_CLASS: &'static str = "assumption";
+    const ASSUMPTION_CHECK_PROPERTY_CLASS: &'static str = "assumption_check";
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const SELF_BLOCKING_PROPERTY_CLASS: &'static str = "self_blocking";
+    const EXPECTED_FAILURE_PROPERTY_CLASS: &'static str = "expected_failure";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -107,6 +117,26 @@ pub fn is_code_coverage_property(&self) -> bool {
         self.property_id.class == Self::COVERAGE_PROPERTY_CLASS
     }
 
+    /// Returns true if this is an assumption created with `kani::assume!`
+    pub fn is_assumption_property(&self) -> bool {
+        self.property_id.class == Self::ASSUMPTION_PROPERTY_CLASS
+    }
+
+    /// Returns true if this is a check added by `-Z assumption-checks`
+    pub fn is_assumption_check_property(&self) -> bool {
+        self.property_id.class == Self::ASSUMPTION_CHECK_PROPERTY_CLASS
+    }
+
+    /// Returns true if this is a self-blocking check of the `kani::sync` models
+    pub fn is_self_blocking_property(&self) -> bool {
+        self.property_id.class == Self::SELF_BLOCKING_PROPERTY_CLASS
+    }
+
+    /// Returns true if this marks an expression wrapped by `kani::expect_fail!`
+    pub fn is_expected_failure_property(&self) -> bool {
+        self.property_id.class == Self::EXPECTED_FAILURE_PROPERTY_CLASS
+    }
+
     /// Returns true if this is a cover property
     pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
@@ -219,6 +249,12 @@ fn deserialize<D>(d: D) -> Result<Self, D::Error>
 #[derive(Clone, Debug, Deserialize)]
 pub struct SourceLocation {
     pub column: Option<String>,
+    /// Where the source span ends, which CBMC doesn't report. This is filled in from the harness
+    /// metadata for the checks that Kani generates with a unique identifier.
+    #[serde(skip)]
+    pub end_column: Option<usize>,
+    #[serde(skip)]
+    pub end_line: Option<usize>,
     pub file: Option<String>,
     pub function: Option<String>,
     pub line: Option<String>,
@@ -229,6 +265,20 @@ impl SourceLocation {
     pub fn is_missing(&self) -> bool {
         self.file.is_none() && self.function.is_none()
     }
+
+    /// Determines if this location is in a file under the current working directory, i.e., in
+    /// the code being verified as opposed to the standard library or the Kani library.
+    pub fn is_in_current_dir(&self) -> bool {
+        self.file.as_deref().is_some_and(is_in_current_dir)
+    }
+}
+
+/// Determines if a file is under the current working directory. Relative paths are.
+pub fn is_in_current_dir(file: &str) -> bool {
+    let path = Path::new(file);
+    let cur_dir = env::current_dir().unwrap();
+    path.is_relative()
+        || without_verbatim_prefix(path).starts_with(without_verbatim_prefix(&cur_dir))
 }
 
 /// `Display` implement for `SourceLocation`.
@@ -272,7 +322,8 @@ fn filepath(file: String) -> String {
     let file_path = PathBuf::from(file.clone());
     let cur_dir = env::current_dir().unwrap();
 
-    let diff_path_opt = diff_paths(file_path, cur_dir);
+    let diff_path_opt =
+        diff_paths(without_verbatim_prefix(&file_path), without_verbatim_prefix(&cur_dir));
     if let Some(diff_path) = diff_path_opt {
         diff_path.into_os_string().into_string().unwrap()
     } else {
@@ -280,6 +331,12 @@ fn filepath(file: String) -> String {
     }
 }
 
+/// Remove the `\\?\` prefix that Windows adds to canonical paths, since paths with and without
+/// the prefix cannot be compared.
+fn without_verbatim_prefix(path: &Path) -> &Path {
+    path.to_str().and_then(|path| path.strip_prefix(r"\\?\")).map_or(path, Path::new)
+}
+
 /// Struct that represents traces.
 ///
 /// In general, traces may include more information than this, but this is not
@@ -329,12 +386,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 pub enum CheckStatus {
     Failure,
     Covered,   // for `code_coverage` properties only
-    Satisfied, // for `cover` properties only
+    Reachable, // for `assumption` properties only
+    Satisfied, // for `cover` and `assumption_check` properties only
     Success,
     Undetermined,
     Unreachable,
     Uncovered,     // for `code_coverage` properties only
-    Unsatisfiable, // for `cover` properties only
+    Unsatisfiable, // for `cover` and `assumption_check` properties only
 }
 
 impl std::fmt::Display for CheckStatus {
@@ -343,6 +401,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             CheckStatus::Satisfied => style("SATISFIED").green(),
             CheckStatus::Success => style("SUCCESS").green(),
             CheckStatus::Covered => style("COVERED").green(),
+            CheckStatus::Reachable => style("REACHABLE").green(),
             CheckStatus::Uncovered => style("UNCOVERED").red(),
             CheckStatus::Failure => style("FAILURE").red(),
             CheckStatus::Unreachable => style("UNREACHABLE").yellow(),
@@ -353,15 +412,141 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
-#[derive(PartialEq)]
-enum Action {
-    ClearInput,
-    ProcessItem,
+/// The phases of a CBMC run, in the order they happen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    /// Reading the goto binary.
+    Parsing,
+    /// Transforming the goto program, e.g. adding the CBMC library and removing function pointers.
+    Conversion,
+    /// Adding the property checks and slicing the program.
+    Instrumentation,
+    /// Symbolic execution, which unwinds the program into an equation.
+    Symex,
+    /// Converting the equation into a formula and solving it.
+    Solving,
+}
+
+/// An event of a CBMC run, as described by one of its status messages.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PhaseEvent {
+    /// A phase started.
+    Started(Phase),
+    /// A step of a phase took `seconds`, e.g. `Runtime Symex: 0.01s`.
+    Runtime { phase: Phase, step: String, seconds: f64 },
+    /// Symbolic execution unwound a loop (or a recursive function) once more.
+    Unwinding { phase: Phase, id: String, iteration: u64 },
+    /// The size of the equation generated by symbolic execution.
+    ProgramSize { steps: u64 },
+    /// The number of verification conditions, before and after simplification.
+    VerificationConditions { generated: u64, remaining: u64 },
+    /// The size of the formula passed to the solver.
+    FormulaSize { variables: u64, clauses: u64 },
+    /// Any other status message about the given phase.
+    Progress(Phase),
+}
+
+impl PhaseEvent {
+    /// Returns the phase of the event.
+    pub fn phase(&self) -> Phase {
+        match self {
+            PhaseEvent::Started(phase)
+            | PhaseEvent::Runtime { phase, .. }
+            | PhaseEvent::Unwinding { phase, .. }
+            | PhaseEvent::Progress(phase) => *phase,
+            PhaseEvent::ProgramSize { .. } | PhaseEvent::VerificationConditions { .. } => {
+                Phase::Symex
+            }
+            PhaseEvent::FormulaSize { .. } => Phase::Solving,
+        }
+    }
+
+    /// Classifies a status message. Returns `None` for messages that aren't about a phase, e.g.
+    /// the verification result.
+    pub fn from_status_message(text: &str) -> Option<PhaseEvent> {
+        if let Some(runtime) = text.strip_prefix("Runtime ") {
+            let (step, seconds) = runtime.split_once(": ")?;
+            let seconds = seconds.strip_suffix('s')?.parse().ok()?;
+            let phase = match step {
+                "Symex" | "Postprocess Equation" => Phase::Symex,
+                _ => Phase::Solving,
+            };
+            return Some(PhaseEvent::Runtime { phase, step: step.to_string(), seconds });
+        }
+        if let Some(unwinding) = text.strip_prefix("Unwinding ") {
+            // E.g. `Unwinding loop main.0 iteration 5 file main.rs line 3 function main thread 0`
+            // or `Unwinding recursion fact iteration 2`.
+            let mut words = unwinding.split_whitespace().skip(1);
+            let id = words.next()?.to_string();
+            let iteration = words.nth(1)?.parse().ok()?;
+            return Some(PhaseEvent::Unwinding { phase: Phase::Symex, id, iteration });
+        }
+        if let Some(size) = text.strip_prefix("size of program expression: ") {
+            let steps = size.strip_suffix(" steps")?.parse().ok()?;
+            return Some(PhaseEvent::ProgramSize { steps });
+        }
+        if let Some(vccs) = text.strip_prefix("Generated ") {
+            // E.g. `Generated 5 VCC(s), 3 remaining after simplification`
+            let (generated, remaining) = vccs.split_once(" VCC(s), ")?;
+            let remaining = remaining.strip_suffix(" remaining after simplification")?;
+            return Some(PhaseEvent::VerificationConditions {
+                generated: generated.parse().ok()?,
+                remaining: remaining.parse().ok()?,
+            });
+        }
+        if let Some((variables, clauses)) = text.split_once(" variables, ") {
+            let clauses = clauses.strip_suffix(" clauses")?;
+            return Some(PhaseEvent::FormulaSize {
+                variables: variables.parse().ok()?,
+                clauses: clauses.parse().ok()?,
+            });
+        }
+        const STARTS: [(&str, Phase); 5] = [
+            ("Reading GOTO program", Phase::Parsing),
+            ("Adding CPROVER library", Phase::Conversion),
+            ("Generic Property Instrumentation", Phase::Instrumentation),
+            ("Starting Bounded Model Checking", Phase::Symex),
+            ("Passing problem to", Phase::Solving),
+        ];
+        const PROGRESS: [(&str, Phase); 10] = [
+            ("Reading", Phase::Parsing),
+            ("Removal of", Phase::Conversion),
+            ("Partial Inlining", Phase::Conversion),
+            ("Removing", Phase::Instrumentation),
+            ("Dropping", Phase::Instrumentation),
+            ("Performing a", Phase::Instrumentation),
+            ("slicing removed", Phase::Symex),
+            ("converting SSA", Phase::Solving),
+            ("Running propositional reduction", Phase::Solving),
+            ("Solving with", Phase::Solving),
+        ];
+        if let Some((_, phase)) = STARTS.iter().find(|(prefix, _)| text.starts_with(prefix)) {
+            Some(PhaseEvent::Started(*phase))
+        } else {
+            let (_, phase) = PROGRESS.iter().find(|(prefix, _)| text.starts_with(prefix))?;
+            Some(PhaseEvent::Progress(*phase))
+        }
+    }
+}
+
+/// How the JSON output is laid out. CBMC pretty-prints every item over several lines, but the
+/// parser doesn't rely on it: the same output may be re-serialized with each item on a line of its
+/// own, or the whole array on one line, e.g. by `jq -c`. The framing is only detected to help
+/// debugging, and the detection has only been tested with synthetic outputs so far.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Framing {
+    /// Items span several lines.
+    Pretty,
+    /// Each item is on its own line.
+    LinePerItem,
+    /// Several items share a line.
+    Compact,
 }
 
 /// A parser for CBMC output, whose state is determined by:
-///  1. The input accumulator, required to process items on the fly.
-///  2. The buffer, which is accessed to retrieve more lines.
+///  1. The input accumulator, which holds the item being read.
+///  2. The position in the JSON structure (nesting depth, and whether we're inside a string).
+///  3. The buffer, which is accessed to retrieve more lines.
 ///
 /// CBMC's JSON output is defined as a JSON array which contains:
 ///  1. One program at the beginning (i.e., a message with CBMC's version).
@@ -370,82 +555,118 @@ enum Action {
 ///  4. Prover status, at the end. Because the verification results depends on
 ///     our postprocessing, this is not used.
 ///
-/// The parser reads the output line by line. A line may trigger one action, and
-/// the action may return a parsed item.
+/// The parser scans the output character by character, and parses an item as
+/// soon as its closing bracket is read. Since it follows the JSON structure
+/// instead of the line breaks, it works with any framing CBMC uses (see [Framing]).
 ///
 /// There is a feature request for serde_json which would obsolete this if
 /// it ever lands: <https://github.com/serde-rs/json/issues/404>
 /// (Would provide a streaming iterator over a json array.)
-struct Parser<'a, 'b> {
-    pub input_so_far: String,
-    pub buffer: &'a mut BufReader<&'b mut ChildStdout>,
+struct Parser<R: BufRead> {
+    input_so_far: String,
+    /// The nesting depth, where 1 is the top-level array.
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// The number of lines read when the current item started.
+    item_start_line: usize,
+    lines_read: usize,
+    framing: Option<Framing>,
+    /// Items that were completed by the last line, but not returned yet.
+    pending: VecDeque<ParserItem>,
+    buffer: R,
 }
 
-impl<'a, 'b> Parser<'a, 'b> {
-    fn new(buffer: &'a mut BufReader<&'b mut ChildStdout>) -> Self {
-        Parser { input_so_far: String::new(), buffer }
-    }
-
-    /// Triggers an action based on the input:
-    ///  * Square brackets ('[' and ']') will trigger the `ClearInput` action
-    ///    because we assume parsing is done on a JSON array.
-    ///  * Curly closing bracket ('}') preceded by two spaces will trigger the
-    ///    `ProcessItem` action. The spaces are important in this case because
-    ///    assume we're in a JSON array. Matching on this specific string guarantees
-    ///    that we'll always get an item when we attempt to process an item.
-    ///
-    /// This has be updated if the output format changes at some point.
-    fn triggers_action(&self, input: String) -> Option<Action> {
-        if input.starts_with('[') || input.starts_with(']') {
-            // We don't expect any other characters (except '\n') to appear
-            // after '[' or ']'. The assert below ensures we won't ignore them.
-            assert!(input.len() == 2);
-            return Some(Action::ClearInput);
-        }
-        if input.starts_with("  }") {
-            return Some(Action::ProcessItem);
+impl<R: BufRead> Parser<R> {
+    fn new(buffer: R) -> Self {
+        Parser {
+            input_so_far: String::new(),
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            item_start_line: 0,
+            lines_read: 0,
+            framing: None,
+            pending: VecDeque::new(),
+            buffer,
         }
-        None
-    }
-
-    /// Clears the input accumulated so far.
-    fn clear_input(&mut self) {
-        self.input_so_far.clear();
     }
 
-    /// Performs an action. In both cases, the input is cleared.
-    fn do_action(&mut self, action: Action) -> Option<ParserItem> {
-        match action {
-            Action::ClearInput => {
-                self.clear_input();
-                None
+    /// Processes a line, and queues the items that it completes.
+    fn process_line(&mut self, input: &str) {
+        self.lines_read += 1;
+        for c in input.chars() {
+            if self.in_string {
+                self.input_so_far.push(c);
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == '\\' {
+                    self.escaped = true;
+                } else if c == '"' {
+                    self.in_string = false;
+                }
+                continue;
             }
-            Action::ProcessItem => {
-                let item = self.parse_item();
-                self.clear_input();
-                Some(item)
+            match c {
+                // The start and end of the top-level array.
+                '[' if self.depth == 0 => self.depth = 1,
+                ']' if self.depth == 1 => self.depth = 0,
+                // Separators and whitespace between items.
+                _ if self.depth <= 1 && (c == ',' || c.is_whitespace()) => {}
+                '{' | '[' => {
+                    if self.depth == 1 {
+                        self.item_start_line = self.lines_read;
+                    }
+                    self.depth += 1;
+                    self.input_so_far.push(c);
+                }
+                '}' | ']' => {
+                    self.depth -= 1;
+                    self.input_so_far.push(c);
+                    if self.depth == 1 {
+                        self.complete_item();
+                    }
+                }
+                '"' => {
+                    self.in_string = true;
+                    self.input_so_far.push(c);
+                }
+                _ => self.input_so_far.push(c),
             }
         }
     }
 
-    // Adds a string to the input accumulated so far
-    fn add_to_input(&mut self, input: String) {
-        self.input_so_far.push_str(&input);
+    /// Parses the item accumulated so far and queues it.
+    fn complete_item(&mut self) {
+        let framing = if self.item_start_line != self.lines_read {
+            Framing::Pretty
+        } else if self.pending.is_empty() {
+            Framing::LinePerItem
+        } else {
+            Framing::Compact
+        };
+        if self.framing.is_none() || framing == Framing::Compact {
+            debug!(?framing, "CBMC output framing");
+            self.framing = Some(framing);
+        }
+        let mut item = self.parse_item();
+        if let ParserItem::Message { message_text, message_type, phase } = &mut item
+            && message_type == "STATUS-MESSAGE"
+        {
+            *phase = PhaseEvent::from_status_message(message_text);
+        }
+        self.input_so_far.clear();
+        self.pending.push_back(item);
     }
 
-    // Returns a `ParserItem` from the input we have accumulated so far. Since
-    // all items except the last one are delimited (with a comma), we first try
-    // to parse the item without the delimiter (i.e., the last character). If
-    // that fails, then we parse the item using the whole input.
+    // Returns a `ParserItem` from the input we have accumulated so far.
     fn parse_item(&self) -> ParserItem {
-        let string_without_delimiter = &self.input_so_far[0..self.input_so_far.len() - 2];
-        let result_item: Result<ParserItem, _> = serde_json::from_str(string_without_delimiter);
+        let result_item: Result<ParserItem, _> = serde_json::from_str(&self.input_so_far);
         if let Ok(item) = result_item {
             return item;
         }
-        // If we failed to parse a `ParserItem::Result` earlier, we will get
-        // this error message when we attempt to parse it using the complete
-        // string:
+        // If we failed to parse a `ParserItem::Result`, we will get this error
+        // message:
         // ```
         // thread '<unnamed>' panicked at 'called `Result::unwrap()` on an `Err` value:
         // Error("data did not match any variant of untagged enum ParserItem", line: 0, column: 0)'
@@ -457,48 +678,28 @@ fn parse_item(&self) -> ParserItem {
         // shouldn't be hard to debug with that information. The same strategy
         // can be used for other `ParserItem` variants, but they're normally
         // easier to debug.
-        if string_without_delimiter.starts_with(RESULT_ITEM_PREFIX) {
-            let result_item: Result<ResultStruct, _> =
-                serde_json::from_str(string_without_delimiter);
+        let value: serde_json::Value = serde_json::from_str(&self.input_so_far).unwrap();
+        if value.get("result").is_some() {
+            let result_item: Result<ResultStruct, _> = serde_json::from_value(value);
             result_item.unwrap();
         }
-        let complete_string = &self.input_so_far[0..self.input_so_far.len()];
-        let result_item: Result<ParserItem, _> = serde_json::from_str(complete_string);
         result_item.unwrap()
     }
-
-    /// Processes a line to determine if an action must be triggered.
-    /// The action may result in a `ParserItem`, which is then returned.
-    fn process_line(&mut self, input: String) -> Option<ParserItem> {
-        self.add_to_input(input.clone());
-        let action_required = self.triggers_action(input);
-        if let Some(action) = action_required {
-            let possible_item = self.do_action(action);
-            return possible_item;
-        }
-        None
-    }
 }
 
 /// The iterator implementation for `Parser` reads the buffer line by line,
-/// and determines if it must return an item based on processing each line.
-impl<'a, 'b> Iterator for Parser<'a, 'b> {
+/// and returns the items as soon as they are complete.
+impl<R: BufRead> Iterator for Parser<R> {
     type Item = ParserItem;
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
             let mut input = String::new();
             match self.buffer.read_line(&mut input) {
-                Ok(len) => {
-                    if len == 0 {
-                        return None;
-                    }
-                    let item = self.process_line(input);
-                    if item.is_some() {
-                        return item;
-                    } else {
-                        continue;
-                    }
-                }
+                Ok(0) => return None,
+                Ok(_) => self.process_line(&input),
                 Err(error) => {
                     panic!("Error: Got error {error} while parsing the output.");
                 }
@@ -511,6 +712,8 @@ fn next(&mut self) -> Option<Self::Item> {
 pub struct VerificationOutput {
     pub process_status: i32,
     pub processed_items: Vec<ParserItem>,
+    /// Whether the process was killed because it didn't complete within the timeout.
+    pub timed_out: bool,
 }
 
 /// The main function to process CBMC's output.
@@ -520,20 +723,43 @@ pub struct VerificationOutput {
 /// In general, a filter will pre-process an item (this may or may not transform the item),
 /// then formatted (according to the output format) and print.
 ///
+/// If a `timeout` is given, the process is killed once it expires, and the items that were
+/// processed until then are returned.
+///
 /// The cbmc process status is returned, along with the (post-filter) items.
 pub fn process_cbmc_output(
     mut process: Child,
+    timeout: Option<Duration>,
     eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
 ) -> Result<VerificationOutput> {
-    let stdout = process.stdout.as_mut().unwrap();
-    let mut stdout_reader = BufReader::new(stdout);
-    let parser = Parser::new(&mut stdout_reader);
-    // This should run until stdout is closed (which should mean the process exited)
-    let processed_items: Vec<_> = parser.filter_map(eager_filter).collect();
+    let stdout = process.stdout.take().unwrap();
+    let parser = Parser::new(BufReader::new(stdout));
+    let process = Mutex::new(process);
+    // The watchdog kills the process unless the parser is done before the timeout. The parser
+    // signals it by dropping `done`.
+    let (done, finished) = mpsc::channel::<()>();
+    let (processed_items, timed_out) = thread::scope(|scope| {
+        let watchdog = timeout.map(|timeout| {
+            let process = &process;
+            scope.spawn(move || {
+                let expired = finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout);
+                if expired {
+                    debug!(?timeout, "killing CBMC after timeout");
+                    let _ = process.lock().unwrap().kill();
+                }
+                expired
+            })
+        });
+        // This should run until stdout is closed (which should mean the process exited)
+        let processed_items: Vec<_> = parser.filter_map(eager_filter).collect();
+        drop(done);
+        let timed_out = watchdog.is_some_and(|watchdog| watchdog.join().unwrap());
+        (processed_items, timed_out)
+    });
     // This will get us the process's exit code
-    let status = process.wait()?;
+    let status = process.into_inner().unwrap().wait()?;
 
-    let process_status = match (status.code(), status.signal()) {
+    let process_status = match (status.code(), exit_signal(&status)) {
         // normal unix exit codes (cbmc uses currently 0-10)
         // https://github.com/diffblue/cbmc/blob/develop/src/util/exit_codes.h
         (Some(x), _) => x,
@@ -545,7 +771,20 @@ pub fn process_cbmc_output(
         (None, None) => unreachable!("Process exited with neither status code nor signal?"),
     };
 
-    Ok(VerificationOutput { process_status, processed_items })
+    Ok(VerificationOutput { process_status, processed_items, timed_out })
+}
+
+/// Return the signal that terminated the process, if any.
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+/// Processes are not terminated by signals on Windows. They always have an exit code.
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
 }
 
 /// Takes (by ownership) a vector of messages, and returns that vector with the `Result`
@@ -569,6 +808,22 @@ pub fn extract_results(mut items: Vec<ParserItem>) -> (Vec<ParserItem>, Option<V
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn check_process_timeout() {
+        use std::process::{Command, Stdio};
+        let spawn = |script: &str| {
+            Command::new("sh").args(["-c", script]).stdout(Stdio::piped()).spawn().unwrap()
+        };
+        let timeout = Some(Duration::from_millis(200));
+        let output = process_cbmc_output(spawn("exec sleep 10"), timeout, Some).unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.process_status, 128 + 9);
+        let output = process_cbmc_output(spawn("exit 3"), timeout, Some).unwrap();
+        assert!(!output.timed_out);
+        assert_eq!(output.process_status, 3);
+    }
+
     #[test]
     fn check_property_id_deserialization_general() {
         let prop_id_string = "\"alloc::raw_vec::RawVec::<u8>::allocate_in.sanity_check.1\"";
@@ -589,6 +844,8 @@ fn check_property_id_deserialization_general() {
                 function: None,
                 file: None,
                 column: None,
+                end_column: None,
+                end_line: None,
                 line: None,
             },
             status: CheckStatus::Success,
@@ -619,6 +876,8 @@ fn check_property_id_deserialization_only_name() {
                 function: None,
                 file: None,
                 column: None,
+                end_column: None,
+                end_line: None,
                 line: None,
             },
             status: CheckStatus::Success,
@@ -648,6 +907,8 @@ fn check_property_id_deserialization_only_class() {
                 function: None,
                 file: None,
                 column: None,
+                end_column: None,
+                end_line: None,
                 line: None,
             },
             status: CheckStatus::Success,
@@ -674,6 +935,8 @@ fn check_property_id_deserialization_special() {
                 function: None,
                 file: None,
                 column: None,
+                end_column: None,
+                end_line: None,
                 line: None,
             },
             status: CheckStatus::Success,
@@ -703,6 +966,8 @@ fn check_property_id_deserialization_special_name() {
                 function: None,
                 file: None,
                 column: None,
+                end_column: None,
+                end_line: None,
                 line: None,
             },
             status: CheckStatus::Success,
@@ -764,4 +1029,166 @@ fn check_result_deserialization_works() {
         assert!(parser_item.is_ok());
         assert!(result_struct.is_ok());
     }
+
+    #[test]
+    fn check_without_verbatim_prefix() {
+        assert_eq!(
+            without_verbatim_prefix(Path::new(r"\\?\C:\kani\src\lib.rs")),
+            Path::new(r"C:\kani\src\lib.rs")
+        );
+        assert_eq!(
+            without_verbatim_prefix(Path::new("/kani/src/lib.rs")),
+            Path::new("/kani/src/lib.rs")
+        );
+    }
+
+    // The outputs below are synthetic, not captured from CBMC, so these tests only check that the
+    // parser follows the JSON structure. Framing detection still has to be tested against real
+    // `--json-ui` captures of at least two CBMC releases, including the pinned one (see
+    // `kani-dependencies`), e.g. by re-running the CBMC command printed by `kani --verbose` and
+    // saving its output to a file named after the CBMC version.
+
+    /// Synthetic output in the layout of `cbmc --json-ui` 5.95.1, which pretty-prints each item
+    /// over several lines. It's reduced to one message and one result, with a description that
+    /// contains brackets and escaped quotes.
+    const SYNTHETIC_PRETTY_OUTPUT: &str = r#"[
+  {
+    "program": "CBMC 5.95.1 (cbmc-5.95.1)"
+  },
+  {
+    "messageText": "Runtime Symex: 0.001s",
+    "messageType": "STATUS-MESSAGE"
+  },
+  {
+    "result": [
+      {
+        "description": "assertion failed: s == \"[}{]\\\"\"",
+        "property": "check.assertion.1",
+        "sourceLocation": {
+          "column": "5",
+          "file": "main.rs",
+          "function": "check",
+          "line": "4"
+        },
+        "status": "FAILURE",
+        "trace": [
+        ]
+      }
+    ]
+  },
+  {
+    "cProverStatus": "failure"
+  }
+]
+"#;
+
+    /// The same output re-serialized with each item on its own line. CBMC doesn't print this
+    /// layout, which checks that the parser doesn't depend on the line breaks.
+    const SYNTHETIC_LINE_PER_ITEM_OUTPUT: &str = r#"[
+{"program": "CBMC 5.95.1 (cbmc-5.95.1)"},
+{"messageText": "Runtime Symex: 0.001s", "messageType": "STATUS-MESSAGE"},
+{"result": [{"description": "assertion failed: s == \"[}{]\\\"\"", "property": "check.assertion.1", "sourceLocation": {"column": "5", "file": "main.rs", "function": "check", "line": "4"}, "status": "FAILURE", "trace": []}]},
+{"cProverStatus": "failure"}
+]
+"#;
+
+    /// The same output re-serialized with the whole array on a single line, as `jq -c` prints it.
+    const SYNTHETIC_COMPACT_OUTPUT: &str = r#"[{"program":"CBMC 5.95.1 (cbmc-5.95.1)"},{"messageText":"Runtime Symex: 0.001s","messageType":"STATUS-MESSAGE"},{"result":[{"description":"assertion failed: s == \"[}{]\\\"\"","property":"check.assertion.1","sourceLocation":{"column":"5","file":"main.rs","function":"check","line":"4"},"status":"FAILURE","trace":[]}]},{"cProverStatus":"failure"}]"#;
+
+    fn parse(output: &str) -> (Vec<ParserItem>, Option<Framing>) {
+        let mut parser = Parser::new(output.as_bytes());
+        let items = parser.by_ref().collect();
+        (items, parser.framing)
+    }
+
+    #[test]
+    fn check_parser_synthetic_framings() {
+        let (pretty, framing) = parse(SYNTHETIC_PRETTY_OUTPUT);
+        assert_eq!(framing, Some(Framing::Pretty));
+        assert_eq!(pretty.len(), 4);
+        let (_, results) = extract_results(pretty);
+        let results = results.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description, r#"assertion failed: s == "[}{]\"""#);
+        assert_eq!(results[0].status, CheckStatus::Failure);
+
+        let expected = format!("{:?}", parse(SYNTHETIC_PRETTY_OUTPUT).0);
+        let (line_per_item, framing) = parse(SYNTHETIC_LINE_PER_ITEM_OUTPUT);
+        assert_eq!(framing, Some(Framing::LinePerItem));
+        assert_eq!(format!("{line_per_item:?}"), expected);
+        let (compact, framing) = parse(SYNTHETIC_COMPACT_OUTPUT);
+        assert_eq!(framing, Some(Framing::Compact));
+        assert_eq!(format!("{compact:?}"), expected);
+    }
+
+    #[test]
+    fn check_parser_incomplete_output() {
+        // CBMC was interrupted in the middle of an item, e.g. because of a timeout.
+        let output = SYNTHETIC_PRETTY_OUTPUT;
+        let truncated = &output[..output.find("\"result\"").unwrap()];
+        let (items, _) = parse(truncated);
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn check_phase_events() {
+        let cases = [
+            ("Reading GOTO program from file a.out", Some(PhaseEvent::Started(Phase::Parsing))),
+            ("Adding CPROVER library (x86_64)", Some(PhaseEvent::Started(Phase::Conversion))),
+            (
+                "Removal of function pointers and virtual functions",
+                Some(PhaseEvent::Progress(Phase::Conversion)),
+            ),
+            ("Generic Property Instrumentation", Some(PhaseEvent::Started(Phase::Instrumentation))),
+            ("Starting Bounded Model Checking", Some(PhaseEvent::Started(Phase::Symex))),
+            (
+                "Unwinding loop main.0 iteration 5 file main.rs line 3 function main thread 0",
+                Some(PhaseEvent::Unwinding {
+                    phase: Phase::Symex,
+                    id: "main.0".into(),
+                    iteration: 5,
+                }),
+            ),
+            ("size of program expression: 120 steps", Some(PhaseEvent::ProgramSize { steps: 120 })),
+            (
+                "Generated 5 VCC(s), 3 remaining after simplification",
+                Some(PhaseEvent::VerificationConditions { generated: 5, remaining: 3 }),
+            ),
+            (
+                "Runtime Symex: 0.0125s",
+                Some(PhaseEvent::Runtime {
+                    phase: Phase::Symex,
+                    step: "Symex".into(),
+                    seconds: 0.0125,
+                }),
+            ),
+            (
+                "Passing problem to propositional reduction",
+                Some(PhaseEvent::Started(Phase::Solving)),
+            ),
+            (
+                "1024 variables, 4096 clauses",
+                Some(PhaseEvent::FormulaSize { variables: 1024, clauses: 4096 }),
+            ),
+            (
+                "Runtime Solver: 1.5s",
+                Some(PhaseEvent::Runtime {
+                    phase: Phase::Solving,
+                    step: "Solver".into(),
+                    seconds: 1.5,
+                }),
+            ),
+            ("VERIFICATION SUCCESSFUL", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(PhaseEvent::from_status_message(text), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn check_parser_sets_phase() {
+        let (items, _) = parse(SYNTHETIC_PRETTY_OUTPUT);
+        let ParserItem::Message { phase, .. } = &items[1] else { panic!("expected a message") };
+        assert_eq!(phase.as_ref().map(PhaseEvent::phase), Some(Phase::Symex));
+    }
 }
@@ -1,15 +1,19 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::args::OutputFormat;
+use crate::args::{OutputFormat, TraceFilter};
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, SourceLocation, TraceItem};
+use crate::coverage::{harness_line_coverage, is_coverage_check, CoverageStatus};
+use crate::results;
 use console::style;
+use kani_metadata::SpanEnd;
+use kani_results::CheckResult;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_demangle::demangle;
 use std::collections::{BTreeMap, HashMap};
-use strum_macros::{AsRefStr, Display};
+use tracing::debug;
 
 type CbmcAltDescriptions = HashMap<&'static str, Vec<(&'static str, Option<&'static str>)>>;
 
@@ -77,6 +81,26 @@
         ],
     );
     map.insert("pointer", vec![("same object violation", None)]);
+    map.insert(
+        "provenance",
+        vec![(
+            "int-to-pointer cast",
+            Some(
+                "int-to-pointer cast creates a pointer that is not derived from any live \
+                allocation (the integer may not come from a pointer-to-integer cast)",
+            ),
+        )],
+    );
+    map.insert(
+        "enum-validity",
+        vec![(
+            "enum validity",
+            Some(
+                "enum has a tag that does not encode any of its variants (the value may have \
+                been created by a `transmute` or a write through a pointer)",
+            ),
+        )],
+    );
     map.insert(
         "pointer_arithmetic",
         vec![
@@ -150,20 +174,21 @@
     map
 });
 
-#[derive(PartialEq, Eq, AsRefStr, Clone, Copy, Display)]
-#[strum(serialize_all = "UPPERCASE")]
-// The status of coverage reported by Kani
-enum CoverageStatus {
-    Full,
-    Partial,
-    None,
-}
-
 const UNSUPPORTED_CONSTRUCT_DESC: &str = "is not currently supported by Kani";
 const UNWINDING_ASSERT_DESC: &str = "unwinding assertion loop";
 const UNWINDING_ASSERT_REC_DESC: &str = "recursion unwinding assertion";
 const DEFAULT_ASSERTION: &str = "assertion";
 
+/// Whether a harness has no checks other than its assumptions and coverage properties, i.e.,
+/// nothing was verified.
+pub fn has_no_checks(properties: &[Property]) -> bool {
+    properties.iter().all(|prop| {
+        prop.is_assumption_property()
+            || prop.is_assumption_check_property()
+            || prop.is_code_coverage_property()
+    })
+}
+
 impl ParserItem {
     /// Determines if an item must be skipped or not.
     fn must_be_skipped(&self) -> bool {
@@ -171,6 +196,40 @@ fn must_be_skipped(&self) -> bool {
     }
 }
 
+/// The output of a single harness. It's printed as soon as it's produced, unless harnesses are
+/// verified in parallel: it's then buffered and printed at once when the harness is dropped, so
+/// the output of harnesses that run at the same time doesn't get mixed.
+pub struct HarnessOutput {
+    buffer: Option<String>,
+}
+
+impl HarnessOutput {
+    pub fn new(buffered: bool) -> Self {
+        HarnessOutput { buffer: buffered.then(String::new) }
+    }
+
+    pub fn print(&mut self, text: &str) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push_str(text),
+            None => print!("{text}"),
+        }
+    }
+
+    pub fn println(&mut self, text: &str) {
+        self.print(text);
+        self.print("\n");
+    }
+}
+
+impl Drop for HarnessOutput {
+    fn drop(&mut self) {
+        if let Some(buffer) = &self.buffer {
+            // A single call, so the buffer isn't interleaved with the output of other threads.
+            print!("{buffer}");
+        }
+    }
+}
+
 /// This is called "live" as CBMC output is streamed in, and we
 /// filter and transform it into the format we expect.
 ///
@@ -181,19 +240,24 @@ pub fn kani_cbmc_output_filter(
     extra_ptr_checks: bool,
     quiet: bool,
     output_format: &OutputFormat,
+    span_ends: &BTreeMap<String, SpanEnd>,
+    output: &mut HarnessOutput,
 ) -> Option<ParserItem> {
     // Some items (e.g., messages) are skipped.
     // We could also process them and decide to skip later.
     if item.must_be_skipped() {
         return None;
     }
-    let processed_item = process_item(item, extra_ptr_checks);
+    if let ParserItem::Message { phase: Some(event), .. } = &item {
+        debug!(phase = ?event.phase(), ?event, "CBMC phase event");
+    }
+    let processed_item = process_item(item, extra_ptr_checks, span_ends);
     // Both formatting and printing could be handled by objects which
     // implement a trait `Printer`.
     if !quiet {
         let formatted_item = format_item(&processed_item, output_format);
         if let Some(fmt_item) = formatted_item {
-            println!("{fmt_item}");
+            output.println(&fmt_item);
         }
     }
     // TODO: Record processed items and dump them into a JSON file
@@ -205,10 +269,14 @@ pub fn kani_cbmc_output_filter(
 /// except for:
 ///  * Error messages, which may be edited.
 ///  * Verification results, which must be postprocessed.
-fn process_item(item: ParserItem, extra_ptr_checks: bool) -> ParserItem {
+fn process_item(
+    item: ParserItem,
+    extra_ptr_checks: bool,
+    span_ends: &BTreeMap<String, SpanEnd>,
+) -> ParserItem {
     match item {
         ParserItem::Result { result } => {
-            let postprocessed_result = postprocess_result(result, extra_ptr_checks);
+            let postprocessed_result = postprocess_result(result, extra_ptr_checks, span_ends);
             ParserItem::Result { result: postprocessed_result }
         }
         ParserItem::Message { ref message_type, .. } if message_type == "ERROR" => {
@@ -224,6 +292,7 @@ fn format_item(item: &ParserItem, output_format: &OutputFormat) -> Option<String
         OutputFormat::Old => todo!(),
         OutputFormat::Regular => format_item_regular(item),
         OutputFormat::Terse => format_item_terse(item),
+        OutputFormat::Json => format_item_json(item),
     }
 }
 
@@ -241,6 +310,12 @@ fn format_item_terse(_item: &ParserItem) -> Option<String> {
     None
 }
 
+/// Formats an item using the JSON output format. Nothing is printed while CBMC runs, so the
+/// output only contains the document printed once all harnesses were verified.
+fn format_item_json(_item: &ParserItem) -> Option<String> {
+    None
+}
+
 /// Formats a result item (i.e., the complete set of verification checks).
 /// This could be split into two functions for clarity, but at the moment
 /// it uses the flag `show_checks` which depends on the output format.
@@ -263,7 +338,8 @@ pub fn format_result(
     let mut number_checks_failed = 0;
     let mut number_checks_unreachable = 0;
     let mut number_checks_undetermined = 0;
-    let mut failed_tests: Vec<&Property> = vec![];
+    // The failed checks, with their index in the list of results.
+    let mut failed_tests: Vec<(usize, &Property)> = vec![];
 
     // cover checks
     let mut number_covers_satisfied = 0;
@@ -277,7 +353,16 @@ pub fn format_result(
         result_str.push_str("\nRESULTS:\n");
     }
 
+    // Assumptions and their checks are reported separately
+    let number_assumptions = properties
+        .iter()
+        .filter(|prop| prop.is_assumption_property() || prop.is_assumption_check_property())
+        .count();
+
     for prop in properties {
+        if prop.is_assumption_property() || prop.is_assumption_check_property() {
+            continue;
+        }
         let name = prop.property_name();
         let status = &prop.status;
         let description = &prop.description;
@@ -286,7 +371,7 @@ pub fn format_result(
         match status {
             CheckStatus::Failure => {
                 number_checks_failed += 1;
-                failed_tests.push(prop);
+                failed_tests.push((index, prop));
             }
             CheckStatus::Undetermined => {
                 if prop.is_cover_property() {
@@ -343,7 +428,7 @@ pub fn format_result(
         + number_covers_unsatisfiable
         + number_covers_undetermined;
 
-    let number_properties = properties.len() - number_cover_properties;
+    let number_properties = properties.len() - number_cover_properties - number_assumptions;
 
     let summary = format!("\n ** {number_checks_failed} of {number_properties} failed");
     result_str.push_str(&summary);
@@ -388,8 +473,21 @@ pub fn format_result(
         result_str.push('\n');
     }
 
-    for prop in failed_tests {
-        let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
+    // Failures that share their description with other failures, e.g. an assertion in a loop,
+    // are numbered so that they can be told apart.
+    let mut description_counts: HashMap<&str, usize> = HashMap::new();
+    for (_, prop) in &failed_tests {
+        *description_counts.entry(&prop.description).or_default() += 1;
+    }
+    let mut description_seen: HashMap<&str, usize> = HashMap::new();
+    for (index, prop) in failed_tests {
+        let count = description_counts[prop.description.as_str()];
+        let occurrence = (count > 1).then(|| {
+            let seen = description_seen.entry(&prop.description).or_default();
+            *seen += 1;
+            (*seen, count)
+        });
+        let failure_message = build_failure_message(prop, index, occurrence);
         result_str.push_str(&failure_message);
     }
 
@@ -423,6 +521,31 @@ pub fn format_result(
         more details.\n",
         );
     }
+    if has_no_checks(properties) {
+        result_str.push_str(
+            "** WARNING: No checks were found in this harness, so nothing was verified. \
+            This usually means that the code of the harness was found to be unreachable, e.g. \
+            after a `kani::assume(false)`. Use `--fail-on-empty-harness` to make this a failure.\n",
+        );
+    }
+    if let Some(prop) = properties
+        .iter()
+        .find(|prop| prop.is_self_blocking_property() && prop.status == CheckStatus::Failure)
+    {
+        result_str.push_str(&format!(
+            "** WARNING: A call blocks forever: {}. Kani runs one thread at a time, so the \
+        current thread would wait for itself. Use `--concrete-playback` or `--visualize` to find \
+        the call that blocks.\n",
+            prop.description
+        ));
+    }
+    if let Some(prop) = first_contradictory_assumption(properties) {
+        result_str.push_str(&format!(
+            "** WARNING: The assumption \"{}\" at {} rules out every execution that reaches it. \
+        Checks that can only be reached through it hold vacuously.\n",
+            prop.description, prop.source_location
+        ));
+    }
     if has_unwinding_assertion_failures(properties) {
         result_str.push_str("[Kani] info: Verification output shows one or more unwinding failures.\n\
         [Kani] tip: Consider increasing the unwinding value or disabling `--unwinding-assertions`.\n");
@@ -431,6 +554,155 @@ pub fn format_result(
     result_str
 }
 
+/// Build the assumption report for a harness from its `assumption` properties
+/// (i.e., assumptions created with the `kani::assume!` macro).
+///
+/// Returns `None` if the harness has no such assumptions.
+pub fn format_assumptions(harness_name: &str, properties: &[Property]) -> Option<String> {
+    let assumptions: Vec<&Property> =
+        properties.iter().filter(|prop| prop.is_assumption_property()).collect();
+    if assumptions.is_empty() {
+        return None;
+    }
+
+    let mut result_str = format!(" - Harness {harness_name}:\n");
+    for prop in assumptions {
+        let description = &prop.description;
+        let status = &prop.status;
+        let location = &prop.source_location;
+        if location.is_missing() {
+            result_str.push_str(&format!("\t - \"{description}\" ({status})\n"));
+        } else {
+            result_str.push_str(&format!("\t - \"{description}\" at {location} ({status})\n"));
+        }
+    }
+    Some(result_str)
+}
+
+/// Build the traces of the failed checks (requested with `--trace-filter` or
+/// `--trace-max-steps`).
+///
+/// Only the steps that match all `filters` are included, and at most `max_steps`
+/// of them per check. Steps keep their original number so they can be matched
+/// against the full trace.
+pub fn format_traces(
+    properties: &[Property],
+    filters: &[TraceFilter],
+    max_steps: Option<usize>,
+) -> String {
+    let mut result_str = String::from("TRACES:\n");
+    let failed = properties.iter().filter(|prop| prop.status == CheckStatus::Failure);
+    for prop in failed {
+        result_str.push_str(&format!("Failed Check: {}\n", prop.description));
+        result_str.push_str(&format_trace(prop, filters, max_steps));
+    }
+    result_str
+}
+
+/// Format the steps of the trace of a single property, one per line.
+pub fn format_trace(prop: &Property, filters: &[TraceFilter], max_steps: Option<usize>) -> String {
+    let Some(trace) = &prop.trace else {
+        return " No trace available\n".to_string();
+    };
+    let mut result_str = String::new();
+    let mut steps = trace
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| filters.iter().all(|filter| filter_trace_item(item, filter)));
+    for (index, item) in steps.by_ref().take(max_steps.unwrap_or(usize::MAX)) {
+        result_str.push_str(&format!(" Step {index}: {}\n", format_trace_item(item)));
+    }
+    let remaining = steps.count();
+    if remaining > 0 {
+        result_str.push_str(&format!(" ... {remaining} more steps\n"));
+    }
+    result_str
+}
+
+/// Whether a trace step should be kept according to the given filter.
+fn filter_trace_item(item: &TraceItem, filter: &TraceFilter) -> bool {
+    match filter {
+        TraceFilter::UserCrate => {
+            item.source_location.as_ref().is_some_and(|location| location.is_in_current_dir())
+        }
+        TraceFilter::Visible => !item.hidden,
+        TraceFilter::Var(pattern) => {
+            item.step_type == "assignment"
+                && item.lhs.as_ref().is_some_and(|lhs| pattern.is_match(lhs))
+        }
+    }
+}
+
+/// Format a single trace step as `<location>: <lhs> = <value>` for assignments,
+/// or `<location>: <step type>` otherwise.
+fn format_trace_item(item: &TraceItem) -> String {
+    let location = match &item.source_location {
+        Some(location) if !location.is_missing() => location.to_string(),
+        _ => "Unknown location".to_string(),
+    };
+    match (&item.lhs, &item.value) {
+        (Some(lhs), Some(value)) if item.step_type == "assignment" => match &value.data {
+            Some(data) => format!("{location}: {lhs} = {data}"),
+            None => format!("{location}: {lhs} = <{}>", value.name),
+        },
+        _ => format!("{location}: {}", item.step_type),
+    }
+}
+
+/// Build the values returned by the `kani::any()` calls in the trace of each failed check
+/// (requested with `--show-counterexample`), one per line, in the order of the calls.
+pub fn format_counterexamples(properties: &[Property]) -> String {
+    let mut result_str = String::from("COUNTEREXAMPLES:\n");
+    let failed = properties.iter().filter(|prop| prop.status == CheckStatus::Failure);
+    for prop in failed {
+        result_str.push_str(&format!("Failed Check: {}\n", prop.description));
+        let Some(trace) = &prop.trace else {
+            result_str.push_str(" No trace available\n");
+            continue;
+        };
+        let values = counterexample_values(trace);
+        if values.is_empty() {
+            result_str.push_str(" No nondeterministic values\n");
+        }
+        for (location, value) in values {
+            let location = match location {
+                Some(location) => location.to_string(),
+                None => "Unknown location".to_string(),
+            };
+            result_str.push_str(&format!(" {location}: kani::any() = {value}\n"));
+        }
+    }
+    result_str
+}
+
+/// The values assigned to the return value of `kani::any_raw_internal`, which generates the
+/// bytes of every `kani::any()` call. Each value is paired with the location of the last step of
+/// the user code before the assignment, i.e. the location of the call.
+fn counterexample_values(trace: &[TraceItem]) -> Vec<(Option<&SourceLocation>, String)> {
+    let mut call_location = None;
+    let mut values = vec![];
+    for item in trace {
+        let Some(location) = &item.source_location else { continue };
+        if location.is_in_current_dir() && !location.is_missing() {
+            call_location = Some(location);
+        }
+        let is_any_value = item.step_type == "assignment"
+            && item.lhs.as_ref().is_some_and(|lhs| lhs.starts_with("goto_symex$$return_value"))
+            && location
+                .function
+                .as_ref()
+                .is_some_and(|function| function.starts_with("kani::any_raw_internal"));
+        if let (true, Some(value)) = (is_any_value, &item.value) {
+            let value = match &value.data {
+                Some(data) => data.to_string(),
+                None => format!("<{}>", value.name),
+            };
+            values.push((call_location, value));
+        }
+    }
+    values
+}
+
 /// Separate checks into coverage and non-coverage based on property class and format them separately for --coverage. We report both verification and processed coverage
 /// results
 pub fn format_coverage(
@@ -440,90 +712,92 @@ pub fn format_coverage(
     failed_properties: FailedProperties,
     show_checks: bool,
 ) -> String {
-    let (coverage_checks, non_coverage_checks): (Vec<Property>, Vec<Property>) =
-        properties.iter().cloned().partition(|x| x.property_class() == "code_coverage");
+    let non_coverage_checks: Vec<Property> =
+        properties.iter().filter(|x| x.property_class() != "code_coverage").cloned().collect();
 
     let verification_output =
         format_result(&non_coverage_checks, status, should_panic, failed_properties, show_checks);
-    let coverage_output = format_result_coverage(&coverage_checks);
+    let checks: Vec<CheckResult> = properties.iter().map(results::check_result).collect();
+    let coverage_output = format_line_coverage(&checks);
     let result = format!("{}\n{}", verification_output, coverage_output);
 
     result
 }
 
-/// Generate coverage result from all coverage properties (i.e., checks with `code_coverage` property class).
-/// Loops through each of the checks with the `code_coverage` property class on a line and gives:
+/// Generate the coverage results of a harness, i.e. the coverage of each line (see
+/// [harness_line_coverage]):
 ///  - A status `FULL` if all checks pertaining to a line number are `COVERED`
 ///  - A status `NONE` if all checks related to a line are `UNCOVERED`
 ///  - Otherwise (i.e., if the line contains both) it reports `PARTIAL`.
 ///
+/// If the harness has no coverage checks, e.g. because it was compiled without coverage
+/// instrumentation, the lines of its other checks and of their traces are reported instead.
+///
 /// Used when the user requests coverage information with `--coverage`.
 /// Output is tested through the `coverage-based` testing suite, not the regular
 /// `expected` suite.
-fn format_result_coverage(properties: &[Property]) -> String {
-    let mut formatted_output = String::new();
-    formatted_output.push_str("\nCoverage Results:\n");
-
-    let mut coverage_results: BTreeMap<String, BTreeMap<usize, CoverageStatus>> =
-        BTreeMap::default();
-    for prop in properties {
-        let src = prop.source_location.clone();
-        let file_entries = coverage_results.entry(src.file.unwrap()).or_default();
-        let check_status = if prop.status == CheckStatus::Covered {
-            CoverageStatus::Full
-        } else {
-            CoverageStatus::None
-        };
-
-        // Create Map<file, Map<line, status>>
-        file_entries
-            .entry(src.line.unwrap().parse().unwrap())
-            .and_modify(|line_status| {
-                if *line_status != check_status {
-                    *line_status = CoverageStatus::Partial
-                }
-            })
-            .or_insert(check_status);
+fn format_line_coverage(checks: &[CheckResult]) -> String {
+    let coverage = harness_line_coverage(checks);
+    let mut formatted_output = String::from("\nCoverage Results:\n");
+    if !checks.iter().any(is_coverage_check) {
+        if coverage.is_empty() {
+            formatted_output.push_str("No coverage information available\n");
+            return formatted_output;
+        }
+        formatted_output.push_str(
+            "No coverage checks were found, showing the lines of the checks and traces instead\n",
+        );
     }
+    formatted_output.push_str(&render_line_coverage(&coverage));
+    formatted_output
+}
 
-    // Create formatted string that is returned to the user as output
-    for (file, checks) in coverage_results.iter() {
-        for (line_number, coverage_status) in checks {
-            formatted_output.push_str(&format!("{}, {}, {}\n", file, line_number, coverage_status));
+/// Render the coverage of each line as `<file>, <line>, <status>`, with a blank line after each
+/// file.
+fn render_line_coverage(coverage: &BTreeMap<&str, BTreeMap<usize, CoverageStatus>>) -> String {
+    let mut formatted_output = String::new();
+    for (file, lines) in coverage {
+        for (line_number, coverage_status) in lines {
+            formatted_output.push_str(&format!("{file}, {line_number}, {coverage_status}\n"));
         }
         formatted_output.push('\n');
     }
-
     formatted_output
 }
 
 /// Attempts to build a message for a failed property with as much detailed
 /// information on the source location as possible.
-fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) -> String {
-    let backup_failure_message = format!("Failed Checks: {description}\n");
-    if trace.is_none() {
-        return backup_failure_message;
+///
+/// If other failed checks have the same description, `occurrence` holds the position of this
+/// check among them and their number. The message then includes the index and name of the check
+/// so that each failure can be identified.
+fn build_failure_message(
+    prop: &Property,
+    index: usize,
+    occurrence: Option<(usize, usize)>,
+) -> String {
+    let mut message = format!("Failed Checks: {}\n", prop.description);
+    if let Some(location) = trace_failure_location(&prop.trace) {
+        message.push_str(&location);
+    } else if occurrence.is_some() && !prop.source_location.is_missing() {
+        message.push_str(&format!(" Location: {}\n", prop.source_location));
     }
-    let failure_trace = trace.clone().unwrap();
-
-    let failure_source_wrap = failure_trace[failure_trace.len() - 1].source_location.clone();
-    if failure_source_wrap.is_none() {
-        return backup_failure_message;
+    if let Some((position, count)) = occurrence {
+        message.push_str(&format!(
+            " Check {index}: {} (failure {position} of {count} with this description)\n",
+            prop.property_name()
+        ));
     }
-    let failure_source = failure_source_wrap.unwrap();
+    message
+}
 
-    if failure_source.file.is_some()
-        && failure_source.function.is_some()
-        && failure_source.line.is_some()
-    {
-        let failure_file = failure_source.file.unwrap();
-        let failure_function = failure_source.function.unwrap();
-        let failure_line = failure_source.line.unwrap();
-        return format!(
-            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n"
-        );
-    }
-    backup_failure_message
+/// The location of the last step of the trace of a failed check, if it's complete.
+fn trace_failure_location(trace: &Option<Vec<TraceItem>>) -> Option<String> {
+    let failure_source = trace.as_ref()?.last()?.source_location.clone()?;
+    let failure_file = failure_source.file?;
+    let failure_function = failure_source.function?;
+    let failure_line = failure_source.line?;
+    Some(format!(" File: \"{failure_file}\", line {failure_line}, in {failure_function}\n"))
 }
 
 /// Edits an error message.
@@ -532,13 +806,14 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
 /// to `--object-bits` being too low. The message is edited to show Kani
 /// options.
 fn postprocess_error_message(message: ParserItem) -> ParserItem {
-    if let ParserItem::Message { ref message_text, message_type: _ } = message
+    if let ParserItem::Message { ref message_text, .. } = message
         && message_text.contains("use the `--object-bits n` option")
     {
         ParserItem::Message {
             message_text: message_text
                 .replace("--object-bits ", "--enable-unstable --cbmc-args --object-bits "),
             message_type: String::from("ERROR"),
+            phase: None,
         }
     } else {
         message
@@ -561,7 +836,11 @@ fn postprocess_error_message(message: ParserItem) -> ParserItem {
 ///
 ///     Additionally, print a message at the end of the output that indicates if any
 ///     of the special cases above was hit.
-pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) -> Vec<Property> {
+pub fn postprocess_result(
+    properties: Vec<Property>,
+    extra_ptr_checks: bool,
+    span_ends: &BTreeMap<String, SpanEnd>,
+) -> Vec<Property> {
     // First, determine if there are reachable unsupported constructs or unwinding assertions
     let has_reachable_unsupported_constructs =
         has_check_failure(&properties, UNSUPPORTED_CONSTRUCT_DESC);
@@ -577,8 +856,10 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
     // Annotate properties with the results of reachability checks
     let properties_annotated =
         annotate_properties_with_reach_results(properties_without_sanity_checks, reach_checks);
+    // Complete the source spans of the properties that have an ID
+    let properties_with_spans = annotate_properties_with_span_ends(properties_annotated, span_ends);
     // Remove reachability check IDs from regular property descriptions
-    let properties_without_ids = remove_check_ids_from_description(properties_annotated);
+    let properties_without_ids = remove_check_ids_from_description(properties_with_spans);
 
     // Filter out extra pointer checks if needed
     let properties_filtered = if !extra_ptr_checks {
@@ -593,7 +874,20 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
     let updated_properties =
         update_properties_with_reach_status(properties_filtered, has_fundamental_failures);
     let results_after_code_coverage = update_results_of_code_covererage_checks(updated_properties);
-    update_results_of_cover_checks(results_after_code_coverage)
+    let results_after_cover = update_results_of_cover_checks(results_after_code_coverage);
+    let results_after_assumptions = update_results_of_assumption_checks(results_after_cover);
+    update_results_of_expected_failures(results_after_assumptions)
+}
+
+/// Returns the first assumption that is reachable but that no execution gets past,
+/// as detected by the checks added with `-Z assumption-checks`.
+///
+/// Assumptions that come after a contradictory one are unreachable, so the first
+/// one reported is the one that made the state space empty.
+fn first_contradictory_assumption(properties: &[Property]) -> Option<&Property> {
+    properties.iter().find(|prop| {
+        prop.is_assumption_check_property() && prop.status == CheckStatus::Unsatisfiable
+    })
 }
 
 /// Determines if there is property with status `FAILURE` and the given description
@@ -738,6 +1032,96 @@ fn update_results_of_cover_checks(mut properties: Vec<Property>) -> Vec<Property
     }
     properties
 }
+/// Update the results of the expressions wrapped by `kani::expect_fail!`.
+/// Each expression is marked by an `expected_failure` property that spans the
+/// whole macro call, and whose description names the expected class, if any.
+/// The failed checks inside that span (and of that class) are expected:
+/// - FAILURE -> SUCCESS, for each expected failure
+/// - The marker is SUCCESS if at least one check failed as expected, and
+///   FAILURE otherwise.
+fn update_results_of_expected_failures(mut properties: Vec<Property>) -> Vec<Property> {
+    static CLASS_PAT: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^expected failure of class `([^`]*)`$").unwrap());
+    let markers: Vec<usize> = properties
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, prop)| prop.is_expected_failure_property().then_some(idx))
+        .collect();
+    for marker_idx in markers {
+        let marker = &properties[marker_idx];
+        let class = CLASS_PAT
+            .captures(&marker.description)
+            .map(|captures| captures.get(1).unwrap().as_str().to_string());
+        let Some(start) = location_position(&marker.source_location) else { continue };
+        let end = match (marker.source_location.end_line, marker.source_location.end_column) {
+            (Some(line), Some(column)) => (line, column),
+            // Without the end of the span, only the checks on the same line are considered.
+            _ => (start.0, usize::MAX),
+        };
+        let file = marker.source_location.file.clone();
+        let mut occurred = false;
+        for prop in properties.iter_mut() {
+            let expected = prop.status == CheckStatus::Failure
+                && !prop.is_expected_failure_property()
+                && prop.source_location.file == file
+                && class.as_ref().map_or(true, |class| prop.property_class() == *class)
+                && location_position(&prop.source_location)
+                    .is_some_and(|position| start <= position && position <= end);
+            if expected {
+                prop.status = CheckStatus::Success;
+                prop.description = format!("{} (failed as expected)", prop.description);
+                occurred = true;
+            }
+        }
+        let marker = &mut properties[marker_idx];
+        if occurred {
+            marker.status = CheckStatus::Success;
+        } else {
+            marker.status = CheckStatus::Failure;
+            marker.description = format!("{} did not occur", marker.description);
+        }
+    }
+    properties
+}
+
+/// The line and column where a source location starts, if known. A missing
+/// column is treated as the start of the line.
+fn location_position(location: &SourceLocation) -> Option<(usize, usize)> {
+    let line = location.line.as_ref()?.parse().ok()?;
+    let column = location.column.as_ref().and_then(|column| column.parse().ok()).unwrap_or(0);
+    Some((line, column))
+}
+
+/// Update the results of `assumption` and `assumption_check` properties.
+/// We encode each `kani::assume!` as an `assert(false)` placed right before the
+/// assumption, so if the assertion fails, then the assumption is reachable.
+/// - SUCCESS -> UNREACHABLE
+/// - FAILURE -> REACHABLE
+/// An `assumption_check` is an `assert(false)` placed right after the assumption,
+/// so, like a cover property:
+/// - SUCCESS -> UNSATISFIABLE
+/// - FAILURE -> SATISFIED
+/// Note that the status stays `CheckStatus::Unreachable` or `CheckStatus::Undetermined`
+/// if it was set by `update_properties_with_reach_status` beforehand.
+fn update_results_of_assumption_checks(mut properties: Vec<Property>) -> Vec<Property> {
+    for prop in properties.iter_mut() {
+        if prop.is_assumption_property() {
+            if prop.status == CheckStatus::Success {
+                prop.status = CheckStatus::Unreachable;
+            } else if prop.status == CheckStatus::Failure {
+                prop.status = CheckStatus::Reachable;
+            }
+        } else if prop.is_assumption_check_property() {
+            if prop.status == CheckStatus::Success {
+                prop.status = CheckStatus::Unsatisfiable;
+            } else if prop.status == CheckStatus::Failure {
+                prop.status = CheckStatus::Satisfied;
+            }
+        }
+    }
+    properties
+}
+
 /// Some Kani-generated asserts have a unique ID in their description of the form:
 /// ```text
 /// [KANI_CHECK_ID_<crate-fn-name>_<index>]
@@ -757,6 +1141,25 @@ fn remove_check_ids_from_description(mut properties: Vec<Property>) -> Vec<Prope
     properties
 }
 
+/// CBMC only reports where a property starts, so properties that start at the
+/// same position, e.g., the overflow checks of `a + b + c`, cannot be told apart.
+/// This function completes the source location of each property that has an ID
+/// with the end of its source span, as recorded by the compiler.
+fn annotate_properties_with_span_ends(
+    mut properties: Vec<Property>,
+    span_ends: &BTreeMap<String, SpanEnd>,
+) -> Vec<Property> {
+    let check_id_pat = Regex::new(r"\[(KANI_CHECK_ID_[^\]]*)\]").unwrap();
+    for prop in properties.iter_mut() {
+        let Some(captures) = check_id_pat.captures(&prop.description) else { continue };
+        if let Some(end) = span_ends.get(captures.get(1).unwrap().as_str()) {
+            prop.source_location.end_line = Some(end.line);
+            prop.source_location.end_column = Some(end.column);
+        }
+    }
+    properties
+}
+
 /// Partitions `properties` into reachability checks (identified by the
 /// "reachability_check" property class) and non-reachability checks
 fn filter_reach_checks(properties: Vec<Property>) -> (Vec<Property>, Vec<Property>) {
@@ -846,3 +1249,238 @@ fn annotate_properties_with_reach_results(
     }
     properties
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation, TraceData, TraceValue};
+
+    fn property(class: &str, id: u32, status: CheckStatus) -> Property {
+        Property {
+            description: format!("{class} {id}"),
+            property_id: PropertyId {
+                fn_name: Some("check".to_string()),
+                class: class.to_string(),
+                id,
+            },
+            source_location: SourceLocation {
+                column: None,
+                end_column: None,
+                end_line: None,
+                file: Some("src/lib.rs".to_string()),
+                function: Some("check".to_string()),
+                line: Some(id.to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_expected_failures() {
+        let marker = |line: u32, description: &str| {
+            let mut marker = property("expected_failure", line, CheckStatus::Success);
+            marker.description = description.to_string();
+            marker.source_location.column = Some("5".to_string());
+            marker.source_location.end_line = Some(line as usize);
+            marker.source_location.end_column = Some(40);
+            marker
+        };
+        let at_column = |mut prop: Property, column: &str| {
+            prop.source_location.column = Some(column.to_string());
+            prop
+        };
+        let properties = update_results_of_expected_failures(vec![
+            marker(3, "expected failure of class `arithmetic_overflow`"),
+            at_column(property("arithmetic_overflow", 3, CheckStatus::Failure), "10"),
+            at_column(property("assertion", 3, CheckStatus::Failure), "12"),
+            marker(5, "expected failure"),
+            at_column(property("assertion", 5, CheckStatus::Success), "10"),
+            at_column(property("arithmetic_overflow", 6, CheckStatus::Failure), "10"),
+        ]);
+        let statuses: Vec<_> = properties.iter().map(|prop| prop.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                CheckStatus::Success,
+                CheckStatus::Success,
+                CheckStatus::Failure,
+                CheckStatus::Failure,
+                CheckStatus::Success,
+                CheckStatus::Failure,
+            ]
+        );
+        assert_eq!(properties[1].description, "arithmetic_overflow 3 (failed as expected)");
+        assert_eq!(properties[3].description, "expected failure did not occur");
+    }
+
+    #[test]
+    fn check_empty_harness() {
+        let assumptions = vec![property("assumption", 1, CheckStatus::Success)];
+        assert!(has_no_checks(&[]));
+        assert!(has_no_checks(&assumptions));
+        assert!(!has_no_checks(&[property("assertion", 1, CheckStatus::Success)]));
+        assert!(!has_no_checks(&[property("cover", 1, CheckStatus::Satisfied)]));
+
+        let output = format_result(
+            &assumptions,
+            VerificationStatus::Success,
+            false,
+            FailedProperties::None,
+            false,
+        );
+        assert!(output.contains("** 0 of 0 failed"));
+        assert!(output.contains("No checks were found in this harness"));
+        let output = format_result(
+            &vec![property("assertion", 1, CheckStatus::Success)],
+            VerificationStatus::Success,
+            false,
+            FailedProperties::None,
+            false,
+        );
+        assert!(!output.contains("No checks were found"));
+    }
+
+    #[test]
+    fn check_duplicate_failure_descriptions() {
+        let mut first = property("assertion", 1, CheckStatus::Failure);
+        let mut second = property("assertion", 2, CheckStatus::Failure);
+        first.description = "assertion failed: x > 0".to_string();
+        second.description = first.description.clone();
+        let unique = property("assertion", 3, CheckStatus::Failure);
+        let properties =
+            vec![first, property("assertion", 4, CheckStatus::Success), second, unique];
+        let output = format_result(
+            &properties,
+            VerificationStatus::Failure,
+            false,
+            FailedProperties::PanicsOnly,
+            false,
+        );
+        assert!(output.contains(
+            "Failed Checks: assertion failed: x > 0\n \
+            Location: src/lib.rs:1 in function check\n \
+            Check 1: check.assertion.1 (failure 1 of 2 with this description)\n"
+        ));
+        assert!(
+            output.contains("Check 3: check.assertion.2 (failure 2 of 2 with this description)\n")
+        );
+        // Unique descriptions are reported as before.
+        assert!(output.contains("Failed Checks: assertion 3\n\n"));
+    }
+
+    #[test]
+    fn check_counterexamples() {
+        let step =
+            |file: &str, line: &str, function: &str, assignment: Option<(&str, &str)>| TraceItem {
+                thread: 0,
+                step_type: if assignment.is_some() { "assignment" } else { "function-call" }
+                    .to_string(),
+                hidden: false,
+                lhs: assignment.map(|(lhs, _)| lhs.to_string()),
+                source_location: Some(SourceLocation {
+                    column: Some("17".to_string()),
+                    end_column: None,
+                    end_line: None,
+                    file: Some(file.to_string()),
+                    function: Some(function.to_string()),
+                    line: Some(line.to_string()),
+                }),
+                value: assignment.map(|(_, data)| TraceValue {
+                    name: "integer".to_string(),
+                    binary: None,
+                    data: Some(TraceData::NonBool(data.to_string())),
+                    width: Some(8),
+                }),
+            };
+        let any = "kani::any_raw_internal::<u8, 1_usize>";
+        let lib = "/kani/library/kani/src/arbitrary.rs";
+        let mut failed = property("assertion", 1, CheckStatus::Failure);
+        failed.trace = Some(vec![
+            step("src/lib.rs", "3", "check", None),
+            step(lib, "10", any, Some(("goto_symex$$return_value::any_raw_internal", "200"))),
+            step("src/lib.rs", "3", "check", Some(("x", "200"))),
+            step("src/lib.rs", "4", "check", None),
+            step(lib, "10", any, Some(("tmp", "7"))),
+            step(lib, "10", any, Some(("goto_symex$$return_value::any_raw_internal", "1"))),
+        ]);
+        let mut no_trace = property("assertion", 2, CheckStatus::Failure);
+        no_trace.description = "no trace".to_string();
+        let output = format_counterexamples(&[
+            property("assertion", 3, CheckStatus::Success),
+            failed,
+            no_trace,
+        ]);
+        assert_eq!(
+            output,
+            "COUNTEREXAMPLES:\n\
+            Failed Check: assertion 1\n \
+            src/lib.rs:3:17 in function check: kani::any() = 200\n \
+            src/lib.rs:4:17 in function check: kani::any() = 1\n\
+            Failed Check: no trace\n \
+            No trace available\n"
+        );
+    }
+
+    #[test]
+    fn check_fallback_coverage() {
+        let format_fallback_coverage = |properties: &[Property]| {
+            let checks: Vec<_> = properties.iter().map(results::check_result).collect();
+            format_line_coverage(&checks)
+        };
+        let step = |file: &str, line: &str| TraceItem {
+            thread: 0,
+            step_type: "location-only".to_string(),
+            hidden: false,
+            lhs: None,
+            source_location: Some(SourceLocation {
+                column: None,
+                end_column: None,
+                end_line: None,
+                file: Some(file.to_string()),
+                function: Some("check".to_string()),
+                line: Some(line.to_string()),
+            }),
+            value: None,
+        };
+        let mut failed = property("assertion", 1, CheckStatus::Failure);
+        failed.trace = Some(vec![
+            step("src/lib.rs", "4"),
+            step("/kani/library/std/src/lib.rs", "10"),
+            step("src/lib.rs", "2"),
+        ]);
+        let mut cover = property("cover", 2, CheckStatus::Satisfied);
+        cover.trace = Some(vec![step("src/lib.rs", "2"), step("src/main.rs", "1")]);
+        let passed = property("assertion", 3, CheckStatus::Success);
+        let unreachable = property("assertion", 4, CheckStatus::Unreachable);
+        let undetermined = property("assertion", 5, CheckStatus::Undetermined);
+        let output = format_fallback_coverage(&[failed, cover, passed, unreachable, undetermined]);
+        assert_eq!(
+            output,
+            "\nCoverage Results:\n\
+            No coverage checks were found, showing the lines of the checks and traces instead\n\
+            src/lib.rs, 1, FULL\n\
+            src/lib.rs, 2, FULL\n\
+            src/lib.rs, 3, FULL\n\
+            src/lib.rs, 4, PARTIAL\n\
+            \n\
+            src/main.rs, 1, FULL\n\
+            \n"
+        );
+
+        // A harness whose checks all pass has no traces, but its checks are reached.
+        let passed = property("assertion", 3, CheckStatus::Success);
+        assert_eq!(
+            format_fallback_coverage(&[passed]),
+            "\nCoverage Results:\n\
+            No coverage checks were found, showing the lines of the checks and traces instead\n\
+            src/lib.rs, 3, FULL\n\
+            \n"
+        );
+        assert_eq!(
+            format_fallback_coverage(&[property("assertion", 5, CheckStatus::Undetermined)]),
+            "\nCoverage Results:\nNo coverage information available\n"
+        );
+    }
+}
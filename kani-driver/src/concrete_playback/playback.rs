@@ -16,6 +16,10 @@
 use std::process::Command;
 use tracing::debug;
 
+/// The environment variable that tells the playback library to shrink the concrete values of
+/// failing tests.
+const SHRINK_ENV_VAR: &str = "KANI_PLAYBACK_SHRINK";
+
 pub fn playback_cargo(args: CargoPlaybackArgs) -> Result<()> {
     let install = InstallType::new()?;
     cargo_test(&install, args)
@@ -59,6 +63,9 @@ fn run_test(exe: &Path, args: &KaniPlaybackArgs) -> Result<()> {
     }
 
     cmd.args(&args.playback.test_args);
+    if args.playback.shrink {
+        cmd.env(SHRINK_ENV_VAR, "1");
+    }
 
     session::run_terminal(&args.playback.common_opts, cmd)?;
     Ok(())
@@ -131,6 +138,9 @@ fn cargo_test(install: &InstallType, args: CargoPlaybackArgs) -> Result<()> {
         // https://doc.rust-lang.org/cargo/reference/environment-variables.html
         .env("CARGO_ENCODED_RUSTFLAGS", rustc_args.join(&OsString::from("\x1f")))
         .env("CARGO_TERM_PROGRESS_WHEN", "never");
+    if args.playback.shrink {
+        cmd.env(SHRINK_ENV_VAR, "1");
+    }
 
     session::run_terminal(&args.playback.common_opts, cmd)?;
     Ok(())
@@ -7,6 +7,7 @@
 use crate::args::ConcretePlaybackMode;
 use crate::call_cbmc::VerificationResult;
 use crate::session::KaniSession;
+use crate::util::message;
 use anyhow::{Context, Result};
 use concrete_vals_extractor::{extract_harness_values, ConcreteVal};
 use kani_metadata::HarnessMetadata;
@@ -35,7 +36,7 @@ pub fn gen_and_add_concrete_playback(
             let harness_values: Vec<Vec<ConcreteVal>> = extract_harness_values(result_items);
 
             if harness_values.is_empty() {
-                println!(
+                message!(
                     "WARNING: Kani could not produce a concrete playback for `{}` because there \
                     were no failing panic checks or satisfiable cover statements.",
                     harness.pretty_name
@@ -52,7 +53,7 @@ pub fn gen_and_add_concrete_playback(
                 match playback_mode {
                     ConcretePlaybackMode::Print => {
                         for generated_unit_test in unit_tests.iter() {
-                            println!(
+                            message!(
                                 "Concrete playback unit test for `{}`:\n```\n{}\n```",
                                 &harness.pretty_name,
                                 &generated_unit_test.code.join("\n")
@@ -60,7 +61,7 @@ pub fn gen_and_add_concrete_playback(
                         }
 
                         if !unit_tests.is_empty() {
-                            println!(
+                            message!(
                                 "INFO: To automatically add the concrete playback unit test(s) to the \
                                  src code, run Kani with `--concrete-playback=inplace`.",
                             );
@@ -68,7 +69,7 @@ pub fn gen_and_add_concrete_playback(
                     }
                     ConcretePlaybackMode::InPlace => {
                         if !self.args.common_args.quiet && !unit_tests.is_empty() {
-                            println!(
+                            message!(
                                 "INFO: Now modifying the source code to include the concrete playback unit test:{}.",
                                 unit_tests
                                     .iter()
@@ -121,7 +122,7 @@ fn modify_src_code(
                 line_range: Some((unit_test_start_line, unit_test_end_line)),
             }];
             self.run_rustfmt(&file_line_ranges, Some(&path))
-                .unwrap_or_else(|err| println!("WARNING: {}", err));
+                .unwrap_or_else(|err| message!("WARNING: {}", err));
         }
 
         Ok(())
@@ -144,7 +145,7 @@ fn add_tests_inplace(
         unit_tests.retain(|unit_test| {
             if source_string.contains(&unit_test.name) {
                 if !self.args.common_args.quiet {
-                    println!(
+                    message!(
                         "Concrete playback unit test `{}/{}` already found in source code, so skipping modification.",
                         source_path, unit_test.name,
                     );
@@ -583,6 +584,8 @@ fn check_concrete_vals_extractor() {
             reach: None,
             source_location: SourceLocation {
                 column: None,
+                end_column: None,
+                end_line: None,
                 file: None,
                 function: None,
                 line: None,
@@ -594,6 +597,8 @@ fn check_concrete_vals_extractor() {
                 lhs: Some("goto_symex$$return_value".to_string()),
                 source_location: Some(SourceLocation {
                     column: None,
+                    end_column: None,
+                    end_line: None,
                     file: None,
                     function: Some("kani::any_raw_internal::<u8>".to_string()),
                     line: None,
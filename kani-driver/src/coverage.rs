@@ -0,0 +1,114 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Computes the line coverage of the harnesses from their checks.
+//!
+//! The coverage results printed with `--coverage` and the reports that summarize them across
+//! harnesses (`--lcov`, `--badge-dir` and `--evidence-dir`) are all computed here, so they agree
+//! on which lines are covered.
+
+use crate::cbmc_output_parser::is_in_current_dir;
+use kani_results::{CheckResult, CheckStatus, HarnessResults, SourceLocation};
+use std::collections::BTreeMap;
+use strum_macros::{AsRefStr, Display};
+
+#[derive(Debug, PartialEq, Eq, AsRefStr, Clone, Copy, Display)]
+#[strum(serialize_all = "UPPERCASE")]
+// The status of coverage reported by Kani
+pub(crate) enum CoverageStatus {
+    Full,
+    Partial,
+    None,
+}
+
+/// Aggregate the coverage of the lines that have some coverage point, given as the file and line
+/// of each point and whether it's covered. A line is:
+///  - `FULL` if all its points are covered,
+///  - `NONE` if none of its points is covered,
+///  - `PARTIAL` otherwise.
+pub(crate) fn line_coverage<'a>(
+    points: impl IntoIterator<Item = (&'a str, usize, bool)>,
+) -> BTreeMap<&'a str, BTreeMap<usize, CoverageStatus>> {
+    let mut coverage_results: BTreeMap<&str, BTreeMap<usize, CoverageStatus>> = BTreeMap::default();
+    for (file, line, covered) in points {
+        let status = if covered { CoverageStatus::Full } else { CoverageStatus::None };
+        coverage_results
+            .entry(file)
+            .or_default()
+            .entry(line)
+            .and_modify(|line_status| {
+                if *line_status != status {
+                    *line_status = CoverageStatus::Partial
+                }
+            })
+            .or_insert(status);
+    }
+    coverage_results
+}
+
+/// Whether a check is a coverage check, i.e. it was added by `--coverage`.
+pub(crate) fn is_coverage_check(check: &CheckResult) -> bool {
+    check.class == "code_coverage"
+}
+
+/// The coverage of the lines of a harness, given its checks, indexed by file.
+///
+/// The coverage checks of the harness are used. If it has none, e.g. because it was compiled
+/// without coverage instrumentation, the reachability of its other checks is used instead: a
+/// check that is `UNREACHABLE` counts as uncovered, and a check with any other status except
+/// `UNDETERMINED` as covered. The lines of the traces of the checks, if CBMC produced any, are
+/// covered too. In that case, only the lines of the current directory are reported.
+pub(crate) fn harness_line_coverage(
+    checks: &[CheckResult],
+) -> BTreeMap<&str, BTreeMap<usize, CoverageStatus>> {
+    if checks.iter().any(is_coverage_check) {
+        let points = checks.iter().filter(|check| is_coverage_check(check)).filter_map(|check| {
+            let (file, line) = source_line(&check.location)?;
+            Some((file, line, check.status == CheckStatus::Covered))
+        });
+        return line_coverage(points);
+    }
+    let check_points = checks.iter().filter_map(|check| {
+        let covered = match check.status {
+            CheckStatus::Unreachable => false,
+            CheckStatus::Undetermined => return None,
+            _ => true,
+        };
+        Some((&check.location, covered))
+    });
+    let trace_points = checks
+        .iter()
+        .filter_map(|check| check.trace.as_ref())
+        .flatten()
+        .filter_map(|step| Some((step.location.as_ref()?, true)));
+    let points = check_points.chain(trace_points).filter_map(|(location, covered)| {
+        let (file, line) = source_line(location)?;
+        is_in_current_dir(file).then_some((file, line, covered))
+    });
+    line_coverage(points)
+}
+
+/// The number of harnesses that fully cover each line, indexed by file. Every line in the
+/// coverage of some harness is included, and a harness that only covers a line partially isn't
+/// counted for it.
+pub(crate) fn line_hits(results: &[HarnessResults]) -> BTreeMap<&str, BTreeMap<usize, usize>> {
+    let mut files: BTreeMap<&str, BTreeMap<usize, usize>> = BTreeMap::new();
+    for result in results {
+        // Aggregate the checks of each harness first, so a line counts once per harness.
+        for (file, lines) in harness_line_coverage(&result.checks) {
+            let file_hits = files.entry(file).or_default();
+            for (line, status) in lines {
+                let hits = file_hits.entry(line).or_default();
+                if status == CoverageStatus::Full {
+                    *hits += 1;
+                }
+            }
+        }
+    }
+    files
+}
+
+/// The file and the line of a source location, if both are known.
+fn source_line(location: &SourceLocation) -> Option<(&str, usize)> {
+    Some((location.file.as_deref()?, location.line? as usize))
+}
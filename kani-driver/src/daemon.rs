@@ -0,0 +1,247 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani daemon`, which builds the package once and then verifies its harnesses
+//! on request. Editors and scripts that watch the sources can use it to avoid paying for starting
+//! Kani, building the package and loading its metadata on every change.
+//!
+//! The daemon listens on a Unix socket. Each request is a JSON object on a line of its own, and
+//! the daemon answers each request with a JSON object on a line of its own:
+//!
+//! - `{"command": "verify", "harnesses": ["<FILTER>"], "exact": false}` verifies the harnesses
+//!   that match the filters, or every harness if there is no filter. The answer is
+//!   `{"results": <RESULTS>}`, where the results follow the format of `kani-results.json`.
+//! - `{"command": "rebuild"}` builds the package again, e.g., after its sources changed. Only the
+//!   crates that changed are compiled again. The answer is `{"rebuilt": {"harnesses": <N>}}`.
+//! - `{"command": "shutdown"}` stops the daemon once it has answered `{"shutdown": {}}`.
+//!
+//! Requests that fail are answered with `{"error": "<MESSAGE>"}`.
+
+use crate::args::daemon_args::DaemonArgs;
+use crate::harness_runner::HarnessRunner;
+use crate::project::{self, Project};
+use crate::results;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_results::VerificationResults;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// A request sent to the daemon.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+enum Request {
+    Verify {
+        #[serde(default)]
+        harnesses: Vec<String>,
+        #[serde(default)]
+        exact: bool,
+    },
+    Rebuild,
+    Shutdown,
+}
+
+/// The answer to a request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Response {
+    Results(VerificationResults),
+    Rebuilt { harnesses: usize },
+    Shutdown {},
+    Error(String),
+}
+
+/// The state kept by the daemon between requests.
+struct Daemon {
+    session: KaniSession,
+    project: Project,
+}
+
+impl Daemon {
+    fn handle(&mut self, request: Request) -> Response {
+        let response = match request {
+            Request::Verify { harnesses, exact } => {
+                self.verify(harnesses, exact).map(Response::Results)
+            }
+            Request::Rebuild => self.rebuild(),
+            Request::Shutdown => Ok(Response::Shutdown {}),
+        };
+        response.unwrap_or_else(|err| Response::Error(format!("{err:#}")))
+    }
+
+    fn verify(&mut self, harnesses: Vec<String>, exact: bool) -> Result<VerificationResults> {
+        self.session.args.harnesses = harnesses;
+        self.session.args.exact = exact;
+        let all_harnesses = self.project.get_all_harnesses();
+        let targets = self.session.determine_targets(&all_harnesses)?;
+        let runner = HarnessRunner { sess: &self.session, project: &self.project };
+        let harness_results = runner.check_all_harnesses(&targets)?;
+        results::write_results(&self.session, &self.project, &harness_results)?;
+        Ok(results::verification_results(&harness_results))
+    }
+
+    fn rebuild(&mut self) -> Result<Response> {
+        self.project = project::cargo_project(&self.session, false)?;
+        Ok(Response::Rebuilt { harnesses: self.project.get_all_harnesses().len() })
+    }
+}
+
+/// Execute the `daemon` subcommand of `cargo kani`.
+#[cfg(unix)]
+pub(crate) fn run_daemon(session: KaniSession, args: DaemonArgs) -> Result<()> {
+    use crate::util::warning;
+    use anyhow::Context;
+    use std::io::BufReader;
+    use std::os::unix::net::UnixListener;
+
+    let project = project::cargo_project(&session, false)?;
+    let socket = match args.socket {
+        Some(socket) => socket,
+        None => {
+            let metadata = project.cargo_metadata.as_ref().expect("expected a cargo project");
+            session.cargo_target_dir(metadata).join("daemon.sock")
+        }
+    };
+    // A socket left behind by a daemon that didn't shut down cleanly would prevent binding.
+    remove_socket(&socket)?;
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("Failed to listen on `{}`", socket.display()))?;
+    println!("Kani daemon listening on {}", socket.display());
+
+    let mut daemon = Daemon { session, project };
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let mut writer = &stream;
+        match serve_connection(BufReader::new(&stream), &mut writer, |req| daemon.handle(req)) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(err) => warning(&format!("Lost connection with a daemon client: {err:#}")),
+        }
+    }
+    remove_socket(&socket)
+}
+
+/// Remove the socket at `path`, if any. Fails instead of removing anything else, since the path
+/// may have been given by mistake.
+#[cfg(unix)]
+fn remove_socket(path: &std::path::Path) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => Ok(std::fs::remove_file(path)?),
+        Ok(_) => anyhow::bail!("`{}` exists and is not a socket", path.display()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Execute the `daemon` subcommand of `cargo kani`.
+#[cfg(not(unix))]
+pub(crate) fn run_daemon(_session: KaniSession, _args: DaemonArgs) -> Result<()> {
+    anyhow::bail!("`cargo kani daemon` is only supported on Unix platforms")
+}
+
+/// Answer the requests read from a client until it disconnects. Returns whether the client
+/// requested the daemon to shut down.
+fn serve_connection(
+    reader: impl BufRead,
+    writer: &mut impl Write,
+    mut handle: impl FnMut(Request) -> Response,
+) -> Result<bool> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = serde_json::from_str::<Request>(&line);
+        let shutdown = matches!(request, Ok(Request::Shutdown));
+        let response = match request {
+            Ok(request) => handle(request),
+            Err(err) => Response::Error(format!("Invalid request: {err}")),
+        };
+        serde_json::to_writer(&mut *writer, &response)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        if shutdown {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_requests() {
+        let parse = |line: &str| serde_json::from_str::<Request>(line).unwrap();
+        assert_eq!(
+            parse(r#"{"command": "verify"}"#),
+            Request::Verify { harnesses: vec![], exact: false }
+        );
+        assert_eq!(
+            parse(r#"{"command": "verify", "harnesses": ["check_a"], "exact": true}"#),
+            Request::Verify { harnesses: vec!["check_a".to_string()], exact: true }
+        );
+        assert_eq!(parse(r#"{"command": "rebuild"}"#), Request::Rebuild);
+        assert_eq!(parse(r#"{"command": "shutdown"}"#), Request::Shutdown);
+        assert!(serde_json::from_str::<Request>(r#"{"command": "compile"}"#).is_err());
+    }
+
+    #[test]
+    fn check_serve_connection() {
+        let input = concat!(
+            "{\"command\": \"verify\", \"harnesses\": [\"check_a\"]}\n",
+            "\n",
+            "not json\n",
+            "{\"command\": \"rebuild\"}\n",
+            "{\"command\": \"shutdown\"}\n",
+            "{\"command\": \"rebuild\"}\n",
+        );
+        let mut output = vec![];
+        let mut requests = vec![];
+        let shutdown = serve_connection(input.as_bytes(), &mut output, |request| {
+            let response = match &request {
+                Request::Verify { .. } => Response::Results(VerificationResults::new(vec![])),
+                Request::Rebuild => Response::Rebuilt { harnesses: 2 },
+                Request::Shutdown => Response::Shutdown {},
+            };
+            requests.push(request);
+            response
+        })
+        .unwrap();
+        assert!(shutdown);
+        // The request after the shutdown is never read.
+        assert_eq!(requests.len(), 3);
+
+        let responses: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["results"]["harnesses"], serde_json::json!([]));
+        assert!(responses[1]["error"].as_str().unwrap().starts_with("Invalid request"));
+        assert_eq!(responses[2], serde_json::json!({"rebuilt": {"harnesses": 2}}));
+        assert_eq!(responses[3], serde_json::json!({"shutdown": {}}));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_remove_socket() {
+        use std::os::unix::net::UnixListener;
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        // Nothing to remove.
+        remove_socket(&socket).unwrap();
+
+        let _listener = UnixListener::bind(&socket).unwrap();
+        remove_socket(&socket).unwrap();
+        assert!(!socket.exists());
+
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "").unwrap();
+        assert!(remove_socket(&file).is_err());
+        assert!(file.exists());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the subcommands that verify the harnesses generated by Kani's derive macros.
+//!
+//! - `cargo kani check-arbitrary` verifies the harnesses generated by `#[derive(Arbitrary)]`,
+//!   which cover the creation of a value of each type with `kani::any()`. If the cover cannot be
+//!   satisfied, the safety constraints of the type can never be met.
+//! - `cargo kani check-invariants` verifies the harnesses generated by `#[derive(Invariant)]`,
+//!   which assert that `kani::any()` only produces safe values for types that also implement
+//!   `Arbitrary`.
+
+use crate::args::OutputFormat;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project;
+use crate::session::KaniSession;
+use anyhow::{bail, Result};
+use console::style;
+
+/// The description of the assertion added to the harnesses generated by `#[derive(Invariant)]`.
+/// This must match the message in `kani::invariant::check_arbitrary_invariant`.
+const INVARIANT_CHECK_MSG: &str = "`kani::any()` only produces values that satisfy the invariant";
+
+/// The harnesses generated by one of the derive macros, and how to interpret their results.
+struct DeriveCheck {
+    /// The configuration that enables the generated harnesses.
+    cfg: &'static str,
+    /// The prefix of the name of the generated harnesses, followed by the name of the type.
+    prefix: &'static str,
+    /// The name of the derive macro.
+    derive: &'static str,
+    /// Find the status of the check among the properties of a harness. Returns `None` if the
+    /// check doesn't apply to the type.
+    status: fn(&[Property]) -> Option<CheckStatus>,
+    /// The status of a type that passes the check.
+    expected: CheckStatus,
+    /// The explanation printed for each type that fails the check.
+    failure_hint: &'static str,
+    /// What the types that pass the check satisfy, e.g. "can be created with `kani::any()`".
+    success_summary: &'static str,
+}
+
+const CHECK_ARBITRARY: DeriveCheck = DeriveCheck {
+    cfg: "kani_check_arbitrary",
+    prefix: "__kani_check_arbitrary_",
+    derive: "Arbitrary",
+    status: |properties| {
+        properties.iter().find(|prop| prop.is_cover_property()).map(|prop| prop.status)
+    },
+    expected: CheckStatus::Satisfied,
+    failure_hint: "`kani::any()` can never produce a value, \
+        check the safety constraints of the type and its fields",
+    success_summary: "can be created with `kani::any()`",
+};
+
+const CHECK_INVARIANTS: DeriveCheck = DeriveCheck {
+    cfg: "kani_check_invariants",
+    prefix: "__kani_check_invariant_",
+    derive: "Invariant",
+    status: |properties| {
+        properties
+            .iter()
+            .find(|prop| prop.description == INVARIANT_CHECK_MSG)
+            .map(|prop| prop.status)
+    },
+    expected: CheckStatus::Success,
+    failure_hint: "`kani::any()` can produce a value that violates the invariant, \
+        check that `Arbitrary` and `Invariant` use the same safety constraints",
+    success_summary: "only produce safe values with `kani::any()`",
+};
+
+/// `cargo kani check-arbitrary` main entry point.
+pub(crate) fn check_arbitrary(session: KaniSession) -> Result<()> {
+    run_derive_check(session, &CHECK_ARBITRARY)
+}
+
+/// `cargo kani check-invariants` main entry point.
+pub(crate) fn check_invariants(session: KaniSession) -> Result<()> {
+    run_derive_check(session, &CHECK_INVARIANTS)
+}
+
+fn run_derive_check(mut session: KaniSession, check: &DeriveCheck) -> Result<()> {
+    session.extra_cfgs.push(check.cfg);
+    session.args.harnesses = vec![check.prefix.to_string()];
+    session.args.exact = false;
+    session.args.output_format = OutputFormat::Terse;
+
+    let project = project::cargo_project(&session, false)?;
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    if harnesses.is_empty() {
+        println!("No types deriving `{}` were found.", check.derive);
+        return Ok(());
+    }
+
+    let runner = HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+    let failures = report_results(check, &results);
+    if failures > 0 {
+        session.record_failure();
+        bail!("{failures} type(s) failed the `{}` check", check.derive);
+    }
+    Ok(())
+}
+
+/// Print the outcome of the check for each type, and return the number of types that failed it.
+fn report_results(check: &DeriveCheck, results: &[HarnessResult]) -> usize {
+    println!("\n{} check results:", check.derive);
+    let mut checked = 0;
+    let mut failures = 0;
+    for result in results {
+        let type_name = result.harness.pretty_name.replace(check.prefix, "");
+        let status = match &result.result.results {
+            Ok(properties) => match (check.status)(properties) {
+                Some(status) => Some(status),
+                // The check doesn't apply to this type.
+                None => continue,
+            },
+            Err(_) => None,
+        };
+        checked += 1;
+        match status {
+            Some(status) if status == check.expected => {
+                println!(" - `{type_name}`: {}", style("OK").green())
+            }
+            Some(CheckStatus::Undetermined) => {
+                failures += 1;
+                println!(
+                    " - `{type_name}`: {} (could not determine the result of the check)",
+                    style("UNDETERMINED").yellow()
+                );
+            }
+            _ => {
+                failures += 1;
+                println!(" - `{type_name}`: {} ({})", style("FAILED").red(), check.failure_hint);
+            }
+        }
+    }
+    println!("Complete - {} of {checked} types {}.", checked - failures, check.success_summary);
+    failures
+}
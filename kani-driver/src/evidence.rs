@@ -0,0 +1,638 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the evidence report written with `--evidence-dir` (`-Z evidence`).
+//!
+//! Functional safety processes, e.g. ISO 26262, require verification results to be reproducible
+//! and traceable to the tools and configuration that produced them. The report packages the
+//! results of a verification run with this information in two files:
+//!
+//! - `evidence.json`: the machine-readable report, described by [Evidence].
+//! - `evidence.md`: the same report in Markdown, which can be converted to a PDF document.
+//!
+//! The JSON report includes the provenance of the results: the source revision, and the SHA-256
+//! digest of the source files and of the goto binaries that were verified. With
+//! `--evidence-signing-key`, it is also signed with `ssh-keygen -Y sign`, so consumers can check
+//! that a published result was produced from a specific revision by the owner of the key.
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::{resolve_solver, resolve_unwind_value};
+use crate::coverage::{harness_line_coverage, CoverageStatus};
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::results;
+use crate::session::{self, KaniSession};
+use crate::util::message;
+use anyhow::{bail, Context, Result};
+use kani_metadata::ArtifactType;
+use kani_results::{CheckStatus, HarnessResults, VerificationStatus};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The version of the evidence format. Adding optional fields doesn't change the version, any
+/// other change increments it.
+const EVIDENCE_FORMAT_VERSION: u32 = 1;
+
+/// The namespace of the signatures created by `ssh-keygen`, which prevents a signature of the
+/// evidence from being accepted for another purpose, and vice versa.
+const SIGNATURE_NAMESPACE: &str = "kani-evidence";
+
+/// The evidence report of a verification run.
+#[derive(Debug, Serialize)]
+struct Evidence {
+    format_version: u32,
+    /// When the report was generated, in seconds since the Unix epoch.
+    generated_at: u64,
+    tools: Tools,
+    configuration: Configuration,
+    provenance: Provenance,
+    harnesses: Vec<HarnessEvidence>,
+}
+
+/// The versions of the tools used for verification.
+#[derive(Debug, Serialize)]
+struct Tools {
+    kani: String,
+    rust_toolchain: String,
+    /// The version of CBMC, if it could be determined.
+    cbmc: Option<String>,
+}
+
+/// The configuration that applies to every harness.
+#[derive(Debug, Serialize)]
+struct Configuration {
+    command_line: Vec<String>,
+    crates: Vec<String>,
+    memory_safety_checks: bool,
+    overflow_checks: bool,
+    undefined_function_checks: bool,
+    unwinding_checks: bool,
+    unstable_features: Vec<String>,
+    cbmc_args: Vec<String>,
+}
+
+/// What the results were produced from.
+#[derive(Debug, Serialize)]
+struct Provenance {
+    /// The git commit of the sources, followed by `-dirty` if they have uncommitted changes.
+    revision: Option<String>,
+    /// The total time spent verifying the harnesses, in seconds.
+    verification_time_secs: f64,
+    sources: Vec<FileDigest>,
+    goto_binaries: Vec<FileDigest>,
+}
+
+/// The SHA-256 digest of a file.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct FileDigest {
+    path: PathBuf,
+    sha256: String,
+}
+
+/// The configuration and results of a harness.
+#[derive(Debug, Serialize)]
+struct HarnessEvidence {
+    crate_name: String,
+    file: String,
+    unwind: Option<u32>,
+    solver: String,
+    stubs: Vec<String>,
+    runtime_secs: f64,
+    /// The line coverage of the harness, if coverage was enabled with `--coverage`. A line is
+    /// covered if the harness fully covers it.
+    coverage: Option<Coverage>,
+    #[serde(flatten)]
+    results: HarnessResults,
+}
+
+/// The number of source lines covered by a harness.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct Coverage {
+    covered_lines: usize,
+    total_lines: usize,
+}
+
+/// Write the evidence report of the given results to `dir`.
+pub(crate) fn write_evidence(
+    session: &KaniSession,
+    project: &Project,
+    harness_results: &[HarnessResult],
+    dir: &Path,
+) -> Result<()> {
+    let evidence = Evidence {
+        format_version: EVIDENCE_FORMAT_VERSION,
+        generated_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        tools: Tools {
+            kani: env!("CARGO_PKG_VERSION").to_string(),
+            rust_toolchain: session::toolchain_shorthand().trim_start_matches('+').to_string(),
+            cbmc: cbmc_version(),
+        },
+        configuration: configuration(&session.args, project),
+        provenance: provenance(project, harness_results)?,
+        harnesses: harness_results
+            .iter()
+            .map(|result| harness_evidence(&session.args, result))
+            .collect(),
+    };
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create evidence directory `{}`", dir.display()))?;
+    let json_path = dir.join("evidence.json");
+    std::fs::write(&json_path, serde_json::to_string_pretty(&evidence)?)
+        .with_context(|| format!("Failed to write `{}`", json_path.display()))?;
+    let md_path = dir.join("evidence.md");
+    std::fs::write(&md_path, render_markdown(&evidence))
+        .with_context(|| format!("Failed to write `{}`", md_path.display()))?;
+    if let Some(key) = &session.args.evidence_signing_key {
+        sign(key, &json_path)?;
+    }
+    if !session.args.common_args.quiet {
+        message!("Evidence report written to {} and {}", json_path.display(), md_path.display());
+    }
+    Ok(())
+}
+
+/// Sign the given file with `ssh-keygen`, which writes the signature to `<file>.sig`.
+fn sign(key: &Path, file: &Path) -> Result<()> {
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "sign", "-n", SIGNATURE_NAMESPACE, "-f"])
+        .arg(key)
+        .arg(file)
+        .output()
+        .context("Failed to invoke `ssh-keygen` to sign the evidence report")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to sign `{}`: {}",
+            file.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn provenance(project: &Project, harness_results: &[HarnessResult]) -> Result<Provenance> {
+    let root = match &project.cargo_metadata {
+        Some(metadata) => metadata.workspace_root.clone().into_std_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let mut sources = BTreeSet::new();
+    match &project.cargo_metadata {
+        Some(metadata) => {
+            for package in metadata.workspace_packages() {
+                let package_dir = package.manifest_path.parent().unwrap().as_std_path();
+                collect_sources(package_dir, &mut sources);
+            }
+            sources.insert(root.join("Cargo.lock"));
+        }
+        None => sources.extend(
+            harness_results.iter().map(|result| PathBuf::from(&result.harness.original_file)),
+        ),
+    }
+    let goto_binaries: BTreeSet<_> = harness_results
+        .iter()
+        .filter_map(|result| project.get_harness_artifact(result.harness, ArtifactType::Goto))
+        .map(|artifact| artifact.to_path_buf())
+        .collect();
+    Ok(Provenance {
+        revision: git_revision(&root),
+        verification_time_secs: harness_results
+            .iter()
+            .map(|result| result.result.runtime.as_secs_f64())
+            .sum(),
+        sources: file_digests(sources, &root)?,
+        goto_binaries: file_digests(goto_binaries, &root)?,
+    })
+}
+
+/// Compute the digest of the given files, with their path relative to `root` if they are inside
+/// of it. Paths that are not files, e.g. a missing `Cargo.lock`, are skipped.
+fn file_digests(paths: BTreeSet<PathBuf>, root: &Path) -> Result<Vec<FileDigest>> {
+    paths
+        .into_iter()
+        .filter(|path| path.is_file())
+        .map(|path| {
+            let sha256 = file_digest(&path)?;
+            let path = path.strip_prefix(root).map(Path::to_path_buf).unwrap_or(path);
+            Ok(FileDigest { path, sha256 })
+        })
+        .collect()
+}
+
+/// Collect the Rust source files and manifests in a package directory, skipping hidden and
+/// `target` directories.
+fn collect_sources(dir: &Path, sources: &mut BTreeSet<PathBuf>) {
+    // Errors are silently skipped, since the digest of a file that cannot be read is meaningless.
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Ok(typ) = entry.file_type() else { continue };
+        if typ.is_dir() && !name.starts_with('.') && name != "target" {
+            collect_sources(&path, sources);
+        } else if typ.is_file() && (name.ends_with(".rs") || name == "Cargo.toml") {
+            sources.insert(path);
+        }
+    }
+}
+
+/// Compute the SHA-256 digest of a file, in hexadecimal.
+fn file_digest(path: &Path) -> Result<String> {
+    let content =
+        std::fs::read(path).with_context(|| format!("Failed to read `{}`", path.display()))?;
+    Ok(hex_digest(&content))
+}
+
+fn hex_digest(content: &[u8]) -> String {
+    Sha256::digest(content).iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// The git commit checked out in the given directory, if any.
+fn git_revision(dir: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").arg("-C").arg(dir).args(args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Some(if dirty { format!("{commit}-dirty") } else { commit })
+}
+
+/// Run `cbmc --version`, and return the first line of its output.
+fn cbmc_version() -> Option<String> {
+    let output = Command::new("cbmc").arg("--version").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    output.status.success().then(|| stdout.lines().next().unwrap_or_default().trim().to_string())
+}
+
+fn configuration(args: &VerificationArgs, project: &Project) -> Configuration {
+    Configuration {
+        command_line: std::env::args().collect(),
+        crates: project.metadata.iter().map(|md| md.crate_name.clone()).collect(),
+        memory_safety_checks: args.checks.memory_safety_on(),
+        overflow_checks: args.checks.overflow_on(),
+        undefined_function_checks: args.checks.undefined_function_on(),
+        unwinding_checks: args.checks.unwinding_on(),
+        unstable_features: args
+            .common_args
+            .unstable_features
+            .as_arguments()
+            .filter(|arg| *arg != "-Z")
+            .map(String::from)
+            .collect(),
+        cbmc_args: args.cbmc_args.iter().map(|arg| arg.to_string_lossy().to_string()).collect(),
+    }
+}
+
+fn harness_evidence(args: &VerificationArgs, result: &HarnessResult) -> HarnessEvidence {
+    let harness = result.harness;
+    let solver = match resolve_solver(args, &harness.attributes.solver) {
+        kani_metadata::CbmcSolver::Binary(binary) => binary.clone(),
+        solver => solver.as_ref().to_string(),
+    };
+    let results = results::harness_results(result);
+    HarnessEvidence {
+        crate_name: harness.crate_name.clone(),
+        file: harness.original_file.clone(),
+        unwind: resolve_unwind_value(args, harness),
+        solver,
+        stubs: harness
+            .attributes
+            .stubs
+            .iter()
+            .map(|stub| format!("{} -> {}", stub.original, stub.replacement))
+            .collect(),
+        runtime_secs: result.result.runtime.as_secs_f64(),
+        coverage: args.coverage.then(|| coverage(&results)).flatten(),
+        results,
+    }
+}
+
+/// Count the lines in the coverage results of a harness, and the ones it fully covers, like the
+/// `--lcov` report and the coverage badge do.
+fn coverage(results: &HarnessResults) -> Option<Coverage> {
+    let lines: Vec<_> = harness_line_coverage(&results.checks).into_values().flatten().collect();
+    let covered_lines = lines.iter().filter(|(_, status)| *status == CoverageStatus::Full).count();
+    (!lines.is_empty()).then_some(Coverage { covered_lines, total_lines: lines.len() })
+}
+
+fn render_markdown(evidence: &Evidence) -> String {
+    let mut md = String::new();
+    let tools = &evidence.tools;
+    let config = &evidence.configuration;
+    // Writing to a `String` cannot fail.
+    let _ = writeln!(md, "# Verification evidence report\n");
+
+    let _ = writeln!(md, "## Tools\n");
+    let _ = writeln!(md, "| Tool | Version |\n|---|---|");
+    let _ = writeln!(md, "| Kani | {} |", tools.kani);
+    let _ = writeln!(md, "| Rust toolchain | {} |", tools.rust_toolchain);
+    let _ = writeln!(md, "| CBMC | {} |\n", tools.cbmc.as_deref().unwrap_or("unknown"));
+
+    let _ = writeln!(md, "## Configuration\n");
+    let _ = writeln!(md, "- Command line: `{}`", config.command_line.join(" "));
+    let _ = writeln!(md, "- Crates: {}", config.crates.join(", "));
+    let enabled = |on: bool| if on { "enabled" } else { "disabled" };
+    let _ = writeln!(md, "- Memory safety checks: {}", enabled(config.memory_safety_checks));
+    let _ = writeln!(md, "- Overflow checks: {}", enabled(config.overflow_checks));
+    let _ =
+        writeln!(md, "- Undefined function checks: {}", enabled(config.undefined_function_checks));
+    let _ = writeln!(md, "- Unwinding checks: {}", enabled(config.unwinding_checks));
+    if !config.unstable_features.is_empty() {
+        let _ = writeln!(md, "- Unstable features: {}", config.unstable_features.join(", "));
+    }
+    if !config.cbmc_args.is_empty() {
+        let _ = writeln!(md, "- Extra CBMC arguments: `{}`", config.cbmc_args.join(" "));
+    }
+
+    let failed = evidence
+        .harnesses
+        .iter()
+        .filter(|harness| harness.results.status == VerificationStatus::Failure)
+        .count();
+    let _ = writeln!(md, "\n## Results\n");
+    let _ = writeln!(
+        md,
+        "{} harnesses verified: {} successful, {failed} failed.\n",
+        evidence.harnesses.len(),
+        evidence.harnesses.len() - failed
+    );
+    let _ = writeln!(
+        md,
+        "| Harness | Crate | Status | Checks | Failed | Undetermined | Unwind | Solver | Coverage | Time (s) |"
+    );
+    let _ = writeln!(md, "|---|---|---|---|---|---|---|---|---|---|");
+    for harness in &evidence.harnesses {
+        let summary = &harness.results.summary;
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} | {} | {} | {} | {} | {:.2} |",
+            harness.results.name,
+            harness.crate_name,
+            match harness.results.status {
+                VerificationStatus::Success => "SUCCESSFUL",
+                VerificationStatus::Failure => "FAILED",
+                VerificationStatus::Unknown => "UNKNOWN",
+            },
+            summary.total,
+            summary.failed,
+            summary.undetermined,
+            harness.unwind.map_or("none".to_string(), |unwind| unwind.to_string()),
+            harness.solver,
+            harness.coverage.as_ref().map_or("n/a".to_string(), |coverage| format!(
+                "{}/{} lines",
+                coverage.covered_lines, coverage.total_lines
+            )),
+            harness.runtime_secs,
+        );
+    }
+
+    let failures: Vec<_> = evidence
+        .harnesses
+        .iter()
+        .flat_map(|harness| {
+            harness
+                .results
+                .checks
+                .iter()
+                .filter(|check| check.status == CheckStatus::Failure)
+                .map(move |check| (harness, check))
+        })
+        .collect();
+    if !failures.is_empty() {
+        let _ = writeln!(md, "\n## Failed checks\n");
+        let _ = writeln!(md, "| Harness | Check | Description | Location |\n|---|---|---|---|");
+        for (harness, check) in failures {
+            let location = &check.location;
+            let _ = writeln!(
+                md,
+                "| `{}` | `{}` | {} | {}:{} |",
+                harness.results.name,
+                check.name,
+                check.description.replace('|', "\\|"),
+                location.file.as_deref().unwrap_or("unknown"),
+                location.line.map_or(String::new(), |line| line.to_string()),
+            );
+        }
+    }
+
+    let provenance = &evidence.provenance;
+    let _ = writeln!(md, "\n## Provenance\n");
+    let _ =
+        writeln!(md, "- Source revision: {}", provenance.revision.as_deref().unwrap_or("unknown"));
+    let _ = writeln!(md, "- Verification time: {:.2} s\n", provenance.verification_time_secs);
+    let _ = writeln!(md, "| File | SHA-256 |\n|---|---|");
+    for digest in provenance.sources.iter().chain(&provenance.goto_binaries) {
+        let _ = writeln!(md, "| {} | `{}` |", digest.path.display(), digest.sha256);
+    }
+
+    let _ = writeln!(md, "\n## Stubs\n");
+    let stubbed: Vec<_> =
+        evidence.harnesses.iter().filter(|harness| !harness.stubs.is_empty()).collect();
+    if stubbed.is_empty() {
+        let _ = writeln!(md, "No harness uses stubs.");
+    }
+    for harness in stubbed {
+        let _ = writeln!(md, "- `{}`: {}", harness.results.name, harness.stubs.join(", "));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::mock;
+    use kani_results::{CheckResult, Summary};
+
+    #[test]
+    fn check_coverage() {
+        assert_eq!(coverage(&mock::harness(VerificationStatus::Failure, vec![])), None);
+        let results = mock::harness(
+            VerificationStatus::Failure,
+            vec![
+                mock::check("code_coverage", CheckStatus::Covered, "src/lib.rs", 1),
+                mock::check("code_coverage", CheckStatus::Uncovered, "src/lib.rs", 1),
+                mock::check("code_coverage", CheckStatus::Uncovered, "src/lib.rs", 2),
+                mock::check("assertion", CheckStatus::Success, "src/lib.rs", 3),
+                mock::check("code_coverage", CheckStatus::Covered, "src/lib.rs", 4),
+            ],
+        );
+        // Line 1 is only partially covered, so only line 4 counts as covered.
+        assert_eq!(coverage(&results), Some(Coverage { covered_lines: 1, total_lines: 3 }));
+    }
+
+    #[test]
+    fn check_render_markdown() {
+        let evidence = Evidence {
+            format_version: EVIDENCE_FORMAT_VERSION,
+            generated_at: 0,
+            tools: Tools {
+                kani: "0.40.0".to_string(),
+                rust_toolchain: "nightly".to_string(),
+                cbmc: None,
+            },
+            configuration: Configuration {
+                command_line: vec!["cargo-kani".to_string()],
+                crates: vec!["my_crate".to_string()],
+                memory_safety_checks: true,
+                overflow_checks: true,
+                undefined_function_checks: true,
+                unwinding_checks: false,
+                unstable_features: vec!["evidence".to_string()],
+                cbmc_args: vec![],
+            },
+            provenance: Provenance {
+                revision: Some("0123abc-dirty".to_string()),
+                verification_time_secs: 0.5,
+                sources: vec![FileDigest {
+                    path: PathBuf::from("src/lib.rs"),
+                    sha256: hex_digest(b""),
+                }],
+                goto_binaries: vec![],
+            },
+            harnesses: vec![HarnessEvidence {
+                crate_name: "my_crate".to_string(),
+                file: "src/lib.rs".to_string(),
+                unwind: Some(3),
+                solver: "cadical".to_string(),
+                stubs: vec![],
+                runtime_secs: 0.5,
+                coverage: None,
+                results: HarnessResults {
+                    name: "check_a".to_string(),
+                    summary: Summary { total: 1, failed: 1, ..Default::default() },
+                    ..mock::harness(
+                        VerificationStatus::Failure,
+                        vec![CheckResult {
+                            description: "a | b".to_string(),
+                            ..mock::check("assertion", CheckStatus::Failure, "src/lib.rs", 7)
+                        }],
+                    )
+                },
+            }],
+        };
+        let md = render_markdown(&evidence);
+        assert!(md.contains("| CBMC | unknown |"));
+        assert!(md.contains("- Unwinding checks: disabled"));
+        assert!(md.contains("1 harnesses verified: 0 successful, 1 failed."));
+        assert!(
+            md.contains("| `check_a` | my_crate | FAILED | 1 | 1 | 0 | 3 | cadical | n/a | 0.50 |")
+        );
+        assert!(md.contains("| `check_a` | `main.assertion.7` | a \\| b | src/lib.rs:7 |"));
+        assert!(md.contains("No harness uses stubs."));
+        assert!(md.contains("- Source revision: 0123abc-dirty"));
+        assert!(md.contains(
+            "| src/lib.rs | `e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855` |"
+        ));
+    }
+
+    #[test]
+    fn check_collect_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in
+            ["Cargo.toml", "src/lib.rs", "src/a/b.rs", "README.md", "target/x.rs", ".git/y.rs"]
+        {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let mut sources = BTreeSet::new();
+        collect_sources(root, &mut sources);
+        let expected: BTreeSet<_> =
+            ["Cargo.toml", "src/a/b.rs", "src/lib.rs"].iter().map(|path| root.join(path)).collect();
+        assert_eq!(sources, expected);
+    }
+
+    #[test]
+    fn check_file_digests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("ws");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        let outside = dir.path().join("harness.goto");
+        std::fs::write(&outside, "abc").unwrap();
+        let paths =
+            BTreeSet::from([root.join("src/lib.rs"), root.join("Cargo.lock"), outside.clone()]);
+        assert_eq!(
+            file_digests(paths, &root).unwrap(),
+            vec![
+                FileDigest { path: outside, sha256: hex_digest(b"abc") },
+                FileDigest { path: PathBuf::from("src/lib.rs"), sha256: hex_digest(b"") },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_git_revision() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        assert_eq!(git_revision(repo), None);
+
+        let git = |args: &[&str]| {
+            let output = Command::new("git").arg("-C").arg(repo).args(args).output().unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.join("lib.rs"), "").unwrap();
+        git(&["add", "lib.rs"]);
+        git(&[
+            "-c",
+            "user.name=Kani",
+            "-c",
+            "user.email=kani@example.com",
+            "commit",
+            "-qm",
+            "init",
+        ]);
+        let commit = git(&["rev-parse", "HEAD"]);
+        assert_eq!(git_revision(repo), Some(commit.clone()));
+
+        // Untracked files don't affect the revision, changes to tracked files do.
+        std::fs::write(repo.join("new.rs"), "").unwrap();
+        assert_eq!(git_revision(repo), Some(commit.clone()));
+        std::fs::write(repo.join("lib.rs"), "fn f() {}").unwrap();
+        assert_eq!(git_revision(repo), Some(format!("{commit}-dirty")));
+    }
+
+    #[test]
+    fn check_sign() {
+        if which::which("ssh-keygen").is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("key");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let file = dir.path().join("evidence.json");
+        std::fs::write(&file, "{}").unwrap();
+
+        sign(&key, &file).unwrap();
+        let signature = dir.path().join("evidence.json.sig");
+        let output = Command::new("ssh-keygen")
+            .args(["-Y", "check-novalidate", "-n", SIGNATURE_NAMESPACE, "-s"])
+            .arg(&signature)
+            .stdin(std::fs::File::open(&file).unwrap())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+        let err = sign(&dir.path().join("missing"), &file).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to sign"));
+    }
+}
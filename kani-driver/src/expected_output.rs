@@ -0,0 +1,129 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `#[kani::expect_output("<FILE>")]`, which checks the output of a harness against
+//! an expected output file.
+//!
+//! The files follow the format of Kani's own `tests/expected` regression tests: every line of
+//! the file must be contained in some line of the output, and a line that ends with `\` must be
+//! directly followed by the next line of the file. Leading and trailing whitespace is ignored.
+
+use crate::args::OutputFormat;
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cbmc_property_renderer::HarnessOutput;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use std::path::Path;
+
+impl KaniSession {
+    /// Check the results of a harness against its expected output file. The outcome of the check
+    /// replaces the status of the harness, so a harness that is expected to fail verification
+    /// succeeds if its results contain the expected failures.
+    pub(crate) fn check_expected_output(
+        &self,
+        harness: &HarnessMetadata,
+        expected_file: &Path,
+        result: &mut VerificationResult,
+        output: &mut HarnessOutput,
+    ) -> Result<()> {
+        let expected = std::fs::read_to_string(expected_file).with_context(|| {
+            format!("Failed to read expected output file `{}`", expected_file.display())
+        })?;
+        // The regular format is used independently of the format selected by the user, so the
+        // same file can be used with every option.
+        let rendered = result.render(
+            &OutputFormat::Regular,
+            harness.attributes.should_panic,
+            self.args.coverage,
+        );
+        let rendered = console::strip_ansi_codes(&rendered);
+        let actual: Vec<_> = rendered.lines().collect();
+        let name = display_name(harness, expected_file);
+        let message = match missing_lines(&actual, &expected) {
+            None => {
+                result.status = VerificationStatus::Success;
+                format!("The results match the expected output `{}`", name.display())
+            }
+            Some(missing) => {
+                result.status = VerificationStatus::Failure;
+                format!(
+                    "Expected output mismatch: the results don't contain the line(s) of `{}`:\n{}",
+                    name.display(),
+                    missing.join("\n")
+                )
+            }
+        };
+        if !self.args.common_args.quiet {
+            output.println(&message);
+        }
+        Ok(())
+    }
+}
+
+/// The path of the expected output file relative to the source file of the harness, i.e. as
+/// written in the attribute, or its full path if it's outside of that directory.
+fn display_name<'a>(harness: &HarnessMetadata, expected_file: &'a Path) -> &'a Path {
+    Path::new(&harness.original_file)
+        .parent()
+        .and_then(|dir| dir.canonicalize().ok())
+        .and_then(|dir| expected_file.strip_prefix(dir).ok())
+        .unwrap_or(expected_file)
+}
+
+/// Return the first group of lines of `expected` that isn't found in `actual`, if any.
+fn missing_lines<'a>(actual: &[&str], expected: &'a str) -> Option<Vec<&'a str>> {
+    let mut consecutive: Vec<&str> = vec![];
+    for line in expected.lines() {
+        match line.strip_suffix('\\') {
+            Some(prefix) => consecutive.push(prefix),
+            None => {
+                consecutive.push(line);
+                if !contains(actual, &consecutive) {
+                    return Some(consecutive);
+                }
+                consecutive.clear();
+            }
+        }
+    }
+    // A trailing `\` in the last line shouldn't make the check pass silently.
+    (!consecutive.is_empty() && !contains(actual, &consecutive)).then_some(consecutive)
+}
+
+/// Whether some consecutive lines of `actual` contain each of the given `lines` in order.
+fn contains(actual: &[&str], lines: &[&str]) -> bool {
+    actual.windows(lines.len()).any(|window| {
+        window.iter().zip(lines).all(|(actual, expected)| actual.contains(expected.trim()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: [&str; 5] = [
+        "Check 1: main.assertion.1",
+        "\t - Status: FAILURE",
+        "\t - Description: \"assertion failed: x < 10\"",
+        "",
+        "VERIFICATION:- FAILED",
+    ];
+
+    #[test]
+    fn check_missing_lines() {
+        assert_eq!(missing_lines(&OUTPUT, "VERIFICATION:- FAILED\nStatus: FAILURE\n"), None);
+        assert_eq!(missing_lines(&OUTPUT, "Status: FAILURE\\\n  Description:  \n"), None);
+        assert_eq!(
+            missing_lines(&OUTPUT, "Status: FAILURE\nVERIFICATION:- SUCCESSFUL"),
+            Some(vec!["VERIFICATION:- SUCCESSFUL"])
+        );
+        assert_eq!(
+            missing_lines(&OUTPUT, "Status: FAILURE\\\nVERIFICATION:- FAILED"),
+            Some(vec!["Status: FAILURE", "VERIFICATION:- FAILED"])
+        );
+        assert_eq!(
+            missing_lines(&OUTPUT, "main.assertion.1\\\n - Status: SUCCESS\\"),
+            Some(vec!["main.assertion.1", " - Status: SUCCESS"])
+        );
+    }
+}
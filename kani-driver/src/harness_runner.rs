@@ -5,9 +5,14 @@
 use kani_metadata::{ArtifactType, HarnessMetadata};
 use rayon::prelude::*;
 use std::path::Path;
+use std::time::Duration;
 
+use crate::args::common::Verbosity;
 use crate::args::OutputFormat;
 use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cbmc_property_renderer::{
+    format_assumptions, format_counterexamples, format_traces, HarnessOutput,
+};
 use crate::project::Project;
 use crate::session::KaniSession;
 use crate::util::error;
@@ -53,8 +58,6 @@ pub(crate) fn check_all_harnesses(
             sorted_harnesses
                 .par_iter()
                 .map(|harness| -> Result<HarnessResult<'pr>> {
-                    let harness_filename = harness.pretty_name.replace("::", "-");
-                    let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
                     let goto_file =
                         self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
                     self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
@@ -63,8 +66,7 @@ pub(crate) fn check_all_harnesses(
                         self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
                     }
 
-                    let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
-                    Ok(HarnessResult { harness, result })
+                    self.recheck_harness(harness)
                 })
                 .collect::<Result<Vec<_>>>()
         })?;
@@ -72,6 +74,20 @@ pub(crate) fn check_all_harnesses(
         Ok(results)
     }
 
+    /// Run the verification of a harness whose model was already instrumented by
+    /// [`Self::check_all_harnesses`]. This is used to check a harness again after the results
+    /// were reported, e.g. from the `--interactive` result browser.
+    pub(crate) fn recheck_harness(
+        &self,
+        harness: &'pr HarnessMetadata,
+    ) -> Result<HarnessResult<'pr>> {
+        let harness_filename = harness.pretty_name.replace("::", "-");
+        let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
+        let goto_file = self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
+        Ok(HarnessResult { harness, result })
+    }
+
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
     /// experimental feature.
     fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
@@ -108,8 +124,15 @@ pub(crate) fn check_harness(
         report_dir: &Path,
         harness: &HarnessMetadata,
     ) -> Result<VerificationResult> {
-        if !self.args.common_args.quiet {
-            println!("Checking harness {}...", harness.pretty_name);
+        let mut output = HarnessOutput::new(self.args.jobs() != Some(1));
+        let json_output = self.args.output_format == OutputFormat::Json;
+        if !self.args.common_args.quiet && !json_output {
+            output.println(&format!("Checking harness {}...", harness.pretty_name));
+            if self.args.common_args.verbose()
+                && let Some(summary) = harness.doc_summary()
+            {
+                output.println(&summary);
+            }
         }
 
         if self.args.visualize {
@@ -117,19 +140,39 @@ pub(crate) fn check_harness(
             // Strictly speaking, we're faking success here. This is more "no error"
             Ok(VerificationResult::mock_success())
         } else {
-            let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
+            let mut result =
+                self.with_timer(|| self.run_cbmc(binary, harness, &mut output), "run_cbmc")?;
 
             // When quiet, we don't want to print anything at all.
             // When output is old, we also don't have real results to print.
-            if !self.args.common_args.quiet && self.args.output_format != OutputFormat::Old {
-                println!(
-                    "{}",
-                    result.render(
-                        &self.args.output_format,
-                        harness.attributes.should_panic,
-                        self.args.coverage
-                    )
-                );
+            // When output is JSON, the results are printed with the final summary.
+            if !self.args.common_args.quiet
+                && !matches!(self.args.output_format, OutputFormat::Old | OutputFormat::Json)
+            {
+                output.println(&result.render(
+                    &self.args.output_format,
+                    harness.attributes.should_panic,
+                    self.args.coverage,
+                ));
+                if self.args.show_traces()
+                    && let Ok(properties) = &result.results
+                    && result.status == VerificationStatus::Failure
+                {
+                    output.print(&format_traces(
+                        properties,
+                        &self.args.trace_filter,
+                        self.args.trace_max_steps,
+                    ));
+                }
+                if self.args.show_counterexample
+                    && let Ok(properties) = &result.results
+                    && result.status == VerificationStatus::Failure
+                {
+                    output.print(&format_counterexamples(properties));
+                }
+            }
+            if let Some(expected_file) = &harness.attributes.expected_output {
+                self.check_expected_output(harness, expected_file, &mut result, &mut output)?;
             }
             self.gen_and_add_concrete_playback(harness, &mut result)?;
             Ok(result)
@@ -149,7 +192,11 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         let failing = failures.len();
         let total = succeeding + failing;
 
-        if self.args.concrete_playback.is_some()
+        if self.args.output_format == OutputFormat::Json {
+            if !self.args.common_args.quiet {
+                println!("{}", crate::results::format_results_json(&self, results));
+            }
+        } else if self.args.concrete_playback.is_some()
             && !self.args.common_args.quiet
             && results.iter().all(|r| !r.result.generated_concrete_test)
         {
@@ -159,7 +206,24 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         }
 
         // We currently omit a summary if there was just 1 harness
-        if !self.args.common_args.quiet && !self.args.visualize {
+        if !self.args.common_args.quiet
+            && !self.args.visualize
+            && self.args.output_format != OutputFormat::Json
+        {
+            let assumption_reports: Vec<String> = results
+                .iter()
+                .filter_map(|r| {
+                    let properties = r.result.results.as_ref().ok()?;
+                    format_assumptions(&r.harness.pretty_name, properties)
+                })
+                .collect();
+            if !assumption_reports.is_empty() {
+                println!("Assumptions:");
+                for report in assumption_reports {
+                    print!("{report}");
+                }
+            }
+
             if failing > 0 {
                 println!("Summary:");
             }
@@ -171,6 +235,7 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
                 println!(
                     "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
                 );
+                println!("{}", format_total_times(results));
             } else {
                 match (self.args.harnesses.as_slice(), &self.args.function) {
                     ([], None) =>
@@ -198,6 +263,7 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
 
         if failing > 0 {
             // Failure exit code without additional error message
+            self.record_failure();
             drop(self);
             std::process::exit(1);
         }
@@ -205,3 +271,46 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         Ok(())
     }
 }
+
+/// Format the total time spent generating the models of the harnesses, if known, next to the
+/// total time spent verifying them.
+fn format_total_times(results: &[HarnessResult<'_>]) -> String {
+    let verification: Duration = results.iter().map(|r| r.result.runtime).sum();
+    let codegen: Option<Duration> = results.iter().map(|r| r.result.codegen_time).sum();
+    match codegen {
+        Some(codegen) => format!(
+            "Total Codegen Time: {}s, Total Verification Time: {}s",
+            codegen.as_secs_f32(),
+            verification.as_secs_f32()
+        ),
+        None => format!("Total Verification Time: {}s", verification.as_secs_f32()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::mock_proof_harness;
+
+    #[test]
+    fn check_format_total_times() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let mut first = VerificationResult::mock_success();
+        first.runtime = Duration::from_millis(1500);
+        first.codegen_time = Some(Duration::from_millis(250));
+        let mut second = VerificationResult::mock_success();
+        second.runtime = Duration::from_millis(500);
+        second.codegen_time = Some(Duration::from_millis(250));
+        let mut results = vec![
+            HarnessResult { harness: &harness, result: first },
+            HarnessResult { harness: &harness, result: second },
+        ];
+        assert_eq!(
+            format_total_times(&results),
+            "Total Codegen Time: 0.5s, Total Verification Time: 2s"
+        );
+        // The codegen time is unknown if any harness doesn't have it.
+        results[1].result.codegen_time = None;
+        assert_eq!(format_total_times(&results), "Total Verification Time: 2s");
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the static HTML report written with `--report-dir <DIR>`.
+//!
+//! Unlike `--visualize`, the report doesn't require `cbmc-viewer`: it is rendered from the
+//! post-processed results of the harnesses. It has two kinds of pages:
+//!
+//! - `index.html`: the summary of every harness, with a link to its page.
+//! - One page per harness, with the source code around each failed check, its trace, and the
+//!   status of every check of the harness.
+
+use crate::args::TraceFilter;
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property, SourceLocation};
+use crate::cbmc_property_renderer::format_trace;
+use crate::harness_runner::HarnessResult;
+use crate::results;
+use crate::session::KaniSession;
+use crate::util::{escape_xml, message};
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::path::Path;
+
+/// The number of source lines shown before and after the line of a failed check.
+const SNIPPET_CONTEXT: usize = 3;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+.success { color: #2e7d32; }
+.failure { color: #c62828; }
+.undetermined { color: #ef6c00; }
+pre { background: #f6f8fa; padding: 0.8em; overflow-x: auto; }
+.line { display: block; }
+.line.highlight { background: #ffebee; font-weight: bold; }
+.lineno { display: inline-block; width: 4em; color: #888; user-select: none; }";
+
+/// Write the HTML report of the given results to `dir`.
+pub(crate) fn write_html_report(
+    session: &KaniSession,
+    harness_results: &[HarnessResult],
+    dir: &Path,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create report directory `{}`", dir.display()))?;
+    let pages: Vec<_> = harness_results.iter().enumerate().map(page_name).collect();
+    let mut files = vec![("index.html".to_string(), render_index(harness_results, &pages))];
+    for (result, page) in harness_results.iter().zip(pages) {
+        let content =
+            render_harness(result, &session.args.trace_filter, session.args.trace_max_steps);
+        files.push((page, content));
+    }
+    for (name, content) in files {
+        let path = dir.join(name);
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write report page `{}`", path.display()))?;
+    }
+    if !session.args.common_args.quiet {
+        message!("HTML report written to: {}", dir.join("index.html").display());
+    }
+    Ok(())
+}
+
+/// The file name of the page of a harness. The index keeps the names of harnesses that only
+/// differ in characters that aren't allowed in file names unique.
+fn page_name((idx, result): (usize, &HarnessResult)) -> String {
+    let name: String = result
+        .harness
+        .pretty_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    format!("{idx}-{name}.html")
+}
+
+/// Wrap the body of a page in an HTML document.
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>{}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_xml(title)
+    )
+}
+
+fn status_cell(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Success => "<td class=\"success\">SUCCESSFUL</td>",
+        VerificationStatus::Failure => "<td class=\"failure\">FAILED</td>",
+    }
+}
+
+fn render_index(harness_results: &[HarnessResult], pages: &[String]) -> String {
+    let failed =
+        harness_results.iter().filter(|r| r.result.status == VerificationStatus::Failure).count();
+    let mut body = String::from("<h1>Kani verification report</h1>\n");
+    writeln!(
+        body,
+        "<p>{} harness(es) verified: {} successful, {failed} failed.</p>",
+        harness_results.len(),
+        harness_results.len() - failed
+    )
+    .unwrap();
+    body.push_str(
+        "<table>\n<tr><th>Harness</th><th>Crate</th><th>Status</th><th>Checks</th>\
+        <th>Failed</th><th>Undetermined</th><th>Time (s)</th></tr>\n",
+    );
+    for (result, page) in harness_results.iter().zip(pages) {
+        let summary = results::harness_results(result).summary;
+        writeln!(
+            body,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td>{}<td>{}</td><td>{}</td><td>{}</td>\
+            <td>{:.2}</td></tr>",
+            escape_xml(page),
+            escape_xml(&result.harness.pretty_name),
+            escape_xml(&result.harness.crate_name),
+            status_cell(result.result.status),
+            summary.total,
+            summary.failed,
+            summary.undetermined,
+            result.result.runtime.as_secs_f64()
+        )
+        .unwrap();
+    }
+    body.push_str("</table>\n");
+    page("Kani verification report", &body)
+}
+
+fn render_harness(
+    result: &HarnessResult,
+    filters: &[TraceFilter],
+    max_steps: Option<usize>,
+) -> String {
+    let harness = result.harness;
+    let mut body = String::from("<p><a href=\"index.html\">&larr; All harnesses</a></p>\n");
+    writeln!(body, "<h1>{}</h1>", escape_xml(&harness.pretty_name)).unwrap();
+    if let Some(doc) = &harness.doc {
+        writeln!(body, "<pre class=\"doc\">{}</pre>", escape_xml(doc)).unwrap();
+    }
+    writeln!(
+        body,
+        "<table>\n<tr><th>Crate</th><td>{}</td></tr>\n<tr><th>File</th><td>{}</td></tr>\n\
+        <tr><th>Status</th>{}</tr>\n<tr><th>Time (s)</th><td>{:.2}</td></tr>\n</table>",
+        escape_xml(&harness.crate_name),
+        escape_xml(&harness.original_file),
+        status_cell(result.result.status),
+        result.result.runtime.as_secs_f64()
+    )
+    .unwrap();
+    let properties = match &result.result.results {
+        Ok(properties) => properties,
+        Err(exit_status) => {
+            let message = match result.result.timeout {
+                Some(timeout) => format!("CBMC timed out after {}s.", timeout.as_secs()),
+                None => format!("CBMC failed with status {exit_status}."),
+            };
+            writeln!(body, "<p class=\"failure\">{}</p>", escape_xml(&message)).unwrap();
+            return page(&harness.pretty_name, &body);
+        }
+    };
+
+    let failed: Vec<_> =
+        properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
+    if !failed.is_empty() {
+        body.push_str("<h2>Failed checks</h2>\n");
+    }
+    for prop in failed {
+        body.push_str(&render_failure(prop, filters, max_steps));
+    }
+
+    body.push_str(
+        "<h2>Checks</h2>\n<table>\n<tr><th>Check</th><th>Class</th><th>Description</th>\
+        <th>Location</th><th>Status</th></tr>\n",
+    );
+    for prop in properties {
+        let status = console::strip_ansi_codes(&prop.status.to_string()).to_string();
+        let class = match prop.status {
+            CheckStatus::Failure | CheckStatus::Uncovered => "failure",
+            CheckStatus::Undetermined | CheckStatus::Unreachable | CheckStatus::Unsatisfiable => {
+                "undetermined"
+            }
+            _ => "success",
+        };
+        writeln!(
+            body,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"{class}\">{status}</td></tr>",
+            escape_xml(&prop.property_name()),
+            escape_xml(&prop.property_class()),
+            escape_xml(&prop.description),
+            escape_xml(&prop.source_location.to_string()),
+        )
+        .unwrap();
+    }
+    body.push_str("</table>\n");
+    page(&harness.pretty_name, &body)
+}
+
+/// The description, location, source code and trace of a failed check.
+fn render_failure(prop: &Property, filters: &[TraceFilter], max_steps: Option<usize>) -> String {
+    let mut section = format!(
+        "<h3 class=\"failure\">{}</h3>\n<p>{} at {}</p>\n",
+        escape_xml(&prop.description),
+        escape_xml(&prop.property_class()),
+        escape_xml(&prop.source_location.to_string())
+    );
+    match source_snippet(&prop.source_location) {
+        Some(snippet) => section.push_str(&snippet),
+        None => section.push_str("<p>Source code not available.</p>\n"),
+    }
+    writeln!(
+        section,
+        "<details>\n<summary>Trace</summary>\n<pre>{}</pre>\n</details>",
+        escape_xml(&format_trace(prop, filters, max_steps))
+    )
+    .unwrap();
+    section
+}
+
+/// The source lines around the given location, with the line of the location highlighted.
+/// Returns `None` if the location doesn't have a line or its file can't be read.
+fn source_snippet(location: &SourceLocation) -> Option<String> {
+    let line: usize = location.line.as_ref()?.parse().ok()?;
+    let source = std::fs::read_to_string(location.file.as_ref()?).ok()?;
+    let first = line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    let mut snippet = String::from("<pre class=\"source\">");
+    for (idx, text) in
+        source.lines().enumerate().skip(first - 1).take(line + SNIPPET_CONTEXT + 1 - first)
+    {
+        let number = idx + 1;
+        let class = if number == line { "line highlight" } else { "line" };
+        write!(
+            snippet,
+            "<span class=\"{class}\"><span class=\"lineno\">{number}</span>{}</span>",
+            escape_xml(text)
+        )
+        .unwrap();
+    }
+    snippet.push_str("</pre>\n");
+    Some(snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::cbmc_output_parser::PropertyId;
+    use crate::metadata::mock_proof_harness;
+    use std::io::Write as _;
+
+    fn mock_property(file: &str, line: usize, status: CheckStatus) -> Property {
+        Property {
+            description: "assertion failed: x < 10".to_string(),
+            property_id: PropertyId {
+                fn_name: Some("check".to_string()),
+                class: "assertion".to_string(),
+                id: 1,
+            },
+            source_location: SourceLocation {
+                column: Some("5".to_string()),
+                end_column: None,
+                end_line: None,
+                file: Some(file.to_string()),
+                function: None,
+                line: Some(line.to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_html_report() {
+        let mut source = tempfile::NamedTempFile::new().unwrap();
+        for line in 1..=10 {
+            writeln!(source, "let x{line} = {line} < 10;").unwrap();
+        }
+        let file = source.path().to_str().unwrap();
+
+        let success = mock_proof_harness("mod::check_ok", None, Some("foo"), None);
+        let mut failure = mock_proof_harness("check_fail", None, Some("foo"), None);
+        failure.doc = Some("Check that x < 10.".to_string());
+        let mut failed = VerificationResult::mock_success();
+        failed.status = VerificationStatus::Failure;
+        failed.results = Ok(vec![mock_property(file, 2, CheckStatus::Failure)]);
+        let results = [
+            HarnessResult { harness: &success, result: VerificationResult::mock_success() },
+            HarnessResult { harness: &failure, result: failed },
+        ];
+        let pages: Vec<_> = results.iter().enumerate().map(page_name).collect();
+        assert_eq!(pages, ["0-mod__check_ok.html", "1-check_fail.html"]);
+
+        let index = render_index(&results, &pages);
+        assert!(index.contains("<p>2 harness(es) verified: 1 successful, 1 failed.</p>"));
+        assert!(index.contains("<a href=\"1-check_fail.html\">check_fail</a>"));
+
+        let harness = render_harness(&results[1], &[], None);
+        assert!(harness.contains("<pre class=\"doc\">Check that x &lt; 10.</pre>"));
+        assert!(!render_harness(&results[0], &[], None).contains("class=\"doc\""));
+        assert!(harness.contains("<h3 class=\"failure\">assertion failed: x &lt; 10</h3>"));
+        // The snippet starts at the first line, and ends 3 lines after the failure.
+        assert!(harness.contains("<span class=\"lineno\">1</span>let x1 = 1 &lt; 10;"));
+        assert!(harness.contains(
+            "<span class=\"line highlight\"><span class=\"lineno\">2</span>let x2 = 2 &lt; 10;"
+        ));
+        assert!(harness.contains("<span class=\"lineno\">5</span>"));
+        assert!(!harness.contains("<span class=\"lineno\">6</span>"));
+        assert!(harness.contains("<td class=\"failure\">FAILURE</td>"));
+
+        assert_eq!(
+            source_snippet(&mock_property("missing.rs", 1, CheckStatus::Failure).source_location),
+            None
+        );
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Terminal browser for the verification results, enabled with `--interactive`.
+//!
+//! Once all harnesses are checked, users can move through the list of harnesses, open the
+//! failed checks of a harness together with their source and trace, and re-run a single harness.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, SourceLocation};
+use crate::cbmc_property_renderer::format_traces;
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use anyhow::{bail, Result};
+use console::{style, Key, Term};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Number of lines shown before and after the line of a failed check.
+const SNIPPET_CONTEXT: usize = 2;
+
+/// Show the results of all harnesses until the user quits. Harnesses that are re-run have their
+/// result replaced, so the final summary reflects the latest run.
+pub(crate) fn browse_results<'pr>(
+    runner: &HarnessRunner<'_, 'pr>,
+    results: &mut [HarnessResult<'pr>],
+) -> Result<()> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        bail!("`--interactive` requires a terminal");
+    }
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    let mut selected = 0;
+    loop {
+        term.clear_screen()?;
+        term.write_str(&format_harness_list(results, selected))?;
+        match term.read_key()? {
+            Key::ArrowUp | Key::Char('k') => selected = selected.saturating_sub(1),
+            Key::ArrowDown | Key::Char('j') => selected = (selected + 1).min(results.len() - 1),
+            Key::Enter | Key::ArrowRight | Key::Char('l') => {
+                term.clear_screen()?;
+                term.write_str(&format_harness_details(runner, &results[selected]))?;
+                wait_for_key(&term)?;
+            }
+            Key::Char('r') => {
+                term.clear_screen()?;
+                results[selected] = runner.recheck_harness(results[selected].harness)?;
+                wait_for_key(&term)?;
+            }
+            Key::Char('q') | Key::Escape => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+fn wait_for_key(term: &Term) -> Result<()> {
+    term.write_line(&style("Press any key to go back.").dim().to_string())?;
+    term.read_key()?;
+    Ok(())
+}
+
+/// One line per harness with its status, with a marker on the selected harness.
+fn format_harness_list(results: &[HarnessResult], selected: usize) -> String {
+    let mut output = String::from(
+        "Verification results (up/down: move, enter: details, r: re-run, q: quit)\n\n",
+    );
+    for (index, harness_result) in results.iter().enumerate() {
+        let marker = if index == selected { ">" } else { " " };
+        let status = match harness_result.result.status {
+            VerificationStatus::Success => style("SUCCESSFUL").green(),
+            VerificationStatus::Failure => style("FAILED    ").red(),
+        };
+        let detail = match &harness_result.result.results {
+            Ok(properties) => {
+                let failed =
+                    properties.iter().filter(|prop| prop.status == CheckStatus::Failure).count();
+                format!("{failed} of {} checks failed", properties.len())
+            }
+            Err(exit_status) => format!("CBMC failed with status {exit_status}"),
+        };
+        writeln!(output, "{marker} {status} {} ({detail})", harness_result.harness.pretty_name)
+            .unwrap();
+    }
+    output
+}
+
+/// The failed checks of a harness, with the source around each of them, followed by their
+/// traces (filtered according to `--trace-filter` and `--trace-max-steps`).
+fn format_harness_details(runner: &HarnessRunner, harness_result: &HarnessResult) -> String {
+    let mut output = format!("Harness: {}\n", harness_result.harness.pretty_name);
+    let result = &harness_result.result;
+    writeln!(output, "Verification Time: {}s\n", result.runtime.as_secs_f32()).unwrap();
+    let properties = match &result.results {
+        Ok(properties) => properties,
+        Err(exit_status) => {
+            writeln!(output, "CBMC failed with status {exit_status}").unwrap();
+            return output;
+        }
+    };
+    let failed: Vec<_> =
+        properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
+    if failed.is_empty() {
+        output.push_str("No failed checks.\n");
+        return output;
+    }
+    for prop in failed {
+        writeln!(output, "Failed Check: {}", prop.description).unwrap();
+        if !prop.source_location.is_missing() {
+            writeln!(output, " Location: {}", prop.source_location).unwrap();
+        }
+        if let Some(snippet) = source_snippet(&prop.source_location) {
+            output.push_str(&snippet);
+        }
+        output.push('\n');
+    }
+    let args = &runner.sess.args;
+    output.push_str(&format_traces(properties, &args.trace_filter, args.trace_max_steps));
+    output
+}
+
+/// The lines around a source location, with a marker on the line of the location, and the
+/// columns of the location underlined if they are known.
+/// Returns `None` if the location doesn't have a line or its file cannot be read.
+fn source_snippet(location: &SourceLocation) -> Option<String> {
+    let file = location.file.as_ref()?;
+    let line: usize = location.line.as_ref()?.parse().ok()?;
+    let source = std::fs::read_to_string(Path::new(file)).ok()?;
+    let first = line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    let mut snippet = String::new();
+    for (number, text) in source.lines().enumerate().map(|(idx, text)| (idx + 1, text)) {
+        if number < first || number > line + SNIPPET_CONTEXT {
+            continue;
+        }
+        let marker = if number == line { ">" } else { " " };
+        writeln!(snippet, " {marker} {number:>5} | {text}").unwrap();
+        if let Some(underline) = (number == line).then(|| underline(location, text)).flatten() {
+            writeln!(snippet, "   {:>5} | {underline}", "").unwrap();
+        }
+    }
+    (!snippet.is_empty()).then_some(snippet)
+}
+
+/// Underline the columns of `location` in `text`, which is the line where the location starts.
+/// If the location ends on a later line, the rest of the line is underlined. If its end is
+/// unknown, only the first column is marked.
+fn underline(location: &SourceLocation, text: &str) -> Option<String> {
+    let column: usize = location.column.as_ref()?.parse().ok()?;
+    let line: usize = location.line.as_ref()?.parse().ok()?;
+    let width = text.chars().count() + 1;
+    let end = match (location.end_line, location.end_column) {
+        (Some(end_line), Some(end_column)) if end_line == line => end_column,
+        (Some(end_line), Some(_)) if end_line > line => width,
+        _ => column + 1,
+    };
+    (column < width)
+        .then(|| format!("{}{}", " ".repeat(column - 1), "^".repeat(end.max(column + 1) - column)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn check_source_snippet() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "fn main() {{\n    let x = 1;\n    assert!(x == 2);\n}}").unwrap();
+        let location = |line: &str| SourceLocation {
+            column: None,
+            end_column: None,
+            end_line: None,
+            file: Some(file.path().to_str().unwrap().to_string()),
+            function: None,
+            line: Some(line.to_string()),
+        };
+        assert_eq!(
+            source_snippet(&location("3")).unwrap(),
+            "       1 | fn main() {\n       2 |     let x = 1;\n >     3 |     assert!(x == 2);\n       4 | }\n"
+        );
+        assert_eq!(source_snippet(&location("10")), None);
+        assert_eq!(source_snippet(&SourceLocation { line: None, ..location("1") }), None);
+    }
+
+    #[test]
+    fn check_source_snippet_underline() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "let z = a + b * 2 - c;").unwrap();
+        let location = SourceLocation {
+            column: Some("9".to_string()),
+            end_column: Some(14),
+            end_line: Some(1),
+            file: Some(file.path().to_str().unwrap().to_string()),
+            function: None,
+            line: Some("1".to_string()),
+        };
+        assert_eq!(
+            source_snippet(&location).unwrap(),
+            " >     1 | let z = a + b * 2 - c;\n         |         ^^^^^\n"
+        );
+        let start_only = SourceLocation { end_column: None, end_line: None, ..location.clone() };
+        assert!(source_snippet(&start_only).unwrap().ends_with("|         ^\n"));
+        let multi_line = SourceLocation { end_column: Some(2), end_line: Some(2), ..location };
+        assert!(source_snippet(&multi_line).unwrap().ends_with("|         ^^^^^^^^^^^^^^\n"));
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the JUnit XML report written with `--junit-report <FILE>`.
+//!
+//! CI systems such as Jenkins and GitLab show JUnit reports as test results. Each crate is
+//! reported as a test suite and each harness as a test case. A harness that failed verification
+//! has one `<failure>` element per failed check, with the trace of the check as its body, or an
+//! `<error>` element if the verification engine didn't complete.
+
+use crate::args::TraceFilter;
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::cbmc_property_renderer::format_trace;
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::util::escape_xml;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Write the JUnit report of the given results to `path`.
+pub(crate) fn write_junit(
+    session: &KaniSession,
+    harness_results: &[HarnessResult],
+    path: &Path,
+) -> Result<()> {
+    let report =
+        junit_report(harness_results, &session.args.trace_filter, session.args.trace_max_steps);
+    std::fs::write(path, report)
+        .with_context(|| format!("Failed to write JUnit report `{}`", path.display()))
+}
+
+fn junit_report(
+    harness_results: &[HarnessResult],
+    filters: &[TraceFilter],
+    max_steps: Option<usize>,
+) -> String {
+    let mut suites: BTreeMap<&str, Vec<&HarnessResult>> = BTreeMap::new();
+    for result in harness_results {
+        suites.entry(&result.harness.crate_name).or_default().push(result);
+    }
+    // Harnesses whose verification didn't complete, e.g. because of a timeout, are reported as
+    // errors rather than failures, like the `<error>` element of their test case.
+    let failures = |results: &[&HarnessResult]| {
+        results
+            .iter()
+            .filter(|r| r.result.status == VerificationStatus::Failure && r.result.results.is_ok())
+            .count()
+    };
+    let errors =
+        |results: &[&HarnessResult]| results.iter().filter(|r| r.result.results.is_err()).count();
+    let time = |results: &[&HarnessResult]| -> f32 {
+        results.iter().map(|r| r.result.runtime.as_secs_f32()).sum()
+    };
+    let all: Vec<_> = harness_results.iter().collect();
+    let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        report,
+        "<testsuites name=\"kani\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">",
+        all.len(),
+        failures(&all),
+        errors(&all),
+        time(&all)
+    )
+    .unwrap();
+    for (crate_name, results) in suites {
+        writeln!(
+            report,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">",
+            escape_xml(crate_name),
+            results.len(),
+            failures(&results),
+            errors(&results),
+            time(&results)
+        )
+        .unwrap();
+        for result in results {
+            report.push_str(&test_case(result, filters, max_steps));
+        }
+        report.push_str("  </testsuite>\n");
+    }
+    report.push_str("</testsuites>\n");
+    report
+}
+
+/// The `<testcase>` element of a harness.
+fn test_case(result: &HarnessResult, filters: &[TraceFilter], max_steps: Option<usize>) -> String {
+    let mut case = format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+        escape_xml(&result.harness.pretty_name),
+        escape_xml(&result.harness.crate_name),
+        result.result.runtime.as_secs_f32()
+    );
+    if result.result.status == VerificationStatus::Success {
+        case.push_str("/>\n");
+        return case;
+    }
+    case.push_str(">\n");
+    match &result.result.results {
+        Ok(properties) => {
+            let failed: Vec<_> =
+                properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
+            if failed.is_empty() {
+                // E.g. a `should_panic` harness that didn't panic.
+                case.push_str("      <failure message=\"verification failed\"/>\n");
+            }
+            for prop in failed {
+                case.push_str(&failure(prop, filters, max_steps));
+            }
+        }
+        Err(exit_status) => {
+            let message = match result.result.timeout {
+                Some(timeout) => format!("CBMC timed out after {}s", timeout.as_secs()),
+                None => format!("CBMC failed with status {exit_status}"),
+            };
+            writeln!(case, "      <error message=\"{}\"/>", escape_xml(&message)).unwrap();
+        }
+    }
+    case.push_str("    </testcase>\n");
+    case
+}
+
+/// The `<failure>` element of a failed check, with the location and the trace of the check.
+fn failure(prop: &Property, filters: &[TraceFilter], max_steps: Option<usize>) -> String {
+    let body = format!(
+        "Failed Check: {}\nLocation: {}\n{}",
+        prop.description,
+        prop.source_location,
+        format_trace(prop, filters, max_steps)
+    );
+    format!(
+        "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+        escape_xml(&prop.property_class()),
+        escape_xml(&prop.description),
+        escape_xml(&body)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+    use crate::metadata::mock_proof_harness;
+    use std::time::Duration;
+
+    fn mock_property(class: &str, description: &str, status: CheckStatus) -> Property {
+        Property {
+            description: description.to_string(),
+            property_id: PropertyId { fn_name: None, class: class.to_string(), id: 1 },
+            source_location: SourceLocation {
+                column: Some("5".to_string()),
+                end_column: None,
+                end_line: None,
+                file: Some("src/lib.rs".to_string()),
+                function: None,
+                line: Some("3".to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_junit_report() {
+        let success = mock_proof_harness("check_ok", None, Some("foo"), None);
+        let failure = mock_proof_harness("check_fail", None, Some("foo"), None);
+        let timeout = mock_proof_harness("check_slow", None, Some("bar"), None);
+        let mut failed = VerificationResult::mock_success();
+        failed.status = VerificationStatus::Failure;
+        failed.results = Ok(vec![
+            mock_property("assertion", "assertion failed: x < 10", CheckStatus::Success),
+            mock_property("assertion", "assertion failed: a & b", CheckStatus::Failure),
+        ]);
+        let mut timed_out = VerificationResult::mock_success();
+        timed_out.status = VerificationStatus::Failure;
+        timed_out.results = Err(137);
+        timed_out.timeout = Some(Duration::from_secs(60));
+        let results = [
+            HarnessResult { harness: &success, result: VerificationResult::mock_success() },
+            HarnessResult { harness: &failure, result: failed },
+            HarnessResult { harness: &timeout, result: timed_out },
+        ];
+        let report = junit_report(&results, &[], None);
+        assert!(
+            report.contains("<testsuites name=\"kani\" tests=\"3\" failures=\"1\" errors=\"1\"")
+        );
+        assert!(report.contains("<testsuite name=\"bar\" tests=\"1\" failures=\"0\" errors=\"1\""));
+        assert!(report.contains("<testsuite name=\"foo\" tests=\"2\" failures=\"1\" errors=\"0\""));
+        assert!(report.contains("<testcase name=\"check_ok\" classname=\"foo\" time=\"0\"/>"));
+        assert!(report.contains(
+            "<failure type=\"assertion\" message=\"assertion failed: a &amp; b\">\
+            Failed Check: assertion failed: a &amp; b\nLocation: src/lib.rs:3:5\n \
+            No trace available\n</failure>"
+        ));
+        assert!(!report.contains("x &lt; 10"));
+        assert!(report.contains("<error message=\"CBMC timed out after 60s\"/>"));
+        assert!(report.find("name=\"bar\"").unwrap() < report.find("name=\"foo\"").unwrap());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the coverage report written with `--lcov <FILE>`.
+//!
+//! The report uses the [lcov](https://github.com/linux-test-project/lcov) tracefile format, which
+//! is accepted by coverage services such as Codecov, so the proof coverage of a project can be
+//! tracked alongside its test coverage. Each line of the coverage results (`--coverage`) is
+//! reported, and its hit count is the number of harnesses that fully cover it, i.e. whose
+//! coverage results show the line as `FULL`. A line that a harness only covers partially is not
+//! counted as hit by that harness, since the lcov format has no partial line coverage.
+
+use crate::coverage::line_hits;
+use crate::harness_runner::HarnessResult;
+use crate::results;
+use anyhow::{Context, Result};
+use kani_results::HarnessResults;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Write the line coverage of the given results to `path` in lcov format.
+pub(crate) fn write_lcov(harness_results: &[HarnessResult], path: &Path) -> Result<()> {
+    let results: Vec<_> = harness_results.iter().map(results::harness_results).collect();
+    std::fs::write(path, lcov_report(&results))
+        .with_context(|| format!("Failed to write lcov report `{}`", path.display()))
+}
+
+/// Render the line coverage of the given results as an lcov tracefile, with a record per file.
+fn lcov_report(results: &[HarnessResults]) -> String {
+    let mut report = String::new();
+    for (file, lines) in line_hits(results) {
+        writeln!(report, "TN:\nSF:{file}").unwrap();
+        for (line, hits) in &lines {
+            writeln!(report, "DA:{line},{hits}").unwrap();
+        }
+        let hit = lines.values().filter(|hits| **hits > 0).count();
+        writeln!(report, "LF:{}\nLH:{hit}\nend_of_record", lines.len()).unwrap();
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::mock;
+    use kani_results::{CheckStatus, VerificationStatus};
+
+    #[test]
+    fn check_lcov_report() {
+        let results = vec![
+            mock::harness(
+                VerificationStatus::Success,
+                vec![
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 2),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/main.rs", 1),
+                ],
+            ),
+            mock::harness(
+                VerificationStatus::Success,
+                vec![
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 3),
+                ],
+            ),
+        ];
+        assert_eq!(
+            lcov_report(&results),
+            "TN:\nSF:src/lib.rs\nDA:1,2\nDA:2,0\nDA:3,0\nLF:3\nLH:1\nend_of_record\n\
+            TN:\nSF:src/main.rs\nDA:1,0\nLF:1\nLH:0\nend_of_record\n"
+        );
+        assert_eq!(lcov_report(&[mock::harness(VerificationStatus::Success, vec![])]), "");
+    }
+
+    #[test]
+    fn check_lcov_partial_line() {
+        let results = vec![
+            mock::harness(
+                VerificationStatus::Success,
+                vec![
+                    mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                    mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 1),
+                ],
+            ),
+            mock::harness(
+                VerificationStatus::Success,
+                vec![mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1)],
+            ),
+        ];
+        assert_eq!(
+            lcov_report(&results),
+            "TN:\nSF:src/lib.rs\nDA:1,1\nLF:1\nLH:1\nend_of_record\n"
+        );
+        let partial = vec![mock::harness(
+            VerificationStatus::Success,
+            vec![
+                mock::coverage_check(CheckStatus::Covered, "src/lib.rs", 1),
+                mock::coverage_check(CheckStatus::Uncovered, "src/lib.rs", 1),
+            ],
+        )];
+        assert_eq!(
+            lcov_report(&partial),
+            "TN:\nSF:src/lib.rs\nDA:1,0\nLF:1\nLH:0\nend_of_record\n"
+        );
+    }
+}
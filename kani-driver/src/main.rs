@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 #![feature(let_chains)]
 #![feature(array_methods)]
+#![feature(result_option_inspect)]
 use std::ffi::OsString;
 use std::process::ExitCode;
 
@@ -10,17 +11,20 @@
 use args::{check_is_valid, CargoKaniSubcommand};
 use args_toml::join_args;
 
-use crate::args::StandaloneSubcommand;
+use crate::args::{OutputFormat, StandaloneSubcommand};
 use crate::concrete_playback::playback::{playback_cargo, playback_standalone};
 use crate::project::Project;
 use crate::session::KaniSession;
 use crate::version::print_kani_version;
 use clap::Parser;
+use kani_metadata::UnstableFeature;
 use tracing::debug;
 
 mod args;
 mod args_toml;
+mod assertion_contest;
 mod assess;
+mod badge;
 mod call_cargo;
 mod call_cbmc;
 mod call_cbmc_viewer;
@@ -31,18 +35,43 @@
 mod cbmc_output_parser;
 mod cbmc_property_renderer;
 mod concrete_playback;
+mod coverage;
+mod daemon;
+mod derive_checks;
+mod evidence;
+mod expected_output;
 mod harness_runner;
+mod html_report;
+mod interactive;
+mod junit;
+mod lcov;
 mod metadata;
 mod project;
+mod project_cache;
+mod results;
+mod sarif;
 mod session;
+mod stats;
+mod trends;
+mod unsafe_inventory;
 mod util;
 mod version;
 
+/// Environment variable used by the `kani` and `cargo-kani` proxies to tell the driver which
+/// command was invoked on platforms where they cannot override `argv[0]`, i.e.: Windows.
+const INVOCATION_NAME_VAR: &str = "KANI_INVOCATION_NAME";
+
 /// The main function for the `kani-driver`.
 /// The driver can be invoked via `cargo kani` and `kani` commands, which determines what kind of
 /// project should be verified.
 fn main() -> ExitCode {
-    let invocation_type = determine_invocation_type(Vec::from_iter(std::env::args_os()));
+    let mut args = Vec::from_iter(std::env::args_os());
+    if let Some(name) = std::env::var_os(INVOCATION_NAME_VAR) {
+        // Don't propagate the variable to any nested Kani invocation.
+        std::env::remove_var(INVOCATION_NAME_VAR);
+        args[0] = name;
+    }
+    let invocation_type = determine_invocation_type(args);
 
     let result = match invocation_type {
         InvocationType::CargoKani(args) => cargokani_main(args),
@@ -64,11 +93,22 @@ fn main() -> ExitCode {
 fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
     let input_args = join_args(input_args)?;
     let args = args::CargoKaniArgs::parse_from(&input_args);
+    if args.verify_opts.output_format == OutputFormat::Json {
+        util::print_messages_to_stderr();
+    }
     check_is_valid(&args);
 
     let session = session::KaniSession::new(args.verify_opts)?;
 
-    if !session.args.common_args.quiet {
+    // Keep the output of the results, trends and stats subcommands machine readable.
+    match args.command {
+        Some(CargoKaniSubcommand::Results(args)) => return results::results_cargo(&session, *args),
+        Some(CargoKaniSubcommand::Trends(args)) => return trends::trends_cargo(&session, *args),
+        Some(CargoKaniSubcommand::Stats(args)) => return stats::stats_cargo(&session, *args),
+        _ => {}
+    }
+
+    if !session.args.common_args.quiet && session.args.output_format != OutputFormat::Json {
         print_kani_version(InvocationType::CargoKani(input_args));
     }
 
@@ -79,6 +119,25 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         Some(CargoKaniSubcommand::Playback(args)) => {
             return playback_cargo(*args);
         }
+        Some(
+            CargoKaniSubcommand::Results(_)
+            | CargoKaniSubcommand::Trends(_)
+            | CargoKaniSubcommand::Stats(_),
+        ) => {
+            unreachable!("handled above")
+        }
+        Some(CargoKaniSubcommand::CheckArbitrary) => {
+            return derive_checks::check_arbitrary(session);
+        }
+        Some(CargoKaniSubcommand::CheckInvariants) => {
+            return derive_checks::check_invariants(session);
+        }
+        Some(CargoKaniSubcommand::Daemon(args)) => {
+            return daemon::run_daemon(session, *args);
+        }
+        Some(CargoKaniSubcommand::UnsafeInventory(args)) => {
+            return unsafe_inventory::unsafe_inventory(session, *args);
+        }
         None => {}
     }
 
@@ -86,38 +145,93 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         return assess::run_assess(session, assess::AssessArgs::default());
     }
 
-    let project = project::cargo_project(&session, false)?;
-    if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
+    let project =
+        project::cargo_project(&session, false).inspect_err(|_| session.record_failure())?;
+    if session.args.only_codegen {
+        Ok(())
+    } else {
+        verify_project(project, session, |session| project::cargo_project(session, false))
+    }
 }
 
 /// The main function for the `kani` command.
 fn standalone_main() -> Result<()> {
     let args = args::StandaloneArgs::parse();
+    if args.verify_opts.output_format == OutputFormat::Json {
+        util::print_messages_to_stderr();
+    }
     check_is_valid(&args);
 
-    if let Some(StandaloneSubcommand::Playback(args)) = args.command {
-        return playback_standalone(*args);
+    match args.command {
+        Some(StandaloneSubcommand::Playback(args)) => return playback_standalone(*args),
+        Some(StandaloneSubcommand::Results(args)) => return results::results_standalone(*args),
+        Some(StandaloneSubcommand::Trends(args)) => return trends::trends_standalone(*args),
+        Some(StandaloneSubcommand::Stats(args)) => return stats::stats_standalone(*args),
+        None => {}
     }
 
     let session = session::KaniSession::new(args.verify_opts)?;
 
-    if !session.args.common_args.quiet {
+    if !session.args.common_args.quiet && session.args.output_format != OutputFormat::Json {
         print_kani_version(InvocationType::Standalone);
     }
 
-    let project = project::standalone_project(&args.input.unwrap(), &session)?;
-    if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
+    let input = args.input.unwrap();
+    let project = project::standalone_project(&input, &args.externs, &session)
+        .inspect_err(|_| session.record_failure())?;
+    if session.args.only_codegen {
+        Ok(())
+    } else {
+        verify_project(project, session, |session| {
+            project::standalone_project(&input, &args.externs, session)
+        })
+    }
 }
 
-/// Run verification on the given project.
-fn verify_project(project: Project, session: KaniSession) -> Result<()> {
+/// Run verification on the given project. `build` is used to rebuild the project when a
+/// harness needs to be verified again with different options, e.g., by `-Z assertion-contest`.
+fn verify_project(
+    project: Project,
+    mut session: KaniSession,
+    build: impl FnOnce(&KaniSession) -> Result<Project>,
+) -> Result<()> {
     debug!(?project, "verify_project");
     let harnesses = session.determine_targets(&project.get_all_harnesses())?;
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
 
     // Verification
     let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
-    let results = runner.check_all_harnesses(&harnesses)?;
+    let mut results =
+        runner.check_all_harnesses(&harnesses).inspect_err(|_| session.record_failure())?;
+    if session.args.interactive {
+        interactive::browse_results(&runner, &mut results)?;
+    }
+    // The results file is only written for later queries, so failing to write it shouldn't hide
+    // the outcome of verification.
+    if let Err(err) = results::write_results(&session, &project, &results) {
+        util::warning(&format!("{err:#}"));
+    }
+    if let Some(path) = &session.args.sarif {
+        sarif::write_sarif(&project, &results, path)?;
+    }
+    if let Some(path) = &session.args.junit_report {
+        junit::write_junit(&session, &results, path)?;
+    }
+    if let Some(dir) = &session.args.badge_dir {
+        badge::write_badges(&results, dir, session.args.coverage)?;
+    }
+    if let Some(path) = &session.args.lcov {
+        lcov::write_lcov(&results, path)?;
+    }
+    if let Some(dir) = &session.args.report_dir {
+        html_report::write_html_report(&session, &results, dir)?;
+    }
+    if let Some(dir) = &session.args.evidence_dir {
+        evidence::write_evidence(&session, &project, &results, dir)?;
+    }
+    if session.args.common_args.unstable_features.contains(UnstableFeature::AssertionContest) {
+        assertion_contest::contest_assertions(&mut session, &results, build)?;
+    }
 
     session.print_final_summary(&results)
 }
@@ -6,11 +6,11 @@
 use tracing::{debug, trace};
 
 use kani_metadata::{
-    HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata, TraitDefinedMethod,
-    VtableCtxResults,
+    HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata, VtableCtxResults,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
+use std::hash::Hash;
 use std::io::{BufReader, BufWriter};
 
 use crate::session::KaniSession;
@@ -50,15 +50,13 @@ fn link_function_pointer_restrictions(
     data_per_crate: Vec<VtableCtxResults>,
     output_filename: &Path,
 ) -> Result<()> {
-    // Combine all method possibilities into one global mapping
-    let mut combined_possible_methods: HashMap<TraitDefinedMethod, Vec<InternedString>> =
-        HashMap::new();
-    for crate_data in &data_per_crate {
-        for entry in &crate_data.possible_methods {
-            combined_possible_methods
-                .insert(entry.trait_method.clone(), entry.possibilities.clone());
-        }
-    }
+    // Combine all method and function pointer possibilities into global mappings
+    let combined_possible_methods = combine_possibilities(data_per_crate.iter().flat_map(|data| {
+        data.possible_methods.iter().map(|entry| (&entry.trait_method, &entry.possibilities[..]))
+    }));
+    let combined_possible_fn_ptrs = combine_possibilities(data_per_crate.iter().flat_map(|data| {
+        data.possible_fn_ptrs.iter().map(|entry| (&entry.fn_ptr_type, &entry.possibilities[..]))
+    }));
 
     // Emit a restriction for every call site
     let mut output = HashMap::new();
@@ -74,6 +72,12 @@ fn link_function_pointer_restrictions(
                 combined_possible_methods.get(&trait_def).unwrap_or(&vec![]).clone();
             output.insert(cbmc_call_site_name, possibilities);
         }
+        for call_site in crate_data.fn_ptr_call_sites {
+            let cbmc_call_site_name = format!("{}.{}", call_site.function_name, call_site.label);
+            let possibilities =
+                combined_possible_fn_ptrs.get(&call_site.fn_ptr_type).cloned().unwrap_or_default();
+            output.insert(cbmc_call_site_name, possibilities);
+        }
     }
 
     let f = File::create(output_filename)?;
@@ -82,6 +86,22 @@ fn link_function_pointer_restrictions(
     Ok(())
 }
 
+/// Merge the possible targets recorded for each key by every crate, without duplicates.
+fn combine_possibilities<'a, K: Eq + Hash + Clone + 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a [InternedString])>,
+) -> HashMap<K, Vec<InternedString>> {
+    let mut combined: HashMap<K, Vec<InternedString>> = HashMap::new();
+    for (key, possibilities) in entries {
+        let combined_possibilities = combined.entry(key.clone()).or_default();
+        for possibility in possibilities {
+            if !combined_possibilities.contains(possibility) {
+                combined_possibilities.push(*possibility);
+            }
+        }
+    }
+    combined
+}
+
 /// Deserialize a json file into a given structure
 pub fn from_json<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T> {
     let file = File::open(path)?;
@@ -97,6 +117,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         proof_harnesses: vec![],
         unsupported_features: vec![],
         test_harnesses: vec![],
+        unsafe_items: vec![],
     };
     for md in files {
         // Note that we're taking ownership of the original vec, and so we can move the data into the new data structure.
@@ -105,6 +126,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         // https://github.com/model-checking/kani/issues/1758
         result.unsupported_features.extend(md.unsupported_features);
         result.test_harnesses.extend(md.test_harnesses);
+        result.unsafe_items.extend(md.unsafe_items);
     }
     result
 }
@@ -114,6 +136,30 @@ impl KaniSession {
     pub fn determine_targets<'a>(
         &self,
         all_harnesses: &[&'a HarnessMetadata],
+    ) -> Result<Vec<&'a HarnessMetadata>> {
+        let targets = self.determine_harness_targets(all_harnesses)?;
+        if self.args.contracts.is_empty() {
+            return Ok(targets);
+        }
+        for contract in &self.args.contracts {
+            if find_contract_harnesses(contract, &targets).is_empty() {
+                bail!(
+                    "No `proof_for_contract` harness checks the contract of `{contract}`.\nPlease add one, or specify the path of a function with a contract."
+                );
+            }
+        }
+        Ok(targets
+            .into_iter()
+            .filter(|harness| {
+                self.args.contracts.iter().any(|contract| checks_contract(harness, contract))
+            })
+            .collect())
+    }
+
+    /// Select the harnesses that match the `--harness` filters, if any.
+    fn determine_harness_targets<'a>(
+        &self,
+        all_harnesses: &[&'a HarnessMetadata],
     ) -> Result<Vec<&'a HarnessMetadata>> {
         let harnesses = if self.args.harnesses.is_empty() {
             BTreeSet::from_iter(self.args.function.iter())
@@ -184,6 +230,12 @@ pub fn mock_proof_harness(
         original_end_line: 0,
         attributes: HarnessAttributes { unwind_value, proof: true, ..Default::default() },
         goto_file: model_file,
+        doc: None,
+        codegen_time: None,
+        nondet_stats: None,
+        check_span_ends: BTreeMap::new(),
+        reached_unsafe: BTreeSet::new(),
+        trusted_code: BTreeSet::new(),
     }
 }
 
@@ -221,9 +273,28 @@ fn find_proof_harnesses<'a>(
     result
 }
 
+/// Whether the harness checks the contract of the given function. The function matches if it is
+/// the full path of the contract target, or a suffix of it that starts after a `::`.
+fn checks_contract(harness: &HarnessMetadata, function: &str) -> bool {
+    harness.attributes.for_contract.as_deref().is_some_and(|target| {
+        target
+            .strip_suffix(function)
+            .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with("::"))
+    })
+}
+
+/// Find the `proof_for_contract` harnesses that check the contract of the given function.
+fn find_contract_harnesses<'a>(
+    function: &str,
+    all_harnesses: &[&'a HarnessMetadata],
+) -> Vec<&'a HarnessMetadata> {
+    all_harnesses.iter().copied().filter(|harness| checks_contract(harness, function)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use kani_metadata::{FnPtrCallSite, PossibleFnPtrEntry};
 
     #[test]
     fn check_find_proof_harness_without_exact() {
@@ -268,6 +339,28 @@ fn check_find_proof_harness_without_exact() {
         );
     }
 
+    #[test]
+    fn check_find_contract_harnesses() {
+        let mut div_harness = mock_proof_harness("check_div", None, None, None);
+        div_harness.attributes.for_contract = Some("arith::div".to_string());
+        let mut subdiv_harness = mock_proof_harness("check_subdiv", None, None, None);
+        subdiv_harness.attributes.for_contract = Some("arith::subdiv".to_string());
+        let harnesses =
+            vec![div_harness, subdiv_harness, mock_proof_harness("div", None, None, None)];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+
+        let names = |function| {
+            find_contract_harnesses(function, &ref_harnesses)
+                .iter()
+                .map(|harness| harness.pretty_name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("div"), vec!["check_div"]);
+        assert_eq!(names("arith::div"), vec!["check_div"]);
+        assert_eq!(names("arith::subdiv"), vec!["check_subdiv"]);
+        assert!(names("rem").is_empty());
+    }
+
     #[test]
     fn check_find_proof_harness_with_exact() {
         // Check with exact match
@@ -310,4 +403,46 @@ fn check_find_proof_harness_with_exact() {
             "module::not_check_three"
         );
     }
+
+    #[test]
+    fn check_link_fn_ptr_restrictions() {
+        let crate_a = VtableCtxResults {
+            call_sites: vec![],
+            possible_methods: vec![],
+            fn_ptr_call_sites: vec![FnPtrCallSite {
+                fn_ptr_type: "fn_u32".into(),
+                function_name: "caller".into(),
+                label: "restricted_call_label_1".into(),
+            }],
+            possible_fn_ptrs: vec![PossibleFnPtrEntry {
+                fn_ptr_type: "fn_u32".into(),
+                possibilities: vec!["double".into()],
+            }],
+        };
+        let crate_b = VtableCtxResults {
+            call_sites: vec![],
+            possible_methods: vec![],
+            fn_ptr_call_sites: vec![],
+            possible_fn_ptrs: vec![
+                PossibleFnPtrEntry {
+                    fn_ptr_type: "fn_u32".into(),
+                    possibilities: vec!["double".into(), "triple".into()],
+                },
+                PossibleFnPtrEntry {
+                    fn_ptr_type: "fn_u8".into(),
+                    possibilities: vec!["negate".into()],
+                },
+            ],
+        };
+        let output = tempfile::NamedTempFile::new().unwrap();
+        link_function_pointer_restrictions(vec![crate_a, crate_b], output.path()).unwrap();
+        let linked: HashMap<String, Vec<String>> = from_json(output.path()).unwrap();
+        assert_eq!(
+            linked,
+            HashMap::from([(
+                "caller.restricted_call_label_1".to_string(),
+                vec!["double".to_string(), "triple".to_string()]
+            )])
+        );
+    }
 }
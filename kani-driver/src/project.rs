@@ -16,9 +16,11 @@
 //! allows the rest of the driver to handle a function under verification the same way it handle
 //! other harnesses.
 
+use crate::args::ExternCrate;
 use crate::metadata::{from_json, merge_kani_metadata, mock_proof_harness};
+use crate::project_cache::ProjectCache;
 use crate::session::KaniSession;
-use crate::util::{crate_name, guess_rlib_name};
+use crate::util::{crate_name, guess_rlib_name, FileLock};
 use anyhow::{Context, Result};
 use kani_metadata::{
     artifact::convert_type, ArtifactType, ArtifactType::*, HarnessMetadata, KaniMetadata,
@@ -27,6 +29,7 @@
 use std::io::BufWriter;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tracing::{debug, trace};
 
 /// This structure represent the project information relevant for verification.
@@ -88,10 +91,14 @@ pub fn get_harness_artifact(
         let expected_path = if self.merged_artifacts {
             None
         } else {
-            harness
-                .goto_file
-                .as_ref()
-                .and_then(|goto_file| convert_type(goto_file, SymTabGoto, typ).canonicalize().ok())
+            harness.goto_file.as_ref().and_then(|goto_file| {
+                let path = if typ == Goto {
+                    session_goto_path(goto_file)
+                } else {
+                    convert_type(goto_file, SymTabGoto, typ)
+                };
+                path.canonicalize().ok()
+            })
         };
         trace!(?harness.goto_file, ?expected_path, ?typ, "get_harness_artifact");
         self.artifacts.iter().find(|artifact| {
@@ -104,7 +111,7 @@ pub fn get_harness_artifact(
     ///
     /// This method will parse the metadata in order to gather all artifacts generated by the
     /// compiler.
-    fn try_new(
+    pub fn try_new(
         session: &KaniSession,
         outdir: PathBuf,
         metadata: Vec<KaniMetadata>,
@@ -122,9 +129,10 @@ fn try_new(
                     harness_metadata.goto_file.as_ref().expect("Expected a model file"),
                     SymTabGoto,
                 )?;
-                let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
+                let goto_path = session_goto_path(&symtab_out.path);
 
                 // Link
+                session.record_temporary_file(&goto_path);
                 session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
                 let goto = Artifact::try_new(&goto_path, Goto)?;
 
@@ -208,7 +216,15 @@ fn dump_metadata(metadata: &KaniMetadata, path: &Path) {
 /// Generate a project using `cargo`.
 /// Accept a boolean to build as many targets as possible. The number of failures in that case can
 /// be collected from the project.
+///
+/// With `-Z project-cache`, the project is cached, so it is only built again if its sources or the
+/// build configuration changed. See [crate::project_cache] for more details.
 pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project> {
+    let cache = ProjectCache::new(session);
+    if let Some(project) = cache.as_ref().and_then(|cache| cache.load(session, keep_going)) {
+        return Ok(project);
+    }
+    let build_start = SystemTime::now();
     let outputs = session.cargo_build(keep_going)?;
     let outdir = outputs.outdir.canonicalize()?;
     if session.args.function.is_some() {
@@ -218,7 +234,7 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
         // Merge goto files.
         // https://github.com/model-checking/kani/issues/2129
         let joined_name = "cbmc-linked";
-        let base_name = outdir.join(joined_name);
+        let base_name = outdir.join(format!("{joined_name}-{}", std::process::id()));
         let goto = base_name.with_extension(Goto);
         let all_gotos = outputs
             .metadata
@@ -240,6 +256,7 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
         );
         let metadata_file = base_name.with_extension(Metadata);
         dump_metadata(&metadata, &metadata_file);
+        session.record_temporary_files(&[&goto, &metadata_file]);
         artifacts.push(goto_artifact);
         artifacts.push(Artifact::try_new(&metadata_file, Metadata)?);
 
@@ -258,19 +275,38 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
             .iter()
             .map(|md_file| from_json(md_file))
             .collect::<Result<Vec<_>>>()?;
-        Project::try_new(
+        let project = Project::try_new(
             session,
             outdir,
             metadata,
             Some(outputs.cargo_metadata),
             outputs.failed_targets,
-        )
+        )?;
+        if let Some(cache) = cache {
+            cache.store(session, &project, &outputs.metadata, &outputs.diagnostics, build_start);
+        }
+        Ok(project)
     }
 }
 
-/// Generate a project directly using `kani-compiler` on a single crate.
-pub fn standalone_project(input: &Path, session: &KaniSession) -> Result<Project> {
-    StandaloneProjectBuilder::try_new(input, session)?.build()
+/// Return the path of the goto binary that this session links and instruments for the given model.
+///
+/// Every session works on its own copy of the goto binary, since the instrumentation modifies it
+/// in place. This allows concurrent sessions to verify the same harnesses.
+fn session_goto_path(symtab_goto: &Path) -> PathBuf {
+    let goto = convert_type(symtab_goto, SymTabGoto, Goto);
+    let extension = goto.extension().unwrap().to_str().unwrap();
+    goto.with_extension(format!("{}.{extension}", std::process::id()))
+}
+
+/// Generate a project directly using `kani-compiler` on a single crate, which may depend on the
+/// given pre-built crates.
+pub fn standalone_project(
+    input: &Path,
+    externs: &[ExternCrate],
+    session: &KaniSession,
+) -> Result<Project> {
+    StandaloneProjectBuilder::try_new(input, externs, session)?.build()
 }
 
 /// Builder for a standalone project.
@@ -283,6 +319,8 @@ struct StandaloneProjectBuilder<'a> {
     input: PathBuf,
     /// The crate name.
     crate_name: String,
+    /// The pre-built dependencies of the crate.
+    externs: &'a [ExternCrate],
     /// The Kani session.
     session: &'a KaniSession,
 }
@@ -290,7 +328,7 @@ struct StandaloneProjectBuilder<'a> {
 impl<'a> StandaloneProjectBuilder<'a> {
     /// Create a `StandaloneProjectBuilder` from the given input and session.
     /// This will perform a few validations before the build.
-    fn try_new(input: &Path, session: &'a KaniSession) -> Result<Self> {
+    fn try_new(input: &Path, externs: &'a [ExternCrate], session: &'a KaniSession) -> Result<Self> {
         // Ensure the directory exist and it's in its canonical form.
         let outdir = if let Some(target_dir) = &session.args.target_dir {
             std::fs::create_dir_all(target_dir)?; // This is a no-op if directory exists.
@@ -305,12 +343,24 @@ fn try_new(input: &Path, session: &'a KaniSession) -> Result<Self> {
             metadata,
             input: input.to_path_buf(),
             crate_name,
+            externs,
             session,
         })
     }
 
+    /// Path of the lock file that protects the compiler outputs of this crate.
+    ///
+    /// The lock is kept next to the outputs, and it is removed once the build is done so we don't
+    /// leave it next to the user's files.
+    fn lock_path(&self) -> PathBuf {
+        self.outdir.join(format!("{}.kani.lock", self.crate_name))
+    }
+
     /// Build a project by compiling `self.input` file.
     fn build(self) -> Result<Project> {
+        // Hold the lock until the project has linked its own copy of the models.
+        let _lock = FileLock::temporary(&self.lock_path())?;
+
         // Register artifacts that may be generated by the compiler / linker for future deletion.
         let rlib_path = guess_rlib_name(&self.outdir.join(self.input.file_name().unwrap()));
         self.session.record_temporary_file(&rlib_path);
@@ -318,7 +368,12 @@ fn build(self) -> Result<Project> {
 
         // Build and link the artifacts.
         debug!(krate=?self.crate_name, input=?self.input, ?rlib_path, "build compile");
-        self.session.compile_single_rust_file(&self.input, &self.crate_name, &self.outdir)?;
+        self.session.compile_single_rust_file(
+            &self.input,
+            &self.crate_name,
+            &self.outdir,
+            self.externs,
+        )?;
 
         let metadata = if let Ok(goto_model) = Artifact::try_from(&self.metadata, SymTabGoto) {
             metadata_with_function(
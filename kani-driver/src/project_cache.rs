@@ -0,0 +1,511 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Cache of the project model built by [crate::project::cargo_project] (`-Z project-cache`).
+//!
+//! Building a cargo project involves calling `cargo metadata` and one `cargo rustc` per target,
+//! and then parsing the metadata of every crate. Even when cargo has nothing to compile, this
+//! is a noticeable cost for subcommands that are invoked repeatedly on the same package.
+//!
+//! The cache stores the cargo metadata and the Kani metadata of the project, together with a
+//! fingerprint of everything that may change them:
+//!  - The Kani version, the compiler and the arguments that affect the build.
+//!  - The environment variables that configure cargo, rustc and Kani, i.e. the ones whose name
+//!    starts with `CARGO`, `RUST` or `KANI`, and the ones read by the code with `env!` and
+//!    `option_env!`.
+//!  - The cargo configuration files, i.e. `.cargo/config.toml` in the current directory and its
+//!    parents, and in `CARGO_HOME`.
+//!  - The `Cargo.lock` and the manifests and sources of every local package, i.e. the ones that
+//!    are not downloaded from a registry or a git repository.
+//!  - The files read by the compiler, as listed in the dep-info file of each crate. This includes
+//!    the files read with `include!` and similar macros, even outside of the package.
+//!  - The metadata files generated by the compiler, since another build could overwrite them.
+//!
+//! The inputs of build scripts are unknown, so projects whose local packages have a build script
+//! are not cached. The build scripts of dependencies are assumed to only depend on their sources.
+//!
+//! The cache lives in the `kani` folder of the target directory, next to the build that it
+//! describes. It is only used if the fingerprint still matches, and it is bypassed by
+//! `--force-build`. The warnings of the compiler are stored too, so they are shown again when the
+//! project is loaded from the cache, like cargo does for the crates it doesn't rebuild.
+
+use crate::call_cargo::print_msg;
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util::{message, FileLock};
+use anyhow::{bail, Context, Result};
+use cargo_metadata::diagnostic::Diagnostic;
+use cargo_metadata::{Metadata, MetadataCommand};
+use kani_metadata::{KaniMetadata, UnstableFeature};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// The prefixes of the environment variables that configure the build.
+const BUILD_ENV_PREFIXES: [&str; 3] = ["CARGO", "RUST", "KANI"];
+
+/// The content of a cache file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedProject {
+    /// The fingerprint of the configuration and the sources of the project.
+    fingerprint: u64,
+    /// The inputs of the crates of the project that are not part of their packages.
+    inputs: CrateInputs,
+    /// The directory where the compiler outputs were written to.
+    outdir: PathBuf,
+    /// The metadata files generated by the compiler, and their modification time.
+    metadata_files: Vec<(PathBuf, SystemTime)>,
+    /// The diagnostics of the compiler other than errors, e.g. warnings.
+    diagnostics: Vec<Diagnostic>,
+    /// The metadata of each crate in the project.
+    metadata: Vec<KaniMetadata>,
+    /// The cargo metadata of the project.
+    cargo_metadata: Metadata,
+}
+
+/// The inputs of a crate that its dep-info file lists.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct CrateInputs {
+    /// The files read by the compiler.
+    files: BTreeSet<PathBuf>,
+    /// The environment variables read with `env!` and `option_env!`.
+    env: BTreeSet<String>,
+}
+
+/// The cache of the project of a Kani session.
+pub struct ProjectCache {
+    /// The file where the project is cached.
+    path: PathBuf,
+    /// The lock on the build outputs of the project.
+    lock: PathBuf,
+    /// The hash of everything in the session that affects the build.
+    config: u64,
+}
+
+impl ProjectCache {
+    /// Create the cache for the project of the given session, if it can be cached.
+    ///
+    /// The cache is only used with `-Z project-cache`. The projects built with `--function` are
+    /// not cached, since their artifacts are merged into files that only live as long as the
+    /// session.
+    pub fn new(session: &KaniSession) -> Option<ProjectCache> {
+        let enabled =
+            session.args.common_args.unstable_features.contains(UnstableFeature::ProjectCache);
+        if !enabled || session.args.force_build || session.args.function.is_some() {
+            return None;
+        }
+        // Unlike the full metadata, finding the target directory doesn't resolve dependencies.
+        let mut cmd = MetadataCommand::new();
+        cmd.no_deps();
+        if let Some(path) = &session.args.cargo.manifest_path {
+            cmd.manifest_path(path);
+        }
+        let target_dir = session.cargo_target_dir(&cmd.exec().ok()?);
+        let cwd = std::env::current_dir().ok()?.canonicalize().ok()?;
+        let manifest =
+            session.args.cargo.manifest_path.as_ref().and_then(|p| p.canonicalize().ok());
+        let mut hasher = DefaultHasher::new();
+        (cwd, manifest).hash(&mut hasher);
+        let path = target_dir.join(format!("project-cache-{:016x}.json", hasher.finish()));
+        // The lock lives outside of the build directory since `--force-build` deletes it.
+        let lock = target_dir.with_extension("lock");
+        Some(ProjectCache { path, lock, config: build_config_hash(session) })
+    }
+
+    /// Load the cached project if the cache is still up to date.
+    ///
+    /// Any problem reading the cache is treated as a miss, so the project is simply built again.
+    pub fn load(&self, session: &KaniSession, keep_going: bool) -> Option<Project> {
+        match self.try_load(session, keep_going) {
+            Ok(project) => {
+                if session.args.common_args.verbose {
+                    message!(
+                        "Using the cached build of the project. Use `--force-build` to build it \
+                        again."
+                    );
+                }
+                Some(project)
+            }
+            Err(error) => {
+                debug!(?error, cache=?self.path, "project cache miss");
+                None
+            }
+        }
+    }
+
+    fn try_load(&self, session: &KaniSession, keep_going: bool) -> Result<Project> {
+        // Hold the same lock as the build while the project links its copy of the models.
+        let _lock = FileLock::exclusive(&self.lock)?;
+        let reader = BufReader::new(File::open(&self.path)?);
+        let cached: CachedProject = serde_json::from_reader(reader)?;
+        check_unmodified(&cached.metadata_files)?;
+        let files = self.tracked_files(session, &cached.cargo_metadata, &cached.inputs)?;
+        let (fingerprint, _) = fingerprint(self.config, &files, &cached.inputs.env);
+        if fingerprint != cached.fingerprint {
+            bail!("the project changed since it was cached");
+        }
+        if !session.args.common_args.quiet {
+            let support_color = std::io::stdout().is_terminal();
+            for diagnostic in &cached.diagnostics {
+                print_msg(diagnostic, support_color)?;
+            }
+        }
+        Project::try_new(
+            session,
+            cached.outdir,
+            cached.metadata,
+            Some(cached.cargo_metadata),
+            keep_going.then_some(vec![]),
+        )
+    }
+
+    /// Store the given project, built from the sources as they were at `build_start`.
+    ///
+    /// Projects with targets that failed to compile are not cached, so their errors are reported
+    /// again. Neither are projects whose sources changed while they were being built, since the
+    /// build may not reflect those changes.
+    ///
+    /// The caller must hold the lock on the build outputs, which also protects the cache file.
+    pub fn store(
+        &self,
+        session: &KaniSession,
+        project: &Project,
+        metadata_files: &[impl AsRef<Path>],
+        diagnostics: &[Diagnostic],
+        build_start: SystemTime,
+    ) {
+        if let Err(error) =
+            self.try_store(session, project, metadata_files, diagnostics, build_start)
+        {
+            debug!(?error, cache=?self.path, "failed to store the project cache");
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    fn try_store(
+        &self,
+        session: &KaniSession,
+        project: &Project,
+        metadata_files: &[impl AsRef<Path>],
+        diagnostics: &[Diagnostic],
+        build_start: SystemTime,
+    ) -> Result<()> {
+        if project.failed_targets.as_ref().is_some_and(|failed| !failed.is_empty()) {
+            bail!("some targets failed to compile");
+        }
+        let cargo_metadata = project.cargo_metadata.clone().context("missing cargo metadata")?;
+        let mut local_packages = cargo_metadata.packages.iter().filter(|pkg| pkg.source.is_none());
+        if let Some(package) =
+            local_packages.find(|pkg| pkg.targets.iter().any(|target| target.is_custom_build()))
+        {
+            bail!("the inputs of the build script of `{}` are unknown", package.name);
+        }
+        let mut inputs = CrateInputs::default();
+        for file in metadata_files {
+            // E.g.: `foo-<hash>.kani-metadata.json` is next to `foo-<hash>.d`.
+            let dep_info = file.as_ref().with_extension("").with_extension("d");
+            let content = fs::read_to_string(&dep_info)
+                .with_context(|| format!("failed to read `{}`", dep_info.display()))?;
+            let crate_inputs =
+                parse_dep_info(&content, cargo_metadata.workspace_root.as_std_path());
+            inputs.files.extend(crate_inputs.files);
+            inputs.env.extend(crate_inputs.env);
+        }
+        let files = self.tracked_files(session, &cargo_metadata, &inputs)?;
+        let (fingerprint, newest) = fingerprint(self.config, &files, &inputs.env);
+        if newest >= build_start {
+            bail!("the project changed during the build");
+        }
+        let metadata_files = metadata_files
+            .iter()
+            .map(|file| {
+                let file = file.as_ref();
+                Ok((file.to_path_buf(), fs::metadata(file)?.modified()?))
+            })
+            .collect::<Result<_>>()?;
+        let cached = CachedProject {
+            fingerprint,
+            inputs,
+            outdir: project.outdir.clone(),
+            metadata_files,
+            diagnostics: diagnostics.to_vec(),
+            metadata: project.metadata.clone(),
+            cargo_metadata,
+        };
+        // Write to a temporary file first, so a session that is interrupted doesn't leave a
+        // truncated cache behind.
+        let tmp = self.path.with_extension("json.tmp");
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        serde_json::to_writer(&mut writer, &cached)?;
+        writer.into_inner()?.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// The files whose content affects the build of the project, sorted and without duplicates.
+    fn tracked_files(
+        &self,
+        session: &KaniSession,
+        metadata: &Metadata,
+        inputs: &CrateInputs,
+    ) -> Result<Vec<PathBuf>> {
+        let root = metadata.workspace_root.as_std_path();
+        let mut files = vec![root.join("Cargo.toml"), root.join("Cargo.lock")];
+        files.extend(cargo_config_files(&std::env::current_dir()?, cargo_home().as_deref()));
+        // Kani's outputs are inside of a `kani` folder of the target directory.
+        let target_dir = session.cargo_target_dir(metadata);
+        let target_dir = target_dir.parent().and_then(|dir| dir.canonicalize().ok());
+        let mut skip = vec![metadata.target_directory.as_std_path()];
+        skip.extend(target_dir.as_deref());
+        for package in metadata.packages.iter().filter(|package| package.source.is_none()) {
+            if let Some(dir) = package.manifest_path.parent() {
+                collect_files(dir.as_std_path(), &skip, &mut files)?;
+            }
+        }
+        files.extend(inputs.files.iter().cloned());
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+}
+
+/// Compute the fingerprint of the given build configuration, files and environment variables.
+/// Return it together with the modification time of the newest file.
+fn fingerprint(config: u64, files: &[PathBuf], env: &BTreeSet<String>) -> (u64, SystemTime) {
+    let mut hasher = DefaultHasher::new();
+    config.hash(&mut hasher);
+    let mut newest = SystemTime::UNIX_EPOCH;
+    for file in files {
+        // Missing files, e.g. the lock file of a new project, are part of the fingerprint too.
+        let modified = fs::metadata(file).and_then(|metadata| metadata.modified()).ok();
+        newest = newest.max(modified.unwrap_or(SystemTime::UNIX_EPOCH));
+        (file, modified).hash(&mut hasher);
+    }
+    for var in env {
+        (var, std::env::var_os(var)).hash(&mut hasher);
+    }
+    (hasher.finish(), newest)
+}
+
+/// Check that the given files were not modified since they were cached.
+fn check_unmodified(files: &[(PathBuf, SystemTime)]) -> Result<()> {
+    for (file, modified) in files {
+        if fs::metadata(file)?.modified()? != *modified {
+            bail!("`{}` was modified", file.display());
+        }
+    }
+    Ok(())
+}
+
+/// Hash everything in the session that affects the build of the project.
+fn build_config_hash(session: &KaniSession) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    session.kani_compiler.hash(&mut hasher);
+    fs::metadata(&session.kani_compiler).and_then(|md| md.modified()).ok().hash(&mut hasher);
+    session.kani_rustc_flags().hash(&mut hasher);
+    session.kani_compiler_flags().hash(&mut hasher);
+    session.reachability_arg().hash(&mut hasher);
+    session.args.tests.hash(&mut hasher);
+    session.args.target_dir.hash(&mut hasher);
+    format!("{:?}", session.args.cargo).hash(&mut hasher);
+    format!("{:?}", session.args.target).hash(&mut hasher);
+    build_env(std::env::vars_os()).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The environment variables that configure cargo, rustc or Kani, e.g. `RUSTFLAGS`,
+/// `CARGO_TARGET_DIR` or `KANIFLAGS`, sorted by name.
+fn build_env(vars: impl Iterator<Item = (OsString, OsString)>) -> Vec<(OsString, OsString)> {
+    let mut vars: Vec<_> = vars
+        .filter(|(name, _)| {
+            let name = name.to_string_lossy();
+            BUILD_ENV_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    vars.sort();
+    vars
+}
+
+/// The home directory of cargo, which contains its user configuration.
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+}
+
+/// The configuration files that cargo reads when it's invoked from `cwd`, whether they exist or
+/// not, so creating one changes the fingerprint.
+fn cargo_config_files(cwd: &Path, cargo_home: Option<&Path>) -> Vec<PathBuf> {
+    let dirs =
+        cwd.ancestors().map(|dir| dir.join(".cargo")).chain(cargo_home.map(Path::to_path_buf));
+    dirs.flat_map(|dir| [dir.join("config.toml"), dir.join("config")]).collect()
+}
+
+/// Parse the inputs listed by a dep-info file, which follows the Makefile syntax:
+/// ```text
+/// /path/to/foo-<hash>.d: src/lib.rs src/generated.rs
+///
+/// src/lib.rs:
+/// src/generated.rs:
+///
+/// # env-dep:FOO_VERSION=1.0
+/// ```
+/// Relative paths are resolved against `root`, the directory where cargo runs the compiler.
+fn parse_dep_info(content: &str, root: &Path) -> CrateInputs {
+    let mut inputs = CrateInputs::default();
+    for line in content.lines() {
+        if let Some(var) = line.strip_prefix("# env-dep:") {
+            inputs.env.insert(var.split('=').next().unwrap_or(var).to_string());
+        } else if let Some((_, deps)) = line.split_once(": ") {
+            // Spaces in paths are escaped with a backslash.
+            let deps = deps.replace("\\ ", "\0");
+            inputs
+                .files
+                .extend(deps.split_whitespace().map(|dep| root.join(dep.replace('\0', " "))));
+        }
+    }
+    inputs
+}
+
+/// Collect all the files inside the given directory, except the ones in hidden directories and
+/// in the directories to skip, e.g. the target directory.
+fn collect_files(dir: &Path, skip: &[&Path], files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let hidden =
+                path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if !hidden && !skip.iter().any(|skip| path == *skip) {
+                collect_files(&path, skip, files)?;
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn check_collect_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for subdir in ["src", "target/debug", ".git"] {
+            fs::create_dir_all(root.join(subdir)).unwrap();
+        }
+        for file in ["Cargo.toml", "src/lib.rs", "target/debug/lib.rlib", ".git/HEAD"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let mut files = vec![];
+        collect_files(root, &[&root.join("target")], &mut files).unwrap();
+        files.sort();
+        assert_eq!(files, vec![root.join("Cargo.toml"), root.join("src/lib.rs")]);
+    }
+
+    #[test]
+    fn check_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("lib.rs");
+        let lock = dir.path().join("Cargo.lock");
+        fs::write(&lib, "").unwrap();
+        let files = vec![lib.clone(), lock.clone()];
+        let env = BTreeSet::from(["KANI_PROJECT_CACHE_TEST_VAR".to_string()]);
+        let (cached, _) = fingerprint(1, &files, &env);
+
+        // Hit: nothing changed.
+        assert_eq!(fingerprint(1, &files, &env).0, cached);
+        // Miss: the build configuration changed.
+        assert_ne!(fingerprint(2, &files, &env).0, cached);
+        // Miss: a file that didn't exist was created.
+        fs::write(&lock, "").unwrap();
+        let (with_lock, _) = fingerprint(1, &files, &env);
+        assert_ne!(with_lock, cached);
+        // Invalidation: a file was modified.
+        std::thread::sleep(Duration::from_millis(10));
+        fs::write(&lib, "fn foo() {}").unwrap();
+        let (modified, newest) = fingerprint(1, &files, &env);
+        assert_ne!(modified, with_lock);
+        assert_eq!(newest, fs::metadata(&lib).unwrap().modified().unwrap());
+        // Invalidation: an environment variable read by the code changed.
+        std::env::set_var("KANI_PROJECT_CACHE_TEST_VAR", "1");
+        assert_ne!(fingerprint(1, &files, &env).0, modified);
+        std::env::remove_var("KANI_PROJECT_CACHE_TEST_VAR");
+        assert_eq!(fingerprint(1, &files, &env).0, modified);
+    }
+
+    #[test]
+    fn check_unmodified_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.kani-metadata.json");
+        fs::write(&file, "{}").unwrap();
+        let modified = fs::metadata(&file).unwrap().modified().unwrap();
+        assert!(check_unmodified(&[(file.clone(), modified)]).is_ok());
+        let earlier = modified - Duration::from_secs(60);
+        assert!(check_unmodified(&[(file.clone(), earlier)]).is_err());
+        fs::remove_file(&file).unwrap();
+        assert!(check_unmodified(&[(file, modified)]).is_err());
+    }
+
+    #[test]
+    fn check_parse_dep_info() {
+        let content = "/target/deps/foo-123.d: src/lib.rs /abs/gen\\ erated.rs\n\
+            \n\
+            src/lib.rs:\n\
+            /abs/gen\\ erated.rs:\n\
+            \n\
+            # env-dep:FOO_VERSION=1.0\n\
+            # env-dep:FOO_OPTIONAL\n";
+        let inputs = parse_dep_info(content, Path::new("/ws"));
+        assert_eq!(
+            inputs.files,
+            BTreeSet::from([PathBuf::from("/ws/src/lib.rs"), PathBuf::from("/abs/gen erated.rs")])
+        );
+        assert_eq!(
+            inputs.env,
+            BTreeSet::from(["FOO_OPTIONAL".to_string(), "FOO_VERSION".to_string()])
+        );
+    }
+
+    #[test]
+    fn check_build_env() {
+        let vars = [
+            ("RUSTFLAGS", "--cfg foo"),
+            ("PATH", "/bin"),
+            ("KANIFLAGS", "-v"),
+            ("CARGO_HOME", "/c"),
+        ]
+        .map(|(name, value)| (OsString::from(name), OsString::from(value)));
+        let names: Vec<_> = build_env(vars.into_iter()).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["CARGO_HOME", "KANIFLAGS", "RUSTFLAGS"]);
+    }
+
+    #[test]
+    fn check_cargo_config_files() {
+        let files = cargo_config_files(Path::new("/ws/pkg"), Some(Path::new("/home/.cargo")));
+        assert_eq!(
+            files,
+            [
+                "/ws/pkg/.cargo/config.toml",
+                "/ws/pkg/.cargo/config",
+                "/ws/.cargo/config.toml",
+                "/ws/.cargo/config",
+                "/.cargo/config.toml",
+                "/.cargo/config",
+                "/home/.cargo/config.toml",
+                "/home/.cargo/config",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+}
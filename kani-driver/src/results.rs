@@ -0,0 +1,363 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the results file written by every verification run, and the `results query`
+//! subcommand that inspects it without running verification again.
+//!
+//! The format of the results file is defined by the `kani-results` crate.
+
+use crate::args::results_args::{QueryArgs, QueryFormat, ResultsArgs, ResultsSubcommand};
+use crate::args::OutputDetail;
+use crate::call_cbmc;
+use crate::cbmc_output_parser::{self, Property, TraceItem};
+use crate::harness_runner::HarnessResult;
+use crate::metadata::from_json;
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::trends;
+use anyhow::{Context, Result};
+use comfy_table::Table;
+use kani_results::{
+    CheckResult, CheckStatus, HarnessResults, SourceLocation, Summary, TraceStep,
+    VerificationResults, VerificationStatus,
+};
+use rustc_demangle::demangle;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// The name of the file where the results of the last verification run are stored.
+const RESULTS_FILE_NAME: &str = "kani-results.json";
+
+pub(crate) fn harness_results(harness_result: &HarnessResult) -> HarnessResults {
+    let properties = harness_result.result.results.as_deref().unwrap_or_default();
+    let checks: Vec<CheckResult> = properties.iter().map(check_result).collect();
+    HarnessResults {
+        name: harness_result.harness.pretty_name.clone(),
+        doc: harness_result.harness.doc.clone(),
+        status: match harness_result.result.status {
+            call_cbmc::VerificationStatus::Success => VerificationStatus::Success,
+            call_cbmc::VerificationStatus::Failure => VerificationStatus::Failure,
+        },
+        summary: summary(properties),
+        checks,
+    }
+}
+
+/// Count the checks in each status, leaving out cover properties and assumptions like the
+/// summary printed after verification does.
+fn summary(properties: &[Property]) -> Summary {
+    let mut summary = Summary::default();
+    let checks = properties.iter().filter(|prop| {
+        !prop.is_cover_property()
+            && !prop.is_code_coverage_property()
+            && !prop.is_assumption_property()
+            && !prop.is_assumption_check_property()
+    });
+    for check in checks {
+        summary.total += 1;
+        match check.status {
+            cbmc_output_parser::CheckStatus::Failure => summary.failed += 1,
+            cbmc_output_parser::CheckStatus::Undetermined => summary.undetermined += 1,
+            cbmc_output_parser::CheckStatus::Unreachable => summary.unreachable += 1,
+            _ => {}
+        }
+    }
+    summary
+}
+
+pub(crate) fn check_result(prop: &Property) -> CheckResult {
+    CheckResult {
+        name: prop.property_name(),
+        class: prop.property_class(),
+        description: prop.description.clone(),
+        status: check_status(prop.status),
+        location: source_location(&prop.source_location),
+        trace: prop.trace.as_ref().map(|trace| trace.iter().map(trace_step).collect()),
+    }
+}
+
+fn check_status(status: cbmc_output_parser::CheckStatus) -> CheckStatus {
+    match status {
+        cbmc_output_parser::CheckStatus::Failure => CheckStatus::Failure,
+        cbmc_output_parser::CheckStatus::Covered => CheckStatus::Covered,
+        cbmc_output_parser::CheckStatus::Reachable => CheckStatus::Reachable,
+        cbmc_output_parser::CheckStatus::Satisfied => CheckStatus::Satisfied,
+        cbmc_output_parser::CheckStatus::Success => CheckStatus::Success,
+        cbmc_output_parser::CheckStatus::Undetermined => CheckStatus::Undetermined,
+        cbmc_output_parser::CheckStatus::Unreachable => CheckStatus::Unreachable,
+        cbmc_output_parser::CheckStatus::Uncovered => CheckStatus::Uncovered,
+        cbmc_output_parser::CheckStatus::Unsatisfiable => CheckStatus::Unsatisfiable,
+    }
+}
+
+fn source_location(location: &cbmc_output_parser::SourceLocation) -> SourceLocation {
+    SourceLocation {
+        file: location.file.clone(),
+        function: location.function.as_ref().map(|function| format!("{:#}", demangle(function))),
+        line: location.line.as_ref().and_then(|line| line.parse().ok()),
+        column: location.column.as_ref().and_then(|column| column.parse().ok()),
+        end_line: location.end_line.and_then(|line| line.try_into().ok()),
+        end_column: location.end_column.and_then(|column| column.try_into().ok()),
+    }
+}
+
+fn trace_step(item: &TraceItem) -> TraceStep {
+    TraceStep {
+        thread: item.thread,
+        step_type: item.step_type.clone(),
+        hidden: item.hidden,
+        location: item.source_location.as_ref().map(source_location),
+        lhs: item.lhs.clone(),
+        value: item.value.as_ref().and_then(|value| Some(value.data.as_ref()?.to_string())),
+    }
+}
+
+/// Convert the results of a verification run to the format of the results file.
+pub(crate) fn verification_results(results: &[HarnessResult]) -> VerificationResults {
+    VerificationResults::new(results.iter().map(harness_results).collect())
+}
+
+/// Drop the traces that shouldn't be stored in the results file with the given detail level.
+/// Without one, the traces that CBMC produced are kept, since other options asked for them.
+fn retain_traces(results: &mut VerificationResults, detail: Option<OutputDetail>) {
+    let Some(detail) = detail else { return };
+    let checks = results.harnesses.iter_mut().flat_map(|harness| harness.checks.iter_mut());
+    for check in checks {
+        let keep = match detail {
+            OutputDetail::Summary => false,
+            OutputDetail::Failures => check.status == CheckStatus::Failure,
+            OutputDetail::Full => true,
+        };
+        if !keep {
+            check.trace = None;
+        }
+    }
+}
+
+/// Format the results of a verification run as the document printed by `--output-format json`,
+/// which follows the format of the results file.
+pub(crate) fn format_results_json(session: &KaniSession, results: &[HarnessResult]) -> String {
+    let mut verification_results = verification_results(results);
+    retain_traces(&mut verification_results, session.args.output_detail);
+    serde_json::to_string_pretty(&verification_results).unwrap()
+}
+
+/// Where the results of the given project are stored. For `kani`, this is the current directory,
+/// which is where the subcommands look for it, unless the user picked an output directory.
+fn project_results_file(session: &KaniSession, project: &Project) -> PathBuf {
+    match (&project.cargo_metadata, &session.args.target_dir) {
+        (Some(metadata), _) => session.cargo_target_dir(metadata).join(RESULTS_FILE_NAME),
+        (None, Some(_)) => project.outdir.join(RESULTS_FILE_NAME),
+        (None, None) => PathBuf::from(RESULTS_FILE_NAME),
+    }
+}
+
+/// Store the results of a verification run, so they can be queried later, and append them to
+/// the history of runs.
+pub(crate) fn write_results(
+    session: &KaniSession,
+    project: &Project,
+    results: &[HarnessResult],
+) -> Result<()> {
+    let path = project_results_file(session, project);
+    let out_file = File::create(&path)
+        .with_context(|| format!("Failed to create results file `{}`", path.display()))?;
+    let mut verification_results = verification_results(results);
+    retain_traces(&mut verification_results, session.args.output_detail);
+    serde_json::to_writer_pretty(BufWriter::new(out_file), &verification_results)?;
+    trends::record_run(path.parent().unwrap_or(Path::new(".")), results)
+}
+
+/// Execute the `results` subcommand of `cargo kani`.
+pub(crate) fn results_cargo(session: &KaniSession, args: ResultsArgs) -> Result<()> {
+    let ResultsSubcommand::Query(query) = args.command;
+    let path = match &query.results {
+        Some(path) => path.clone(),
+        None => {
+            let metadata = session.cargo_metadata(env!("TARGET"))?;
+            session.cargo_target_dir(&metadata).join(RESULTS_FILE_NAME)
+        }
+    };
+    run_query(&path, &query)
+}
+
+/// Execute the `results` subcommand of `kani`.
+pub(crate) fn results_standalone(args: ResultsArgs) -> Result<()> {
+    let ResultsSubcommand::Query(query) = args.command;
+    let path = query.results.clone().unwrap_or_else(|| PathBuf::from(RESULTS_FILE_NAME));
+    run_query(&path, &query)
+}
+
+fn run_query(path: &Path, query: &QueryArgs) -> Result<()> {
+    let results: VerificationResults = from_json(path).with_context(|| {
+        format!("Failed to read results file `{}`. Run verification first.", path.display())
+    })?;
+    results.check_format_version().with_context(|| {
+        format!("Cannot read results file `{}`. Run verification again.", path.display())
+    })?;
+    let matches: Vec<(&str, &CheckResult)> = results
+        .harnesses
+        .iter()
+        .filter(|harness| query.harness.as_ref().map_or(true, |name| harness.name.contains(name)))
+        .flat_map(|harness| harness.checks.iter().map(|check| (harness.name.as_str(), check)))
+        .filter(|(_, check)| matches_query(check, query))
+        .collect();
+    match query.format {
+        QueryFormat::Json => {
+            let json: Vec<_> = matches
+                .iter()
+                .map(|(harness, check)| serde_json::json!({ "harness": harness, "check": check }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        QueryFormat::Table => {
+            let mut table = Table::new();
+            table.set_header(vec!["Harness", "Check", "Status", "Description", "Location"]);
+            for (harness, check) in &matches {
+                table.add_row(vec![
+                    harness.to_string(),
+                    check.name.clone(),
+                    check.status.to_string(),
+                    check.description.clone(),
+                    format_location(&check.location),
+                ]);
+            }
+            println!("{table}");
+            println!("{} matching checks.", matches.len());
+        }
+    }
+    Ok(())
+}
+
+/// Format a location as `<file>:<line>:<column> in function <function>`, omitting the parts that
+/// are unknown.
+fn format_location(location: &SourceLocation) -> String {
+    let mut formatted = location.file.clone().unwrap_or_else(|| "Unknown file".to_string());
+    if let Some(line) = location.line {
+        formatted.push_str(&format!(":{line}"));
+        if let Some(column) = location.column {
+            formatted.push_str(&format!(":{column}"));
+        }
+    }
+    if let Some(function) = &location.function {
+        formatted.push_str(&format!(" in function {function}"));
+    }
+    formatted
+}
+
+/// Whether a check matches all the filters of the query.
+fn matches_query(check: &CheckResult, query: &QueryArgs) -> bool {
+    let status_matches = query.status.is_empty()
+        || query.status.iter().any(|status| check.status.to_string().eq_ignore_ascii_case(status));
+    let class_matches = query.class.as_ref().map_or(true, |class| check.class.contains(class));
+    let module_matches = query.module.as_ref().map_or(true, |module| {
+        check.location.function.as_ref().is_some_and(|function| in_module(function, module))
+    });
+    status_matches && class_matches && module_matches
+}
+
+/// Whether the function is inside the given module.
+/// E.g.: `my_crate::parser::parse` is inside `parser` and inside `my_crate::parser`.
+fn in_module(function: &str, module: &str) -> bool {
+    let path = format!("::{function}");
+    path.contains(&format!("::{}::", module.trim_start_matches("::")))
+}
+
+/// Results of harnesses to use in the unit tests of the reports generated from them.
+#[cfg(test)]
+pub(crate) mod mock {
+    use kani_results::{
+        CheckResult, CheckStatus, HarnessResults, SourceLocation, Summary, VerificationStatus,
+    };
+
+    /// A check of the given class at a line of `file`, named after both.
+    pub(crate) fn check(class: &str, status: CheckStatus, file: &str, line: u32) -> CheckResult {
+        CheckResult {
+            name: format!("main.{class}.{line}"),
+            class: class.to_string(),
+            description: format!("{class} check"),
+            status,
+            location: SourceLocation {
+                file: Some(file.to_string()),
+                line: Some(line),
+                ..Default::default()
+            },
+            trace: None,
+        }
+    }
+
+    /// A coverage check (`--coverage`) at a line of `file`.
+    pub(crate) fn coverage_check(status: CheckStatus, file: &str, line: u32) -> CheckResult {
+        check("code_coverage", status, file, line)
+    }
+
+    /// The results of a harness named `check`, with an empty summary.
+    pub(crate) fn harness(status: VerificationStatus, checks: Vec<CheckResult>) -> HarnessResults {
+        HarnessResults {
+            name: "check".to_string(),
+            doc: None,
+            status,
+            summary: Summary::default(),
+            checks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_in_module() {
+        assert!(in_module("my_crate::parser::parse", "parser"));
+        assert!(in_module("my_crate::parser::parse", "my_crate::parser"));
+        assert!(in_module("my_crate::parser::inner::parse", "parser"));
+        assert!(!in_module("my_crate::parser_utils::parse", "parser"));
+        assert!(!in_module("my_crate::parser", "parser"));
+    }
+
+    #[test]
+    fn check_source_location() {
+        let location = source_location(&cbmc_output_parser::SourceLocation {
+            column: Some("9".to_string()),
+            end_column: Some(21),
+            end_line: Some(12),
+            file: Some("src/lib.rs".to_string()),
+            function: Some("_ZN8my_crate6parser5parse17h0123456789abcdefE".to_string()),
+            line: Some("12".to_string()),
+        });
+        assert_eq!(location.function.as_deref(), Some("my_crate::parser::parse"));
+        assert_eq!((location.line, location.column), (Some(12), Some(9)));
+        assert_eq!((location.end_line, location.end_column), (Some(12), Some(21)));
+        assert_eq!(
+            format_location(&location),
+            "src/lib.rs:12:9 in function my_crate::parser::parse"
+        );
+    }
+
+    #[test]
+    fn check_retain_traces() {
+        let check = |status| CheckResult {
+            trace: Some(vec![]),
+            ..mock::check("assertion", status, "src/lib.rs", 1)
+        };
+        let results = VerificationResults::new(vec![mock::harness(
+            VerificationStatus::Failure,
+            vec![check(CheckStatus::Failure), check(CheckStatus::Satisfied)],
+        )]);
+        let traces = |detail| {
+            let mut results = results.clone();
+            retain_traces(&mut results, detail);
+            results.harnesses[0]
+                .checks
+                .iter()
+                .map(|check| check.trace.is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(traces(None), [true, true]);
+        assert_eq!(traces(Some(OutputDetail::Summary)), [false, false]);
+        assert_eq!(traces(Some(OutputDetail::Failures)), [true, false]);
+        assert_eq!(traces(Some(OutputDetail::Full)), [true, true]);
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the SARIF report written with `--sarif <FILE>`.
+//!
+//! [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) is the format used by
+//! GitHub code scanning and by IDE extensions, e.g. the SARIF viewer of VS Code, to show the
+//! results of static analysis tools inline with the code. The report has one result for each
+//! failed or undetermined check, and one rule for each property class, e.g. `arithmetic_overflow`.
+
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::results;
+use anyhow::{Context, Result};
+use kani_results::{CheckResult, CheckStatus, HarnessResults};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The base that relative artifact locations are resolved against, i.e. the project root.
+const SOURCE_ROOT: &str = "%SRCROOT%";
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    original_uri_base_ids: BTreeMap<&'static str, ArtifactLocation>,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+/// A rule groups the checks of a property class.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    /// `error` for failed checks and `warning` for undetermined ones.
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    properties: ResultProperties,
+}
+
+/// Kani specific information about a result.
+#[derive(Debug, Serialize)]
+struct ResultProperties {
+    harness: String,
+    check: String,
+    status: CheckStatus,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+/// Write the SARIF report of the given results to `path`.
+pub(crate) fn write_sarif(
+    project: &Project,
+    harness_results: &[HarnessResult],
+    path: &Path,
+) -> Result<()> {
+    let root = match &project.cargo_metadata {
+        Some(metadata) => metadata.workspace_root.clone().into_std_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let harnesses: Vec<_> = harness_results.iter().map(results::harness_results).collect();
+    let log = sarif_log(&harnesses, &root);
+    std::fs::write(path, serde_json::to_string_pretty(&log)?)
+        .with_context(|| format!("Failed to write SARIF report `{}`", path.display()))
+}
+
+fn sarif_log(harnesses: &[HarnessResults], root: &Path) -> SarifLog {
+    let mut rules: Vec<Rule> = vec![];
+    let mut results = vec![];
+    for harness in harnesses {
+        for check in &harness.checks {
+            let level = match check.status {
+                CheckStatus::Failure => "error",
+                CheckStatus::Undetermined => "warning",
+                _ => continue,
+            };
+            let rule_index =
+                rules.iter().position(|rule| rule.id == check.class).unwrap_or_else(|| {
+                    rules.push(Rule {
+                        id: check.class.clone(),
+                        short_description: Message {
+                            text: format!("Kani `{}` checks", check.class),
+                        },
+                    });
+                    rules.len() - 1
+                });
+            results.push(SarifResult {
+                rule_id: check.class.clone(),
+                rule_index,
+                level,
+                message: Message {
+                    text: format!("{} (harness `{}`)", check.description, harness.name),
+                },
+                locations: vec![location(check, root)],
+                properties: ResultProperties {
+                    harness: harness.name.clone(),
+                    check: check.name.clone(),
+                    status: check.status,
+                },
+            });
+        }
+    }
+    let root_uri = format!("file://{}/", root.display());
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: ToolComponent {
+                    name: "Kani",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: "https://github.com/model-checking/kani",
+                    rules,
+                },
+            },
+            original_uri_base_ids: BTreeMap::from([(
+                SOURCE_ROOT,
+                ArtifactLocation { uri: root_uri, uri_base_id: None },
+            )]),
+            results,
+        }],
+    }
+}
+
+/// The location of a check. Files inside `root` are made relative to it, so code scanning can
+/// match them with the files of the repository.
+fn location(check: &CheckResult, root: &Path) -> Location {
+    let physical_location = check.location.file.as_ref().map(|file| {
+        let path = Path::new(file);
+        let artifact_location = match path.strip_prefix(root) {
+            Ok(relative) => ArtifactLocation {
+                uri: relative.to_string_lossy().into_owned(),
+                uri_base_id: Some(SOURCE_ROOT),
+            },
+            Err(_) if path.is_relative() => {
+                ArtifactLocation { uri: file.clone(), uri_base_id: Some(SOURCE_ROOT) }
+            }
+            Err(_) => ArtifactLocation { uri: format!("file://{file}"), uri_base_id: None },
+        };
+        let region = check.location.line.map(|start_line| Region {
+            start_line,
+            start_column: check.location.column,
+            end_line: check.location.end_line,
+            end_column: check.location.end_column,
+        });
+        PhysicalLocation { artifact_location, region }
+    });
+    let logical_locations = check
+        .location
+        .function
+        .iter()
+        .map(|function| LogicalLocation {
+            fully_qualified_name: function.clone(),
+            kind: "function",
+        })
+        .collect();
+    Location { physical_location, logical_locations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::mock;
+    use kani_results::{Summary, VerificationStatus};
+
+    /// A check with a span and a function, which the SARIF locations include.
+    fn mock_check(class: &str, status: CheckStatus, file: &str) -> CheckResult {
+        let mut check = mock::check(class, status, file, 3);
+        check.location.function = Some("crate::main".to_string());
+        check.location.column = Some(5);
+        check.location.end_line = Some(3);
+        check.location.end_column = Some(10);
+        check
+    }
+
+    #[test]
+    fn check_sarif_log() {
+        let harness = HarnessResults {
+            name: "check_a".to_string(),
+            summary: Summary { total: 4, failed: 2, undetermined: 1, ..Default::default() },
+            ..mock::harness(
+                VerificationStatus::Failure,
+                vec![
+                    mock_check("assertion", CheckStatus::Success, "src/lib.rs"),
+                    mock_check("arithmetic_overflow", CheckStatus::Failure, "/ws/src/lib.rs"),
+                    mock_check("pointer_dereference", CheckStatus::Undetermined, "src/main.rs"),
+                    mock_check("arithmetic_overflow", CheckStatus::Failure, "/rust/lib.rs"),
+                ],
+            )
+        };
+        let log = serde_json::to_value(sarif_log(&[harness], Path::new("/ws"))).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["originalUriBaseIds"]["%SRCROOT%"]["uri"], "file:///ws/");
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let rule_ids: Vec<_> = rules.iter().map(|rule| rule["id"].as_str().unwrap()).collect();
+        assert_eq!(rule_ids, ["arithmetic_overflow", "pointer_dereference"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "arithmetic_overflow");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "arithmetic_overflow check (harness `check_a`)");
+        let physical = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(physical["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(physical["region"]["startLine"], 3);
+        assert_eq!(physical["region"]["endColumn"], 10);
+        assert_eq!(
+            results[0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "crate::main"
+        );
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[1]["properties"]["status"], "undetermined");
+        let external = &results[2]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(external["uri"], "file:///rust/lib.rs");
+        assert!(external.get("uriBaseId").is_none());
+        assert_eq!(results[2]["ruleIndex"], 0);
+    }
+}
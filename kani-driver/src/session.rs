@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::args::common::Verbosity;
-use crate::args::VerificationArgs;
-use crate::util::render_command;
+use crate::args::{CleanupPolicy, VerificationArgs};
+use crate::util::{self, message, render_command};
 use anyhow::{bail, Context, Result};
 use std::io::IsTerminal;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::time::Instant;
 use strum_macros::Display;
@@ -31,6 +32,14 @@ pub struct KaniSession {
     /// proof attributes.
     pub codegen_tests: bool,
 
+    /// Extra configuration options (`--cfg`) used to build the code under verification. These
+    /// enable the harnesses generated for subcommands such as `check-arbitrary`.
+    pub extra_cfgs: Vec<&'static str>,
+
+    /// Drop the assumptions made by the code under verification. Used by `-Z assertion-contest`
+    /// to check which assertions depend on them.
+    pub ignore_user_assumptions: bool,
+
     /// The location we found the 'kani_rustc' command
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
@@ -38,6 +47,9 @@ pub struct KaniSession {
 
     /// The temporary files we littered that need to be cleaned up at the end of execution
     pub temporaries: Mutex<Vec<PathBuf>>,
+
+    /// Whether this session failed. Used to decide whether temporary files should be kept.
+    failed: AtomicBool,
 }
 
 /// Represents where we detected Kani, with helper methods for using that information to find critical paths
@@ -58,9 +70,12 @@ pub fn new(args: VerificationArgs) -> Result<Self> {
         Ok(KaniSession {
             args,
             codegen_tests: false,
+            extra_cfgs: vec![],
+            ignore_user_assumptions: false,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
+            failed: AtomicBool::new(false),
         })
     }
 
@@ -79,6 +94,12 @@ pub fn record_temporary_files<T: AsRef<Path>>(&self, temps: &[T]) {
         t.extend(temps.iter().map(|p| p.as_ref().to_owned()));
     }
 
+    /// Record that this session failed, either because Kani hit an error or because a harness
+    /// failed verification.
+    pub fn record_failure(&self) {
+        self.failed.store(true, Ordering::Relaxed);
+    }
+
     /// Determine which symbols Kani should codegen (i.e. by slicing away symbols
     /// that are considered unreachable.)
     pub fn reachability_mode(&self) -> ReachabilityMode {
@@ -104,7 +125,12 @@ pub enum ReachabilityMode {
 
 impl Drop for KaniSession {
     fn drop(&mut self) {
-        if !self.args.keep_temps {
+        let cleanup = match self.args.cleanup_policy() {
+            CleanupPolicy::Always => true,
+            CleanupPolicy::OnSuccess => !self.failed.load(Ordering::Relaxed),
+            CleanupPolicy::Never => false,
+        };
+        if cleanup {
             let temporaries = self.temporaries.lock().unwrap();
 
             for file in temporaries.iter() {
@@ -163,9 +189,11 @@ pub fn run_terminal(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
     if verbosity.quiet() {
         cmd.stdout(std::process::Stdio::null());
         cmd.stderr(std::process::Stdio::null());
+    } else if util::messages_to_stderr() {
+        cmd.stdout(std::io::stderr());
     }
     if verbosity.verbose() {
-        println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+        message!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
     }
     let program = cmd.get_program().to_string_lossy().to_string();
     let result = with_timer(
@@ -194,8 +222,11 @@ pub fn run_suppress(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
         // Don't suppress the output. There doesn't seem to be a way to easily get Command
         // to give one output stream of both out/err with interleaving correct, it seems
         // you'd have to resort to some lower-level interface.
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
+        let mut handle: Box<dyn Write> = if util::messages_to_stderr() {
+            Box::new(std::io::stderr().lock())
+        } else {
+            Box::new(std::io::stdout().lock())
+        };
         handle.write_all(&result.stdout)?;
         handle.write_all(&result.stderr)?;
         bail!("{} exited with status {}", cmd.get_program().to_string_lossy(), result.status);
@@ -210,7 +241,7 @@ pub fn run_redirect(
     stdout: &Path,
 ) -> Result<ExitStatus> {
     if verbosity.verbose() {
-        println!(
+        message!(
             "[Kani] Running: `{} > {}`",
             render_command(&cmd).to_string_lossy(),
             stdout.display()
@@ -237,7 +268,7 @@ pub fn run_redirect(
 /// the process exit code, you need to remember to check this yourself.
 pub fn run_piped(verbosity: &impl Verbosity, mut cmd: Command) -> Result<Option<Child>> {
     if verbosity.verbose() {
-        println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+        message!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
     }
     // Run the process as a child process
     let process = cmd
@@ -258,7 +289,7 @@ fn with_timer<T, F>(verbosity: &impl Verbosity, func: F, description: &str) -> T
     let ret = func();
     if verbosity.verbose() {
         let elapsed = start.elapsed();
-        println!("Finished {description} in {}s", elapsed.as_secs_f32())
+        message!("Finished {description} in {}s", elapsed.as_secs_f32())
     }
     ret
 }
@@ -319,14 +350,15 @@ pub fn new() -> Result<Self> {
     }
 
     pub fn kani_compiler(&self) -> Result<PathBuf> {
+        let compiler = format!("kani-compiler{}", std::env::consts::EXE_SUFFIX);
         match self {
             Self::DevRepo(_) => {
                 // Use bin_folder to hide debug/release differences.
-                let path = bin_folder()?.join("kani-compiler");
+                let path = bin_folder()?.join(compiler);
                 expect_path(path)
             }
             Self::Release(release) => {
-                let path = release.join("bin/kani-compiler");
+                let path = release.join("bin").join(compiler);
                 expect_path(path)
             }
         }
@@ -0,0 +1,243 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the `stats` subcommand, which summarizes the verification runs of the last days
+//! recorded in `kani-history.jsonl` (see [crate::trends]).
+//!
+//! The statistics are computed from the local history file only: the total verification and
+//! solver time, the property classes with the most failures, and the harnesses whose
+//! verification time grew the most between their first and last run of the period.
+
+use crate::args::stats_args::{StatsArgs, StatsFormat};
+use crate::session::KaniSession;
+use crate::trends::{cargo_history_path, read_history, RunRecord, HISTORY_FILE_NAME};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The statistics of the runs of a period.
+#[derive(Debug, PartialEq, Serialize)]
+struct Stats {
+    days: u32,
+    runs: usize,
+    verification_secs: f64,
+    solver_secs: f64,
+    /// The classes with failed checks, from the most failures to the least.
+    failing_classes: Vec<FailingClass>,
+    /// The harnesses whose verification time grew, from the largest growth to the smallest.
+    growing_harnesses: Vec<GrowingHarness>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct FailingClass {
+    class: String,
+    /// The number of failed checks of the class, added across runs.
+    failed: usize,
+    /// The number of runs with failed checks of the class.
+    runs: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct GrowingHarness {
+    name: String,
+    /// The verification time of the first run of the period that includes the harness.
+    first_secs: f64,
+    /// The verification time of the last run of the period that includes the harness.
+    last_secs: f64,
+}
+
+/// Execute the `stats` subcommand of `cargo kani`.
+pub(crate) fn stats_cargo(session: &KaniSession, args: StatsArgs) -> Result<()> {
+    let path = cargo_history_path(session, args.history.as_deref())?;
+    show_stats(&path, &args)
+}
+
+/// Execute the `stats` subcommand of `kani`.
+pub(crate) fn stats_standalone(args: StatsArgs) -> Result<()> {
+    let path = args.history.clone().unwrap_or_else(|| PathBuf::from(HISTORY_FILE_NAME));
+    show_stats(&path, &args)
+}
+
+fn show_stats(path: &Path, args: &StatsArgs) -> Result<()> {
+    let runs = read_history(path)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let since = now.saturating_sub(u64::from(args.days) * SECONDS_PER_DAY);
+    let stats = compute_stats(&runs, args.days, since, args.top as usize);
+    match args.format {
+        StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+        StatsFormat::Text => print!("{}", render_stats(&stats)),
+    }
+    Ok(())
+}
+
+/// Compute the statistics of the runs recorded since the given timestamp.
+fn compute_stats(runs: &[RunRecord], days: u32, since: u64, top: usize) -> Stats {
+    let runs: Vec<_> = runs.iter().filter(|run| run.timestamp >= since).collect();
+
+    let mut classes: BTreeMap<&str, FailingClass> = BTreeMap::new();
+    let mut harnesses: BTreeMap<&str, GrowingHarness> = BTreeMap::new();
+    for run in &runs {
+        for (class, counts) in run.classes.iter().filter(|(_, counts)| counts.failed > 0) {
+            let entry = classes.entry(class).or_insert_with(|| FailingClass {
+                class: class.clone(),
+                failed: 0,
+                runs: 0,
+            });
+            entry.failed += counts.failed;
+            entry.runs += 1;
+        }
+        for (name, secs) in &run.harness_secs {
+            harnesses
+                .entry(name)
+                .or_insert_with(|| GrowingHarness {
+                    name: name.clone(),
+                    first_secs: *secs,
+                    last_secs: *secs,
+                })
+                .last_secs = *secs;
+        }
+    }
+
+    let mut failing_classes: Vec<_> = classes.into_values().collect();
+    // The sort is stable, so classes with the same number of failures stay sorted by name.
+    failing_classes.sort_by(|a, b| b.failed.cmp(&a.failed));
+    failing_classes.truncate(top);
+    let mut growing_harnesses: Vec<_> =
+        harnesses.into_values().filter(|harness| harness.growth() > 0.0).collect();
+    growing_harnesses.sort_by(|a, b| b.growth().total_cmp(&a.growth()));
+    growing_harnesses.truncate(top);
+
+    Stats {
+        days,
+        runs: runs.len(),
+        verification_secs: runs.iter().map(|run| run.duration_secs).sum(),
+        solver_secs: runs.iter().map(|run| run.solver_secs).sum(),
+        failing_classes,
+        growing_harnesses,
+    }
+}
+
+impl GrowingHarness {
+    fn growth(&self) -> f64 {
+        self.last_secs - self.first_secs
+    }
+}
+
+fn render_stats(stats: &Stats) -> String {
+    let mut out = String::new();
+    // Writing to a `String` cannot fail.
+    let _ = writeln!(out, "Verification runs in the last {} day(s): {}", stats.days, stats.runs);
+    if stats.runs == 0 {
+        return out;
+    }
+    let _ = writeln!(out, "Total verification time: {:.2}s", stats.verification_secs);
+    let _ = writeln!(out, "Total solver time: {:.2}s", stats.solver_secs);
+
+    let _ = writeln!(out, "\nMost failing property classes:");
+    if stats.failing_classes.is_empty() {
+        let _ = writeln!(out, "  No failed checks");
+    }
+    let width = stats.failing_classes.iter().map(|class| class.class.len()).max().unwrap_or(0);
+    for class in &stats.failing_classes {
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {} failed check(s) in {} run(s)",
+            class.class, class.failed, class.runs
+        );
+    }
+
+    let _ = writeln!(out, "\nSlowest-growing harnesses:");
+    if stats.growing_harnesses.is_empty() {
+        let _ = writeln!(out, "  No harness got slower");
+    }
+    let width = stats.growing_harnesses.iter().map(|harness| harness.name.len()).max().unwrap_or(0);
+    for harness in &stats.growing_harnesses {
+        let _ = writeln!(
+            out,
+            "  {:<width$}  +{:.2}s ({:.2}s -> {:.2}s)",
+            harness.name,
+            harness.growth(),
+            harness.first_secs,
+            harness.last_secs
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trends::ClassCounts;
+
+    fn mock_run(
+        timestamp: u64,
+        solver_secs: f64,
+        failed: &[(&str, usize)],
+        harness_secs: &[(&str, f64)],
+    ) -> RunRecord {
+        RunRecord {
+            timestamp,
+            duration_secs: harness_secs.iter().map(|(_, secs)| secs).sum(),
+            harnesses: harness_secs.len(),
+            failed_harnesses: 0,
+            classes: failed
+                .iter()
+                .map(|(class, failed)| {
+                    (class.to_string(), ClassCounts { total: 10, failed: *failed, undetermined: 0 })
+                })
+                .collect(),
+            solver_secs,
+            harness_secs: harness_secs
+                .iter()
+                .map(|(name, secs)| (name.to_string(), *secs))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn check_stats() {
+        let runs = [
+            // Too old to be included.
+            mock_run(50, 9.0, &[("assertion", 9)], &[("check_a", 9.0)]),
+            mock_run(100, 1.0, &[("assertion", 1), ("overflow", 0)], &[("check_a", 1.0)]),
+            mock_run(200, 0.5, &[("overflow", 2)], &[("check_a", 2.0), ("check_b", 4.0)]),
+            mock_run(300, 0.25, &[("overflow", 1), ("pointer", 1)], &[("check_b", 3.0)]),
+        ];
+        let stats = compute_stats(&runs, 7, 100, 2);
+        assert_eq!(stats.runs, 3);
+        assert_eq!(stats.verification_secs, 10.0);
+        assert_eq!(stats.solver_secs, 1.75);
+        assert_eq!(
+            stats.failing_classes,
+            [
+                FailingClass { class: "overflow".to_string(), failed: 3, runs: 2 },
+                FailingClass { class: "assertion".to_string(), failed: 1, runs: 1 },
+            ]
+        );
+        assert_eq!(
+            stats.growing_harnesses,
+            [GrowingHarness { name: "check_a".to_string(), first_secs: 1.0, last_secs: 2.0 }]
+        );
+        assert_eq!(
+            render_stats(&stats),
+            "Verification runs in the last 7 day(s): 3\n\
+            Total verification time: 10.00s\n\
+            Total solver time: 1.75s\n\
+            \n\
+            Most failing property classes:\n  \
+              overflow   3 failed check(s) in 2 run(s)\n  \
+              assertion  1 failed check(s) in 1 run(s)\n\
+            \n\
+            Slowest-growing harnesses:\n  \
+              check_a  +1.00s (1.00s -> 2.00s)\n"
+        );
+
+        let empty = compute_stats(&runs, 1, 1000, 5);
+        assert_eq!(render_stats(&empty), "Verification runs in the last 1 day(s): 0\n");
+    }
+}
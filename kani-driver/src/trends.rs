@@ -0,0 +1,368 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the history of verification runs, and the `trends` subcommand that shows how
+//! failures, undetermined checks and verification time evolve across these runs.
+//!
+//! Every verification run appends one line to `kani-history.jsonl`, next to the results file,
+//! with the number of checks of each property class in each status. Only the last
+//! [MAX_HISTORY_RUNS] runs are kept.
+
+use crate::args::trends_args::{TrendsArgs, TrendsFormat};
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::results;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_results::CheckStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The name of the file where the history of verification runs is stored.
+pub(crate) const HISTORY_FILE_NAME: &str = "kani-history.jsonl";
+
+/// The number of runs kept in the history file. Older runs are dropped, so the file doesn't grow
+/// without bound in long-lived target directories.
+const MAX_HISTORY_RUNS: usize = 1000;
+
+/// The characters used to draw sparklines, from the lowest to the highest value.
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The number of checks of a property class in a verification run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ClassCounts {
+    pub total: usize,
+    pub failed: usize,
+    pub undetermined: usize,
+}
+
+/// A line of the history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RunRecord {
+    /// When the run finished, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The total time spent verifying the harnesses of the run.
+    pub duration_secs: f64,
+    pub harnesses: usize,
+    pub failed_harnesses: usize,
+    /// The counts of each property class, e.g. `assertion` or `arithmetic_overflow`.
+    pub classes: BTreeMap<String, ClassCounts>,
+    /// The time spent by the solver, which is included in `duration_secs`.
+    #[serde(default)]
+    pub solver_secs: f64,
+    /// The time spent verifying each harness, by name.
+    #[serde(default)]
+    pub harness_secs: BTreeMap<String, f64>,
+}
+
+impl RunRecord {
+    fn new(timestamp: u64, results: &[HarnessResult]) -> RunRecord {
+        let mut classes: BTreeMap<String, ClassCounts> = BTreeMap::new();
+        let mut failed_harnesses = 0;
+        let mut harness_secs = BTreeMap::new();
+        for result in results {
+            let harness = results::harness_results(result);
+            harness_secs.insert(harness.name.clone(), result.result.runtime.as_secs_f64());
+            if result.result.status == VerificationStatus::Failure {
+                failed_harnesses += 1;
+            }
+            for check in harness.checks {
+                let counts = classes.entry(check.class).or_default();
+                counts.total += 1;
+                match check.status {
+                    CheckStatus::Failure => counts.failed += 1,
+                    CheckStatus::Undetermined => counts.undetermined += 1,
+                    _ => {}
+                }
+            }
+        }
+        RunRecord {
+            timestamp,
+            duration_secs: results.iter().map(|result| result.result.runtime.as_secs_f64()).sum(),
+            harnesses: results.len(),
+            failed_harnesses,
+            classes,
+            solver_secs: results
+                .iter()
+                .map(|result| result.result.solver_time().as_secs_f64())
+                .sum(),
+            harness_secs,
+        }
+    }
+
+    /// The counts of the classes that match the filter, added together.
+    fn counts(&self, class_filter: Option<&str>) -> ClassCounts {
+        let mut total = ClassCounts::default();
+        for (_, counts) in self.matching_classes(class_filter) {
+            total.total += counts.total;
+            total.failed += counts.failed;
+            total.undetermined += counts.undetermined;
+        }
+        total
+    }
+
+    fn matching_classes<'a>(
+        &'a self,
+        class_filter: Option<&'a str>,
+    ) -> impl Iterator<Item = (&'a String, &'a ClassCounts)> {
+        self.classes
+            .iter()
+            .filter(move |(class, _)| class_filter.map_or(true, |filter| class.contains(filter)))
+    }
+}
+
+/// Append the results of a verification run to the history file in the given directory.
+pub(crate) fn record_run(dir: &Path, results: &[HarnessResult]) -> Result<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let path = dir.join(HISTORY_FILE_NAME);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open history file `{}`", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&RunRecord::new(timestamp, results))?)?;
+    drop(file);
+    trim_history(&path, MAX_HISTORY_RUNS)
+}
+
+/// Drop the oldest runs of the history file if it has more than `max_runs`. The trimmed history
+/// is written to a temporary file first, so the history is never left truncated.
+fn trim_history(path: &Path, max_runs: usize) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read history file `{}`", path.display()))?;
+    let runs: Vec<_> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    if runs.len() <= max_runs {
+        return Ok(());
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    let mut trimmed = runs[runs.len() - max_runs..].join("\n");
+    trimmed.push('\n');
+    std::fs::write(&tmp_path, trimmed)
+        .with_context(|| format!("Failed to write history file `{}`", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to write history file `{}`", path.display()))
+}
+
+/// Execute the `trends` subcommand of `cargo kani`.
+pub(crate) fn trends_cargo(session: &KaniSession, args: TrendsArgs) -> Result<()> {
+    let path = cargo_history_path(session, args.history.as_deref())?;
+    show_trends(&path, &args)
+}
+
+/// The history file of a `cargo kani` subcommand: the given one, or the one in the target
+/// directory of the package.
+pub(crate) fn cargo_history_path(session: &KaniSession, history: Option<&Path>) -> Result<PathBuf> {
+    match history {
+        Some(path) => Ok(path.to_path_buf()),
+        None => {
+            let metadata = session.cargo_metadata(env!("TARGET"))?;
+            Ok(session.cargo_target_dir(&metadata).join(HISTORY_FILE_NAME))
+        }
+    }
+}
+
+/// Execute the `trends` subcommand of `kani`.
+pub(crate) fn trends_standalone(args: TrendsArgs) -> Result<()> {
+    let path = args.history.clone().unwrap_or_else(|| PathBuf::from(HISTORY_FILE_NAME));
+    show_trends(&path, &args)
+}
+
+/// Read the runs recorded in a history file, from the oldest to the most recent.
+pub(crate) fn read_history(path: &Path) -> Result<Vec<RunRecord>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open history file `{}`", path.display()))?;
+    BufReader::new(file)
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|line| Ok(serde_json::from_str::<RunRecord>(&line?)?))
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read history file `{}`", path.display()))
+}
+
+fn show_trends(path: &Path, args: &TrendsArgs) -> Result<()> {
+    let mut runs = read_history(path)?;
+    let last = args.last as usize;
+    if runs.len() > last {
+        runs.drain(..runs.len() - last);
+    }
+    match args.format {
+        TrendsFormat::Json => println!("{}", serde_json::to_string_pretty(&runs)?),
+        TrendsFormat::Sparkline => print!("{}", render_trends(&runs, args.class.as_deref())),
+    }
+    Ok(())
+}
+
+/// Render one sparkline for each metric, followed by one for each class that had failed or
+/// undetermined checks in any of the runs.
+fn render_trends(runs: &[RunRecord], class_filter: Option<&str>) -> String {
+    if runs.is_empty() {
+        return "No verification runs recorded.\n".to_string();
+    }
+    let totals: Vec<_> = runs.iter().map(|run| run.counts(class_filter)).collect();
+    let mut lines = vec![
+        metric_line("failed checks", &totals.iter().map(|c| c.failed as f64).collect::<Vec<_>>()),
+        metric_line(
+            "undetermined checks",
+            &totals.iter().map(|c| c.undetermined as f64).collect::<Vec<_>>(),
+        ),
+        metric_line("duration (s)", &runs.iter().map(|run| run.duration_secs).collect::<Vec<_>>()),
+    ];
+    let mut classes: BTreeMap<&str, Vec<(f64, f64)>> = BTreeMap::new();
+    for (idx, run) in runs.iter().enumerate() {
+        for (class, counts) in run.matching_classes(class_filter) {
+            let series = classes.entry(class).or_insert_with(|| vec![(0.0, 0.0); runs.len()]);
+            series[idx] = (counts.failed as f64, counts.undetermined as f64);
+        }
+    }
+    for (class, series) in classes {
+        let failed: Vec<_> = series.iter().map(|(failed, _)| *failed).collect();
+        let undetermined: Vec<_> = series.iter().map(|(_, undetermined)| *undetermined).collect();
+        if failed.iter().any(|value| *value > 0.0) {
+            lines.push(metric_line(&format!("{class}: failed"), &failed));
+        }
+        if undetermined.iter().any(|value| *value > 0.0) {
+            lines.push(metric_line(&format!("{class}: undetermined"), &undetermined));
+        }
+    }
+    let width = lines.iter().map(|(label, _)| label.chars().count()).max().unwrap_or_default();
+    let mut out = format!("Last {} verification run(s):\n", runs.len());
+    for (label, chart) in lines {
+        out.push_str(&format!("{label:<width$}  {chart}\n"));
+    }
+    out
+}
+
+/// A sparkline of the values, followed by the last value.
+fn metric_line(label: &str, values: &[f64]) -> (String, String) {
+    let last = values.last().copied().unwrap_or_default();
+    let last = if last.fract() == 0.0 { format!("{last}") } else { format!("{last:.2}") };
+    (label.to_string(), format!("{}  (last: {last})", sparkline(values)))
+}
+
+/// Draw the values with one character each, scaled between the lowest and the highest value.
+fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let top = (SPARK_LEVELS.len() - 1) as f64;
+    values
+        .iter()
+        .map(|value| {
+            let level = if max > min { ((value - min) / (max - min) * top).round() } else { 0.0 };
+            SPARK_LEVELS[level as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_run(duration_secs: f64, classes: &[(&str, usize, usize, usize)]) -> RunRecord {
+        RunRecord {
+            timestamp: 0,
+            duration_secs,
+            harnesses: 1,
+            failed_harnesses: 0,
+            solver_secs: 0.0,
+            harness_secs: BTreeMap::new(),
+            classes: classes
+                .iter()
+                .map(|(class, total, failed, undetermined)| {
+                    (
+                        class.to_string(),
+                        ClassCounts { total: *total, failed: *failed, undetermined: *undetermined },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn check_sparkline() {
+        assert_eq!(sparkline(&[0.0, 1.0, 7.0]), "▁▂█");
+        assert_eq!(sparkline(&[3.0, 3.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn check_render_trends() {
+        let runs = [
+            mock_run(1.5, &[("assertion", 2, 1, 0), ("arithmetic_overflow", 3, 0, 0)]),
+            mock_run(2.0, &[("assertion", 2, 0, 0), ("arithmetic_overflow", 3, 0, 1)]),
+        ];
+        let out = render_trends(&runs, None);
+        let lines: Vec<String> =
+            out.lines().map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ")).collect();
+        assert_eq!(
+            lines,
+            [
+                "Last 2 verification run(s):",
+                "failed checks █▁ (last: 0)",
+                "undetermined checks ▁█ (last: 1)",
+                "duration (s) ▁█ (last: 2)",
+                "arithmetic_overflow: undetermined ▁█ (last: 1)",
+                "assertion: failed █▁ (last: 0)",
+            ]
+        );
+
+        let out = render_trends(&runs, Some("overflow"));
+        assert!(!out.contains("assertion"));
+        assert_eq!(
+            runs[0].counts(Some("overflow")),
+            ClassCounts { total: 3, ..Default::default() }
+        );
+        assert_eq!(render_trends(&[], None), "No verification runs recorded.\n");
+    }
+
+    #[test]
+    fn check_failed_harnesses() {
+        use crate::call_cbmc::VerificationResult;
+        use crate::metadata::mock_proof_harness;
+        let success = mock_proof_harness("check_ok", None, None, None);
+        let failure = mock_proof_harness("check_fail", None, None, None);
+        // A harness can fail without any failed check, e.g. a `should_panic` harness that
+        // doesn't panic.
+        let mut failed = VerificationResult::mock_success();
+        failed.status = VerificationStatus::Failure;
+        let results = [
+            HarnessResult { harness: &success, result: VerificationResult::mock_success() },
+            HarnessResult { harness: &failure, result: failed },
+        ];
+        let run = RunRecord::new(0, &results);
+        assert_eq!(run.harnesses, 2);
+        assert_eq!(run.failed_harnesses, 1);
+    }
+
+    #[test]
+    fn check_trim_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE_NAME);
+        let lines: Vec<_> = (0..5)
+            .map(|timestamp| {
+                let run = RunRecord { timestamp, ..mock_run(1.0, &[]) };
+                serde_json::to_string(&run).unwrap()
+            })
+            .collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+        trim_history(&path, 5).unwrap();
+        assert_eq!(read_history(&path).unwrap().len(), 5);
+        trim_history(&path, 2).unwrap();
+        let runs = read_history(&path).unwrap();
+        assert_eq!(runs.iter().map(|run| run.timestamp).collect::<Vec<_>>(), [3, 4]);
+        assert!(!path.with_extension("jsonl.tmp").exists());
+    }
+
+    #[test]
+    fn check_record_roundtrip() {
+        let run = mock_run(0.25, &[("assertion", 1, 0, 0)]);
+        let line = serde_json::to_string(&run).unwrap();
+        assert_eq!(serde_json::from_str::<RunRecord>(&line).unwrap(), run);
+        // Records written before the solver and harness times were recorded can still be read.
+        let old = r#"{"timestamp":0,"duration_secs":0.25,"harnesses":1,"failed_harnesses":0,"classes":{"assertion":{"total":1,"failed":0,"undetermined":0}}}"#;
+        assert_eq!(serde_json::from_str::<RunRecord>(old).unwrap(), run);
+    }
+}
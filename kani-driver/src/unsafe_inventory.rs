@@ -0,0 +1,217 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani unsafe-inventory`, which verifies the package and lists each `unsafe`
+//! block and function written in it, along with:
+//!
+//! - The harnesses that reach it. The compiler tracks this at the granularity of functions, so an
+//!   `unsafe` block is reachable if the function that contains it is.
+//! - The status of the pointer and overflow checks located inside it, in each of these harnesses.
+//!
+//! Only harnesses of the crate that contains the `unsafe` code are taken into account.
+
+use crate::args::results_args::QueryFormat;
+use crate::args::unsafe_inventory_args::UnsafeInventoryArgs;
+use crate::args::OutputFormat;
+use crate::cbmc_output_parser::Property;
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project;
+use crate::results;
+use crate::session::KaniSession;
+use anyhow::{bail, Result};
+use comfy_table::Table;
+use kani_metadata::{UnsafeItem, UnsafeKind};
+use kani_results::{CheckResult, CheckStatus};
+use serde::Serialize;
+use std::path::Path;
+
+/// The status of an `unsafe` item, based on the checks located inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ItemStatus {
+    /// No harness reaches the item.
+    Unreached,
+    /// All the related checks passed, in every harness that reaches the item.
+    Verified,
+    /// At least one related check failed.
+    Failed,
+    /// No related check failed, but some could not be determined, e.g. because verification of
+    /// a harness timed out.
+    Undetermined,
+}
+
+/// A check related to an `unsafe` item, in one of the harnesses that reach it.
+#[derive(Debug, Serialize)]
+struct RelatedCheck<'a> {
+    harness: &'a str,
+    #[serde(flatten)]
+    check: CheckResult,
+}
+
+/// An entry of the inventory.
+#[derive(Debug, Serialize)]
+struct InventoryEntry<'a> {
+    #[serde(flatten)]
+    item: &'a UnsafeItem,
+    harnesses: Vec<&'a str>,
+    checks: Vec<RelatedCheck<'a>>,
+    status: ItemStatus,
+}
+
+/// `cargo kani unsafe-inventory` main entry point.
+pub(crate) fn unsafe_inventory(mut session: KaniSession, args: UnsafeInventoryArgs) -> Result<()> {
+    session.args.output_format = OutputFormat::Terse;
+    if args.format == QueryFormat::Json {
+        session.args.common_args.quiet = true;
+    }
+
+    let project = project::cargo_project(&session, false)?;
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let runner = HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+
+    let entries: Vec<_> = project
+        .metadata
+        .iter()
+        .flat_map(|crate_md| {
+            let crate_results: Vec<_> = results
+                .iter()
+                .filter(|result| result.harness.crate_name == crate_md.crate_name)
+                .collect();
+            crate_md.unsafe_items.iter().map(move |item| inventory_entry(item, &crate_results))
+        })
+        .collect();
+
+    match args.format {
+        QueryFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        QueryFormat::Table => print_table(&entries),
+    }
+
+    let failures = entries.iter().filter(|entry| entry.status == ItemStatus::Failed).count();
+    if failures > 0 {
+        session.record_failure();
+        bail!("{failures} unsafe item(s) have failed checks");
+    }
+    Ok(())
+}
+
+fn inventory_entry<'a>(item: &'a UnsafeItem, results: &[&HarnessResult<'a>]) -> InventoryEntry<'a> {
+    let reaching: Vec<_> = results
+        .iter()
+        .filter(|result| result.harness.reached_unsafe.contains(&item.function))
+        .collect();
+    let mut checks = vec![];
+    let mut undetermined = false;
+    for result in &reaching {
+        match &result.result.results {
+            Ok(properties) => {
+                checks.extend(properties.iter().filter(|prop| is_related_check(prop, item)).map(
+                    |prop| RelatedCheck {
+                        harness: &result.harness.pretty_name,
+                        check: results::check_result(prop),
+                    },
+                ))
+            }
+            // Verification didn't complete, so the checks of this harness are unknown.
+            Err(_) => undetermined = true,
+        }
+    }
+    let status = if reaching.is_empty() {
+        ItemStatus::Unreached
+    } else if checks.iter().any(|related| related.check.status == CheckStatus::Failure) {
+        ItemStatus::Failed
+    } else if undetermined
+        || checks.iter().any(|related| related.check.status == CheckStatus::Undetermined)
+    {
+        ItemStatus::Undetermined
+    } else {
+        ItemStatus::Verified
+    };
+    InventoryEntry {
+        item,
+        harnesses: reaching.iter().map(|result| result.harness.pretty_name.as_str()).collect(),
+        checks,
+        status,
+    }
+}
+
+/// Whether the property is a pointer or overflow check located inside the item.
+fn is_related_check(prop: &Property, item: &UnsafeItem) -> bool {
+    let class = prop.property_class();
+    if !class.contains("pointer") && !class.contains("overflow") {
+        return false;
+    }
+    let location = &prop.source_location;
+    let in_file = location.file.as_ref().is_some_and(|file| {
+        Path::new(file).ends_with(&item.filename) || Path::new(&item.filename).ends_with(file)
+    });
+    let line = location.line.as_ref().and_then(|line| line.parse::<usize>().ok());
+    in_file && line.is_some_and(|line| (item.start_line..=item.end_line).contains(&line))
+}
+
+fn print_table(entries: &[InventoryEntry]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Status", "Kind", "Function", "Checks", "Harnesses", "Location"]);
+    for entry in entries {
+        let item = entry.item;
+        let passed = entry
+            .checks
+            .iter()
+            .filter(|related| related.check.status == CheckStatus::Success)
+            .count();
+        table.add_row(vec![
+            format!("{:?}", entry.status).to_lowercase(),
+            match item.kind {
+                UnsafeKind::Block => "block".to_string(),
+                UnsafeKind::Function => "fn".to_string(),
+            },
+            item.function.clone(),
+            format!("{passed}/{} passed", entry.checks.len()),
+            entry.harnesses.join("\n"),
+            format!("{}:{}-{}", item.filename, item.start_line, item.end_line),
+        ]);
+    }
+    println!("{table}");
+    let reached = entries.iter().filter(|entry| entry.status != ItemStatus::Unreached).count();
+    println!("{} unsafe items, {reached} reachable from a harness.", entries.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{self, PropertyId, SourceLocation};
+
+    fn mock_property(class: &str, file: &str, line: usize) -> Property {
+        Property {
+            description: String::new(),
+            property_id: PropertyId { fn_name: None, class: class.to_string(), id: 1 },
+            source_location: SourceLocation {
+                column: None,
+                end_column: None,
+                end_line: None,
+                file: Some(file.to_string()),
+                function: None,
+                line: Some(line.to_string()),
+            },
+            status: cbmc_output_parser::CheckStatus::Success,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_is_related_check() {
+        let item = UnsafeItem {
+            kind: UnsafeKind::Block,
+            function: "read".to_string(),
+            filename: "/home/user/pkg/src/lib.rs".to_string(),
+            start_line: 10,
+            end_line: 12,
+        };
+        assert!(is_related_check(&mock_property("pointer_dereference", "src/lib.rs", 11), &item));
+        assert!(is_related_check(&mock_property("arithmetic_overflow", "src/lib.rs", 10), &item));
+        assert!(!is_related_check(&mock_property("assertion", "src/lib.rs", 11), &item));
+        assert!(!is_related_check(&mock_property("pointer_dereference", "src/lib.rs", 13), &item));
+        assert!(!is_related_check(&mock_property("pointer_dereference", "src/main.rs", 11), &item));
+    }
+}
@@ -4,15 +4,48 @@
 //! Module that provides functions which are convenient for different purposes.
 //!
 //! In particular, the `warning` and `error` functions must be used for
-//! diagnostic output across the `kani-driver` components. Please follow the
+//! diagnostic output across the `kani-driver` components, and the `message!`
+//! macro for any other message that isn't a result. Please follow the
 //! recommendations in <https://model-checking.github.io/kani/conventions.html>
 //! when reporting any kind of diagnostic for users. Note that it's recommended
 //! to use the Rust compiler's error message utilities if you're working on the
 //! `kani-compiler`.
 
+use anyhow::{Context, Result};
 use std::ffi::OsString;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the messages of Kani are printed to the standard error instead of the standard output.
+/// See [print_messages_to_stderr].
+static MESSAGES_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Print the messages of Kani, i.e. anything other than the results of the command, to the
+/// standard error from now on. This keeps the standard output machine readable, e.g. with
+/// `--output-format json`.
+pub fn print_messages_to_stderr() {
+    MESSAGES_TO_STDERR.store(true, Ordering::Relaxed)
+}
+
+/// Whether the messages of Kani are printed to the standard error.
+pub fn messages_to_stderr() -> bool {
+    MESSAGES_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Print a message like `println!`, to the standard error if [print_messages_to_stderr] was
+/// called.
+macro_rules! message {
+    ($($arg:tt)*) => {
+        if $crate::util::messages_to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+pub(crate) use message;
 
 /// Replace an extension with another one, in a new PathBuf. (See tests for examples)
 pub fn alter_extension(path: &Path, ext: &str) -> PathBuf {
@@ -79,25 +112,127 @@ pub fn render_command(cmd: &Command) -> OsString {
     str
 }
 
+/// Escape the characters that are not allowed in XML (and HTML) text and attribute values.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// Print a warning message. This will add a "warning:" tag before the message and style accordingly.
 pub fn warning(msg: &str) {
     let warning = console::style("warning:").bold().yellow();
     let msg_fmt = console::style(msg).bold();
-    println!("{warning} {msg_fmt}")
+    message!("{warning} {msg_fmt}")
 }
 
 /// Print an error message. This will add an "error:" tag before the message and style accordingly.
 pub fn error(msg: &str) {
     let error = console::style("error:").bold().red();
     let msg_fmt = console::style(msg).bold();
-    println!("{error} {msg_fmt}")
+    message!("{error} {msg_fmt}")
 }
 
 /// Print an info message. This will print the stage in bold green and the rest in regular style.
 pub fn info_operation(op: &str, msg: &str) {
     let op_fmt = console::style(op).bold().green();
     let msg_fmt = console::style(msg);
-    println!("{op_fmt} {msg_fmt}")
+    message!("{op_fmt} {msg_fmt}")
+}
+
+/// An advisory lock on a file that is held until this object is dropped.
+///
+/// This is used to stop concurrent Kani sessions from modifying build outputs that another
+/// session is still reading. The lock is a no-op on platforms without `flock`.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+    /// The path of the lock file if it should be removed when the lock is released.
+    temporary: Option<PathBuf>,
+}
+
+impl FileLock {
+    /// Block until this process holds an exclusive lock on the given file. The file and its parent
+    /// directories are created if they don't exist.
+    pub fn exclusive(path: &Path) -> Result<FileLock> {
+        FileLock::lock(path, false)
+    }
+
+    /// Same as [FileLock::exclusive], but the lock file is removed when the lock is released.
+    pub fn temporary(path: &Path) -> Result<FileLock> {
+        FileLock::lock(path, true)
+    }
+
+    fn lock(path: &Path, temporary: bool) -> Result<FileLock> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        loop {
+            let file = File::options()
+                .create(true)
+                .write(true)
+                .open(path)
+                .with_context(|| format!("Failed to open lock file `{}`", path.display()))?;
+            lock_exclusive(&file)
+                .with_context(|| format!("Failed to lock file `{}`", path.display()))?;
+            // The session that held the lock may have removed the file while we were waiting, in
+            // which case we hold a lock that nobody else sees. Lock the new file instead.
+            if is_same_file(&file, path) {
+                return Ok(FileLock { _file: file, temporary: temporary.then(|| path.into()) });
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // The file is only closed after this, so the lock is still held while we remove it.
+        if let Some(path) = &self.temporary {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(opened), Ok(current)) => opened.dev() == current.dev() && opened.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, _path: &Path) -> bool {
+    true
+}
+
+#[cfg(unix)]
+fn lock_exclusive(file: &File) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: `flock` only operates on the file descriptor, which is valid while `file` is alive.
+    // The lock is released when the file is closed.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Concurrent sessions are not serialized on other platforms yet, so they are not offered by
+/// `cargo kani setup` and must be built from source.
+#[cfg(not(unix))]
+fn lock_exclusive(_file: &File) -> std::io::Result<()> {
+    Ok(())
 }
 
 #[cfg(test)]
@@ -125,6 +260,30 @@ fn check_guess_rlib_name() {
         assert_eq!(guess_rlib_name(Path::new("a/b/foo.rs")), PathBuf::from("a/b/libfoo.rlib"));
     }
 
+    #[test]
+    fn check_file_lock_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("kani.lock");
+        let lock = FileLock::exclusive(&path).unwrap();
+        assert!(path.exists());
+        drop(lock);
+        // This would block forever if the lock was still held.
+        FileLock::exclusive(&path).unwrap();
+    }
+
+    #[test]
+    fn check_temporary_file_lock_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kani.lock");
+        let lock = FileLock::temporary(&path).unwrap();
+        assert!(path.exists());
+        drop(lock);
+        assert!(!path.exists());
+        // A new lock recreates the file.
+        let _lock = FileLock::temporary(&path).unwrap();
+        assert!(path.exists());
+    }
+
     #[test]
     fn check_exe_basename() {
         assert_eq!(
@@ -0,0 +1,266 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Types that describe the `kani-results.json` files written by Kani after each verification run.
+//!
+//! These types define the JSON schema of the results. Every file embeds the version of the
+//! schema it follows in its `format_version` field, and the schema follows these rules:
+//!  * Adding a new optional field or a new status does not change the format version.
+//!    Readers ignore fields they don't know about, and read statuses they don't know about as
+//!    `Unknown`.
+//!  * Removing or renaming a field, or changing its type or meaning, increments the format
+//!    version.
+//!
+//! Use [`VerificationResults::check_format_version`] to find out whether a file can be read with
+//! this version of the crate.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The version of the results format defined by this crate.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The results of a verification run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationResults {
+    /// The version of the results format, i.e. [`FORMAT_VERSION`] when the file was written.
+    pub format_version: u32,
+    /// The overall status of the run, i.e. failure if any harness failed. This is missing from
+    /// results written by older versions of Kani.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<VerificationStatus>,
+    /// The results of each harness that was verified.
+    pub harnesses: Vec<HarnessResults>,
+}
+
+/// The results of a single harness.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessResults {
+    /// The fully qualified name of the harness.
+    pub name: String,
+    /// The documentation comment of the harness, if any, without the leading `///`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Whether the harness was verified successfully.
+    pub status: VerificationStatus,
+    /// The number of checks in each status.
+    pub summary: Summary,
+    /// The checks of this harness. This is empty if the verification engine failed to run.
+    pub checks: Vec<CheckResult>,
+}
+
+/// The overall status of a harness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationStatus {
+    Success,
+    Failure,
+    /// A status written by a newer version of Kani.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The number of checks of a harness in each status. Cover properties are not included.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub total: usize,
+    pub failed: usize,
+    pub undetermined: usize,
+    pub unreachable: usize,
+}
+
+/// The result of a single check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// The unique name of the check, e.g. `main.assertion.1`.
+    pub name: String,
+    /// The class of the check, e.g. `assertion` or `arithmetic_overflow`.
+    pub class: String,
+    pub description: String,
+    pub status: CheckStatus,
+    pub location: SourceLocation,
+    /// The steps that lead to the check, if the trace was kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceStep>>,
+}
+
+/// The status of a single check. Like [VerificationStatus], it is serialized in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Failure,
+    Covered,
+    Reachable,
+    Satisfied,
+    Success,
+    Undetermined,
+    Unreachable,
+    Uncovered,
+    Unsatisfiable,
+    /// A status written by a newer version of Kani.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            CheckStatus::Failure => "FAILURE",
+            CheckStatus::Covered => "COVERED",
+            CheckStatus::Reachable => "REACHABLE",
+            CheckStatus::Satisfied => "SATISFIED",
+            CheckStatus::Success => "SUCCESS",
+            CheckStatus::Undetermined => "UNDETERMINED",
+            CheckStatus::Unreachable => "UNREACHABLE",
+            CheckStatus::Uncovered => "UNCOVERED",
+            CheckStatus::Unsatisfiable => "UNSATISFIABLE",
+            CheckStatus::Unknown => "UNKNOWN",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// A location in the source code. All members are optional, since the verification engine
+/// doesn't always know where a check or a step comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub file: Option<String>,
+    /// The function, as a demangled path without the hash.
+    pub function: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// The line where the source span ends, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    /// The column where the source span ends, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+}
+
+/// A single step of a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub thread: u32,
+    /// The kind of step, e.g. `assignment` or `function-call`.
+    pub step_type: String,
+    /// Whether the step is an internal step of the verification engine.
+    pub hidden: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    /// The variable that is assigned in this step, for assignments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lhs: Option<String>,
+    /// The value that is assigned in this step, for assignments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// The error returned when a results file uses a format version that this crate cannot read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedVersion(pub u32);
+
+impl Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "results format version {} is not supported (expected version {FORMAT_VERSION})",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+impl VerificationResults {
+    /// Create the results of a verification run using the current format version.
+    pub fn new(harnesses: Vec<HarnessResults>) -> Self {
+        let status =
+            if harnesses.iter().any(|harness| harness.status == VerificationStatus::Failure) {
+                VerificationStatus::Failure
+            } else {
+                VerificationStatus::Success
+            };
+        VerificationResults { format_version: FORMAT_VERSION, status: Some(status), harnesses }
+    }
+
+    /// Return an error if these results follow a format version other than [`FORMAT_VERSION`].
+    pub fn check_format_version(&self) -> Result<(), UnsupportedVersion> {
+        if self.format_version == FORMAT_VERSION {
+            Ok(())
+        } else {
+            Err(UnsupportedVersion(self.format_version))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_results_round_trip() {
+        let check = CheckResult {
+            name: "main.assertion.1".to_string(),
+            class: "assertion".to_string(),
+            description: "assertion failed: x < 10".to_string(),
+            status: CheckStatus::Failure,
+            location: SourceLocation {
+                file: Some("src/main.rs".to_string()),
+                function: Some("main".to_string()),
+                line: Some(3),
+                column: None,
+                end_line: None,
+                end_column: None,
+            },
+            trace: None,
+        };
+        let results = VerificationResults::new(vec![HarnessResults {
+            name: "check_main".to_string(),
+            doc: None,
+            status: VerificationStatus::Failure,
+            summary: Summary { total: 1, failed: 1, ..Default::default() },
+            checks: vec![check],
+        }]);
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["format_version"], FORMAT_VERSION);
+        assert_eq!(json["status"], "failure");
+        assert_eq!(json["harnesses"][0]["status"], "failure");
+        assert_eq!(json["harnesses"][0]["checks"][0]["status"], "failure");
+        assert!(json["harnesses"][0]["checks"][0].get("trace").is_none());
+        let parsed: VerificationResults = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, results);
+        assert_eq!(parsed.check_format_version(), Ok(()));
+    }
+
+    #[test]
+    fn check_unknown_fields_are_ignored() {
+        let json = r#"{"format_version": 1, "harnesses": [], "new_field": true}"#;
+        let parsed: VerificationResults = serde_json::from_str(json).unwrap();
+        assert!(parsed.harnesses.is_empty());
+        assert_eq!(parsed.status, None);
+    }
+
+    #[test]
+    fn check_unknown_statuses_are_accepted() {
+        let json = r#"{"format_version": 1, "status": "inconclusive", "harnesses": [{
+            "name": "check", "status": "inconclusive",
+            "summary": {"total": 1, "failed": 0, "undetermined": 0, "unreachable": 0},
+            "checks": [{"name": "check.assertion.1", "class": "assertion", "description": "",
+                "status": "vacuous", "location": {"file": null, "function": null, "line": null,
+                "column": null}}]}]}"#;
+        let parsed: VerificationResults = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.status, Some(VerificationStatus::Unknown));
+        assert_eq!(parsed.harnesses[0].status, VerificationStatus::Unknown);
+        assert_eq!(parsed.harnesses[0].checks[0].status, CheckStatus::Unknown);
+    }
+
+    #[test]
+    fn check_unsupported_version() {
+        let results = VerificationResults {
+            format_version: FORMAT_VERSION + 1,
+            status: None,
+            harnesses: vec![],
+        };
+        assert_eq!(results.check_format_version(), Err(UnsupportedVersion(FORMAT_VERSION + 1)));
+    }
+}
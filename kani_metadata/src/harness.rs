@@ -3,7 +3,9 @@
 
 use crate::CbmcSolver;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// We emit this structure for each annotated proof harness (`#[kani::proof]`) we find.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -24,6 +26,86 @@ pub struct HarnessMetadata {
     pub goto_file: Option<PathBuf>,
     /// The `#[kani::<>]` attributes added to a harness.
     pub attributes: HarnessAttributes,
+    /// The documentation comments attached to the harness, if any.
+    /// Each line of the comment is kept, with the leading `///` removed.
+    pub doc: Option<String>,
+    /// How long the compiler took to generate the model for this harness, if known.
+    pub codegen_time: Option<Duration>,
+    /// The nondeterministic values introduced by the model of this harness, if known.
+    pub nondet_stats: Option<NondetStats>,
+    /// Where the checks that have a unique identifier end in the source code, indexed by their
+    /// identifier. The verification output only includes where a check starts, which doesn't
+    /// tell apart nested expressions, e.g., the overflow checks of `a + b + c`.
+    #[serde(default)]
+    pub check_span_ends: BTreeMap<String, SpanEnd>,
+    /// The functions of this crate that contain `unsafe` code and that are reachable from this
+    /// harness. See [crate::UnsafeItem].
+    #[serde(default)]
+    pub reached_unsafe: BTreeSet<String>,
+    /// The trusted functions reachable from this harness, which were compiled with their checks
+    /// disabled (`--trusted <PATH>`). Their paths start with the name of their crate.
+    #[serde(default)]
+    pub trusted_code: BTreeSet<String>,
+}
+
+/// The position where the source span of a check ends.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpanEnd {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Statistics about the nondeterministic values (e.g.: created by `kani::any()`) that the model
+/// of a harness introduces.
+///
+/// The model creates all the values of a type in the same place, i.e. the instance of
+/// `kani::any_raw_internal` for that type. So each type is counted once, and with the size of a
+/// single value, no matter how many values of that type the harness creates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NondetStats {
+    /// The number of places that create a nondeterministic value, i.e. the number of types.
+    pub count: usize,
+    /// The total size in bits of a single value of each type.
+    pub total_bits: u64,
+    /// The nondeterministic values grouped by type.
+    #[serde(default)]
+    pub inputs: Vec<NondetInput>,
+}
+
+/// The nondeterministic values of a single type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NondetInput {
+    /// The type of the values, e.g. `[u8; 1024]`.
+    pub ty: String,
+    /// The number of places that create a value of this type.
+    pub count: usize,
+    /// The total size in bits of the values created in those places.
+    pub total_bits: u64,
+}
+
+impl NondetStats {
+    /// Record a nondeterministic value of the given type and size.
+    pub fn record(&mut self, ty: &str, bits: u64) {
+        self.count += 1;
+        self.total_bits += bits;
+        match self.inputs.iter_mut().find(|input| input.ty == ty) {
+            Some(input) => {
+                input.count += 1;
+                input.total_bits += bits;
+            }
+            None => {
+                self.inputs.push(NondetInput { ty: ty.to_string(), count: 1, total_bits: bits })
+            }
+        }
+    }
+
+    /// The inputs with the largest total size, largest first.
+    pub fn largest_inputs(&self, max: usize) -> Vec<&NondetInput> {
+        let mut inputs: Vec<_> = self.inputs.iter().filter(|input| input.total_bits > 0).collect();
+        inputs.sort_by(|a, b| b.total_bits.cmp(&a.total_bits).then_with(|| a.ty.cmp(&b.ty)));
+        inputs.truncate(max);
+        inputs
+    }
 }
 
 /// The attributes added by the user to control how a harness is executed.
@@ -37,8 +119,17 @@ pub struct HarnessAttributes {
     pub solver: Option<CbmcSolver>,
     /// Optional data to store unwind value.
     pub unwind_value: Option<u32>,
+    /// The verification timeout in seconds, if any.
+    #[serde(default)]
+    pub timeout: Option<u32>,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
+    /// The function whose contract this harness checks (`proof_for_contract`), if any.
+    #[serde(default)]
+    pub for_contract: Option<String>,
+    /// The file with the lines that the output of the harness must contain, if any.
+    #[serde(default)]
+    pub expected_output: Option<PathBuf>,
 }
 
 /// The stubbing type.
@@ -60,4 +151,10 @@ pub fn get_harness_name_unqualified(&self) -> &str {
             &self.pretty_name
         }
     }
+
+    /// Get the first line of the harness documentation, which is usually a summary of what the
+    /// harness checks.
+    pub fn doc_summary(&self) -> Option<&str> {
+        self.doc.as_deref().and_then(|doc| doc.lines().find(|line| !line.trim().is_empty()))
+    }
 }
@@ -10,11 +10,15 @@
 pub use artifact::ArtifactType;
 pub use cbmc_solver::CbmcSolver;
 pub use harness::*;
+pub use mmio::MmioRegion;
+pub use unsafe_code::{UnsafeItem, UnsafeKind};
 pub use vtable::*;
 
 pub mod artifact;
 mod cbmc_solver;
 mod harness;
+mod mmio;
+mod unsafe_code;
 pub mod unstable;
 mod vtable;
 
@@ -32,6 +36,9 @@ pub struct KaniMetadata {
     pub unsupported_features: Vec<UnsupportedFeature>,
     /// If crates are built in test-mode, then test harnesses will be recorded here.
     pub test_harnesses: Vec<HarnessMetadata>,
+    /// The `unsafe` blocks and functions written in this crate.
+    #[serde(default)]
+    pub unsafe_items: Vec<UnsafeItem>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,66 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// An address range of memory-mapped I/O registers, given as `<BASE>:<LEN>`.
+///
+/// Volatile reads from this range return non-deterministic values and volatile writes to it
+/// are ignored. Both values may be given in decimal or hexadecimal (with a `0x` prefix) notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MmioRegion {
+    /// The first address of the region.
+    pub base: u64,
+    /// The size of the region in bytes.
+    pub len: u64,
+}
+
+fn parse_address(value: &str) -> Result<u64, String> {
+    let value = value.trim().replace('_', "");
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("`{value}` is not a valid address or length"))
+}
+
+impl FromStr for MmioRegion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (base, len) = value
+            .split_once(':')
+            .ok_or_else(|| format!("expected `<BASE>:<LEN>`, but found `{value}`"))?;
+        let region = MmioRegion { base: parse_address(base)?, len: parse_address(len)? };
+        if region.len == 0 {
+            return Err(format!("MMIO region `{value}` is empty"));
+        }
+        if region.base.checked_add(region.len - 1).is_none() {
+            return Err(format!("MMIO region `{value}` overflows the address space"));
+        }
+        Ok(region)
+    }
+}
+
+impl Display for MmioRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}:{:#x}", self.base, self.len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MmioRegion;
+
+    #[test]
+    fn check_parse_mmio_region() {
+        let region: MmioRegion = "0x4000_0000:0x100".parse().unwrap();
+        assert_eq!(region, MmioRegion { base: 0x4000_0000, len: 0x100 });
+        assert_eq!(region.to_string().parse::<MmioRegion>(), Ok(region));
+        assert_eq!("16:4".parse::<MmioRegion>(), Ok(MmioRegion { base: 16, len: 4 }));
+        assert!("0x4000_0000".parse::<MmioRegion>().is_err());
+        assert!("0x10:0".parse::<MmioRegion>().is_err());
+        assert!("0xffffffffffffffff:2".parse::<MmioRegion>().is_err());
+    }
+}
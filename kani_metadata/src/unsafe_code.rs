@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde::{Deserialize, Serialize};
+
+/// An `unsafe` block or function written in a crate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsafeItem {
+    pub kind: UnsafeKind,
+    /// The function that contains the item, or the item itself if it's an `unsafe fn`.
+    /// Harnesses that reach this function list it in their `reached_unsafe` metadata.
+    pub function: String,
+    pub filename: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsafeKind {
+    Block,
+    Function,
+}
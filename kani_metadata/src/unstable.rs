@@ -82,6 +82,55 @@ pub enum UnstableFeature {
     LineCoverage,
     /// Enable function contracts [RFC 9](https://model-checking.github.io/kani/rfc/rfcs/0009-function-contracts.html)
     FunctionContracts,
+    /// Check whether each `kani::assume` rules out every execution that reaches it.
+    AssumptionChecks,
+    /// Check which of the assertions that hold only do so because of the assumptions made by
+    /// the harness.
+    AssertionContest,
+    /// Warn about calls that reach code known to be expensive to verify, such as formatting,
+    /// hashing, or allocation inside a loop.
+    ComplexityLints,
+    /// Warn about the fields that an `unsafe impl Send` or `unsafe impl Sync` makes assumptions
+    /// about, because their type doesn't implement the trait. The assumptions aren't verified.
+    SendSyncLints,
+    /// Warn about symbolic values that a harness never uses, and about assumptions that follow
+    /// code that never returns.
+    HarnessLints,
+    /// Warn about the operations of a `const fn` whose result may differ between compile-time
+    /// evaluation and runtime, and verify the constants marked with `#[kani::const_parity]`.
+    ConstFnParity,
+    /// Replace the formatting machinery of `core::fmt` by a model that doesn't write anything.
+    FmtAbstraction,
+    /// Verify the byte-order conversions of integers, e.g. `to_ne_bytes`, for both little and big
+    /// endian targets.
+    EndianPortability,
+    /// Check that branches and memory addresses do not depend on values created with
+    /// `kani::secret()`.
+    ConstantTime,
+    /// Check that integer-to-pointer casts produce pointers derived from a live allocation.
+    ProvenanceChecks,
+    /// Check that mutable references passed to a function do not alias other references.
+    Aliasing,
+    /// Model stack unwinding on panic (`-C panic=unwind`) instead of aborting.
+    Unwind,
+    /// Check that the tag of an enum encodes one of its variants when its discriminant is read.
+    EnumValidityChecks,
+    /// Check that values are not dropped more than once. Leaks and drop order are not checked.
+    DropChecks,
+    /// Treat volatile accesses to the regions given by `--mmio-region` as memory-mapped I/O.
+    Mmio,
+    /// Model interrupts by invoking the handlers attached with `kani::interrupt::attach`.
+    Interrupts,
+    /// Export the verification results along with the configuration and tool versions used to
+    /// produce them, with `--evidence-dir`.
+    Evidence,
+    /// Verify the examples of the documentation that are fenced with ```` ```kani ````.
+    DocHarnesses,
+    /// Compile the code inside the paths given by `--trusted` with its checks disabled.
+    TrustedCode,
+    /// Reuse the build of a cargo project across invocations while its sources and its build
+    /// configuration don't change.
+    ProjectCache,
 }
 
 impl UnstableFeature {
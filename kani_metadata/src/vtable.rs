@@ -47,6 +47,27 @@ pub struct PossibleMethodEntry {
     pub possibilities: Vec<InternedString>,
 }
 
+/// A call-site that invokes a function pointer of a given type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FnPtrCallSite {
+    /// The canonical name of the function pointer type (see function `fn_ptr_restriction_key`
+    /// in the Kani compiler).
+    pub fn_ptr_type: InternedString,
+    /// The (mangled symbol name of the) function this code is within
+    pub function_name: InternedString,
+    /// The unique label we applied to this function invocation.
+    pub label: InternedString,
+}
+
+/// A set of possible targets for calls through a function pointer type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PossibleFnPtrEntry {
+    /// The canonical name of the function pointer type.
+    pub fn_ptr_type: InternedString,
+    /// The (mangled symbol names of the) functions whose address is taken with this type.
+    pub possibilities: Vec<InternedString>,
+}
+
 /// Represents the full set of vtable restrictions visible in this crate.
 /// Currently corresponds to a `*.restrictions.json` file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,4 +78,12 @@ pub struct VtableCtxResults {
     /// When all of these are aggregated together from all linked crates, these collectively represent
     /// the only function pointers that might exist in this vtable entry.
     pub possible_methods: Vec<PossibleMethodEntry>,
+    /// Each call through a function pointer that is visible in this crate.
+    #[serde(default)]
+    pub fn_ptr_call_sites: Vec<FnPtrCallSite>,
+    /// The functions whose address is taken in this crate, grouped by function pointer type.
+    /// When aggregated from all linked crates, these are the only functions that a call through
+    /// a function pointer of that type may invoke.
+    #[serde(default)]
+    pub possible_fn_ptrs: Vec<PossibleFnPtrEntry>,
 }
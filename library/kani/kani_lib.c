@@ -118,3 +118,43 @@ uint8_t *__rust_realloc(uint8_t *ptr, size_t old_size, size_t align, size_t new_
 
     return result;
 }
+
+// The layout of `struct timespec` on 64-bit targets. We don't include <time.h> to avoid
+// conflicting definitions (see the declarations above).
+struct __KANI_timespec {
+    int64_t tv_sec;
+    int64_t tv_nsec;
+};
+
+#define __KANI_CLOCK_REALTIME 0
+#define __KANI_NANOS_PER_SEC 1000000000
+
+// The last value returned for a monotonic clock.
+static bool __KANI_monotonic_init = 0;
+static struct __KANI_timespec __KANI_monotonic_last;
+
+// This is a model of the `clock_gettime` function, which is used by `std::time::Instant::now`
+// and `std::time::SystemTime::now`. It has the following signature:
+//     int clock_gettime(clockid_t clock_id, struct timespec *tp);
+// The real-time clock returns any valid time, since it can be adjusted at any moment. Every
+// other clock is treated as monotonic: it returns any time that isn't earlier than the time it
+// returned last.
+int clock_gettime(int clock_id, struct __KANI_timespec *tp)
+{
+    __KANI_assert(tp != 0, "clock_gettime must be called with a non-null pointer");
+    // Uninitialized variables have a non-deterministic value.
+    struct __KANI_timespec now;
+    __CPROVER_assume(now.tv_nsec >= 0 && now.tv_nsec < __KANI_NANOS_PER_SEC);
+    if (clock_id != __KANI_CLOCK_REALTIME) {
+        __CPROVER_assume(now.tv_sec >= 0);
+        if (__KANI_monotonic_init) {
+            __CPROVER_assume(now.tv_sec > __KANI_monotonic_last.tv_sec ||
+                             (now.tv_sec == __KANI_monotonic_last.tv_sec &&
+                              now.tv_nsec >= __KANI_monotonic_last.tv_nsec));
+        }
+        __KANI_monotonic_init = 1;
+        __KANI_monotonic_last = now;
+    }
+    *tp = now;
+    return 0;
+}
@@ -6,6 +6,7 @@
 
 use std::{
     marker::{PhantomData, PhantomPinned},
+    mem::MaybeUninit,
     num::*,
 };
 
@@ -15,6 +16,7 @@ pub trait Arbitrary
 where
     Self: Sized,
 {
+    #[rustc_diagnostic_item = "KaniArbitraryAny"]
     fn any() -> Self;
     fn any_array<const MAX_ARRAY_LENGTH: usize>() -> [Self; MAX_ARRAY_LENGTH]
     // the requirement defined in the where clause must appear on the `impl`'s method `any_array`
@@ -22,10 +24,55 @@ pub trait Arbitrary
     where
         [(); std::mem::size_of::<[Self; MAX_ARRAY_LENGTH]>()]:,
     {
-        [(); MAX_ARRAY_LENGTH].map(|_| Self::any())
+        // Initialize the elements in place, so `Self` doesn't need to be `Copy` or `Default`, and
+        // we avoid the drop guards used by `array::map`. If `Self::any()` unwinds, e.g. with
+        // `-Z unwind` or when concrete playback runs out of values, the elements already written
+        // are leaked, which is safe.
+        let mut array = MaybeUninit::<[Self; MAX_ARRAY_LENGTH]>::uninit();
+        let first = array.as_mut_ptr() as *mut Self;
+        let mut idx = 0;
+        while idx < MAX_ARRAY_LENGTH {
+            // SAFETY: `idx` is in bounds of the array, and each element is written exactly once.
+            unsafe { first.add(idx).write(Self::any()) };
+            idx += 1;
+        }
+        // SAFETY: All elements were initialized by the loop above.
+        unsafe { array.assume_init() }
     }
 }
 
+/// Restrict a value created by a derived `Arbitrary` implementation to the ones that satisfy the
+/// safety constraints of its type. The derive macro calls this function instead of
+/// `kani::assume`, so the assumption is made by the Kani library and `-Z assertion-contest`, which
+/// drops the assumptions made by the local crate, keeps it.
+#[doc(hidden)]
+#[inline(never)]
+pub fn assume_safety_constraints(cond: bool) {
+    crate::assume(cond)
+}
+
+/// This trait is implemented by local mirrors of foreign types, in order to create symbolic values
+/// of types that cannot implement `Arbitrary` due to the orphan rule.
+///
+/// Use the `#[kani::arbitrary_remote(path::to::ForeignType)]` attribute on a type with the same
+/// fields or variants as `ForeignType` to implement this trait, then use [crate::any_remote] to
+/// create a symbolic `ForeignType`:
+///
+/// ```
+/// #[kani::arbitrary_remote(std::ops::Range<u8>)]
+/// struct RangeDef {
+///     start: u8,
+///     end: u8,
+/// }
+///
+/// let range: std::ops::Range<u8> = kani::any_remote::<RangeDef>();
+/// ```
+pub trait RemoteArbitrary {
+    /// The foreign type that this type mirrors.
+    type Target;
+    fn any_remote() -> Self::Target;
+}
+
 /// The given type can be represented by an unconstrained symbolic value of size_of::<T>.
 macro_rules! trivial_arbitrary {
     ( $type: ty ) => {
@@ -179,3 +226,121 @@ fn any() -> Self {
         std::time::Duration::new(u64::any(), nanos)
     }
 }
+
+impl Arbitrary for std::net::Ipv4Addr {
+    fn any() -> Self {
+        std::net::Ipv4Addr::from(u32::any())
+    }
+}
+
+impl Arbitrary for std::net::Ipv6Addr {
+    fn any() -> Self {
+        std::net::Ipv6Addr::from(u128::any())
+    }
+}
+
+impl Arbitrary for std::net::IpAddr {
+    fn any() -> Self {
+        if bool::any() {
+            std::net::IpAddr::V4(Arbitrary::any())
+        } else {
+            std::net::IpAddr::V6(Arbitrary::any())
+        }
+    }
+}
+
+impl Arbitrary for std::net::SocketAddrV4 {
+    fn any() -> Self {
+        std::net::SocketAddrV4::new(Arbitrary::any(), u16::any())
+    }
+}
+
+impl Arbitrary for std::net::SocketAddrV6 {
+    fn any() -> Self {
+        std::net::SocketAddrV6::new(Arbitrary::any(), u16::any(), u32::any(), u32::any())
+    }
+}
+
+impl Arbitrary for std::net::SocketAddr {
+    fn any() -> Self {
+        if bool::any() {
+            std::net::SocketAddr::V4(Arbitrary::any())
+        } else {
+            std::net::SocketAddr::V6(Arbitrary::any())
+        }
+    }
+}
+
+impl Arbitrary for std::net::Shutdown {
+    fn any() -> Self {
+        match u8::any() {
+            0 => std::net::Shutdown::Read,
+            1 => std::net::Shutdown::Write,
+            _ => std::net::Shutdown::Both,
+        }
+    }
+}
+
+impl Arbitrary for std::cmp::Ordering {
+    fn any() -> Self {
+        match i8::any() {
+            0 => std::cmp::Ordering::Equal,
+            x if x < 0 => std::cmp::Ordering::Less,
+            _ => std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+/// Functions that can be called with arguments created by `kani::any()`, where `Args` is the
+/// tuple of argument types. This is implemented for functions with up to 12 arguments that
+/// implement `Arbitrary`.
+///
+/// `#[derive(Arbitrary)]` uses it to call the constructor given with
+/// `#[kani(constructor = "...")]`, see [call_with_any].
+pub trait CallWithAny<Args> {
+    type Output;
+    fn call_with_any(self) -> Self::Output;
+}
+
+/// This macro implements `CallWithAny` for functions whose arguments implement `Arbitrary`.
+macro_rules! call_with_any {
+    ($($typ:ident),*) => {
+        impl<Func, Ret, $($typ : Arbitrary),*> CallWithAny<($($typ,)*)> for Func
+        where
+            Func: FnOnce($($typ),*) -> Ret,
+        {
+            type Output = Ret;
+            #[inline(always)]
+            fn call_with_any(self) -> Ret {
+                self($(crate::any::<$typ>()),*)
+            }
+        }
+    }
+}
+
+call_with_any!();
+call_with_any!(A);
+call_with_any!(A, B);
+call_with_any!(A, B, C);
+call_with_any!(A, B, C, D);
+call_with_any!(A, B, C, D, E);
+call_with_any!(A, B, C, D, E, F);
+call_with_any!(A, B, C, D, E, F, G);
+call_with_any!(A, B, C, D, E, F, G, H);
+call_with_any!(A, B, C, D, E, F, G, H, I);
+call_with_any!(A, B, C, D, E, F, G, H, I, J);
+call_with_any!(A, B, C, D, E, F, G, H, I, J, K);
+call_with_any!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Call the given function with arguments created by `kani::any()`, and return its result.
+///
+/// ```
+/// fn new_range(start: u8, len: u8) -> std::ops::Range<u16> {
+///     start as u16..start as u16 + len as u16
+/// }
+///
+/// let range = kani::arbitrary::call_with_any(new_range);
+/// ```
+pub fn call_with_any<Args, Func: CallWithAny<Args>>(func: Func) -> Func::Output {
+    func.call_with_any()
+}
@@ -3,17 +3,44 @@
 
 //! Helper code for concrete playback.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// The environment variable that enables shrinking of failing concrete values.
+/// It is set by `kani playback --shrink`.
+const SHRINK_ENV_VAR: &str = "KANI_PLAYBACK_SHRINK";
+
+/// The maximum number of times the harness is executed while shrinking.
+const MAX_SHRINK_RUNS: usize = 1000;
 
 thread_local! {
     /// thread_local! gives us a separate CONCRETE_VALS instance for each thread.
     /// This allows us to run concrete playback unit tests in parallel.
     /// RefCell is necessary for mut statics.
     static CONCRETE_VALS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+    /// The type of each concrete value consumed by the current execution of the harness.
+    static CONCRETE_TYPES: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    /// Whether this thread is executing the harness to shrink its concrete values, in which case
+    /// panics are recorded in LAST_PANIC instead of being printed.
+    static SHRINKING: Cell<bool> = Cell::new(false);
+    static LAST_PANIC: RefCell<Option<String>> = RefCell::new(None);
 }
 
 /// This function sets concrete values and plays back the user's proof harness.
-pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, proof_harness: F) {
+///
+/// If shrinking is enabled and the harness fails, the concrete values are first shrunk to the
+/// simplest values that still fail with the same panic, and the harness is played back with them.
+pub fn concrete_playback_run<F: Fn()>(local_concrete_vals: Vec<Vec<u8>>, proof_harness: F) {
+    let concrete_vals = if std::env::var_os(SHRINK_ENV_VAR).is_some() {
+        shrink(local_concrete_vals, &proof_harness)
+    } else {
+        local_concrete_vals
+    };
+    playback(concrete_vals, &proof_harness);
+}
+
+fn playback<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, proof_harness: &F) {
     // Det vals in the user test case should be in the same order as the order of kani::any() calls.
     // Here, we need to reverse this order because det vals are popped off of the outer Vec,
     // so the chronological first det val should come last.
@@ -52,6 +79,7 @@ pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
     CONCRETE_VALS.with(|glob_concrete_vals| {
         let mut_ref_glob_concrete_vals = &mut *glob_concrete_vals.borrow_mut();
         next_concrete_val = if SIZE_T > 0 {
+            CONCRETE_TYPES.with(|types| types.borrow_mut().push(std::any::type_name::<T>()));
             mut_ref_glob_concrete_vals.pop().expect("Not enough det vals found")
         } else {
             vec![]
@@ -63,3 +91,216 @@ pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
     ));
     std::mem::transmute_copy::<[u8; SIZE_T], T>(&bytes_t)
 }
+
+/// The kind of a concrete value, which determines the direction it is shrunk in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ValueKind {
+    /// Signed integers are shrunk toward zero from both sides.
+    Signed,
+    /// Floats are shrunk to zero, or to their integral part.
+    Float,
+    /// Anything else, including unsigned integers, `bool`, `char`, and values that weren't
+    /// consumed by the harness, is shrunk toward zero as an unsigned number.
+    Unsigned,
+}
+
+impl ValueKind {
+    fn of(type_name: Option<&str>) -> ValueKind {
+        match type_name {
+            Some("i8" | "i16" | "i32" | "i64" | "i128" | "isize") => ValueKind::Signed,
+            Some("f32" | "f64") => ValueKind::Float,
+            _ => ValueKind::Unsigned,
+        }
+    }
+}
+
+/// Shrink the concrete values of a failing harness.
+///
+/// Each value is shrunk toward zero while the harness keeps failing with the same panic, i.e. at
+/// the same location and with the same message. Checking the message too tells apart the checks
+/// that are reported at the same location, e.g. the ones made by a function of the Kani library.
+/// Since `#[derive(Arbitrary)]` picks the variant of an enum with a single integer, and
+/// `kani::vec::any_vec` picks the length of the vector with another, this shrinks enum variants
+/// toward the first one and collections toward empty, besides numbers toward zero.
+/// A simpler variant or a shorter collection can consume fewer values than the original one, so
+/// each candidate is also tried without the value that follows it.
+fn shrink<F: Fn()>(concrete_vals: Vec<Vec<u8>>, proof_harness: &F) -> Vec<Vec<u8>> {
+    let Some((failure, mut types)) = failure(&concrete_vals, proof_harness) else {
+        return concrete_vals;
+    };
+    let mut best = concrete_vals.clone();
+    let mut runs = 0;
+    let mut still_fails = |vals: &[Vec<u8>], types: &mut Vec<&'static str>| {
+        if runs == MAX_SHRINK_RUNS {
+            return false;
+        }
+        runs += 1;
+        match self::failure(vals, proof_harness) {
+            Some((panic, new_types)) if panic == failure => {
+                *types = new_types;
+                true
+            }
+            _ => false,
+        }
+    };
+    let mut improved = true;
+    while improved {
+        improved = false;
+        let mut idx = 0;
+        while idx < best.len() {
+            let kind = ValueKind::of(types.get(idx).copied());
+            for candidate in shrink_candidates(&best[idx], kind) {
+                let mut vals = best.clone();
+                vals[idx] = candidate;
+                if !still_fails(&vals, &mut types) {
+                    if idx + 1 == vals.len() {
+                        continue;
+                    }
+                    vals.remove(idx + 1);
+                    if !still_fails(&vals, &mut types) {
+                        continue;
+                    }
+                }
+                best = vals;
+                improved = true;
+                break;
+            }
+            idx += 1;
+        }
+    }
+    // Drop the values that the harness doesn't consume anymore.
+    if types.len() < best.len() && still_fails(&best[..types.len()], &mut types) {
+        best.truncate(types.len());
+    }
+    if best != concrete_vals {
+        println!("Shrunk the concrete values of the failing test to:");
+        println!("{:<4}let concrete_vals: Vec<Vec<u8>> = vec![", " ");
+        for (idx, val) in best.iter().enumerate() {
+            if let Some(interp_val) = types.get(idx).and_then(|name| interpret(val, name)) {
+                println!("{:<8}// {interp_val}", " ");
+            }
+            println!("{:<8}vec!{val:?},", " ");
+        }
+        println!("{:<4}];", " ");
+    }
+    best
+}
+
+/// Play back the harness without printing panics. Return the location and the message of the
+/// panic, if the harness failed, along with the types of the concrete values it consumed.
+fn failure<F: Fn()>(
+    concrete_vals: &[Vec<u8>],
+    proof_harness: &F,
+) -> Option<(String, Vec<&'static str>)> {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if SHRINKING.with(|shrinking| shrinking.get()) {
+                let location = info.location().map(|location| location.to_string());
+                let payload = info.payload();
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+                let panic = format!("{}: {}", location.unwrap_or_default(), message.unwrap_or(""));
+                LAST_PANIC.with(|last| *last.borrow_mut() = Some(panic));
+            } else {
+                default_hook(info)
+            }
+        }));
+    });
+    CONCRETE_TYPES.with(|types| types.borrow_mut().clear());
+    SHRINKING.with(|shrinking| shrinking.set(true));
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(|| playback(concrete_vals.to_vec(), proof_harness)));
+    SHRINKING.with(|shrinking| shrinking.set(false));
+    let types = CONCRETE_TYPES.with(|types| types.take());
+    result.err()?;
+    let panic = LAST_PANIC.with(|last| last.take()).unwrap_or_default();
+    Some((panic, types))
+}
+
+/// The values to try instead of the given one, from the simplest to the closest to it.
+fn shrink_candidates(bytes: &[u8], kind: ValueKind) -> Vec<Vec<u8>> {
+    let zero = vec![0; bytes.len()];
+    if bytes.is_empty() {
+        return vec![];
+    }
+    if bytes.len() > 16 {
+        return if bytes == zero { vec![] } else { vec![zero] };
+    }
+    let value = to_int(bytes);
+    let mut candidates = vec![];
+    match kind {
+        ValueKind::Float => {
+            if value != 0 {
+                candidates.push(zero);
+            }
+            let trunc = match bytes.len() {
+                4 => f32::from_bits(value as u32).trunc().to_bits() as u128,
+                8 => f64::from_bits(value as u64).trunc().to_bits() as u128,
+                _ => value,
+            };
+            if trunc != value && trunc != 0 {
+                candidates.push(from_int(trunc, bytes.len()));
+            }
+        }
+        ValueKind::Signed | ValueKind::Unsigned => {
+            let bits = bytes.len() as u32 * 8;
+            let negative = kind == ValueKind::Signed && value >> (bits - 1) == 1;
+            let mask = u128::MAX >> (128 - bits);
+            let distance = if negative { value.wrapping_neg() & mask } else { value };
+            // Try to halve the distance to zero, then to reduce it by smaller and smaller steps.
+            let mut step = distance;
+            while step > 0 {
+                let new_distance = distance - step;
+                let new_value =
+                    if negative { new_distance.wrapping_neg() & mask } else { new_distance };
+                candidates.push(from_int(new_value, bytes.len()));
+                step /= 2;
+            }
+        }
+    }
+    candidates
+}
+
+/// Read the bytes of a value of up to 16 bytes as an unsigned integer, in the target endianness.
+fn to_int(bytes: &[u8]) -> u128 {
+    let fold = |acc: u128, byte: &u8| (acc << 8) | *byte as u128;
+    if cfg!(target_endian = "little") {
+        bytes.iter().rev().fold(0, fold)
+    } else {
+        bytes.iter().fold(0, fold)
+    }
+}
+
+fn from_int(value: u128, len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = (0..len).map(|idx| (value >> (idx * 8)) as u8).collect();
+    if cfg!(target_endian = "big") {
+        bytes.reverse();
+    }
+    bytes
+}
+
+/// The value of a primitive, in the same format as the comments of generated tests.
+fn interpret(bytes: &[u8], type_name: &str) -> Option<String> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let value = to_int(bytes);
+    let bits = bytes.len() as u32 * 8;
+    let interp = match ValueKind::of(Some(type_name)) {
+        ValueKind::Signed => (((value << (128 - bits)) as i128) >> (128 - bits)).to_string(),
+        ValueKind::Float if bits == 32 => f32::from_bits(value as u32).to_string(),
+        ValueKind::Float => f64::from_bits(value as u64).to_string(),
+        ValueKind::Unsigned if type_name == "bool" => (value != 0).to_string(),
+        ValueKind::Unsigned
+            if matches!(type_name, "u8" | "u16" | "u32" | "u64" | "u128" | "usize") =>
+        {
+            value.to_string()
+        }
+        ValueKind::Unsigned => return None,
+    };
+    Some(interp)
+}
@@ -90,7 +90,9 @@
 //! ```
 //!
 //! The harness is checked like any other by running `cargo kani` and can be
-//! specifically selected with `--harness my_div_harness`.
+//! specifically selected with `--harness my_div_harness`. All the harnesses
+//! that check the contract of a function can be selected with
+//! `--contract my_div`.
 //!
 //! Once we have verified that our contract holds, we can use perhaps it's
 //! coolest feature: verified stubbing. This allows us to use the conditions of
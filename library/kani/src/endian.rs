@@ -0,0 +1,168 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides helpers to verify serialization code for both byte orders.
+//!
+//! The result of the native byte-order conversions, e.g. `u32::to_ne_bytes`, depends on the
+//! target, and Kani only supports little endian targets. With `-Z endian-portability`, Kani
+//! stubs these conversions with the models in this module, which follow the byte order returned
+//! by [Endian::target]. This byte order is nondeterministic, so harnesses are verified for both
+//! little and big endian targets. Conversions with an explicit byte order, e.g. `to_le_bytes`,
+//! keep their semantics.
+//!
+//! Only the conversion functions of the integer and floating-point types are modeled. Code that
+//! reinterprets memory, e.g. with `transmute` or by casting pointers, still uses the little
+//! endian layout.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! use kani::endian::{ByteOrder, Endian};
+//!
+//! #[kani::proof]
+//! fn check_round_trip() {
+//!     let value: u32 = kani::any();
+//!     let endian: Endian = kani::any();
+//!     assert_eq!(u32::from_bytes(value.to_bytes(endian), endian), value);
+//! }
+//! ```
+
+use crate::Arbitrary;
+
+/// The order of the bytes of a value in memory.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Endian {
+    /// The least significant byte comes first.
+    Little,
+    /// The most significant byte comes first.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target that Kani was built for.
+    pub const NATIVE: Endian =
+        if cfg!(target_endian = "big") { Endian::Big } else { Endian::Little };
+
+    /// The byte order of the target that the code is verified for. This is [Endian::NATIVE],
+    /// unless `-Z endian-portability` is enabled, in which case it is nondeterministic, but the
+    /// same for the whole execution of the harness.
+    #[inline(never)]
+    pub fn target() -> Endian {
+        Endian::NATIVE
+    }
+}
+
+impl Arbitrary for Endian {
+    fn any() -> Self {
+        if bool::any() { Endian::Little } else { Endian::Big }
+    }
+}
+
+/// Conversions between values and their bytes in a given byte order.
+pub trait ByteOrder: Sized {
+    /// The bytes of the value, e.g. `[u8; 4]` for `u32`.
+    type Bytes;
+
+    /// The bytes of the value in the given byte order.
+    fn to_bytes(self, endian: Endian) -> Self::Bytes;
+
+    /// The value represented by the bytes in the given byte order.
+    fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self;
+}
+
+macro_rules! byte_order_impl {
+    ( $($t:ty),* ) => {
+        $(
+            impl ByteOrder for $t {
+                type Bytes = [u8; std::mem::size_of::<$t>()];
+
+                fn to_bytes(self, endian: Endian) -> Self::Bytes {
+                    match endian {
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Big => self.to_be_bytes(),
+                    }
+                }
+
+                fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self {
+                    match endian {
+                        Endian::Little => <$t>::from_le_bytes(bytes),
+                        Endian::Big => <$t>::from_be_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+byte_order_impl! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64 }
+
+/// Models of the byte-order conversions of the integer types, which Kani uses as stubs with
+/// `-Z endian-portability`.
+///
+/// The conversions with an explicit byte order, e.g. `to_le_bytes`, are implemented in `core`
+/// with the native ones, e.g. `self.to_le().to_ne_bytes()`, so the models must be consistent
+/// with each other. They only rely on `swap_bytes` and on the little endian layout of the
+/// targets supported by Kani.
+#[doc(hidden)]
+pub mod models {
+    use super::Endian;
+    use crate::Arbitrary;
+
+    /// The byte order chosen for the current execution.
+    static mut TARGET: Option<Endian> = None;
+
+    /// Model of [Endian::target], which picks a nondeterministic byte order the first time it's
+    /// called.
+    pub fn any_target() -> Endian {
+        // SAFETY: Kani verifies a single thread.
+        unsafe { *TARGET.get_or_insert_with(Endian::any) }
+    }
+
+    macro_rules! byte_order_models {
+        ( $($t:ident),* ) => {
+            $(
+                pub mod $t {
+                    use super::{any_target, Endian};
+
+                    type T = core::primitive::$t;
+                    const SIZE: usize = core::mem::size_of::<T>();
+
+                    /// Convert from the target byte order to the little endian layout.
+                    fn to_layout(x: T) -> T {
+                        if any_target() == Endian::Big { x.swap_bytes() } else { x }
+                    }
+
+                    // `to_ne_bytes` is the function that this model replaces.
+                    #[allow(clippy::transmute_num_to_bytes)]
+                    pub fn to_ne_bytes(x: T) -> [u8; SIZE] {
+                        // SAFETY: integers can always be transmuted to arrays of bytes.
+                        unsafe { core::mem::transmute(to_layout(x)) }
+                    }
+
+                    pub fn from_ne_bytes(bytes: [u8; SIZE]) -> T {
+                        // SAFETY: arrays of bytes can always be transmuted to integers.
+                        to_layout(unsafe { core::mem::transmute(bytes) })
+                    }
+
+                    pub fn to_le(x: T) -> T {
+                        to_layout(x)
+                    }
+
+                    pub fn from_le(x: T) -> T {
+                        to_layout(x)
+                    }
+
+                    pub fn to_be(x: T) -> T {
+                        to_layout(x).swap_bytes()
+                    }
+
+                    pub fn from_be(x: T) -> T {
+                        to_layout(x).swap_bytes()
+                    }
+                }
+            )*
+        };
+    }
+
+    byte_order_models! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize }
+}
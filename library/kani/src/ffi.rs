@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains functions to generate symbolic values of the types used at FFI
+//! boundaries, such as C strings.
+use crate::vec::any_vec;
+use std::ffi::CString;
+use std::num::NonZeroU8;
+
+/// Generates an arbitrary C string whose length, excluding the nul terminator, is at most
+/// MAX_LENGTH. The string has no interior nul byte.
+///
+/// Use [`CString::as_c_str`] to obtain a [`std::ffi::CStr`] from the result.
+///
+/// # Example:
+///
+/// ```rust
+/// let s = kani::ffi::any_c_string::<4>();
+/// let c_str: &std::ffi::CStr = s.as_c_str();
+/// assert!(c_str.to_bytes().len() <= 4);
+/// ```
+pub fn any_c_string<const MAX_LENGTH: usize>() -> CString
+where
+    [(); std::mem::size_of::<[NonZeroU8; MAX_LENGTH]>()]:,
+{
+    CString::from(any_vec::<NonZeroU8, MAX_LENGTH>())
+}
+
+/// Generates an arbitrary byte string whose length is at most MAX_LENGTH that is nul terminated
+/// and has no interior nul byte, i.e., the bytes of a valid C string including its terminator.
+pub fn any_c_string_bytes<const MAX_LENGTH: usize>() -> Vec<u8>
+where
+    [(); std::mem::size_of::<[NonZeroU8; MAX_LENGTH]>()]:,
+{
+    any_c_string::<MAX_LENGTH>().into_bytes_with_nul()
+}
@@ -0,0 +1,109 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides nondeterministic functions, to verify higher-order code, such as sorting
+//! with a comparator, against every behavior of its callbacks.
+//!
+//! An [AnyFn] behaves like a function from `I` to `O` that is only known through a table of
+//! input-output pairs. The table is filled when the function is called with a new input: the
+//! output is created with `kani::any()`, and later calls with the same input return the same
+//! output. The table holds up to `N` pairs, and calling the function with more than `N` distinct
+//! inputs fails verification, like an unwinding assertion does.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! use std::cmp::Ordering;
+//!
+//! #[kani::proof]
+//! #[kani::unwind(10)]
+//! fn check_sort_by_never_panics() {
+//!     let mut input: [u8; 3] = kani::any();
+//!     // A comparator with any behavior, including ones that aren't a total order.
+//!     let cmp = kani::any_fn::<(u8, u8), Ordering, 9>();
+//!     input.sort_by(|a, b| cmp.call((*a, *b)));
+//! }
+//! ```
+
+use crate::Arbitrary;
+use std::cell::RefCell;
+
+/// A nondeterministic function from `I` to `O`, defined by a table of up to `N` input-output
+/// pairs. Functions with several arguments take a tuple of them as input.
+///
+/// Use [any_fn] or `kani::any()` to create one.
+#[derive(Debug)]
+pub struct AnyFn<I, O, const N: usize> {
+    /// The inputs that the function was called with so far, and their output.
+    table: RefCell<Vec<(I, O)>>,
+}
+
+impl<I, O, const N: usize> AnyFn<I, O, N>
+where
+    I: PartialEq,
+    O: Arbitrary + Clone,
+{
+    /// Call the function. The output is arbitrary the first time the function is called with
+    /// the given input, and the same as before afterwards.
+    pub fn call(&self, input: I) -> O {
+        let mut table = self.table.borrow_mut();
+        let mut i = 0;
+        while i < table.len() {
+            if table[i].0 == input {
+                return table[i].1.clone();
+            }
+            i += 1;
+        }
+        assert!(
+            table.len() < N,
+            "`kani::AnyFn` was called with more distinct inputs than its table can hold"
+        );
+        let output = O::any();
+        table.push((input, output.clone()));
+        output
+    }
+
+    /// Borrow the function as a closure, e.g. to pass it to a function that is generic over
+    /// `Fn(I) -> O`.
+    pub fn as_fn(&self) -> impl Fn(I) -> O + '_ {
+        move |input| self.call(input)
+    }
+
+    /// The input-output pairs of the calls made so far, in the order of their first call.
+    pub fn table(&self) -> Vec<(I, O)>
+    where
+        I: Clone,
+    {
+        self.table.borrow().clone()
+    }
+}
+
+impl<I, O, const N: usize> Arbitrary for AnyFn<I, O, N>
+where
+    I: PartialEq,
+    O: Arbitrary + Clone,
+{
+    fn any() -> Self {
+        AnyFn { table: RefCell::new(Vec::with_capacity(N)) }
+    }
+}
+
+/// Creates a nondeterministic function from `I` to `O` that can be called with up to `N`
+/// distinct inputs.
+///
+/// The loops that look up the table need an unwinding bound of at least `N + 1`.
+///
+/// # Example:
+///
+/// ```rust
+/// let is_even = kani::any_fn::<u8, bool, 2>();
+/// let first = is_even.call(4);
+/// assert_eq!(is_even.call(4), first);
+/// ```
+pub fn any_fn<I, O, const N: usize>() -> AnyFn<I, O, N>
+where
+    I: PartialEq,
+    O: Arbitrary + Clone,
+{
+    AnyFn::any()
+}
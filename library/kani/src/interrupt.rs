@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains functions to model interrupts in embedded code.
+//!
+//! When Kani runs with `-Z interrupts`, the handlers attached with [`attach`] may be invoked
+//! before any statement of the crate under verification that accesses memory through a pointer.
+//! The total number of interrupts is bounded by the `--max-interrupts` option, which defaults to 1.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! static mut TICKS: u32 = 0;
+//!
+//! fn on_timer() {
+//!     unsafe { TICKS += 1 };
+//! }
+//!
+//! #[kani::proof]
+//! fn check_ticks() {
+//!     kani::interrupt::attach(on_timer);
+//!     let before = unsafe { TICKS };
+//!     let after = unsafe { TICKS };
+//!     // Fails with `-Z interrupts`, since the timer may fire between both reads.
+//!     assert_eq!(before, after);
+//! }
+//! ```
+
+/// Attach an interrupt handler. Up to 8 handlers may be attached.
+///
+/// Without `-Z interrupts`, this function has no effect.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAttachInterrupt"]
+pub fn attach(handler: fn()) {
+    let _ = handler;
+}
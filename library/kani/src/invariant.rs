@@ -0,0 +1,207 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module introduces the `Invariant` trait as well as its implementation for primitive types
+//! and common std containers.
+
+use crate::Arbitrary;
+use std::marker::{PhantomData, PhantomPinned};
+use std::num::*;
+
+/// This trait should be used to specify and check type safety invariants for a type.
+///
+/// A type's safety invariant is the condition that every value of that type must satisfy so that
+/// safe code can use it without causing undefined behavior. For example, a `Vec` whose length is
+/// larger than its capacity is not safe to use.
+///
+/// You can derive `Invariant` for structs and enums whose fields implement `Invariant`. The
+/// derived `is_safe` checks the invariant of every field, as well as any `#[safety_constraint]`
+/// attached to the fields:
+///
+/// ```
+/// #[derive(kani::Invariant)]
+/// struct PositivePoint {
+///     #[safety_constraint(*x > 0)]
+///     x: i32,
+///     #[safety_constraint(*y > 0)]
+///     y: i32,
+/// }
+/// ```
+pub trait Invariant {
+    /// Check whether `self` satisfies the safety invariant of its type.
+    fn is_safe(&self) -> bool;
+}
+
+/// Selects whether the harnesses that `#[derive(Invariant)]` generates for
+/// `cargo kani check-invariants` check a type.
+///
+/// A derive macro cannot tell whether the type also implements `Arbitrary`. Instead, the
+/// generated harness defines a trait implemented for `InvariantCheck<T>` when `T: Arbitrary`, and a
+/// fallback trait implemented for `&InvariantCheck<T>`. Method resolution on
+/// `(&InvariantCheck::<T>::NEW)` then picks [`check_arbitrary_invariant`] if `T` implements
+/// `Arbitrary`, and the no-op fallback otherwise.
+#[doc(hidden)]
+pub struct InvariantCheck<T>(PhantomData<T>);
+
+impl<T> InvariantCheck<T> {
+    pub const NEW: Self = InvariantCheck(PhantomData);
+}
+
+/// Check that `kani::any()` only produces values of `T` that satisfy its invariant.
+#[doc(hidden)]
+pub fn check_arbitrary_invariant<T: Arbitrary + Invariant>() {
+    let value: T = crate::any();
+    // `cargo kani check-invariants` finds the result of the check by this message.
+    crate::assert(value.is_safe(), "`kani::any()` only produces values that satisfy the invariant");
+}
+
+/// Any valid value of the given type is safe.
+macro_rules! trivial_invariant {
+    ( $type: ty ) => {
+        impl Invariant for $type {
+            #[inline(always)]
+            fn is_safe(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+trivial_invariant!(u8);
+trivial_invariant!(u16);
+trivial_invariant!(u32);
+trivial_invariant!(u64);
+trivial_invariant!(u128);
+trivial_invariant!(usize);
+
+trivial_invariant!(i8);
+trivial_invariant!(i16);
+trivial_invariant!(i32);
+trivial_invariant!(i64);
+trivial_invariant!(i128);
+trivial_invariant!(isize);
+
+// Similar to their `Arbitrary` implementation, NaN, infinite and subnormal values are considered
+// safe.
+trivial_invariant!(f32);
+trivial_invariant!(f64);
+
+trivial_invariant!(());
+trivial_invariant!(bool);
+trivial_invariant!(char);
+trivial_invariant!(str);
+trivial_invariant!(String);
+trivial_invariant!(PhantomPinned);
+
+// The validity of `NonZero` types already guarantees that they are not zero.
+trivial_invariant!(NonZeroU8);
+trivial_invariant!(NonZeroU16);
+trivial_invariant!(NonZeroU32);
+trivial_invariant!(NonZeroU64);
+trivial_invariant!(NonZeroU128);
+trivial_invariant!(NonZeroUsize);
+
+trivial_invariant!(NonZeroI8);
+trivial_invariant!(NonZeroI16);
+trivial_invariant!(NonZeroI32);
+trivial_invariant!(NonZeroI64);
+trivial_invariant!(NonZeroI128);
+trivial_invariant!(NonZeroIsize);
+
+impl<T: ?Sized> Invariant for PhantomData<T> {
+    #[inline(always)]
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Invariant + ?Sized> Invariant for &T {
+    fn is_safe(&self) -> bool {
+        (**self).is_safe()
+    }
+}
+
+impl<T: Invariant + ?Sized> Invariant for &mut T {
+    fn is_safe(&self) -> bool {
+        (**self).is_safe()
+    }
+}
+
+impl<T: Invariant + ?Sized> Invariant for Box<T> {
+    fn is_safe(&self) -> bool {
+        (**self).is_safe()
+    }
+}
+
+/// A slice is safe if all its elements are safe.
+impl<T: Invariant> Invariant for [T] {
+    fn is_safe(&self) -> bool {
+        // Use a while loop to avoid the overhead of the iterator machinery.
+        let mut idx = 0;
+        while idx < self.len() {
+            if !self[idx].is_safe() {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+}
+
+impl<T: Invariant, const N: usize> Invariant for [T; N] {
+    fn is_safe(&self) -> bool {
+        self.as_slice().is_safe()
+    }
+}
+
+/// A vector is safe if its length doesn't exceed its capacity and all its elements are safe.
+impl<T: Invariant> Invariant for Vec<T> {
+    fn is_safe(&self) -> bool {
+        self.len() <= self.capacity() && self.as_slice().is_safe()
+    }
+}
+
+impl<T: Invariant> Invariant for Option<T> {
+    fn is_safe(&self) -> bool {
+        match self {
+            Some(val) => val.is_safe(),
+            None => true,
+        }
+    }
+}
+
+impl<T: Invariant, E: Invariant> Invariant for Result<T, E> {
+    fn is_safe(&self) -> bool {
+        match self {
+            Ok(val) => val.is_safe(),
+            Err(err) => err.is_safe(),
+        }
+    }
+}
+
+/// This macro implements `Invariant` for a tuple whose elements implement `Invariant` by checking
+/// each element of the tuple.
+macro_rules! tuple_invariant {
+    ($($typ:ident),*) => {
+        impl<$($typ : Invariant),*> Invariant for ($($typ,)*) {
+            #[allow(non_snake_case)]
+            fn is_safe(&self) -> bool {
+                let ($($typ,)*) = self;
+                true $(&& $typ.is_safe())*
+            }
+        }
+    }
+}
+
+tuple_invariant!(A);
+tuple_invariant!(A, B);
+tuple_invariant!(A, B, C);
+tuple_invariant!(A, B, C, D);
+tuple_invariant!(A, B, C, D, E);
+tuple_invariant!(A, B, C, D, E, F);
+tuple_invariant!(A, B, C, D, E, F, G);
+tuple_invariant!(A, B, C, D, E, F, G, H);
+tuple_invariant!(A, B, C, D, E, F, G, H, I);
+tuple_invariant!(A, B, C, D, E, F, G, H, I, J);
+tuple_invariant!(A, B, C, D, E, F, G, H, I, J, K);
+tuple_invariant!(A, B, C, D, E, F, G, H, I, J, K, L);
@@ -0,0 +1,167 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains verification-friendly models of common iterator adapter chains.
+//!
+//! Chains such as `slice.iter().filter(..).map(..).collect()` are compiled into several layers of
+//! nested closures and `Iterator::next` calls, which makes symbolic execution expensive. The
+//! functions in this module compute the same results over a slice with a single indexed loop.
+//! Unlike their `std` counterparts, the output vector is allocated once with the capacity of the
+//! input, which avoids modeling reallocations.
+//!
+//! These models are opt-in: use them in place of the adapter chain in harnesses or in stubs.
+//!
+//! This module also provides [any_iter], an iterator over a bounded number of symbolic items, to
+//! verify code that consumes an iterator without building a vector first.
+//!
+//! # Example:
+//!
+//! ```rust
+//! let input: [u8; 4] = kani::any();
+//! let evens = kani::iter::filter_map(&input, |x| if x % 2 == 0 { Some(x / 2) } else { None });
+//! assert_eq!(evens, input.iter().filter(|x| *x % 2 == 0).map(|x| x / 2).collect::<Vec<_>>());
+//! ```
+
+use crate::{any_where, Arbitrary};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Equivalent to `slice.iter().map(f).collect::<Vec<_>>()`.
+pub fn map<T, U, F>(slice: &[T], mut f: F) -> Vec<U>
+where
+    F: FnMut(&T) -> U,
+{
+    let mut result = Vec::with_capacity(slice.len());
+    let mut i = 0;
+    while i < slice.len() {
+        result.push(f(&slice[i]));
+        i += 1;
+    }
+    result
+}
+
+/// Equivalent to `slice.iter().filter(|x| pred(x)).cloned().collect::<Vec<_>>()`.
+pub fn filter<T, P>(slice: &[T], mut pred: P) -> Vec<T>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    filter_map(slice, |x| if pred(x) { Some(x.clone()) } else { None })
+}
+
+/// Equivalent to `slice.iter().filter_map(f).collect::<Vec<_>>()`.
+pub fn filter_map<T, U, F>(slice: &[T], mut f: F) -> Vec<U>
+where
+    F: FnMut(&T) -> Option<U>,
+{
+    let mut result = Vec::with_capacity(slice.len());
+    let mut i = 0;
+    while i < slice.len() {
+        if let Some(val) = f(&slice[i]) {
+            result.push(val);
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Equivalent to `slice.iter().fold(init, f)`.
+pub fn fold<T, B, F>(slice: &[T], init: B, mut f: F) -> B
+where
+    F: FnMut(B, &T) -> B,
+{
+    let mut acc = init;
+    let mut i = 0;
+    while i < slice.len() {
+        acc = f(acc, &slice[i]);
+        i += 1;
+    }
+    acc
+}
+
+/// Equivalent to `slice.iter().position(pred)`.
+pub fn position<T, P>(slice: &[T], mut pred: P) -> Option<usize>
+where
+    P: FnMut(&T) -> bool,
+{
+    let mut i = 0;
+    while i < slice.len() {
+        if pred(&slice[i]) {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Equivalent to `slice.iter().any(pred)`.
+pub fn any<T, P>(slice: &[T], pred: P) -> bool
+where
+    P: FnMut(&T) -> bool,
+{
+    position(slice, pred).is_some()
+}
+
+/// Equivalent to `slice.iter().all(pred)`.
+pub fn all<T, P>(slice: &[T], mut pred: P) -> bool
+where
+    P: FnMut(&T) -> bool,
+{
+    position(slice, |x| !pred(x)).is_none()
+}
+
+/// Equivalent to `slice.iter().filter(|x| pred(x)).count()`.
+pub fn count<T, P>(slice: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    fold(slice, 0, |acc, x| if pred(x) { acc + 1 } else { acc })
+}
+
+/// An iterator that yields a symbolic number of symbolic items, up to `MAX_LENGTH` items.
+///
+/// Each item is created with `kani::any()` when it is requested, so no storage is allocated for
+/// the items. Use [any_iter] to create one.
+#[derive(Debug)]
+pub struct AnyIter<T, const MAX_LENGTH: usize> {
+    /// The number of items that are left.
+    remaining: usize,
+    _item: PhantomData<T>,
+}
+
+impl<T: Arbitrary, const MAX_LENGTH: usize> Iterator for AnyIter<T, MAX_LENGTH> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            None
+        } else {
+            self.remaining -= 1;
+            Some(T::any())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: Arbitrary, const MAX_LENGTH: usize> ExactSizeIterator for AnyIter<T, MAX_LENGTH> {}
+
+impl<T: Arbitrary, const MAX_LENGTH: usize> FusedIterator for AnyIter<T, MAX_LENGTH> {}
+
+/// Creates an iterator that yields at most `MAX_LENGTH` symbolic items. The number of items is
+/// also symbolic.
+///
+/// The harness needs an unwinding bound of at least `MAX_LENGTH + 1` for the loops that consume
+/// the iterator.
+///
+/// # Example:
+///
+/// ```rust
+/// let total: u32 = kani::any_iter::<u8, 3>().map(u32::from).sum();
+/// assert!(total <= 3 * 255);
+/// ```
+pub fn any_iter<T: Arbitrary, const MAX_LENGTH: usize>() -> AnyIter<T, MAX_LENGTH> {
+    AnyIter { remaining: any_where(|len| *len <= MAX_LENGTH), _item: PhantomData }
+}
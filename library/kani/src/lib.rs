@@ -20,16 +20,30 @@
 pub mod arbitrary;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
+pub mod endian;
+pub mod ffi;
+pub mod fmt;
+pub mod function;
 pub mod futures;
+pub mod interrupt;
+pub mod invariant;
+pub mod iter;
+pub mod simd;
 pub mod slice;
+pub mod state_machine;
+pub mod strings;
+pub mod sync;
 pub mod tuple;
 pub mod vec;
 
 mod models;
 
-pub use arbitrary::Arbitrary;
+pub use arbitrary::{Arbitrary, RemoteArbitrary};
 #[cfg(feature = "concrete_playback")]
 pub use concrete_playback::concrete_playback_run;
+pub use function::any_fn;
+pub use invariant::Invariant;
+pub use iter::any_iter;
 #[cfg(not(feature = "concrete_playback"))]
 /// NOP `concrete_playback` for type checking during verification mode.
 pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
@@ -70,10 +84,35 @@ pub fn assume(cond: bool) {
 #[inline(never)]
 #[rustc_diagnostic_item = "KaniAssume"]
 #[cfg(feature = "concrete_playback")]
+#[track_caller]
 pub fn assume(cond: bool) {
     assert!(cond, "`kani::assume` should always hold");
 }
 
+/// Creates an assumption like [`assume`], and records the given message so the
+/// assumption shows up in the assumption report printed at the end of the run.
+///
+/// The report lists, for every harness, each assumption created with this
+/// function together with its location and whether Kani found it reachable.
+///
+/// This function is called by the [`assume!`] macro. The macro is more
+/// convenient to use.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumeWithMessage"]
+#[cfg(not(feature = "concrete_playback"))]
+pub fn assume_with_message(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumeWithMessage"]
+#[cfg(feature = "concrete_playback")]
+#[track_caller]
+pub fn assume_with_message(cond: bool, msg: &'static str) {
+    assert!(cond, "`kani::assume` should always hold: {msg}");
+}
+
 /// `implies!(premise => conclusion)` means that if the `premise` is true, so
 /// must be the `conclusion`.
 ///
@@ -92,6 +131,74 @@ macro_rules! implies {
     };
 }
 
+/// `iff!(a, b)` means that `a` is true if and only if `b` is true.
+///
+/// This compares the two boolean expressions for equality, which is easier to
+/// read than `(a && b) || (!a && !b)`. Both sides are always evaluated, so they
+/// should not have side effects.
+#[macro_export]
+macro_rules! iff {
+    ($lhs:expr, $rhs:expr) => {{
+        let lhs: bool = $lhs;
+        let rhs: bool = $rhs;
+        lhs == rhs
+    }};
+}
+
+/// `result_of!(value, pattern => condition)` means that if `value` matches
+/// `pattern`, the `condition` must be true. The `condition` may use the
+/// bindings introduced by the `pattern`, and is only evaluated if the `value`
+/// matches it.
+///
+/// This is intended to be used in postconditions on functions that return an
+/// `Option` or a `Result`, e.g.:
+///
+/// ```ignore
+/// #[kani::ensures(kani::result_of!(result, Some(quotient) => quotient <= dividend))]
+/// fn checked_div(dividend: u32, divisor: u32) -> Option<u32> { ... }
+/// ```
+#[macro_export]
+macro_rules! result_of {
+    ($value:expr, $pattern:pat => $condition:expr) => {
+        match $value {
+            $pattern => $condition,
+            #[allow(unreachable_patterns)]
+            _ => true,
+        }
+    };
+}
+
+/// Whether this library was built for concrete playback. Used by [`cfg!`].
+#[doc(hidden)]
+pub const IS_PLAYBACK: bool = cfg!(feature = "concrete_playback");
+
+/// `kani::cfg!(predicate)` evaluates a configuration predicate at compile time, like
+/// [`core::cfg!`], with two extra predicates to tell how Kani is building the code:
+///
+/// - `kani::cfg!(verification)` is true when the code is compiled for verification.
+/// - `kani::cfg!(playback)` is true when the code is compiled for concrete playback.
+///
+/// Any other predicate is forwarded to [`core::cfg!`]. Note that `cfg(kani)` is set in both
+/// modes, so `kani::cfg!(kani)` cannot be used to tell them apart.
+///
+/// # Example:
+///
+/// ```rust
+/// let limit: usize = if kani::cfg!(verification) { 4 } else { 1024 };
+/// ```
+#[macro_export]
+macro_rules! cfg {
+    (verification) => {
+        !$crate::IS_PLAYBACK
+    };
+    (playback) => {
+        $crate::IS_PLAYBACK
+    };
+    ($($predicate:tt)*) => {
+        ::core::cfg!($($predicate)*)
+    };
+}
+
 /// A way to break the ownerhip rules. Only used by contracts where we can
 /// guarantee it is done safely.
 #[inline(never)]
@@ -121,10 +228,31 @@ pub const fn assert(cond: bool, msg: &'static str) {
 #[cfg(feature = "concrete_playback")]
 #[inline(never)]
 #[rustc_diagnostic_item = "KaniAssert"]
+#[track_caller]
 pub const fn assert(cond: bool, msg: &'static str) {
     assert!(cond, "{}", msg);
 }
 
+/// Creates a self-blocking check, which fails if `cond` doesn't hold, i.e. if the current thread
+/// would wait forever for a resource that only it could release. Used by the models in [`sync`].
+#[cfg(not(feature = "concrete_playback"))]
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniSelfBlockingCheck"]
+pub fn self_blocking_check(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+#[cfg(feature = "concrete_playback")]
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniSelfBlockingCheck"]
+#[track_caller]
+pub fn self_blocking_check(cond: bool, msg: &'static str) {
+    assert!(cond, "blocks forever: {}", msg);
+}
+
 /// Creates a cover property with the specified condition and message.
 ///
 /// # Example:
@@ -148,6 +276,17 @@ pub const fn assert(cond: bool, msg: &'static str) {
 #[rustc_diagnostic_item = "KaniCover"]
 pub fn cover(_cond: bool, _msg: &'static str) {}
 
+/// Return `value` unchanged, and mark the checks of the expression that computed it as expected
+/// to fail.
+///
+/// This function is called by the [`expect_fail!`] macro, which should be used instead.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniExpectFail"]
+#[doc(hidden)]
+pub fn expect_fail<T>(value: T, _class: &'static str) -> T {
+    value
+}
+
 /// This creates an symbolic *valid* value of type `T`. You can assign the return value of this
 /// function to a variable that you want to make symbolic.
 ///
@@ -164,11 +303,33 @@ pub fn cover(_cond: bool, _msg: &'static str) {}
 /// Note: This is a safe construct and can only be used with types that implement the `Arbitrary`
 /// trait. The Arbitrary trait is used to build a symbolic value that represents all possible
 /// valid values for type `T`.
+#[rustc_diagnostic_item = "KaniAny"]
 #[inline(always)]
 pub fn any<T: Arbitrary>() -> T {
     T::any()
 }
 
+/// This creates a symbolic *valid* value of a foreign type that is mirrored by `R`.
+///
+/// See [RemoteArbitrary] for how to declare the mirror of a foreign type.
+///
+/// # Example:
+///
+/// ```rust
+/// #[kani::arbitrary_remote(std::ops::Range<u8>)]
+/// struct RangeDef {
+///     start: u8,
+///     end: u8,
+/// }
+///
+/// let range: std::ops::Range<u8> = kani::any_remote::<RangeDef>();
+/// fn_under_verification(range);
+/// ```
+#[inline(always)]
+pub fn any_remote<R: RemoteArbitrary>() -> R::Target {
+    R::any_remote()
+}
+
 /// This creates a symbolic *valid* value of type `T`.
 /// The value is constrained to be a value accepted by the predicate passed to the filter.
 /// You can assign the return value of this function to a variable that you want to make symbolic.
@@ -186,6 +347,7 @@ pub fn any<T: Arbitrary>() -> T {
 /// Note: This is a safe construct and can only be used with types that implement the `Arbitrary`
 /// trait. The Arbitrary trait is used to build a symbolic value that represents all possible
 /// valid values for type `T`.
+#[rustc_diagnostic_item = "KaniAnyWhere"]
 #[inline(always)]
 pub fn any_where<T: Arbitrary, F: FnOnce(&T) -> bool>(f: F) -> T {
     let result = T::any();
@@ -193,6 +355,29 @@ pub fn any_where<T: Arbitrary, F: FnOnce(&T) -> bool>(f: F) -> T {
     result
 }
 
+/// This creates a symbolic *valid* value of type `T` that is treated as a secret.
+///
+/// Outside of constant-time mode, this is equivalent to [`any`]. When Kani is run with
+/// `-Z constant-time`, any branch condition or memory address that depends on a secret
+/// value is reported as a failed `constant_time` check.
+///
+/// # Example:
+///
+/// ```rust
+/// # fn compare(a: &[u8; 16], b: &[u8; 16]) -> bool {
+/// #     a.iter().zip(b).all(|(x, y)| x == y)
+/// # }
+/// let key: [u8; 16] = kani::secret();
+/// let tag: [u8; 16] = kani::any();
+/// // Fails in constant-time mode if `compare` exits early on the first mismatch.
+/// compare(&key, &tag);
+/// ```
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniSecret"]
+pub fn secret<T: Arbitrary>() -> T {
+    T::any()
+}
+
 /// This function creates a symbolic value of type `T`. This may result in an invalid value.
 ///
 /// # Safety
@@ -294,6 +479,97 @@ macro_rules! cover {
     };
 }
 
+/// `kani::expect_fail!(expr)` checks that at least one of the checks of `expr` fails.
+///
+/// This is used to write negative tests, which are only expected to fail at a specific place:
+/// the checks of `expr` that fail are reported as successful, and a failure is reported instead
+/// if none of them fails. Any other failure in the harness still makes verification fail.
+///
+/// The class of the checks that are expected to fail, e.g. `arithmetic_overflow` or `assertion`,
+/// can be given as a first argument. Failures of checks from other classes are then reported as
+/// usual.
+///
+/// Only the checks whose location is inside the macro call are considered, i.e., the checks of
+/// functions called by `expr` aren't.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// let y = kani::expect_fail!("arithmetic_overflow", x + 1);
+/// ```
+#[macro_export]
+macro_rules! expect_fail {
+    ($class:literal, $e:expr $(,)?) => {
+        kani::expect_fail($e, $class)
+    };
+    ($e:expr $(,)?) => {
+        kani::expect_fail($e, "")
+    };
+}
+
+/// `kani::assert_noninterference!(secret: T => output)` checks that `output` does not
+/// depend on `secret`.
+///
+/// This is implemented with self-composition: `output` is computed twice, each time with
+/// a different symbolic value of type `T` for `secret`, and the two results are asserted
+/// to be equal. Every other variable used by `output` is captured from the enclosing scope,
+/// so both computations share the same public inputs.
+///
+/// More than one secret can be given, separated by commas. The output type must implement
+/// `PartialEq`, and the computation must not mutate the variables it captures.
+///
+/// # Example:
+///
+/// ```rust
+/// let public: u32 = kani::any();
+/// // Prove that the result only depends on the public input.
+/// kani::assert_noninterference!(key: u32 => public.wrapping_add(key & 0));
+/// ```
+#[macro_export]
+macro_rules! assert_noninterference {
+    ($($secret:ident : $ty:ty),+ => $output:expr $(,)?) => {{
+        let run = |$($secret: $ty),+| $output;
+        let first = run($(kani::any::<$ty>()),+);
+        let second = run($(kani::any::<$ty>()),+);
+        kani::assert(
+            first == second,
+            concat!(
+                "noninterference: `",
+                stringify!($output),
+                "` depends on `",
+                stringify!($($secret),+),
+                "`"
+            ),
+        );
+    }};
+}
+
+/// `kani::assume!(cond, "why")` creates an assumption and records why it was made.
+///
+/// Unlike [`assume`], assumptions created with this macro are listed at the end
+/// of the run, with their location and whether they were reachable in each
+/// harness. This makes it easy to audit the constraints a harness places on
+/// its inputs.
+///
+/// # Example:
+///
+/// ```rust
+/// let len: usize = kani::any();
+/// kani::assume!(len < 10, "keep the buffer small to bound the loop");
+/// ```
+///
+/// If no message is given, the stringified condition is used instead.
+#[macro_export]
+macro_rules! assume {
+    ($cond:expr $(,)?) => {
+        kani::assume_with_message($cond, concat!("assumption: ", stringify!($cond)));
+    };
+    ($cond:expr, $msg:literal $(,)?) => {
+        kani::assume_with_message($cond, $msg);
+    };
+}
+
 // Kani proc macros must be in a separate crate
 pub use kani_macros::*;
 
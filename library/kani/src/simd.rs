@@ -0,0 +1,145 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides helpers to write harnesses for SIMD code.
+//!
+//! [Vector] is a SIMD vector with the same layout as the vector types used by `core::arch` and
+//! `std::simd`, so harnesses can create symbolic vectors with [any_vector], pass them to the code
+//! under verification, and inspect the result lane by lane, without declaring their own
+//! `#[repr(simd)]` types or platform intrinsics.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! use std::arch::x86_64::{__m128i, _mm_add_epi32};
+//!
+//! #[kani::proof]
+//! fn check_add() {
+//!     let a = kani::simd::any_vector::<i32, 4>();
+//!     let b = kani::simd::any_vector::<i32, 4>();
+//!     // SAFETY: `__m128i` is a SIMD type of the same size as the vectors.
+//!     let sum: __m128i = unsafe { _mm_add_epi32(a.to_simd(), b.to_simd()) };
+//!     let sum = unsafe { kani::simd::Vector::<i32, 4>::from_simd(sum) };
+//!     for i in 0..4 {
+//!         assert_eq!(sum.extract(i), a.extract(i).wrapping_add(b.extract(i)));
+//!     }
+//! }
+//! ```
+
+use crate::Arbitrary;
+use std::mem::{size_of, transmute_copy};
+
+/// The types that can be used as the lanes of a [Vector].
+pub trait Element: Copy + private::Sealed {}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! element_impl {
+    ( $($t:ty),* ) => {
+        $(
+            impl private::Sealed for $t {}
+            impl Element for $t {}
+        )*
+    };
+}
+
+element_impl! { u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64 }
+
+/// A SIMD vector with `LANES` lanes of type `T`.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Vector<T: Element, const LANES: usize>([T; LANES]);
+
+impl<T: Element, const LANES: usize> Vector<T, LANES> {
+    /// The number of lanes of the vector.
+    pub const LANES: usize = LANES;
+
+    /// Create a vector with the given value in every lane.
+    pub fn splat(value: T) -> Self {
+        Vector([value; LANES])
+    }
+
+    /// Create a vector from the values of its lanes.
+    pub fn from_array(lanes: [T; LANES]) -> Self {
+        Vector(lanes)
+    }
+
+    /// The values of the lanes of the vector.
+    pub fn to_array(self) -> [T; LANES] {
+        self.0
+    }
+
+    /// The value of the lane at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than `LANES`.
+    pub fn extract(self, index: usize) -> T {
+        self.0[index]
+    }
+
+    /// Replace the value of the lane at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than `LANES`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.0[index] = value;
+    }
+
+    /// Reinterpret the vector as another SIMD type, e.g. `__m128i` or a `std::simd` vector.
+    ///
+    /// # Panics
+    ///
+    /// If `V` doesn't have the same size as the vector.
+    ///
+    /// # Safety
+    ///
+    /// Every bit pattern of the vector must be a valid value of `V`. This holds for the SIMD
+    /// types of `core::arch` and `std::simd`, and for arrays of numbers, but not e.g. for
+    /// references, `bool` or `char`.
+    pub unsafe fn to_simd<V: Copy>(self) -> V {
+        assert_eq!(size_of::<V>(), size_of::<Self>(), "expected a SIMD type of the same size");
+        // SAFETY: both types have the same size, and the caller guarantees that the lanes are a
+        // valid value of `V`.
+        unsafe { transmute_copy(&self) }
+    }
+
+    /// Reinterpret another SIMD type, e.g. `__m128i` or a `std::simd` vector, as a vector.
+    ///
+    /// # Panics
+    ///
+    /// If `V` doesn't have the same size as the vector.
+    ///
+    /// # Safety
+    ///
+    /// Every byte of `simd` must be initialized, i.e. `V` must not have padding. This holds for
+    /// the SIMD types of `core::arch` and `std::simd`, and for arrays of numbers.
+    pub unsafe fn from_simd<V: Copy>(simd: V) -> Self {
+        assert_eq!(size_of::<V>(), size_of::<Self>(), "expected a SIMD type of the same size");
+        // SAFETY: both types have the same size, the caller guarantees that every byte is
+        // initialized, and any initialized bytes are valid lanes.
+        unsafe { transmute_copy(&simd) }
+    }
+}
+
+impl<T, const LANES: usize> Arbitrary for Vector<T, LANES>
+where
+    T: Element + Arbitrary,
+    [(); size_of::<[T; LANES]>()]:,
+{
+    fn any() -> Self {
+        Vector(crate::any())
+    }
+}
+
+/// Create a vector where every lane has a nondeterministic value.
+pub fn any_vector<T, const LANES: usize>() -> Vector<T, LANES>
+where
+    T: Element + Arbitrary,
+    [(); size_of::<[T; LANES]>()]:,
+{
+    crate::any()
+}
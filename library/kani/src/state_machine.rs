@@ -0,0 +1,119 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains a small framework to verify state machines, such as protocol
+//! implementations.
+//!
+//! A state machine implements the [`StateMachine`] trait, which describes how an action changes
+//! the current state and which invariant every reachable state must satisfy. The [`run`]
+//! function then explores every sequence of up to `MAX_STEPS` arbitrary actions starting from a
+//! given initial state, and checks the invariant after each step.
+//!
+//! Use the [`crate::state_machine_harness`] macro to generate a harness for a state machine.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! use kani::state_machine::StateMachine;
+//!
+//! #[derive(kani::Arbitrary)]
+//! enum Event { Open, Send, Close }
+//!
+//! #[derive(PartialEq)]
+//! enum Connection { Closed, Open { sent: u8 } }
+//!
+//! impl StateMachine for Connection {
+//!     type Action = Event;
+//!
+//!     fn is_enabled(&self, event: &Event) -> bool {
+//!         matches!((self, event), (Connection::Closed, Event::Open) | (Connection::Open { .. }, _))
+//!     }
+//!
+//!     fn step(&mut self, event: Event) {
+//!         *self = match (&*self, event) {
+//!             (Connection::Open { sent }, Event::Send) => Connection::Open { sent: sent.saturating_add(1) },
+//!             (_, Event::Close) => Connection::Closed,
+//!             _ => Connection::Open { sent: 0 },
+//!         }
+//!     }
+//!
+//!     fn invariant(&self) -> bool {
+//!         !matches!(self, Connection::Open { sent } if *sent > 2)
+//!     }
+//! }
+//!
+//! // Checks every sequence of up to 4 events. Fails, since a connection may send 3 messages.
+//! kani::state_machine_harness!(check_connection, Connection::Closed, 4, unwind = 5);
+//! ```
+
+use crate::Arbitrary;
+
+/// A state machine whose states are values of `Self`.
+pub trait StateMachine {
+    /// The actions that trigger a transition.
+    type Action: Arbitrary;
+
+    /// Whether the given action may be taken in the current state. Actions that are not enabled
+    /// are never passed to [`StateMachine::step`]. All actions are enabled by default.
+    fn is_enabled(&self, _action: &Self::Action) -> bool {
+        true
+    }
+
+    /// Perform the transition triggered by `action`.
+    fn step(&mut self, action: Self::Action);
+
+    /// The invariant that every reachable state must satisfy. Defaults to `true`.
+    fn invariant(&self) -> bool {
+        true
+    }
+}
+
+/// Check that every state reachable from `initial` in at most `MAX_STEPS` steps satisfies the
+/// invariant of the state machine. The invariant is also checked on the initial state.
+///
+/// This function contains a loop with `MAX_STEPS` iterations, so it requires an unwinding bound
+/// of at least `MAX_STEPS + 1`.
+pub fn run<M: StateMachine, const MAX_STEPS: usize>(initial: M) -> M {
+    let mut state = initial;
+    crate::assert(state.invariant(), "state machine invariant holds in the initial state");
+    for _ in 0..MAX_STEPS {
+        if crate::any() {
+            break;
+        }
+        let action: M::Action = crate::any();
+        crate::assume(state.is_enabled(&action));
+        state.step(action);
+        crate::assert(state.invariant(), "state machine invariant holds after each step");
+    }
+    state
+}
+
+/// Generate a proof harness that checks a state machine with [`state_machine::run`].
+///
+/// The harness explores every sequence of up to `$steps` actions from the initial state `$init`.
+/// An unwinding bound of `$steps + 1` must be provided, either with `unwind = <BOUND>` or with one
+/// of the `--unwind` and `--default-unwind` options.
+///
+/// [`state_machine::run`]: crate::state_machine::run
+///
+/// # Example:
+///
+/// ```ignore
+/// kani::state_machine_harness!(check_connection, Connection::Closed, 4, unwind = 5);
+/// ```
+#[macro_export]
+macro_rules! state_machine_harness {
+    ($name:ident, $init:expr, $steps:literal $(,)?) => {
+        #[kani::proof]
+        fn $name() {
+            kani::state_machine::run::<_, $steps>($init);
+        }
+    };
+    ($name:ident, $init:expr, $steps:literal, unwind = $unwind:literal $(,)?) => {
+        #[kani::proof]
+        #[kani::unwind($unwind)]
+        fn $name() {
+            kani::state_machine::run::<_, $steps>($init);
+        }
+    };
+}
@@ -0,0 +1,145 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains bounded models of common `str` operations and of integer parsing.
+//!
+//! The implementations in the standard library are optimized for speed on real hardware: they
+//! process several bytes at a time, rely on SIMD-friendly searching algorithms, and share code
+//! across radixes. This makes them expensive to verify. The functions in this module compute the
+//! same results with a single loop over the input bytes, so the number of loop iterations is
+//! bounded by the length of the input.
+//!
+//! # Example:
+//!
+//! ```rust
+//! assert_eq!(kani::strings::parse::<u32>("123"), "123".parse::<u32>());
+//! assert_eq!(kani::strings::split_once("key=value", '='), Some(("key", "value")));
+//! ```
+
+use std::num::ParseIntError;
+
+/// Integer types that can be parsed with [`parse`].
+pub trait ParseInt: Sized {
+    /// Parse an integer in base 10. Equivalent to `<Self as FromStr>::from_str(s)`.
+    fn parse_model(s: &str) -> Result<Self, ParseIntError>;
+}
+
+/// Equivalent to `s.parse::<T>()` for integer types.
+pub fn parse<T: ParseInt>(s: &str) -> Result<T, ParseIntError> {
+    T::parse_model(s)
+}
+
+// `ParseIntError` cannot be constructed outside the standard library, so we obtain each kind of
+// error by parsing a short constant string.
+fn empty_error() -> ParseIntError {
+    "".parse::<u8>().unwrap_err()
+}
+
+fn invalid_digit_error() -> ParseIntError {
+    "x".parse::<u8>().unwrap_err()
+}
+
+fn overflow_error(negative: bool) -> ParseIntError {
+    if negative { "-129".parse::<i8>().unwrap_err() } else { "256".parse::<u8>().unwrap_err() }
+}
+
+macro_rules! parse_int_model {
+    ( $type: ty, $signed: expr ) => {
+        impl ParseInt for $type {
+            fn parse_model(s: &str) -> Result<Self, ParseIntError> {
+                let bytes = s.as_bytes();
+                if bytes.is_empty() {
+                    return Err(empty_error());
+                }
+                let (negative, digits) = match bytes[0] {
+                    b'+' => (false, &bytes[1..]),
+                    b'-' if $signed => (true, &bytes[1..]),
+                    _ => (false, bytes),
+                };
+                if digits.is_empty() {
+                    return Err(invalid_digit_error());
+                }
+                let mut result: Self = 0;
+                let mut i = 0;
+                while i < digits.len() {
+                    let digit = digits[i].wrapping_sub(b'0');
+                    if digit > 9 {
+                        return Err(invalid_digit_error());
+                    }
+                    let next = result.checked_mul(10).and_then(|val| {
+                        if negative {
+                            val.checked_sub(digit as Self)
+                        } else {
+                            val.checked_add(digit as Self)
+                        }
+                    });
+                    result = match next {
+                        Some(val) => val,
+                        None => return Err(overflow_error(negative)),
+                    };
+                    i += 1;
+                }
+                Ok(result)
+            }
+        }
+    };
+}
+
+parse_int_model!(u8, false);
+parse_int_model!(u16, false);
+parse_int_model!(u32, false);
+parse_int_model!(u64, false);
+parse_int_model!(u128, false);
+parse_int_model!(usize, false);
+
+parse_int_model!(i8, true);
+parse_int_model!(i16, true);
+parse_int_model!(i32, true);
+parse_int_model!(i64, true);
+parse_int_model!(i128, true);
+parse_int_model!(isize, true);
+
+/// Equivalent to `haystack.find(needle)` where `needle` is a string.
+pub fn find(haystack: &str, needle: &str) -> Option<usize> {
+    let haystack = haystack.as_bytes();
+    let needle = needle.as_bytes();
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let mut start = 0;
+    while start <= haystack.len() - needle.len() {
+        let mut i = 0;
+        while i < needle.len() && haystack[start + i] == needle[i] {
+            i += 1;
+        }
+        if i == needle.len() {
+            return Some(start);
+        }
+        start += 1;
+    }
+    None
+}
+
+/// Equivalent to `s.find(c)` where `c` is a character.
+pub fn find_char(s: &str, c: char) -> Option<usize> {
+    let mut buf = [0u8; 4];
+    find(s, c.encode_utf8(&mut buf))
+}
+
+/// Equivalent to `s.split_once(delimiter)`.
+pub fn split_once(s: &str, delimiter: char) -> Option<(&str, &str)> {
+    let idx = find_char(s, delimiter)?;
+    Some((&s[..idx], &s[idx + delimiter.len_utf8()..]))
+}
+
+/// Equivalent to `s.split(delimiter).collect::<Vec<_>>()`.
+pub fn split(s: &str, delimiter: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut rest = s;
+    while let Some((first, second)) = split_once(rest, delimiter) {
+        result.push(first);
+        rest = second;
+    }
+    result.push(rest);
+    result
+}
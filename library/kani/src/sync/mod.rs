@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Verification-friendly models of the synchronization primitives of `std::sync`.
+//!
+//! Kani verifies a single thread, so these models replace the blocking and atomic machinery of
+//! their `std` counterparts, which is expensive to verify, with plain data structures that have
+//! the same API. Operations that would block forever in a single thread, e.g. locking a mutex that
+//! is already locked, or receiving from an empty channel whose senders are still alive, fail a
+//! self-blocking check instead.
+//!
+//! The models don't explore the interleavings of threads, so they don't detect deadlocks: they
+//! don't find data races, nor a thread that waits for another one, e.g. two threads that acquire
+//! two locks in opposite order. They check how a single thread uses them.
+//!
+//! These models are opt-in: import them instead of the `std` modules when building for Kani, e.g.:
+//!
+//! ```rust
+//! #[cfg(kani)]
+//! use kani::sync::{mpsc, Mutex};
+//! #[cfg(not(kani))]
+//! use std::sync::{mpsc, Mutex};
+//! ```
+
+/// Report that the current thread would block forever. The check has its own property class,
+/// `self_blocking`, so it is reported apart from the assertions of the harness.
+macro_rules! blocks_forever {
+    ($msg:literal) => {{
+        crate::self_blocking_check(false, $msg);
+        unreachable!($msg)
+    }};
+}
+
+pub mod mpsc;
+mod mutex;
+mod rwlock;
+
+pub use mutex::{Mutex, MutexGuard};
+pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use std::sync::{LockResult, PoisonError, TryLockError, TryLockResult};
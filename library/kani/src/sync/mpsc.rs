@@ -0,0 +1,276 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Model of `std::sync::mpsc`, for verifying producer and consumer logic that communicates
+//! through channels.
+//!
+//! A channel is a queue shared by its senders and its receiver. Messages are delivered in the
+//! order they are sent, and sending or receiving fails once the other side is disconnected, like
+//! in `std`. Since Kani verifies a single thread:
+//!
+//! - Receiving from an empty channel fails a self-blocking check if a sender is still connected,
+//!   since the call would block forever. Use [Receiver::try_recv] to check whether a message is
+//!   ready.
+//! - Sending to a full [sync_channel] fails a self-blocking check for the same reason. Rendezvous
+//!   channels, i.e. with a bound of 0, are always full.
+//! - An asynchronous [channel] can hold up to [MAX_MESSAGES] messages. Sending more fails
+//!   verification, like an unwinding assertion does.
+//!
+//! The senders and the receiver are not `Send`, since they are not meant to cross threads.
+//!
+//! # Example:
+//!
+//! ```rust
+//! use kani::sync::mpsc;
+//!
+//! let (tx, rx) = mpsc::channel();
+//! let producer = tx.clone();
+//! producer.send(1u8).unwrap();
+//! drop(producer);
+//! tx.send(2).unwrap();
+//! drop(tx);
+//! assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+pub use std::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+
+/// The maximum number of messages that an asynchronous channel can hold.
+pub const MAX_MESSAGES: usize = 8;
+
+/// The state shared by both sides of a channel.
+struct Channel<T> {
+    queue: VecDeque<T>,
+    /// The maximum number of messages in the queue.
+    capacity: usize,
+    senders: usize,
+    receiver_connected: bool,
+}
+
+type Shared<T> = Rc<RefCell<Channel<T>>>;
+
+fn new_channel<T>(capacity: usize) -> (Shared<T>, Receiver<T>) {
+    let channel = Rc::new(RefCell::new(Channel {
+        queue: VecDeque::with_capacity(capacity),
+        capacity,
+        senders: 1,
+        receiver_connected: true,
+    }));
+    (channel.clone(), Receiver { channel })
+}
+
+/// Push a message into the channel, or return it if the channel can't take it.
+fn try_push<T>(channel: &Shared<T>, t: T) -> Result<(), TrySendError<T>> {
+    let mut channel = channel.borrow_mut();
+    if !channel.receiver_connected {
+        Err(TrySendError::Disconnected(t))
+    } else if channel.queue.len() == channel.capacity {
+        Err(TrySendError::Full(t))
+    } else {
+        channel.queue.push_back(t);
+        Ok(())
+    }
+}
+
+/// Model of [`std::sync::mpsc::channel`]. The channel holds up to [MAX_MESSAGES] messages.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (channel, receiver) = new_channel(MAX_MESSAGES);
+    (Sender { channel }, receiver)
+}
+
+/// Model of [`std::sync::mpsc::sync_channel`]. The channel holds up to `bound` messages.
+pub fn sync_channel<T>(bound: usize) -> (SyncSender<T>, Receiver<T>) {
+    let (channel, receiver) = new_channel(bound);
+    (SyncSender { channel }, receiver)
+}
+
+/// Model of [`std::sync::mpsc::Sender`].
+pub struct Sender<T> {
+    channel: Shared<T>,
+}
+
+impl<T> Sender<T> {
+    /// Send a message, or return it if the receiver is disconnected.
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        match try_push(&self.channel, t) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Disconnected(t)) => Err(SendError(t)),
+            Err(TrySendError::Full(_)) => {
+                panic!("`kani::sync::mpsc::channel` holds more than `MAX_MESSAGES` messages")
+            }
+        }
+    }
+}
+
+/// Model of [`std::sync::mpsc::SyncSender`].
+pub struct SyncSender<T> {
+    channel: Shared<T>,
+}
+
+impl<T> SyncSender<T> {
+    /// Send a message, or return it if the receiver is disconnected. Sending to a full channel
+    /// fails a self-blocking check, since it would block forever.
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        match try_push(&self.channel, t) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Disconnected(t)) => Err(SendError(t)),
+            Err(TrySendError::Full(_)) => blocks_forever!("`send` blocks on a full sync channel"),
+        }
+    }
+
+    /// Send a message if the channel has room for it.
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        try_push(&self.channel, t)
+    }
+}
+
+macro_rules! impl_sender {
+    ($sender:ident) => {
+        impl<T> Clone for $sender<T> {
+            fn clone(&self) -> Self {
+                self.channel.borrow_mut().senders += 1;
+                $sender { channel: self.channel.clone() }
+            }
+        }
+
+        impl<T> Drop for $sender<T> {
+            fn drop(&mut self) {
+                self.channel.borrow_mut().senders -= 1;
+            }
+        }
+
+        impl<T> fmt::Debug for $sender<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($sender)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+impl_sender!(Sender);
+impl_sender!(SyncSender);
+
+/// Model of [`std::sync::mpsc::Receiver`].
+pub struct Receiver<T> {
+    channel: Shared<T>,
+}
+
+impl<T> Receiver<T> {
+    /// Receive the oldest message, or fail if the channel is empty and all senders are
+    /// disconnected. Receiving from an empty channel whose senders are connected fails a
+    /// self-blocking check, since it would block forever.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        match self.try_recv() {
+            Ok(t) => Ok(t),
+            Err(TryRecvError::Disconnected) => Err(RecvError),
+            Err(TryRecvError::Empty) => blocks_forever!("`recv` blocks on an empty channel"),
+        }
+    }
+
+    /// Receive the oldest message, if there is one.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut channel = self.channel.borrow_mut();
+        match channel.queue.pop_front() {
+            Some(t) => Ok(t),
+            None if channel.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Receive the oldest message. Since no other thread can send a message while waiting, this
+    /// times out immediately if the channel is empty and a sender is connected.
+    pub fn recv_timeout(&self, _timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match self.try_recv() {
+            Ok(t) => Ok(t),
+            Err(TryRecvError::Disconnected) => Err(RecvTimeoutError::Disconnected),
+            Err(TryRecvError::Empty) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    /// An iterator that receives messages until all senders are disconnected.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rx: self }
+    }
+
+    /// An iterator that receives the messages that are ready, without blocking.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        TryIter { rx: self }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.channel.borrow_mut().receiver_connected = false;
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// Model of [`std::sync::mpsc::Iter`].
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    rx: &'a Receiver<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+/// Model of [`std::sync::mpsc::TryIter`].
+#[derive(Debug)]
+pub struct TryIter<'a, T> {
+    rx: &'a Receiver<T>,
+}
+
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Model of [`std::sync::mpsc::IntoIter`].
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements the `#[kani::const_parity]` attribute, which checks that a constant
+//! computed at compile time has the value that its initializer computes at runtime.
+//!
+//! The validation is shared by the sysroot and the regular implementation of the attribute, so
+//! misplaced attributes are also reported when building the code with the regular rust compiler.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::emit_error;
+use quote::quote;
+#[cfg(kani_sysroot)]
+use quote::format_ident;
+use syn::{Item, ItemConst};
+
+/// Check that the attribute has no arguments and is applied to a constant.
+pub fn validate_const_parity(attr: &TokenStream, item: Item) -> Result<ItemConst, TokenStream> {
+    let Item::Const(const_item) = item else {
+        emit_error!(item, "the `const_parity` attribute can only be applied to constants");
+        return Err(quote!(#item));
+    };
+    if !attr.is_empty() {
+        emit_error!(attr, "`#[kani::const_parity]` does not take any arguments");
+        return Err(quote!(#const_item));
+    }
+    Ok(const_item)
+}
+
+/// Generate a harness that evaluates the initializer of the constant at runtime, and asserts
+/// that the result has the same bytes as the value of the constant, which was evaluated at
+/// compile time. Comparing the bytes tells apart NaNs with a different sign or payload, which
+/// `==` would not. The harness requires `-Z const-fn-parity`.
+#[cfg(kani_sysroot)]
+pub fn parity_harness(const_item: &ItemConst) -> TokenStream {
+    let ItemConst { ident, ty, expr, .. } = const_item;
+    let harness = format_ident!("__kani_const_parity_{}", ident);
+    let msg = format!("`{ident}` has the same value at compile time and at runtime");
+    quote! {
+        #[allow(non_snake_case)]
+        #[kani::proof]
+        #[kani::unstable(
+            feature = "const-fn-parity",
+            issue = "none",
+            reason = "the `const_parity` attribute is experimental"
+        )]
+        fn #harness() {
+            // The initializer is not in a const context here, so it's evaluated at runtime.
+            let runtime_value: #ty = #expr;
+            let size = ::core::mem::size_of::<#ty>();
+            let compile_time = &#ident as *const #ty as *const u8;
+            let runtime = &runtime_value as *const #ty as *const u8;
+            let same_bytes = unsafe {
+                ::core::slice::from_raw_parts(compile_time, size)
+                    == ::core::slice::from_raw_parts(runtime, size)
+            };
+            assert!(same_bytes, #msg);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-//! This module provides Kani's `derive` macro for `Arbitrary`.
+//! This module provides Kani's `derive` macros for `Arbitrary` and `Invariant`.
 //!
 //! ```
 //! use kani::Arbitrary;
@@ -9,46 +9,790 @@
 //! struct S;
 //!
 //! ```
-use proc_macro2::{Ident, Span, TokenStream};
-use proc_macro_error::abort;
-use quote::{quote, quote_spanned};
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{abort, abort_if_dirty, emit_error, emit_warning};
+use quote::{format_ident, quote, quote_spanned};
 use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
 use syn::{
-    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, GenericParam, Generics,
-    Index,
+    parse_macro_input, parse_quote, BinOp, Data, DataEnum, DataUnion, DeriveInput, Expr, Fields,
+    GenericParam, Generics, Index,
 };
 
+/// The name of the attribute used to specify a safety constraint for a field.
+const SAFETY_CONSTRAINT: &str = "safety_constraint";
+
+/// The name of the attribute used to customize the derived `Arbitrary` implementation, e.g. how
+/// the item or a field is initialized.
+const KANI: &str = "kani";
+
 pub fn expand_derive_arbitrary(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_item = parse_macro_input!(item as DeriveInput);
+    proc_macro::TokenStream::from(derive_arbitrary(&derive_item, false))
+}
+
+/// Generate the `Arbitrary` implementation of the item, as well as its check harness.
+///
+/// If `assume_invariant` is set, the generated values are restricted to the ones that satisfy
+/// `kani::Invariant::is_safe`, instead of the safety constraints of the fields. In that case, the
+/// implementation also requires the bounds of the derived `Invariant` implementation.
+fn derive_arbitrary(derive_item: &DeriveInput, assume_invariant: bool) -> TokenStream {
     let item_name = &derive_item.ident;
+    // The safety constraints are already checked by the derived `Invariant` when it's assumed.
+    let safety_conds = if assume_invariant {
+        None
+    } else {
+        safety_conds(item_name, &derive_item.data, "Arbitrary")
+    };
 
-    // Add a bound `T: Arbitrary` to every type parameter T.
-    let generics = add_trait_bound(derive_item.generics);
-    // Generate an expression to sum up the heap size of each field.
+    // Add the `Arbitrary` bounds required by the fields, e.g.: `T: Arbitrary`.
+    let mut generics = add_trait_bound_arbitrary(derive_item.generics.clone(), derive_item);
+    if assume_invariant {
+        generics = add_trait_bound_invariant(generics, &derive_item.data);
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let body = fn_any_body(&item_name, &derive_item.data);
-    let expanded = quote! {
+    let options = item_options(derive_item);
+    let body = match &options.constructor {
+        Some(constructor) => quote! { kani::arbitrary::call_with_any(#constructor) },
+        None => fn_any_body(&item_name, &options, &derive_item.data),
+    };
+    // Restrict the generated object to the ones that satisfy the safety constraints of its fields.
+    let body = if assume_invariant {
+        quote! {
+            let obj = #body;
+            kani::arbitrary::assume_safety_constraints(kani::Invariant::is_safe(&obj));
+            obj
+        }
+    } else if let Some(arms) = safety_conds {
+        quote! {
+            let obj = #body;
+            kani::arbitrary::assume_safety_constraints(match &obj {
+                #arms
+            });
+            obj
+        }
+    } else {
+        body
+    };
+    let check_harness = check_arbitrary_harness(item_name, &generics);
+    quote! {
         // The generated implementation.
         impl #impl_generics kani::Arbitrary for #item_name #ty_generics #where_clause {
             fn any() -> Self {
                 #body
             }
         }
+        #check_harness
+    }
+}
+
+/// The options of the derived `Arbitrary` that are given with `#[kani(..)]` on the item.
+#[derive(Default)]
+struct ItemOptions {
+    /// The function that creates the values, given with `constructor = "<path>"`.
+    ///
+    /// A single identifier refers to an associated function of the item, e.g.: `"new"` is
+    /// `Self::new`. The derived `any()` calls it with arguments created by `kani::any()`, instead
+    /// of initializing the fields directly.
+    constructor: Option<syn::Path>,
+    /// The field of a union that is initialized by the derived `any()`, given with
+    /// `active = <field>`.
+    active: Option<Ident>,
+}
+
+/// Parse the `#[kani(..)]` attributes of the item, and abort if any of them is invalid.
+fn item_options(item: &DeriveInput) -> ItemOptions {
+    let is_union = matches!(item.data, Data::Union(_));
+    let mut options = ItemOptions::default();
+    for attr in item.attrs.iter().filter(|attr| attr.path().is_ident(KANI)) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("constructor") {
+                let name: syn::LitStr = meta.value()?.parse()?;
+                let path: syn::Path = name
+                    .parse()
+                    .map_err(|_| syn::Error::new(name.span(), "expected the path to a function"))?;
+                if options.constructor.is_some() {
+                    return Err(meta.error("duplicate `constructor` option"));
+                }
+                options.constructor = Some(match path.get_ident() {
+                    Some(ident) => parse_quote!(Self::#ident),
+                    None => path,
+                });
+                Ok(())
+            } else if meta.path.is_ident("active") && is_union {
+                if options.active.is_some() {
+                    return Err(meta.error("duplicate `active` option"));
+                }
+                options.active = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if is_union {
+                Err(meta.error("unsupported `kani` option for a union"))
+            } else {
+                Err(meta.error("unsupported `kani` option"))
+            }
+        });
+        if let Err(err) = result {
+            if is_union {
+                emit_error!(err.span(), "{}", err;
+                    help = "use `#[{}(active = <field>)]` to create the union from one of its \
+                    fields",
+                    KANI
+                );
+            } else {
+                emit_error!(err.span(), "{}", err;
+                    help = "use `#[{}(constructor = \"new\")]` to create values with `Self::new`",
+                    KANI
+                );
+            }
+        }
+    }
+    abort_if_dirty();
+    options
+}
+
+/// Generate a harness that covers the creation of a value with `kani::any()`. The cover is
+/// unsatisfiable if the safety constraints of the type (or of its fields) can never be met.
+///
+/// The harness is only compiled by `cargo kani check-arbitrary`, which sets the
+/// `kani_check_arbitrary` configuration. Types with type or const parameters are skipped, since
+/// the harness cannot pick their arguments.
+fn check_arbitrary_harness(item_name: &Ident, generics: &Generics) -> TokenStream {
+    if !has_only_lifetimes(generics) {
+        return quote!();
+    }
+    let harness = format_ident!("__kani_check_arbitrary_{}", item_name);
+    let msg = format!("`{item_name}` can be created with `kani::any()`");
+    quote! {
+        #[cfg(kani_check_arbitrary)]
+        #[allow(non_snake_case)]
+        #[kani::proof]
+        fn #harness() {
+            let _value: #item_name = kani::any();
+            kani::cover(true, #msg);
+        }
+    }
+}
+
+/// Whether the only generic parameters of an item, if any, are lifetimes.
+fn has_only_lifetimes(generics: &Generics) -> bool {
+    generics.params.iter().all(|param| matches!(param, GenericParam::Lifetime(_)))
+}
+
+/// Add the `Arbitrary` bounds required to create the fields of an item with `kani::any()`.
+/// Fields with a `#[kani(..)]` attribute are not created with `kani::any()`, so they don't
+/// require any bound, and neither do the inactive fields of a union.
+/// See [add_field_bounds] for the bounds that are added.
+fn add_trait_bound_arbitrary(generics: Generics, item: &DeriveInput) -> Generics {
+    let fields = match &item.data {
+        Data::Union(union_data) => {
+            match union_active_field(&item.ident, &item_options(item), union_data) {
+                Some(field) => Box::new(std::iter::once(field)),
+                None => data_fields(&item.data),
+            }
+        }
+        data => data_fields(data),
     };
-    proc_macro::TokenStream::from(expanded)
+    let fields = fields.filter(|field| !field.attrs.iter().any(is_field_init_attr));
+    add_field_bounds(generics, fields, parse_quote!(kani::Arbitrary))
+}
+
+/// The fields of all the variants of an item.
+fn data_fields(data: &Data) -> Box<dyn Iterator<Item = &syn::Field> + '_> {
+    match data {
+        Data::Struct(struct_data) => Box::new(struct_data.fields.iter()),
+        Data::Enum(enum_data) => {
+            Box::new(enum_data.variants.iter().flat_map(|variant| variant.fields.iter()))
+        }
+        Data::Union(union_data) => Box::new(union_data.fields.named.iter()),
+    }
 }
 
-/// Add a bound `T: Arbitrary` to every type parameter T.
-fn add_trait_bound(mut generics: Generics) -> Generics {
-    generics.params.iter_mut().for_each(|param| {
-        if let GenericParam::Type(type_param) = param {
-            type_param.bounds.push(parse_quote!(kani::Arbitrary));
+/// Add the bound `bound` to the where clause of `generics` for:
+/// - Every type parameter `T` that is used by the type of a field, e.g.: `T: Arbitrary`. Uses
+///   inside of `PhantomData` are ignored, since `PhantomData<T>` implements the traits for any `T`.
+/// - Every associated type of a type parameter that is used by the type of a field, e.g.:
+///   `T::Item: Arbitrary`. This doesn't require the bound for `T` itself.
+///
+/// Lifetimes, const parameters and the existing where clause of the item are left untouched.
+fn add_field_bounds<'a>(
+    mut generics: Generics,
+    fields: impl Iterator<Item = &'a syn::Field>,
+    bound: syn::Path,
+) -> Generics {
+    let mut collector = BoundedTypes {
+        params: generics.type_params().map(|param| param.ident.clone()).collect(),
+        types: Vec::new(),
+    };
+    fields.for_each(|field| collector.visit_type(&field.ty));
+    let mut bounded: Vec<String> = Vec::new();
+    for ty in collector.types {
+        // Avoid duplicate predicates, which would make the generated code harder to read.
+        let key = quote!(#ty).to_string();
+        if !bounded.contains(&key) {
+            bounded.push(key);
+            generics.make_where_clause().predicates.push(parse_quote!(#ty: #bound));
         }
-    });
+    }
     generics
 }
 
+/// Collect the types that need to be bounded for a derived implementation to be valid, which
+/// are the type parameters and their associated types used by the fields.
+struct BoundedTypes {
+    params: Vec<Ident>,
+    types: Vec<syn::Type>,
+}
+
+impl BoundedTypes {
+    /// Whether the given type uses any of the type parameters.
+    fn mentions_param(&self, ty: &syn::Type) -> bool {
+        let mut inner = BoundedTypes { params: self.params.clone(), types: Vec::new() };
+        inner.visit_type(ty);
+        !inner.types.is_empty()
+    }
+}
+
+impl<'ast> Visit<'ast> for BoundedTypes {
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        let segments = &ty.path.segments;
+        match &ty.qself {
+            // E.g.: `<T as Iterator>::Item`.
+            Some(qself) if self.mentions_param(&qself.ty) => {
+                self.types.push(syn::Type::Path(ty.clone()))
+            }
+            Some(_) => syn::visit::visit_type_path(self, ty),
+            // E.g.: `T` or `T::Item`.
+            None if ty.path.leading_colon.is_none() && self.params.contains(&segments[0].ident) => {
+                self.types.push(syn::Type::Path(ty.clone()))
+            }
+            None if segments.last().is_some_and(|segment| segment.ident == "PhantomData") => {}
+            None => syn::visit::visit_type_path(self, ty),
+        }
+    }
+
+    fn visit_type_macro(&mut self, _: &'ast syn::TypeMacro) {
+        // The expansion of a macro is unknown, so it cannot be bounded.
+    }
+}
+
+/// Collect the safety constraints of the fields of a struct or of the variants of an enum.
+///
+/// If there are any, this returns the match arms that bind every field of the struct or variant
+/// by name, and evaluate the conjunction of its constraints. E.g.:
+/// ```
+/// #[derive(Arbitrary)]
+/// struct Point {
+///     #[safety_constraint(*x < 10)]
+///     x: u8,
+///     #[safety_constraint(*y < *x)]
+///     y: u8,
+/// }
+/// ```
+/// will return `Self { x, y } => (*x < 10) && (*y < *x),`.
+///
+/// The unnamed fields of structs and enum variants are referred to by their index, e.g.:
+/// ```
+/// #[derive(Arbitrary)]
+/// enum Speed {
+///     Stopped,
+///     Kmh(#[safety_constraint(*0 <= 300)] u16),
+/// }
+/// ```
+/// will return `Self::Stopped { .. } => true, Self::Kmh(field0) => (*field0 <= 300),`.
+fn safety_conds(ident: &Ident, data: &Data, trait_name: &str) -> Option<TokenStream> {
+    let arms = match data {
+        Data::Struct(struct_data) => {
+            let scope = ConstraintScope::new(ident.to_string(), "struct", &struct_data.fields);
+            let conds = scope.conds(&struct_data.fields);
+            if conds.is_empty() {
+                return None;
+            }
+            let pattern = scope.pattern(quote!(Self), &struct_data.fields);
+            quote! {
+                #[allow(unused_variables)]
+                #pattern => #(#conds)&&*,
+            }
+        }
+        Data::Enum(enum_data) => {
+            let mut has_conds = false;
+            let arms: Vec<_> = enum_data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    let name = format!("{ident}::{variant_name}");
+                    let scope = ConstraintScope::new(name, "variant", &variant.fields);
+                    let conds = scope.conds(&variant.fields);
+                    if conds.is_empty() {
+                        quote! { Self::#variant_name { .. } => true, }
+                    } else {
+                        has_conds = true;
+                        let pattern = scope.pattern(quote!(Self::#variant_name), &variant.fields);
+                        quote! {
+                            #[allow(unused_variables)]
+                            #pattern => #(#conds)&&*,
+                        }
+                    }
+                })
+                .collect();
+            if !has_conds {
+                return None;
+            }
+            quote!(#(#arms)*)
+        }
+        Data::Union(union_data) => {
+            reject_safety_constraints(ident, union_data.fields.named.iter(), trait_name, "union");
+            return None;
+        }
+    };
+    abort_if_dirty();
+    Some(arms)
+}
+
+/// The struct or enum variant whose fields can be used by its safety constraints.
+struct ConstraintScope {
+    /// The name of the struct or variant, e.g.: `Point` or `Speed::Kmh`.
+    name: String,
+    /// Either "struct" or "variant".
+    kind: &'static str,
+    /// The variable bound to each field, and the name of the field in the constraints, which is
+    /// the index of the field for unnamed fields.
+    fields: Vec<(Ident, String)>,
+    /// The variables of the fields whose type is an integer, `bool` or `char`, which are always
+    /// equal to themselves, unlike floats.
+    reflexive: Vec<Ident>,
+}
+
+impl ConstraintScope {
+    fn new(name: String, kind: &'static str, fields: &Fields) -> Self {
+        let vars = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| match &field.ident {
+                Some(ident) => (ident.clone(), ident.to_string()),
+                None => (Ident::new(&format!("field{idx}"), field.span()), idx.to_string()),
+            })
+            .collect::<Vec<_>>();
+        let reflexive = fields
+            .iter()
+            .zip(&vars)
+            .filter(|(field, _)| is_reflexive_type(&field.ty))
+            .map(|(_, (var, _))| var.clone())
+            .collect();
+        ConstraintScope { name, kind, fields: vars, reflexive }
+    }
+
+    /// The pattern that binds every field to its variable, e.g.: `Self::Kmh(field0)`.
+    fn pattern(&self, path: TokenStream, fields: &Fields) -> TokenStream {
+        let vars = self.fields.iter().map(|(var, _)| var);
+        match fields {
+            Fields::Named(_) => quote!(#path { #(#vars),* }),
+            Fields::Unnamed(_) => quote!(#path ( #(#vars),* )),
+            Fields::Unit => quote!(#path),
+        }
+    }
+
+    /// Parse the `#[safety_constraint(<expr>)]` attributes of all the fields.
+    fn conds(&self, fields: &Fields) -> Vec<TokenStream> {
+        fields
+            .iter()
+            .zip(&self.fields)
+            .flat_map(|(field, (_, name))| self.field_conds(field, name))
+            .collect()
+    }
+
+    /// Parse the `#[safety_constraint(<expr>)]` attributes of a field.
+    fn field_conds(&self, field: &syn::Field, name: &str) -> Vec<TokenStream> {
+        field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(SAFETY_CONSTRAINT))
+            .map(|attr| {
+                let mut cond = match attr.parse_args::<Expr>() {
+                    Ok(cond) => cond,
+                    Err(err) => {
+                        abort!(err.span(), "Cannot parse the safety constraint of field `{}`: {}", name, err;
+                            note = attr.span() => "in this safety constraint";
+                            help = "expected a boolean expression, e.g. `#[safety_constraint(*{} > 0)]`",
+                            name
+                        )
+                    }
+                };
+                if field.ident.is_none() {
+                    FieldIndices(self).visit_expr_mut(&mut cond);
+                }
+                check_safety_cond(self, &cond);
+                quote_spanned! {cond.span()=> (#cond) }
+            })
+            .collect()
+    }
+}
+
+/// Replace the uses of unnamed fields by their index, e.g.: `*0`, with their variables.
+struct FieldIndices<'a>(&'a ConstraintScope);
+
+impl VisitMut for FieldIndices<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), expr: inner, .. }) = expr {
+            if let Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) = &**inner {
+                let scope = self.0;
+                match scope.fields.iter().find(|(_, name)| *name == lit.base10_digits()) {
+                    Some((var, _)) => {
+                        let var = Ident::new(&var.to_string(), lit.span());
+                        **inner = parse_quote!(#var);
+                    }
+                    None => emit_error!(lit, "`{}` is not a field of `{}`", lit, scope.name;
+                        help = "the {} has {} field(s)", scope.kind, scope.fields.len()
+                    ),
+                }
+                return;
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, expr)
+    }
+}
+
+/// Report likely mistakes in a safety constraint of the struct or variant `scope`:
+/// - An error for every variable that is not a field of the struct. Only the identifiers that are
+///   used like a field, i.e. dereferenced (`*x`), or as the receiver of a method call or a field
+///   access, are reported. Identifiers bound by the constraint itself, e.g. by a closure parameter
+///   or a match arm, are only known in the scope that binds them.
+/// - A warning if the constraint is always true, since it doesn't restrict the field.
+fn check_safety_cond(scope: &ConstraintScope, cond: &Expr) {
+    let mut collector = ConstraintVars::default();
+    collector.visit_expr(cond);
+    for var in &collector.unbound {
+        if !scope.fields.iter().any(|(field, _)| field == var) {
+            emit_error!(var, "`{}` is not a field of `{}`", var, scope.name;
+                help = "safety constraints can only refer to the fields of the {}: {}", scope.kind,
+                scope.fields.iter().map(|(_, name)| format!("`{name}`")).collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+    if is_tautology(scope, cond) {
+        emit_warning!(cond, "this safety constraint is always true";
+            help = "this constraint has no effect and can be removed"
+        );
+    }
+}
+
+/// Whether values of the given type are always equal to themselves. Floats are not, since `NaN`
+/// is not equal to itself.
+fn is_reflexive_type(ty: &syn::Type) -> bool {
+    const REFLEXIVE: [&str; 14] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "bool", "char",
+    ];
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none()
+        && path.path.get_ident().is_some_and(|ident| REFLEXIVE.iter().any(|ty| ident == ty)))
+}
+
+/// Whether the expression is trivially true, i.e.: `true` or a reflexive comparison of a field
+/// whose type is always equal to itself, like `*x == *x` for an integer `x`.
+fn is_tautology(scope: &ConstraintScope, cond: &Expr) -> bool {
+    match cond {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(lit), .. }) => lit.value,
+        Expr::Paren(paren) => is_tautology(scope, &paren.expr),
+        Expr::Binary(syn::ExprBinary { left, op, right, .. }) => {
+            matches!(op, BinOp::Eq(_) | BinOp::Le(_) | BinOp::Ge(_))
+                && quote!(#left).to_string() == quote!(#right).to_string()
+                && deref_var(left).is_some_and(|var| scope.reflexive.contains(var))
+        }
+        _ => false,
+    }
+}
+
+/// The variable dereferenced by the expression, e.g.: `x` in `*x`.
+fn deref_var(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Paren(paren) => deref_var(&paren.expr),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), expr, .. }) => path_var(expr),
+        _ => None,
+    }
+}
+
+/// The variable named by the expression, if it's a single identifier.
+fn path_var(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Path(path) if path.qself.is_none() => path.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// Collect the identifiers used like variables in an expression that are not bound by the
+/// expression itself. Bindings are tracked per scope: closure parameters, match arms, `if let`
+/// and `while let` branches, `for` loops and `let` statements in blocks.
+#[derive(Default)]
+struct ConstraintVars {
+    /// The variables bound by each enclosing scope, innermost last.
+    scopes: Vec<Vec<Ident>>,
+    unbound: Vec<Ident>,
+}
+
+impl ConstraintVars {
+    /// Record a use of `var` if it's not bound by an enclosing scope.
+    fn use_var(&mut self, var: &Ident) {
+        if !self.scopes.iter().flatten().any(|bound| bound == var) {
+            self.unbound.push(var.clone());
+        }
+    }
+
+    /// Add the variables bound by the pattern to the innermost scope.
+    fn bind(&mut self, pat: &syn::Pat) {
+        let mut bindings = PatBindings(vec![]);
+        bindings.visit_pat(pat);
+        self.scopes.last_mut().expect("a scope to bind variables").extend(bindings.0);
+    }
+
+    /// Visit `f` in a new scope.
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(vec![]);
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for ConstraintVars {
+    fn visit_expr_unary(&mut self, expr: &'ast syn::ExprUnary) {
+        match (&expr.op, path_var(&expr.expr)) {
+            (syn::UnOp::Deref(_), Some(var)) => self.use_var(var),
+            _ => syn::visit::visit_expr_unary(self, expr),
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        match path_var(&call.receiver) {
+            Some(var) => self.use_var(var),
+            None => self.visit_expr(&call.receiver),
+        }
+        call.args.iter().for_each(|arg| self.visit_expr(arg));
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        match path_var(&expr.base) {
+            Some(var) => self.use_var(var),
+            None => self.visit_expr(&expr.base),
+        }
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast syn::ExprClosure) {
+        self.scoped(|this| {
+            closure.inputs.iter().for_each(|input| this.bind(input));
+            this.visit_expr(&closure.body);
+        });
+    }
+
+    fn visit_arm(&mut self, arm: &'ast syn::Arm) {
+        self.scoped(|this| {
+            this.bind(&arm.pat);
+            if let Some((_, guard)) = &arm.guard {
+                this.visit_expr(guard);
+            }
+            this.visit_expr(&arm.body);
+        });
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        // The bindings of `if let` are only visible in the `then` branch.
+        self.scoped(|this| {
+            this.visit_expr(&expr.cond);
+            this.visit_block(&expr.then_branch);
+        });
+        if let Some((_, else_branch)) = &expr.else_branch {
+            self.visit_expr(else_branch);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr: &'ast syn::ExprWhile) {
+        self.scoped(|this| {
+            this.visit_expr(&expr.cond);
+            this.visit_block(&expr.body);
+        });
+    }
+
+    fn visit_expr_let(&mut self, expr: &'ast syn::ExprLet) {
+        self.visit_expr(&expr.expr);
+        if self.scopes.is_empty() {
+            // A `let` outside of a condition cannot bind anything that is used later.
+            return;
+        }
+        self.bind(&expr.pat);
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &'ast syn::ExprForLoop) {
+        self.visit_expr(&expr.expr);
+        self.scoped(|this| {
+            this.bind(&expr.pat);
+            this.visit_block(&expr.body);
+        });
+    }
+
+    fn visit_block(&mut self, block: &'ast syn::Block) {
+        self.scoped(|this| {
+            for stmt in &block.stmts {
+                match stmt {
+                    syn::Stmt::Local(local) => {
+                        if let Some(init) = &local.init {
+                            this.visit_expr(&init.expr);
+                            if let Some((_, diverge)) = &init.diverge {
+                                this.visit_expr(diverge);
+                            }
+                        }
+                        // The bindings are visible from the next statement on.
+                        this.bind(&local.pat);
+                    }
+                    _ => this.visit_stmt(stmt),
+                }
+            }
+        });
+    }
+}
+
+/// Collect the variables bound by a pattern.
+struct PatBindings(Vec<Ident>);
+
+impl<'ast> Visit<'ast> for PatBindings {
+    fn visit_pat_ident(&mut self, pat: &'ast syn::PatIdent) {
+        self.0.push(pat.ident.clone());
+        syn::visit::visit_pat_ident(self, pat)
+    }
+}
+
+/// Abort if any of the given fields has a safety constraint, since they cannot be used in
+/// structs with unnamed fields nor in unions. The kind of the fields is used in the error, e.g.:
+/// "union".
+fn reject_safety_constraints<'a>(
+    ident: &Ident,
+    mut fields: impl Iterator<Item = &'a syn::Field>,
+    trait_name: &str,
+    kind: &str,
+) {
+    let attr = fields
+        .find_map(|field| field.attrs.iter().find(|attr| attr.path().is_ident(SAFETY_CONSTRAINT)));
+    if let Some(attr) = attr {
+        abort!(
+            attr.span(),
+            "Cannot derive `{}` for `{}`: `#[{}]` is not supported on {} fields",
+            trait_name,
+            ident,
+            SAFETY_CONSTRAINT,
+            kind
+        )
+    }
+}
+
+/// Expand `#[kani::arbitrary_remote(Target)]` applied to a mirror of the foreign type `Target`.
+///
+/// This derives `Arbitrary` for the mirror, and generates the conversion from the mirror to the
+/// foreign type as well as the `RemoteArbitrary` implementation that uses it.
+pub fn expand_arbitrary_remote(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let target = parse_macro_input!(attr as syn::Type);
+    let mirror = parse_macro_input!(item as DeriveInput);
+    let mirror_name = &mirror.ident;
+    let conversion = remote_conversion(mirror_name, &target, &mirror.data);
+
+    let (impl_generics, ty_generics, where_clause) = mirror.generics.split_for_impl();
+    let arbitrary_generics = add_trait_bound_arbitrary(mirror.generics.clone(), &mirror);
+    let (arbitrary_impl_generics, _, arbitrary_where_clause) = arbitrary_generics.split_for_impl();
+    let expanded = quote! {
+        #[derive(kani::Arbitrary)]
+        #mirror
+
+        impl #impl_generics From<#mirror_name #ty_generics> for #target #where_clause {
+            fn from(mirror: #mirror_name #ty_generics) -> Self {
+                #conversion
+            }
+        }
+
+        impl #arbitrary_impl_generics kani::RemoteArbitrary for #mirror_name #ty_generics
+        #arbitrary_where_clause
+        {
+            type Target = #target;
+            fn any_remote() -> Self::Target {
+                kani::any::<Self>().into()
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Generate the body of the conversion from a mirror to the foreign type (`Self`), by moving
+/// every field of the mirror to the field with the same name in the foreign type. E.g.:
+/// ```
+/// #[kani::arbitrary_remote(foreign::Shape)]
+/// enum ShapeDef { Point, Circle(u32) }
+/// ```
+/// will generate the following body:
+/// ```
+/// match mirror {
+///     ShapeDef::Point => Self::Point,
+///     ShapeDef::Circle(field0) => Self::Circle(field0),
+/// }
+/// ```
+fn remote_conversion(ident: &Ident, target: &syn::Type, data: &Data) -> TokenStream {
+    match data {
+        Data::Struct(struct_data) => {
+            let (pattern, init) = remote_fields(&struct_data.fields);
+            quote! {
+                let #ident #pattern = mirror;
+                Self #init
+            }
+        }
+        Data::Enum(enum_data) => {
+            let arms = enum_data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let (pattern, init) = remote_fields(&variant.fields);
+                quote! {
+                    #ident::#variant_name #pattern => Self::#variant_name #init,
+                }
+            });
+            quote! {
+                match mirror {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(union_data) => {
+            abort!(union_data.union_token.span, "Cannot mirror `{}` with union `{}`", quote!(#target), ident;
+                note = ident.span() =>
+                "`#[kani::arbitrary_remote]` cannot be used for unions such as `{}`", ident
+            )
+        }
+    }
+}
+
+/// Generate the pattern that binds the fields of the mirror, and the initialization of the
+/// foreign type from those bindings.
+/// Each field in the initialization has the span of the mirror's field, so errors such as a
+/// private or missing field in the foreign type point to the corresponding field of the mirror.
+fn remote_fields(fields: &Fields) -> (TokenStream, TokenStream) {
+    match fields {
+        Fields::Named(fields) => {
+            let names: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+            let init = fields.named.iter().map(|field| {
+                let name = &field.ident;
+                quote_spanned! {field.span()=> #name: #name }
+            });
+            (quote!({ #(#names),* }), quote!({ #(#init),* }))
+        }
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<_> = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| Ident::new(&format!("field{idx}"), field.span()))
+                .collect();
+            (quote!(( #(#bindings),* )), quote!(( #(#bindings),* )))
+        }
+        Fields::Unit => (quote!(), quote!()),
+    }
+}
+
 /// Generate the body of the function `any()`.
 /// This will create the non-deterministic object.
 /// E.g.:
@@ -62,23 +806,59 @@ fn add_trait_bound(mut generics: Generics) -> Generics {
 ///    Self { x: kani::any(), y: kani::any() }
 /// }
 /// ```
-fn fn_any_body(ident: &Ident, data: &Data) -> TokenStream {
+fn fn_any_body(ident: &Ident, options: &ItemOptions, data: &Data) -> TokenStream {
     match data {
         Data::Struct(struct_data) => init_symbolic_item(ident, &struct_data.fields),
         Data::Enum(enum_data) => fn_any_enum(ident, enum_data),
-        Data::Union(_) => {
-            abort!(Span::call_site(), "Cannot derive `Arbitrary` for `{}` union", ident;
-                note = ident.span() =>
-                "`#[derive(Arbitrary)]` cannot be used for unions such as `{}`", ident
-            )
-        }
+        Data::Union(union_data) => match union_active_field(ident, options, union_data) {
+            Some(field) => {
+                let name = &field.ident;
+                let value = field_init(field);
+                quote! {
+                    #ident { #name: #value }
+                }
+            }
+            None => {
+                abort!(union_data.union_token.span, "Cannot derive `Arbitrary` for `{}` union", ident;
+                    note = ident.span() =>
+                    "`#[derive(Arbitrary)]` cannot be used for unions such as `{}`", ident;
+                    help = "use `#[{}(active = <field>)]` to create the union from one of its fields",
+                    KANI
+                )
+            }
+        },
     }
 }
 
+/// Find the field of a union that is selected with `#[kani(active = <field>)]`, which is
+/// initialized by the derived `any()`. Return `None` if the union doesn't have the attribute,
+/// and abort if the field doesn't exist.
+fn union_active_field<'a>(
+    ident: &Ident,
+    options: &ItemOptions,
+    data: &'a DataUnion,
+) -> Option<&'a syn::Field> {
+    let active = options.active.as_ref()?;
+    let field = data.fields.named.iter().find(|field| field.ident.as_ref() == Some(active));
+    if field.is_none() {
+        let names: Vec<_> = data
+            .fields
+            .named
+            .iter()
+            .map(|field| format!("`{}`", field.ident.as_ref().unwrap()))
+            .collect();
+        abort!(active, "`{}` is not a field of `{}`", active, ident;
+            help = "the fields of the union are: {}", names.join(", ")
+        );
+    }
+    field
+}
+
 /// Generate an item initialization where an item can be a struct or a variant.
 /// For named fields, this will generate: `Item { field1: kani::any(), field2: kani::any(), .. }`
 /// For unnamed fields, this will generate: `Item (kani::any(), kani::any(), ..)`
 /// For unit field, generate an empty initialization.
+/// Fields with a `#[kani(..)]` attribute are initialized as described in [field_init].
 fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
     match fields {
         Fields::Named(ref fields) => {
@@ -87,8 +867,9 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
             // is. An example is shown in the readme of the parent directory.
             let init = fields.named.iter().map(|field| {
                 let name = &field.ident;
+                let value = field_init(field);
                 quote_spanned! {field.span()=>
-                    #name: kani::any()
+                    #name: #value
                 }
             });
             quote! {
@@ -98,11 +879,7 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
         Fields::Unnamed(ref fields) => {
             // Expands to an expression like
             // Self(kani::any(), kani::any(), ..., kani::any());
-            let init = fields.unnamed.iter().map(|field| {
-                quote_spanned! {field.span()=>
-                    kani::any()
-                }
-            });
+            let init = fields.unnamed.iter().map(field_init);
             quote! {
                 #ident(#( #init,)*)
             }
@@ -115,6 +892,44 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
     }
 }
 
+/// Whether the attribute customizes the initialization of a field, i.e.: `#[kani(..)]`.
+fn is_field_init_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident(KANI)
+}
+
+/// Generate the initialization of a field, which is `kani::any()` unless the field has one of
+/// the following attributes:
+/// - `#[kani(skip)]` or `#[kani(default)]`: the field is initialized with `Default::default()`.
+/// - `#[kani(default = <expr>)]`: the field is initialized with the given expression.
+fn field_init(field: &syn::Field) -> TokenStream {
+    let mut init: Option<TokenStream> = None;
+    for attr in field.attrs.iter().filter(|attr| is_field_init_attr(attr)) {
+        let result = attr.parse_nested_meta(|meta| {
+            let value = if meta.path.is_ident("default") && meta.input.peek(syn::Token![=]) {
+                let expr: Expr = meta.value()?.parse()?;
+                quote_spanned! {expr.span()=> #expr }
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("default") {
+                quote_spanned! {field.span()=> Default::default() }
+            } else {
+                return Err(meta.error("unsupported `kani` option for a field"));
+            };
+            if init.is_some() {
+                return Err(meta.error("the initialization of this field is already specified"));
+            }
+            init = Some(value);
+            Ok(())
+        });
+        if let Err(err) = result {
+            emit_error!(err.span(), "{}", err;
+                help = "use `#[{}(skip)]` to initialize the field with `Default::default()`, or \
+                `#[{}(default = <expr>)]` to initialize it with the given expression",
+                KANI, KANI
+            );
+        }
+    }
+    init.unwrap_or_else(|| quote_spanned! {field.span()=> kani::any() })
+}
+
 /// Generate the body of the function `any()` for enums. The cases are:
 /// 1. For zero-variants enumerations, this will encode a `panic!()` statement.
 /// 2. For one or more variants, the code will be something like:
@@ -165,3 +980,225 @@ fn fn_any_enum(ident: &Ident, data: &DataEnum) -> TokenStream {
         }
     }
 }
+
+pub fn expand_derive_invariant(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_item = parse_macro_input!(item as DeriveInput);
+    proc_macro::TokenStream::from(derive_invariant(&derive_item))
+}
+
+/// Generate the `Invariant` implementation of the item, as well as its check harness.
+fn derive_invariant(derive_item: &DeriveInput) -> TokenStream {
+    let item_name = &derive_item.ident;
+    let safety_conds = safety_conds(item_name, &derive_item.data, "Invariant");
+
+    // Add the `Invariant` bounds required by the fields, e.g.: `T: Invariant`.
+    let generics = add_trait_bound_invariant(derive_item.generics.clone(), &derive_item.data);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields_safe = fn_is_safe_body(&item_name, &derive_item.data);
+    let body = if let Some(arms) = safety_conds {
+        quote! {
+            (#fields_safe) && match self {
+                #arms
+            }
+        }
+    } else {
+        fields_safe
+    };
+    let check_harness = check_invariant_harness(item_name, &generics);
+    quote! {
+        // The generated implementation.
+        impl #impl_generics kani::Invariant for #item_name #ty_generics #where_clause {
+            fn is_safe(&self) -> bool {
+                #body
+            }
+        }
+        #check_harness
+    }
+}
+
+/// Expand `#[kani::verifiable]`, which implements both `Arbitrary` and `Invariant` for the item.
+///
+/// The safety constraints of the fields are only evaluated by the generated `is_safe`, and the
+/// generated `any()` assumes that `is_safe` holds for the values it creates. This way, the values
+/// created by `kani::any()` always satisfy the invariant of the type.
+pub fn expand_verifiable(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let attr = TokenStream::from(attr);
+    if !attr.is_empty() {
+        abort!(attr, "`#[kani::verifiable]` doesn't take any arguments";
+            help = "use `#[safety_constraint(<cond>)]` on the fields to restrict their values"
+        )
+    }
+    let mut item = parse_macro_input!(item as DeriveInput);
+    let arbitrary = derive_arbitrary(&item, true);
+    let invariant = derive_invariant(&item);
+    remove_helper_attrs(&mut item);
+    let expanded = quote! {
+        #item
+        #arbitrary
+        #invariant
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Remove the attributes that are interpreted by the derived implementations, since they are not
+/// registered as the helper attributes of a derive macro.
+fn remove_helper_attrs(item: &mut DeriveInput) {
+    let is_helper = |attr: &syn::Attribute| {
+        [SAFETY_CONSTRAINT, KANI].iter().any(|name| attr.path().is_ident(name))
+    };
+    item.attrs.retain(|attr| !is_helper(attr));
+    let fields: Box<dyn Iterator<Item = &mut syn::Field>> = match &mut item.data {
+        Data::Struct(struct_data) => Box::new(struct_data.fields.iter_mut()),
+        Data::Enum(enum_data) => {
+            Box::new(enum_data.variants.iter_mut().flat_map(|variant| variant.fields.iter_mut()))
+        }
+        Data::Union(union_data) => Box::new(union_data.fields.named.iter_mut()),
+    };
+    fields.for_each(|field| field.attrs.retain(|attr| !is_helper(attr)));
+}
+
+/// Generate a harness that checks that `kani::any()` only produces values that satisfy the
+/// invariant of the type. The check is skipped if the type doesn't implement `Arbitrary`.
+///
+/// The harness is only compiled by `cargo kani check-invariants`, which sets the
+/// `kani_check_invariants` configuration. Types with type or const parameters are skipped, since
+/// the harness cannot pick their arguments.
+fn check_invariant_harness(item_name: &Ident, generics: &Generics) -> TokenStream {
+    if !has_only_lifetimes(generics) {
+        return quote!();
+    }
+    let harness = format_ident!("__kani_check_invariant_{}", item_name);
+    // Dispatch on whether the type implements `Arbitrary`, see `kani::invariant::InvariantCheck`.
+    // The traits are local to the harness, since `use` paths inside functions don't resolve
+    // external crates in the 2015 edition.
+    quote! {
+        #[cfg(kani_check_invariants)]
+        #[allow(non_snake_case)]
+        #[kani::proof]
+        fn #harness() {
+            trait CheckArbitraryInvariant {
+                fn check_invariant(&self);
+            }
+            impl<T: kani::Arbitrary + kani::Invariant> CheckArbitraryInvariant
+                for kani::invariant::InvariantCheck<T>
+            {
+                fn check_invariant(&self) {
+                    kani::invariant::check_arbitrary_invariant::<T>()
+                }
+            }
+            trait SkipInvariantCheck {
+                fn check_invariant(&self);
+            }
+            impl<T> SkipInvariantCheck for &kani::invariant::InvariantCheck<T> {
+                fn check_invariant(&self) {}
+            }
+            (&kani::invariant::InvariantCheck::<#item_name>::NEW).check_invariant();
+        }
+    }
+}
+
+/// Add the `Invariant` bounds required to check that every field of an item is safe.
+/// Fields that are not checked, see [is_checked_field], don't require any bound.
+/// See [add_field_bounds] for the bounds that are added.
+fn add_trait_bound_invariant(generics: Generics, data: &Data) -> Generics {
+    let fields = data_fields(data).filter(|field| is_checked_field(field));
+    add_field_bounds(generics, fields, parse_quote!(kani::Invariant))
+}
+
+/// Whether the derived `is_safe` checks that the field is safe. Fields with a `#[kani(..)]`
+/// attribute are not checked, since they are not created with `kani::any()` and their type
+/// doesn't need to implement `Invariant`.
+fn is_checked_field(field: &syn::Field) -> bool {
+    !field.attrs.iter().any(is_field_init_attr)
+}
+
+/// Generate the expression that checks that every field of the item is safe.
+/// E.g.:
+/// ```
+/// #[derive(Invariant)]
+/// struct Point { x: u8, y: u8 }
+/// ```
+/// will generate the following expression:
+/// ```
+/// true && kani::Invariant::is_safe(&self.x) && kani::Invariant::is_safe(&self.y)
+/// ```
+fn fn_is_safe_body(ident: &Ident, data: &Data) -> TokenStream {
+    match data {
+        Data::Struct(struct_data) => {
+            let fields = struct_data.fields.iter().enumerate();
+            let checks = fields.filter(|(_, field)| is_checked_field(field)).map(|(idx, field)| {
+                let member = match &field.ident {
+                    Some(name) => quote!(#name),
+                    None => {
+                        let index = Index::from(idx);
+                        quote!(#index)
+                    }
+                };
+                quote_spanned! {field.span()=>
+                    kani::Invariant::is_safe(&self.#member)
+                }
+            });
+            quote! {
+                true #(&& #checks)*
+            }
+        }
+        Data::Enum(enum_data) if enum_data.variants.is_empty() => {
+            // A reference to an uninhabited type can still be matched exhaustively by
+            // dereferencing it.
+            quote! {
+                match *self {}
+            }
+        }
+        Data::Enum(enum_data) => {
+            let arms = enum_data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                // The fields that are not checked are not bound, so they don't trigger the
+                // `unused_variables` lint.
+                let bindings: Vec<_> = variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, field)| {
+                        is_checked_field(field)
+                            .then(|| Ident::new(&format!("field{idx}"), field.span()))
+                    })
+                    .collect();
+                let checks = bindings.iter().flatten().map(|binding| {
+                    quote_spanned! {binding.span()=>
+                        kani::Invariant::is_safe(#binding)
+                    }
+                });
+                let bindings = bindings.iter().map(|binding| match binding {
+                    Some(binding) => quote!(#binding),
+                    None => quote!(_),
+                });
+                let pattern = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let names = fields.named.iter().map(|field| &field.ident);
+                        quote!(#ident::#variant_name { #(#names: #bindings),* })
+                    }
+                    Fields::Unnamed(_) => quote!(#ident::#variant_name(#(#bindings),*)),
+                    Fields::Unit => quote!(#ident::#variant_name),
+                };
+                quote! {
+                    #pattern => true #(&& #checks)*,
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(union_data) => {
+            abort!(union_data.union_token.span, "Cannot derive `Invariant` for `{}` union", ident;
+                note = ident.span() =>
+                "`#[derive(Invariant)]` cannot be used for unions such as `{}`", ident
+            )
+        }
+    }
+}
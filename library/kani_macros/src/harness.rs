@@ -0,0 +1,277 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module contains the validation of the `#[kani::proof]` and `#[kani::proof_for_contract]`
+//! attributes.
+//!
+//! The validation is shared by the sysroot and the regular implementation of the attributes, so
+//! invalid harnesses are also reported when building the code with the regular rust compiler.
+//! The error messages match the ones emitted by `kani-compiler` for the same problems.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_error};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, GenericParam, Item, ItemFn};
+
+/// The valid options for `#[kani::proof(...)]`.
+const PROOF_OPTIONS: [&str; 4] = ["schedule", "timeout", "unwind", "solver"];
+
+/// Kani attributes that can be used at most once per harness.
+const SINGLE_USE_ATTRIBUTES: [&str; 5] =
+    ["proof", "proof_for_contract", "should_panic", "solver", "unwind"];
+
+/// The options given to `#[kani::proof(...)]`.
+pub struct ProofOptions {
+    pub schedule: Option<syn::Expr>,
+    /// The verification timeout of the harness in seconds.
+    pub timeout: Option<syn::LitInt>,
+    /// The loop unwinding bound of the harness, like `#[kani::unwind(<bound>)]`.
+    pub unwind: Option<syn::LitInt>,
+    /// The name of the SAT solver used for the harness, like `#[kani::solver(<name>)]`.
+    pub solver: Option<syn::Ident>,
+}
+
+/// A single `<name> = <value>` option.
+struct ProofOption {
+    name: syn::Ident,
+    value: syn::Expr,
+}
+
+impl Parse for ProofOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?;
+        if !PROOF_OPTIONS.iter().any(|option| name == option) {
+            abort!(name, "`{}` is not a valid option for `#[kani::proof]`.", name;
+                note = "the valid options for `#[kani::proof]` are `schedule`, `timeout`, `unwind` and `solver`.";
+            );
+        }
+        let _ = input.parse::<syn::Token![=]>()?;
+        let value = input.parse::<syn::Expr>()?;
+        Ok(ProofOption { name, value })
+    }
+}
+
+impl Parse for ProofOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let options = Punctuated::<ProofOption, syn::Token![,]>::parse_terminated(input)?;
+        let mut seen: Vec<syn::Ident> = vec![];
+        let mut proof_options =
+            ProofOptions { schedule: None, timeout: None, unwind: None, solver: None };
+        for ProofOption { name, value } in options {
+            if let Some(first) = seen.iter().find(|first| **first == name) {
+                abort!(name, "the `{}` option is specified more than once", name;
+                    note = first.span() => "first specified here";
+                );
+            }
+            if name == "timeout" {
+                match value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(timeout), .. })
+                        if timeout.base10_parse::<u32>().is_ok_and(|timeout| timeout > 0) =>
+                    {
+                        proof_options.timeout = Some(timeout)
+                    }
+                    _ => abort!(value, "invalid value for the `timeout` option";
+                        help = "the timeout must be a positive number of seconds, e.g. `timeout = 60`";
+                    ),
+                }
+            } else if name == "unwind" {
+                match value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(unwind), .. })
+                        if unwind.base10_parse::<u32>().is_ok() =>
+                    {
+                        proof_options.unwind = Some(unwind)
+                    }
+                    _ => abort!(value, "invalid value for the `unwind` option";
+                        help = "the unwinding bound must be an integer up to `u32::MAX`, e.g. `unwind = 5`";
+                    ),
+                }
+            } else if name == "solver" {
+                proof_options.solver = Some(parse_solver(&value));
+            } else {
+                proof_options.schedule = Some(value);
+            }
+            seen.push(name);
+        }
+        Ok(proof_options)
+    }
+}
+
+/// Return the solver named by the value of the `solver` option, which can be given either as an
+/// identifier or as a string, e.g. `solver = kissat` or `solver = "kissat"`.
+///
+/// Whether the solver is supported is checked by the compiler, the same as for
+/// `#[kani::solver(<name>)]`.
+fn parse_solver(value: &syn::Expr) -> syn::Ident {
+    let solver = match value {
+        syn::Expr::Path(path) => path.path.get_ident().cloned(),
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(name), .. }) => name.parse().ok(),
+        _ => None,
+    };
+    solver.unwrap_or_else(|| {
+        abort!(value, "invalid value for the `solver` option";
+            help = "the solver must be given by name, e.g. `solver = \"kissat\"`. \
+                Use `#[kani::solver(bin = \"<SAT_SOLVER_BINARY>\")]` to use a solver binary";
+        )
+    })
+}
+
+/// Check that the item annotated with `#[kani::proof]` is a valid harness for the given options,
+/// and return the harness function.
+///
+/// All the problems found are reported. In that case, this returns the item without its Kani
+/// attributes, so it can still be emitted without causing follow-up errors.
+pub fn validate_proof(options: &ProofOptions, item: Item) -> Result<ItemFn, TokenStream> {
+    let Item::Fn(mut fn_item) = item else {
+        emit_error!(item, "the `proof` attribute can only be applied to functions");
+        return Err(quote!(#item));
+    };
+    let sig = &fn_item.sig;
+    let mut valid = true;
+    let mut generics = sig.generics.params.iter();
+    if let Some(param) = generics.find(|param| !matches!(param, GenericParam::Lifetime(_))) {
+        emit_error!(param, "the `proof` attribute cannot be applied to generic functions");
+        valid = false;
+    }
+    if sig.asyncness.is_none() {
+        if options.schedule.is_some() {
+            emit_error!(
+                proc_macro2::Span::call_site(),
+                "`#[kani::proof(schedule = ...)]` can only be used with `async` functions.";
+                help = "did you mean to make this function `async`?";
+            );
+            valid = false;
+        }
+        if !sig.inputs.is_empty() {
+            emit_error!(sig.inputs, "functions used as harnesses cannot have any arguments";
+                help = "use `kani::any()` to create symbolic values inside the harness instead";
+            );
+            valid = false;
+        }
+    } else if !sig.inputs.is_empty() {
+        emit_error!(
+            sig.inputs,
+            "`#[kani::proof]` cannot be applied to async functions that take arguments for now";
+            help = "try removing the arguments";
+        );
+        valid = false;
+    }
+    valid &= check_harness_attributes("proof", &fn_item.attrs);
+    for attr in &fn_item.attrs {
+        let Some(name) = kani_attribute_name(attr) else { continue };
+        let option = match name {
+            "unwind" => options.unwind.as_ref().map(|unwind| unwind.span()),
+            "solver" => options.solver.as_ref().map(|solver| solver.span()),
+            _ => None,
+        };
+        if let Some(option) = option {
+            emit_error!(attr, "the `{}` of the harness is specified more than once", name;
+                note = option => "also given to `#[kani::proof]` here";
+                help = "remove either the `{}` option or the `#[kani::{}]` attribute", name, name;
+            );
+            valid = false;
+        }
+    }
+    if valid {
+        Ok(fn_item)
+    } else {
+        fn_item.attrs.retain(|attr| !is_kani_attribute(attr));
+        Err(quote!(#fn_item))
+    }
+}
+
+/// Check that the item annotated with `#[kani::proof_for_contract(<target>)]` is a valid contract
+/// harness, and return the harness function.
+///
+/// Besides the problems reported for `#[kani::proof]` harnesses, this rejects attributes that
+/// would defeat the check of the contract: `#[kani::should_panic]`, and stubs of the target.
+/// Like [validate_proof], this returns the item without its Kani attributes if it is invalid.
+pub fn validate_proof_for_contract(
+    target: &TokenStream,
+    item: Item,
+) -> Result<ItemFn, TokenStream> {
+    let Item::Fn(mut fn_item) = item else {
+        emit_error!(item, "the `proof_for_contract` attribute can only be applied to functions");
+        return Err(quote!(#item));
+    };
+    let mut valid = check_harness_attributes("proof_for_contract", &fn_item.attrs);
+    let target = target.to_string();
+    for attr in &fn_item.attrs {
+        if kani_attribute_name(attr) == Some("should_panic") {
+            emit_error!(attr, "`should_panic` may not be used on a `proof_for_contract` harness";
+                note = "the harness checks that `{}` satisfies its contract, which includes not panicking", target;
+            );
+            valid = false;
+        }
+        let is_stub = is_kani_attribute(attr)
+            && ["stub", "stub_verified"].iter().any(|stub| attr.path().segments[1].ident == stub);
+        if is_stub && stub_original(attr).is_some_and(|original| original == target) {
+            emit_error!(attr, "the target of `proof_for_contract` cannot be stubbed by its harness";
+                help = "remove this attribute to check the contract of `{}`", target;
+            );
+            valid = false;
+        }
+    }
+    if valid {
+        Ok(fn_item)
+    } else {
+        fn_item.attrs.retain(|attr| !is_kani_attribute(attr));
+        Err(quote!(#fn_item))
+    }
+}
+
+/// The function replaced by a `#[kani::stub(<original>, <replacement>)]` or
+/// `#[kani::stub_verified(<original>)]` attribute, formatted like the target of
+/// `#[kani::proof_for_contract]`.
+fn stub_original(attr: &Attribute) -> Option<String> {
+    let args = attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated);
+    args.ok()?.first().map(|original| quote!(#original).to_string())
+}
+
+/// Report Kani attributes that are used more than once or that conflict with the attribute that
+/// marks the harness, i.e.: `proof` or `proof_for_contract`.
+///
+/// Only the attributes that follow the harness attribute are visible to the macro. The compiler
+/// checks the remaining ones.
+fn check_harness_attributes(harness: &str, attrs: &[Attribute]) -> bool {
+    let mut valid = true;
+    let mut seen: Vec<(&str, &Attribute)> = vec![];
+    for attr in attrs {
+        let Some(name) = kani_attribute_name(attr) else { continue };
+        if name == harness {
+            emit_error!(attr, "only one '#[kani::{}]' attribute is allowed per harness", name);
+            valid = false;
+        } else if name == "proof" || name == "proof_for_contract" {
+            emit_error!(
+                attr,
+                "`proof` and `proof_for_contract` may not be used on the same function."
+            );
+            valid = false;
+        } else if let Some((_, first)) = seen.iter().find(|(prev, _)| *prev == name) {
+            emit_error!(attr, "only one '#[kani::{}]' attribute is allowed per harness", name;
+                note = first.span() => "first used here";
+            );
+            valid = false;
+        } else {
+            seen.push((name, attr));
+        }
+    }
+    valid
+}
+
+/// Whether this is a `#[kani::<name>]` attribute.
+fn is_kani_attribute(attr: &Attribute) -> bool {
+    let segments = &attr.path().segments;
+    segments.len() == 2 && segments[0].ident == "kani"
+}
+
+/// Return the name of the attribute if it is one of Kani's single use attributes, i.e.: if it is
+/// `#[kani::<name>]`.
+fn kani_attribute_name(attr: &Attribute) -> Option<&'static str> {
+    if !is_kani_attribute(attr) {
+        return None;
+    }
+    let name = &attr.path().segments[1].ident;
+    SINGLE_USE_ATTRIBUTES.into_iter().find(|single_use| name == single_use)
+}
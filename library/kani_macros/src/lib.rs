@@ -9,7 +9,9 @@
 //   RUSTFLAGS="-Zcrate-attr=feature(register_tool) -Zcrate-attr=register_tool(kanitool)"
 #![feature(proc_macro_diagnostic)]
 
+mod const_parity;
 mod derive;
+mod harness;
 
 // proc_macro::quote is nightly-only, so we'll cobble things together instead
 use proc_macro::TokenStream;
@@ -29,6 +31,14 @@
 /// e.g. `#[kani::proof(schedule = kani::RoundRobin::default())]`.
 ///
 /// This will wrap the async function in a call to [`block_on_with_spawn`](https://model-checking.github.io/kani/crates/doc/kani/futures/fn.block_on_with_spawn.html) (see its documentation for more information).
+///
+/// The verification of a harness can be limited to a number of seconds with the `timeout` option,
+/// e.g. `#[kani::proof(timeout = 600)]`. This overrides the `--harness-timeout` command line option.
+///
+/// The loop unwinding bound and the SAT solver of a harness can also be given as options, e.g.
+/// `#[kani::proof(unwind = 5, solver = "kissat")]`. These are equivalent to the
+/// [`unwind`][macro@unwind] and [`solver`][macro@solver] attributes, so each of them can only be
+/// given once per harness.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -82,6 +92,34 @@ pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::solver(attr, item)
 }
 
+/// Check that the output of a proof harness contains the lines of the given file.
+///
+/// The attribute `#[kani::expect_output("path")]` can only be used alongside `#[kani::proof]`.
+/// Relative paths are resolved against the directory of the source file of the harness.
+///
+/// Each line of the file must be contained in some line of the verification results of the
+/// harness. A line that ends with `\` must be followed directly by the next one. The harness
+/// fails if any line is missing.
+#[proc_macro_attribute]
+pub fn expect_output(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::expect_output(attr, item)
+}
+
+/// Check that a constant has the same value when its initializer is evaluated at runtime.
+///
+/// The value of a constant, e.g. a lookup table computed by a `const fn`, is evaluated at compile
+/// time, while harnesses verify the code that runs at runtime. The attribute generates a harness
+/// named `__kani_const_parity_<NAME>` that evaluates the initializer of the constant at runtime,
+/// and asserts that the result has the same bytes as the constant. The type of the constant must
+/// not contain padding nor pointers, e.g. integers, floats and arrays of them.
+///
+/// This attribute is unstable and it requires `-Z const-fn-parity`.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn const_parity(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::const_parity(attr, item)
+}
+
 /// Mark an API as unstable. This should only be used inside the Kani sysroot.
 /// See https://model-checking.github.io/kani/rfc/rfcs/0006-unstable-api.html for more details.
 #[doc(hidden)]
@@ -91,12 +129,97 @@ pub fn unstable(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /// Allow users to auto generate Arbitrary implementations by using `#[derive(Arbitrary)]` macro.
+///
+/// Fields of structs and enum variants can be annotated with `#[safety_constraint(<cond>)]` to
+/// restrict the values that are generated. The condition is a boolean expression where every
+/// field of the struct or variant is bound to a reference to its value. Unnamed fields are
+/// referred to by their index, e.g. `Kmh(#[safety_constraint(*0 <= 300)] u16)`.
+///
+/// Types whose invariants are enforced by a constructor can use
+/// `#[kani(constructor = "new")]`, in which case values are created by calling `Self::new`
+/// with arguments created by `kani::any()`. The constructor can also be given as a path to any
+/// function that returns `Self`, e.g. `#[kani(constructor = "helpers::make")]`.
+///
+/// Fields that should not be symbolic, such as caches or markers, can be annotated with
+/// `#[kani(skip)]` or `#[kani(default)]` to be initialized with `Default::default()`, or with
+/// `#[kani(default = <expr>)]` to be initialized with the given expression.
+///
+/// Unions must select the field that is created with `kani::any()` with
+/// `#[kani(active = <field>)]`, e.g. for `repr(C)` unions used with FFI code.
+///
+/// The implementation requires `Arbitrary` for the type parameters used by the fields, other than
+/// in `PhantomData`, and for their associated types used by the fields, e.g. `T::Item`.
+///
+/// For non-generic types, the macro also generates a harness that is only compiled by
+/// `cargo kani check-arbitrary`, which reports types whose safety constraints can never be met.
 #[proc_macro_error]
-#[proc_macro_derive(Arbitrary)]
+#[proc_macro_derive(Arbitrary, attributes(safety_constraint, kani))]
 pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
     derive::expand_derive_arbitrary(item)
 }
 
+/// Allow users to create symbolic values of foreign types, which cannot implement `Arbitrary` due
+/// to the orphan rule.
+///
+/// The attribute is applied to a local type that mirrors the fields or variants of the foreign
+/// type given as argument, similar to serde's remote derive. It derives `Arbitrary` for the mirror
+/// and implements `kani::RemoteArbitrary` for it, so `kani::any_remote::<Mirror>()` returns a
+/// symbolic value of the foreign type. The fields of the foreign type must be visible.
+///
+/// ```ignore
+/// #[kani::arbitrary_remote(std::ops::Range<u8>)]
+/// struct RangeDef {
+///     #[safety_constraint(*start <= *end)]
+///     start: u8,
+///     end: u8,
+/// }
+///
+/// let range: std::ops::Range<u8> = kani::any_remote::<RangeDef>();
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn arbitrary_remote(attr: TokenStream, item: TokenStream) -> TokenStream {
+    derive::expand_arbitrary_remote(attr, item)
+}
+
+/// Allow users to auto generate Invariant implementations by using `#[derive(Invariant)]` macro.
+///
+/// The generated `is_safe` checks that every field is safe, as well as the conditions given in
+/// the `#[safety_constraint(<cond>)]` attributes of the struct or enum variant fields. Fields
+/// with a `#[kani(..)]` attribute of the `Arbitrary` derive are not checked. Like the
+/// `Arbitrary` derive, it requires `Invariant` for the type parameters and associated types used
+/// by the fields.
+///
+/// For non-generic types, the macro also generates a harness that is only compiled by
+/// `cargo kani check-invariants`, which checks that `kani::any()` only produces safe values for
+/// types that also implement `Arbitrary`.
+#[proc_macro_error]
+#[proc_macro_derive(Invariant, attributes(safety_constraint))]
+pub fn derive_invariant(item: TokenStream) -> TokenStream {
+    derive::expand_derive_invariant(item)
+}
+
+/// Implement both `Arbitrary` and `Invariant` for a struct or enum from a single set of
+/// `#[safety_constraint(<cond>)]` attributes.
+///
+/// The fields accept the same attributes as with `#[derive(Arbitrary, Invariant)]`. However, the
+/// generated `kani::any()` assumes that the generated `is_safe` holds for the values it creates,
+/// instead of assuming the safety constraints separately, so the two can never diverge.
+///
+/// ```ignore
+/// #[kani::verifiable]
+/// struct Range {
+///     #[safety_constraint(*start <= *end)]
+///     start: u8,
+///     end: u8,
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn verifiable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    derive::expand_verifiable(attr, item)
+}
+
 /// Add a precondition to this function.
 ///
 /// This is part of the function contract API, for more general information see
@@ -144,6 +267,10 @@ pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// This is part of the function contract API, for more general information see
 /// the [module-level documentation](../kani/contracts/index.html).
+///
+/// A contract harness cannot be combined with `#[kani::should_panic]`, nor stub the function
+/// whose contract it checks.
+#[proc_macro_error]
 #[proc_macro_attribute]
 pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::proof_for_contract(attr, item)
@@ -171,17 +298,16 @@ pub fn stub_verified(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This code should only be activated when pre-building Kani's sysroot.
 #[cfg(kani_sysroot)]
 mod sysroot {
-    use proc_macro_error::{abort, abort_call_site};
-
     mod contracts;
 
     pub use contracts::{ensures, proof_for_contract, requires, stub_verified};
 
     use super::*;
+    use crate::const_parity::{parity_harness, validate_const_parity};
+    use crate::harness::{validate_proof, ProofOptions};
 
     use {
         quote::{format_ident, quote},
-        syn::parse::{Parse, ParseStream},
         syn::{parse_macro_input, ItemFn},
     };
 
@@ -211,49 +337,29 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
         };
     }
 
-    struct ProofOptions {
-        schedule: Option<syn::Expr>,
-    }
-
-    impl Parse for ProofOptions {
-        fn parse(input: ParseStream) -> syn::Result<Self> {
-            if input.is_empty() {
-                Ok(ProofOptions { schedule: None })
-            } else {
-                let ident = input.parse::<syn::Ident>()?;
-                if ident != "schedule" {
-                    abort_call_site!("`{}` is not a valid option for `#[kani::proof]`.", ident;
-                        help = "did you mean `schedule`?";
-                        note = "for now, `schedule` is the only option for `#[kani::proof]`.";
-                    );
-                }
-                let _ = input.parse::<syn::Token![=]>()?;
-                let schedule = Some(input.parse::<syn::Expr>()?);
-                Ok(ProofOptions { schedule })
-            }
-        }
-    }
-
     pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         let proof_options = parse_macro_input!(attr as ProofOptions);
-        let fn_item = parse_macro_input!(item as ItemFn);
+        let fn_item = match validate_proof(&proof_options, parse_macro_input!(item as syn::Item)) {
+            Ok(fn_item) => fn_item,
+            Err(item) => return item.into(),
+        };
         let attrs = fn_item.attrs;
         let vis = fn_item.vis;
         let sig = fn_item.sig;
         let body = fn_item.block;
 
+        let timeout = proof_options.timeout.iter();
+        let unwind = proof_options.unwind.iter();
+        let solver = proof_options.solver.iter();
         let kani_attributes = quote!(
             #[allow(dead_code)]
             #[kanitool::proof]
+            #(#[kanitool::timeout(#timeout)])*
+            #(#[kanitool::unwind(#unwind)])*
+            #(#[kanitool::solver(#solver)])*
         );
 
         if sig.asyncness.is_none() {
-            if proof_options.schedule.is_some() {
-                abort_call_site!(
-                    "`#[kani::proof(schedule = ...)]` can only be used with `async` functions.";
-                    help = "did you mean to make this function `async`?";
-                );
-            }
             // Adds `#[kanitool::proof]` and other attributes
             quote!(
                 #kani_attributes
@@ -281,13 +387,6 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
             //   // where `schedule` was provided as an argument to `#[kani::proof]`.
             // }
             // ```
-            if !sig.inputs.is_empty() {
-                abort!(
-                    sig.inputs,
-                    "`#[kani::proof]` cannot be applied to async functions that take arguments for now";
-                    help = "try removing the arguments";
-                );
-            }
             let mut modified_sig = sig.clone();
             modified_sig.asyncness = None;
             let fn_name = &sig.ident;
@@ -309,6 +408,22 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
+    pub fn const_parity(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let item = parse_macro_input!(item as syn::Item);
+        match validate_const_parity(&attr.into(), item) {
+            Ok(const_item) => {
+                let harness = parity_harness(&const_item);
+                quote!(
+                    #const_item
+                    #harness
+                )
+                .into()
+            }
+            Err(item) => item.into(),
+        }
+    }
+
+    kani_attribute!(expect_output);
     kani_attribute!(should_panic, no_args);
     kani_attribute!(solver);
     kani_attribute!(stub);
@@ -323,6 +438,8 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
 #[cfg(not(kani_sysroot))]
 mod regular {
     use super::*;
+    use crate::const_parity::validate_const_parity;
+    use crate::harness::{validate_proof, validate_proof_for_contract, ProofOptions};
 
     /// Encode a noop proc macro which ignores the given attribute.
     macro_rules! no_op {
@@ -334,13 +451,44 @@ pub fn $name(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     /// Add #[allow(dead_code)] to a proof harness to avoid dead code warnings.
-    pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    /// The harness is still validated, so invalid harnesses are reported by the regular compiler.
+    pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let proof_options = syn::parse_macro_input!(attr as ProofOptions);
+        let harness = item.clone();
+        if let Err(item) =
+            validate_proof(&proof_options, syn::parse_macro_input!(harness as syn::Item))
+        {
+            return item.into();
+        }
         let mut result = TokenStream::new();
         result.extend("#[allow(dead_code)]".parse::<TokenStream>().unwrap());
         result.extend(item);
         result
     }
 
+    /// Validate a contract harness, which is otherwise ignored by the regular compiler.
+    pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let harness = item.clone();
+        if let Err(item) =
+            validate_proof_for_contract(&attr.into(), syn::parse_macro_input!(harness as syn::Item))
+        {
+            return item.into();
+        }
+        item
+    }
+
+    /// Validate the constant, which is otherwise ignored by the regular compiler.
+    pub fn const_parity(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let const_item = item.clone();
+        if let Err(item) =
+            validate_const_parity(&attr.into(), syn::parse_macro_input!(const_item as syn::Item))
+        {
+            return item.into();
+        }
+        item
+    }
+
+    no_op!(expect_output);
     no_op!(should_panic);
     no_op!(solver);
     no_op!(stub);
@@ -348,6 +496,5 @@ pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
     no_op!(unwind);
     no_op!(requires);
     no_op!(ensures);
-    no_op!(proof_for_contract);
     no_op!(stub_verified);
 }
@@ -1020,7 +1020,21 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 passthrough!(stub_verified, false);
-passthrough!(proof_for_contract, true);
+
+pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(attr);
+    let item = syn::parse_macro_input!(item as syn::Item);
+    let fn_item = match crate::harness::validate_proof_for_contract(&args, item) {
+        Ok(fn_item) => fn_item,
+        Err(item) => return item.into(),
+    };
+    quote!(
+        #[allow(dead_code)]
+        #[kanitool::proof_for_contract = stringify!(#args)]
+        #fn_item
+    )
+    .into()
+}
 
 #[cfg(test)]
 mod test {
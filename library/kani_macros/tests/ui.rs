@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the code generated and the diagnostics emitted by Kani's macros without running the
+//! verifier. Run with `TRYBUILD=overwrite` to update the expected output of the tests.
+
+#[test]
+fn derive() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/derive/*.rs");
+}
+
+#[test]
+fn proof() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/proof/*.rs");
+}
+
+#[test]
+fn pass() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass/*.rs");
+}
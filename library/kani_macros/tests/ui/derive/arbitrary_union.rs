@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that deriving `Arbitrary` for a union points at the union.
+
+#[derive(kani::Arbitrary)]
+union Wrapper {
+    b: bool,
+    c: char,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints on enum variants that refer to unknown fields are reported.
+
+#[derive(kani::Invariant)]
+enum Speed {
+    Stopped,
+    Moving { #[safety_constraint(*kmh > 0 && *mph > 0)] kmh: u16 },
+    Kmh(#[safety_constraint(*1 <= 300)] u16),
+}
+
+fn main() {}
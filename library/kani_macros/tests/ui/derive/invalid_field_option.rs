@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that unknown, duplicate and invalid field options are reported.
+
+#[derive(kani::Arbitrary)]
+struct Unknown {
+    #[kani(ignore)]
+    val: u8,
+}
+
+#[derive(kani::Arbitrary)]
+struct Duplicate {
+    #[kani(skip, default = 0)]
+    val: u8,
+}
+
+#[derive(kani::Arbitrary)]
+struct MissingDefault {
+    #[kani(default = )]
+    val: u8,
+}
+
+#[derive(kani::Arbitrary)]
+struct FieldConstructor {
+    #[kani(constructor = "new")]
+    val: u8,
+}
+
+fn main() {}
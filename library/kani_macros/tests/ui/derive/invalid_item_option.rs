@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that unknown `kani` options of an item and invalid constructors are reported.
+
+#[derive(kani::Arbitrary)]
+#[kani(builder = "new")]
+struct Unknown {
+    val: u8,
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(constructor = "not a path")]
+struct InvalidPath {
+    val: u8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that an unknown active field of a union is reported.
+
+#[derive(kani::Arbitrary)]
+#[kani(active = d)]
+union Wrapper {
+    b: bool,
+    c: char,
+}
+
+fn main() {}
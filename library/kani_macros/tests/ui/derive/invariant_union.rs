@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that deriving `Invariant` for a union points at the union.
+
+#[derive(kani::Invariant)]
+union Wrapper {
+    b: bool,
+    c: char,
+}
+
+fn main() {}
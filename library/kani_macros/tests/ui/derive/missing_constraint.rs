@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that a safety constraint attribute without a condition is rejected.
+
+#[derive(kani::Invariant)]
+struct Point {
+    #[safety_constraint]
+    x: i32,
+}
+
+fn main() {}
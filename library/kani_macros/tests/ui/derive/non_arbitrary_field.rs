@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the error for a field that doesn't implement `Arbitrary` points at the field.
+
+struct NotArbitrary;
+
+#[derive(kani::Arbitrary)]
+struct Wrapper {
+    valid: u8,
+    invalid: NotArbitrary,
+}
+
+fn main() {}
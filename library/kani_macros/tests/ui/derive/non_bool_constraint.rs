@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that a safety constraint that is not a boolean expression is reported at the constraint.
+
+#[derive(kani::Arbitrary)]
+struct Point {
+    #[safety_constraint(*x + 1)]
+    x: i32,
+}
+
+fn main() {}
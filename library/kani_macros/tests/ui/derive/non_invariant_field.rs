@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the error for a field that doesn't implement `Invariant` points at the field.
+
+struct NoInvariant;
+
+#[derive(kani::Invariant)]
+struct Wrapper(u8, NoInvariant);
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that mirroring a type with private fields points at the field of the mirror.
+
+#[allow(dead_code)]
+mod foreign {
+    pub struct Secret {
+        pub public: u8,
+        private: u8,
+    }
+}
+
+#[kani::arbitrary_remote(foreign::Secret)]
+struct SecretDef {
+    public: u8,
+    private: u8,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that unions cannot be mirrored.
+
+union Foreign {
+    b: bool,
+}
+
+#[kani::arbitrary_remote(Foreign)]
+union ForeignDef {
+    b: bool,
+}
+
+fn main() {}
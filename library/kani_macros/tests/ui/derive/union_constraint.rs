@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints on union fields are rejected.
+
+#[derive(kani::Arbitrary)]
+#[kani(active = b)]
+union Wrapper {
+    #[safety_constraint(*b)]
+    b: bool,
+    c: char,
+}
+
+fn main() {}
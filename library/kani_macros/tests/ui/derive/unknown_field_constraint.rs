@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints that refer to variables that are not fields of the struct are
+//! rejected, even if a variable with the same name is in scope.
+
+#[allow(non_upper_case_globals)]
+const z: i32 = 10;
+
+#[derive(kani::Invariant)]
+struct Point {
+    #[safety_constraint(*x < *z && *y > *w)]
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the error of a safety constraint that cannot be parsed points at the invalid token
+//! and includes the parse error.
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Point {
+    #[safety_constraint(*x >)]
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::verifiable]` rejects arguments.
+
+#[kani::verifiable(strict)]
+struct Point {
+    x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `Arbitrary` can be derived using a constructor, given either as the name of an
+//! associated function or as a path to a function.
+
+mod units {
+    #[derive(kani::Arbitrary)]
+    #[kani(constructor = "new")]
+    pub struct Celsius {
+        degrees: i16,
+    }
+
+    impl Celsius {
+        pub fn new(degrees: i8) -> Self {
+            Celsius { degrees: degrees.max(-100) as i16 }
+        }
+    }
+}
+
+mod builders {
+    pub fn empty_stack() -> super::Stack {
+        super::Stack { len: 0 }
+    }
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(constructor = "builders::empty_stack")]
+struct Stack {
+    len: usize,
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(constructor = "Self::from_parts")]
+enum Shape {
+    Square(u32),
+    Rect(u32, u32),
+}
+
+impl Shape {
+    fn from_parts(width: u32, height: u32, square: bool) -> Self {
+        if square { Shape::Square(width) } else { Shape::Rect(width, height) }
+    }
+}
+
+fn main() {
+    let _ = |_: units::Celsius, _: Stack, _: Shape| ();
+}
@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::arbitrary_remote]` generates valid code for different kinds of mirrors.
+
+mod foreign {
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub struct Wrapper<T>(pub T);
+
+    pub enum Empty {}
+
+    pub enum Shape {
+        Circle(u32),
+        Rect { width: u32, height: u32 },
+    }
+}
+
+#[kani::arbitrary_remote(foreign::Point)]
+struct PointDef {
+    #[safety_constraint(*x >= 0)]
+    x: i32,
+    y: i32,
+}
+
+#[kani::arbitrary_remote(foreign::Wrapper<T>)]
+struct WrapperDef<T>(T);
+
+#[kani::arbitrary_remote(foreign::Empty)]
+enum EmptyDef {}
+
+#[kani::arbitrary_remote(foreign::Shape)]
+enum ShapeDef {
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+}
+
+fn check_remote<R: kani::RemoteArbitrary + kani::Arbitrary>() {}
+
+fn main() {
+    check_remote::<PointDef>();
+    check_remote::<WrapperDef<u8>>();
+    check_remote::<EmptyDef>();
+    check_remote::<ShapeDef>();
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that constants annotated with `#[kani::const_parity]` keep their value.
+
+const fn table() -> [u8; 4] {
+    let mut table = [0; 4];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = (i * i) as u8;
+        i += 1;
+    }
+    table
+}
+
+#[kani::const_parity]
+const TABLE: [u8; 4] = table();
+
+fn main() {
+    assert_eq!(TABLE, [0, 1, 4, 9]);
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints can refer to constants, functions, and variables bound inside the
+//! constraint itself.
+
+const MAX: usize = 10;
+
+fn is_even(val: u32) -> bool {
+    val % 2 == 0
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Bounded {
+    #[safety_constraint(*len <= MAX && data.iter().take(*len).all(|elem| is_even(*elem)))]
+    len: usize,
+    data: [u32; 4],
+    #[safety_constraint(match kind { Some(k) => *k < 3, None => true })]
+    kind: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the derive macros generate valid code for different kinds of items.
+
+use std::marker::PhantomData;
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Unit;
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Tuple(u8, bool);
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Generic<T, U> {
+    first: T,
+    second: Option<U>,
+    marker: PhantomData<T>,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+enum Empty {}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+enum Shape {
+    Point,
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Range {
+    #[safety_constraint(*start <= *end)]
+    start: u32,
+    #[safety_constraint(*end < 100)]
+    #[safety_constraint(*end > 0)]
+    end: u32,
+    // Field names should not clash with the code generated by the derive macros.
+    #[safety_constraint(*obj)]
+    obj: bool,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+enum Speed {
+    Stopped,
+    Kmh(#[safety_constraint(*0 <= 300)] u16),
+    Ratio(u8, #[safety_constraint(*1 > 0 && *0 <= *1)] u8),
+    Moving {
+        #[safety_constraint(*kmh > 0)]
+        kmh: u16,
+        // Field names should not clash with the code generated by the derive macros.
+        #[safety_constraint(!*obj)]
+        obj: bool,
+    },
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Percent(#[safety_constraint(*0 <= 100)] u8);
+
+fn check_impls<T: kani::Arbitrary + kani::Invariant>() {}
+
+fn main() {
+    check_impls::<Unit>();
+    check_impls::<Tuple>();
+    check_impls::<Generic<u8, char>>();
+    check_impls::<Empty>();
+    check_impls::<Shape>();
+    check_impls::<Range>();
+    check_impls::<Speed>();
+    check_impls::<Percent>();
+}
@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the derive macros generate valid bounds for items with lifetimes, where clauses,
+//! associated types and parameters that are only used in `PhantomData`.
+
+use std::marker::PhantomData;
+
+pub trait Container {
+    type Item;
+}
+
+/// A type that implements neither `Arbitrary` nor `Invariant`.
+pub struct Opaque;
+
+impl Container for Opaque {
+    type Item = u8;
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Borrowed<'a, T: 'a> {
+    value: T,
+    _marker: PhantomData<&'a T>,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Constrained<T>
+where
+    T: Copy,
+{
+    value: Option<T>,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Assoc<C: Container> {
+    item: C::Item,
+    qualified: Option<<C as Container>::Item>,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+enum Either<L, R>
+where
+    L: Clone,
+{
+    Left(L),
+    Right { value: R },
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Tagged<Tag> {
+    value: u8,
+    _tag: PhantomData<Tag>,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Lifetime<'a> {
+    value: u8,
+    _marker: PhantomData<&'a ()>,
+}
+
+fn check_impls<T: kani::Arbitrary + kani::Invariant>() {}
+
+fn main() {
+    check_impls::<Borrowed<'static, u8>>();
+    check_impls::<Constrained<u8>>();
+    check_impls::<Assoc<Opaque>>();
+    check_impls::<Either<u8, bool>>();
+    check_impls::<Tagged<Opaque>>();
+    check_impls::<Lifetime<'static>>();
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the harness attributes are accepted in their valid forms.
+
+#[kani::proof]
+#[kani::unwind(2)]
+#[kani::solver(kissat)]
+fn sync_harness() {}
+
+#[kani::proof]
+#[kani::should_panic]
+fn panic_harness() {
+    panic!("expected");
+}
+
+#[kani::proof]
+async fn async_harness() {}
+
+#[kani::proof(schedule = kani::RoundRobin::default())]
+async fn scheduled_harness() {}
+
+#[kani::proof(unwind = 5, solver = "kissat")]
+fn options_harness() {}
+
+#[kani::proof(timeout = 60, unwind = 0, solver = cadical)]
+#[kani::should_panic]
+fn all_options_harness() {
+    panic!("expected");
+}
+
+fn main() {}
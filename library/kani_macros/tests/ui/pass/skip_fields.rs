@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that fields annotated with `#[kani(..)]` don't need to implement `Arbitrary`, and that
+//! enums with explicit discriminants can be derived.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+#[derive(kani::Arbitrary)]
+struct Cached<T> {
+    key: u32,
+    #[kani(skip)]
+    cache: RefCell<HashMap<u32, T>>,
+    #[kani(default = 1)]
+    hits: usize,
+    #[kani(default = PhantomData)]
+    _marker: PhantomData<*const T>,
+}
+
+#[derive(kani::Arbitrary)]
+struct Handle(u8, #[kani(skip)] Vec<String>);
+
+#[derive(kani::Arbitrary)]
+struct Stats<T> {
+    #[kani(default)]
+    count: usize,
+    #[kani(skip)]
+    samples: Vec<T>,
+    #[kani(default = 0.5)]
+    ratio: f32,
+    #[kani(default)]
+    last: Option<String>,
+}
+
+#[derive(kani::Arbitrary)]
+enum Message {
+    Ping { id: u16, #[kani(default = String::from("ping"))] text: String },
+    Data(#[kani(skip)] Vec<u8>),
+}
+
+#[derive(kani::Arbitrary)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    Medium = 5,
+    High = 10,
+}
+
+fn main() {
+    let _ = |_: Cached<u8>, _: Handle, _: Stats<String>, _: Message, _: Level| ();
+}
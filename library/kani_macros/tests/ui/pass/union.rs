@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `Arbitrary` can be derived for unions with an active field.
+
+use std::mem::ManuallyDrop;
+
+/// A type that doesn't implement `Arbitrary`.
+#[derive(Clone, Copy)]
+struct Opaque;
+
+#[derive(kani::Arbitrary)]
+#[kani(active = int)]
+#[repr(C)]
+union Value {
+    int: u32,
+    float: f32,
+    opaque: Opaque,
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(active = value)]
+union Generic<T: Copy, U> {
+    value: T,
+    other: ManuallyDrop<U>,
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(active = bytes)]
+union Buffer {
+    #[kani(default = [0; 4])]
+    bytes: [u8; 4],
+    word: u32,
+}
+
+fn main() {
+    let _ = |_: Value, _: Generic<u8, Opaque>, _: Buffer| ();
+}
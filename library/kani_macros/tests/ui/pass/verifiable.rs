@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::verifiable]` implements both `Arbitrary` and `Invariant`, and accepts the
+//! same field attributes as the derive macros. Fields with `#[kani(..)]` don't need to implement
+//! either trait.
+
+use std::marker::PhantomData;
+
+#[kani::verifiable]
+#[derive(Clone, Debug)]
+struct Range {
+    #[safety_constraint(*start <= *end)]
+    start: u32,
+    end: u32,
+}
+
+#[kani::verifiable]
+struct Tagged<T> {
+    value: T,
+    #[kani(default = PhantomData)]
+    _tag: PhantomData<T>,
+}
+
+#[kani::verifiable]
+enum Speed {
+    Stopped,
+    Kmh(#[safety_constraint(*0 <= 300)] u16),
+}
+
+#[kani::verifiable]
+#[kani(constructor = "new")]
+struct Even {
+    #[safety_constraint(*val % 2 == 0)]
+    val: u8,
+}
+
+impl Even {
+    fn new(half: u8) -> Self {
+        Even { val: half.wrapping_mul(2) }
+    }
+}
+
+/// A type that implements neither `Arbitrary` nor `Invariant`.
+#[derive(Default)]
+struct Cache;
+
+#[kani::verifiable]
+struct Cached {
+    key: u8,
+    #[kani(skip)]
+    cache: Cache,
+}
+
+#[kani::verifiable]
+enum Entry {
+    Empty,
+    Full(u8, #[kani(default)] Cache),
+}
+
+fn check_impls<T: kani::Arbitrary + kani::Invariant>() {}
+
+fn main() {
+    check_impls::<Range>();
+    check_impls::<Tagged<u8>>();
+    check_impls::<Speed>();
+    check_impls::<Even>();
+    check_impls::<Cached>();
+    check_impls::<Entry>();
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `async` harnesses cannot take arguments.
+
+#[kani::proof]
+async fn harness(value: u8) {
+    assert!(value == value);
+}
+
+fn main() {}
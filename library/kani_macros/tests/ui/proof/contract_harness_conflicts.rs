@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that contract harnesses are rejected when they are combined with attributes that would
+//! defeat the check of the contract.
+
+fn double(x: u8) -> u8 {
+    x.wrapping_mul(2)
+}
+
+fn stub_double(x: u8) -> u8 {
+    x
+}
+
+#[kani::proof_for_contract(double)]
+#[kani::should_panic]
+fn check_should_panic() {
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+#[kani::stub(double, stub_double)]
+#[kani::stub_verified(double)]
+fn check_stubbed_target() {
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+#[kani::proof]
+#[kani::proof_for_contract(double)]
+fn check_duplicate() {
+    double(kani::any());
+}
+
+fn main() {}
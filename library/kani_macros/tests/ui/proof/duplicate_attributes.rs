@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that Kani attributes that can only be used once per harness are rejected when repeated,
+//! and that all the problems of a harness are reported together.
+
+#[kani::proof]
+#[kani::unwind(2)]
+#[kani::unwind(3)]
+#[kani::proof]
+fn harness() {}
+
+#[kani::proof]
+#[kani::should_panic]
+#[kani::solver(kissat)]
+#[kani::should_panic]
+fn other_harness() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` rejects options that are given more than once.
+
+#[kani::proof(schedule = kani::RoundRobin::default(), schedule = kani::RoundRobin::default())]
+async fn harness() {}
+
+fn main() {}
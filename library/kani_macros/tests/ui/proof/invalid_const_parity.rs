@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::const_parity]` can only be applied to constants, without arguments.
+
+const fn square(x: u8) -> u8 {
+    x * x
+}
+
+#[kani::const_parity]
+static SQUARE: u8 = square(3);
+
+#[kani::const_parity(exact)]
+const CUBE: u8 = square(3) * 3;
+
+fn main() {}
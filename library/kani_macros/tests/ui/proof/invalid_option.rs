@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` rejects unknown options.
+
+#[kani::proof(scheduel = kani::RoundRobin::default())]
+async fn harness() {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that harnesses cannot be generic or take arguments.
+
+#[kani::proof]
+fn with_args(value: u8) {
+    assert!(value == value);
+}
+
+#[kani::proof]
+fn generic<T: Default>() {
+    let _ = T::default();
+}
+
+// Lifetimes don't require monomorphization, so they are accepted.
+#[kani::proof]
+fn with_lifetime<'a>() {
+    let _: &'a str = "";
+}
+
+fn main() {}
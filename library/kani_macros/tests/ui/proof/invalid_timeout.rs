@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` rejects timeouts that aren't a positive number of seconds.
+
+#[kani::proof(timeout = 0)]
+fn zero() {}
+
+#[kani::proof(timeout = "60s")]
+fn not_a_number() {}
+
+fn main() {}
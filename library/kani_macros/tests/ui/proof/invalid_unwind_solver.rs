@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` rejects invalid values for the `unwind` and `solver` options.
+
+#[kani::proof(unwind = -1)]
+fn negative_unwind() {}
+
+#[kani::proof(unwind = "5")]
+fn string_unwind() {}
+
+#[kani::proof(solver = kani::kissat)]
+fn path_solver() {}
+
+#[kani::proof(solver = "not a solver")]
+fn invalid_solver_name() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` can only be applied to functions.
+
+#[kani::proof]
+struct Harness;
+
+fn main() {}
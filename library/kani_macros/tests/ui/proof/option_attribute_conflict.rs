@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::proof]` rejects options that are also given by a separate attribute.
+
+#[kani::proof(unwind = 5)]
+#[kani::unwind(10)]
+fn unwind_twice() {}
+
+#[kani::proof(solver = kissat)]
+#[kani::solver(minisat)]
+fn solver_twice() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that a schedule can only be given to `async` harnesses.
+
+#[kani::proof(schedule = kani::RoundRobin::default())]
+fn harness() {}
+
+fn main() {}
@@ -45,6 +45,8 @@ cargo test -p kani --lib # skip doc tests.
 # Test the actual macros, skipping doc tests and enabling extra traits for "syn"
 # so we can debug print AST
 RUSTFLAGS=--cfg=kani_sysroot cargo test -p kani_macros --features syn/extra-traits --lib
+# Check the diagnostics emitted by the macros.
+cargo test -p kani_macros --test ui
 
 # Declare testing suite information (suite and mode)
 TESTS=(
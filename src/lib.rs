@@ -17,7 +17,6 @@
 mod setup;
 
 use std::ffi::OsString;
-use std::os::unix::prelude::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::{env, fs};
@@ -99,7 +98,7 @@ fn fail_if_in_dev_environment() -> Result<()> {
 /// augmenting environment variables to accomodate our release environment
 fn exec(bin: &str) -> Result<()> {
     let kani_dir = setup::kani_dir()?;
-    let program = kani_dir.join("bin").join("kani-driver");
+    let program = kani_dir.join("bin").join(format!("kani-driver{}", env::consts::EXE_SUFFIX));
     let pyroot = kani_dir.join("pyroot");
     let bin_kani = kani_dir.join("bin");
     let bin_pyroot = pyroot.join("bin");
@@ -115,13 +114,28 @@ fn exec(bin: &str) -> Result<()> {
     set_kani_rust_toolchain(&kani_dir)?;
 
     let mut cmd = Command::new(program);
-    cmd.args(env::args_os().skip(1)).env("PYTHONPATH", pythonpath).env("PATH", path).arg0(bin);
+    cmd.args(env::args_os().skip(1)).env("PYTHONPATH", pythonpath).env("PATH", path);
+    set_invocation_name(&mut cmd, bin);
 
     let result = cmd.status().context("Failed to invoke kani-driver")?;
 
     std::process::exit(result.code().expect("No exit code?"));
 }
 
+/// Tell `kani-driver` which command the user invoked by setting `argv[0]`.
+#[cfg(unix)]
+fn set_invocation_name(cmd: &mut Command, bin: &str) {
+    use std::os::unix::process::CommandExt;
+    cmd.arg0(bin);
+}
+
+/// Windows doesn't allow us to set `argv[0]`, so we use an environment variable that is
+/// understood by `kani-driver` instead.
+#[cfg(not(unix))]
+fn set_invocation_name(cmd: &mut Command, bin: &str) {
+    cmd.env("KANI_INVOCATION_NAME", bin);
+}
+
 /// Prepend paths to an environment variable search string like PATH
 fn prepend_search_path(paths: &[PathBuf], original: Option<OsString>) -> Result<OsString> {
     match original {
@@ -150,6 +164,7 @@ fn prepend_search_path(paths: &[PathBuf], original: Option<OsString>) -> Result<
 /// path that rustup set. Then we can safely invoke our binaries. Note also that we update
 /// `PATH` in [`exec`] to include our favored Rust toolchain, so we won't re-drive `rustup` when
 /// `kani-driver` later invokes `cargo`.
+#[cfg(not(windows))]
 fn fixup_dynamic_linking_environment() {
     #[cfg(not(target_os = "macos"))]
     const LOADER_PATH: &str = "LD_LIBRARY_PATH";
@@ -164,11 +179,17 @@ fn fixup_dynamic_linking_environment() {
     }
 }
 
+/// On Windows, libraries are looked up using `PATH`, which [`exec`] already updates to prefer the
+/// Kani toolchain.
+#[cfg(windows)]
+fn fixup_dynamic_linking_environment() {}
+
 /// Determines if a path looks unlike a toolchain library path. These often looks like:
 /// `/home/user/.rustup/toolchains/nightly-x86_64-unknown-linux-gnu/lib`
 // Ignore this lint (recommending Path instead of PathBuf),
 // we want to take the right argument type for use in `filter` above.
 #[allow(clippy::ptr_arg)]
+#[cfg_attr(windows, allow(dead_code))]
 fn unlike_toolchain_path(path: &PathBuf) -> bool {
     let mut components = path.iter().rev();
 
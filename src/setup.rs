@@ -164,7 +164,9 @@ fn setup_python_deps(kani_dir: &Path, os: &os_info::Info) -> Result<()> {
         return Ok(());
     }
 
-    Command::new("python3")
+    // The python launcher on Windows isn't called `python3`.
+    let python = if cfg!(windows) { "python" } else { "python3" };
+    Command::new(python)
         .args(["-m", "pip", "install", "--target"])
         .arg(&pyroot)
         .args(pkg_versions)
@@ -206,6 +208,9 @@ fn symlink_rust_toolchain(toolchain: &Path, kani_dir: &Path) -> Result<()> {
     if path.exists() && path.is_symlink() {
         std::fs::remove_file(&path)?;
     }
+    #[cfg(unix)]
     std::os::unix::fs::symlink(toolchain, path)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(toolchain, path)?;
     Ok(())
 }
@@ -0,0 +1,7 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_bench() {
+    assert_eq!(all_targets::double(u8::MAX), 510);
+}
@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_example() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(all_targets::double(x) < 20);
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `--all-targets` finds the harnesses of every target of the package.
+
+pub fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[kani::proof]
+fn check_lib() {
+    let x: u8 = kani::any();
+    assert!(double(x) >= x as u16);
+}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_integration_test() {
+    let x: u8 = kani::any();
+    assert_eq!(all_targets::double(x) % 2, 0);
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z aliasing
+
+//! Check that `-Z aliasing` flags mutable references that alias another
+//! reference passed to the same call.
+
+fn swap_first(a: &mut [u8; 4], b: &mut u8) {
+    std::mem::swap(&mut a[0], b);
+}
+
+#[kani::proof]
+fn check_aliasing_mut_refs() {
+    let mut buf = [0u8; 4];
+    let idx: usize = kani::any();
+    kani::assume(idx < 4);
+    let ptr = buf.as_mut_ptr();
+    // SAFETY: both pointers are in bounds, but they may alias.
+    unsafe { swap_first(&mut *(ptr as *mut [u8; 4]), &mut *ptr.add(idx)) };
+}
+
+#[kani::proof]
+fn check_disjoint_mut_refs() {
+    let mut buf = [0u8; 4];
+    let mut val = 1u8;
+    swap_first(&mut buf, &mut val);
+    assert_eq!(buf[0], 1);
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z assertion-contest
+
+//! Check that `-Z assertion-contest` keeps the safety constraints assumed by a
+//! derived `Arbitrary` implementation, while it drops the assumptions of the
+//! harness.
+
+#[derive(kani::Arbitrary)]
+struct Percentage {
+    #[safety_constraint(*value <= 100)]
+    value: u8,
+}
+
+#[kani::proof]
+fn check_safety_constraint() {
+    let percentage: Percentage = kani::any();
+    let limit: u8 = kani::any();
+    kani::assume(limit > 100);
+    assert!(percentage.value <= 100, "the safety constraint holds");
+    assert!(percentage.value < limit, "the value is below the limit");
+}
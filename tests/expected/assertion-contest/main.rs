@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z assertion-contest
+
+//! Check that `-Z assertion-contest` tells apart the assertions that only hold
+//! because of the assumptions of the harness from the ones that always hold.
+
+#[kani::proof]
+fn check_assertions() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    assert!(x < 200, "x is in range");
+    assert!(x / 2 < 128, "half of x is below 128");
+}
+
+#[kani::proof]
+fn check_failure() {
+    let x: u8 = kani::any();
+    assert!(x == 0, "x is zero");
+}
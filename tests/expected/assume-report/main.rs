@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that assumptions created with `kani::assume!` are listed in the
+//! assumption report, together with their reachability.
+
+#[kani::proof]
+fn check_assume_report() {
+    let x: u8 = kani::any();
+    kani::assume!(x < 100, "x is a percentage");
+    if x > 200 {
+        kani::assume!(x % 2 == 0, "x is even");
+    }
+    kani::assume!(x != 0);
+    assert!(x < 100);
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z assumption-checks
+
+//! Check that `-Z assumption-checks` points at the assumption that made the
+//! harness vacuous, and not at the assumptions that come after it.
+
+#[kani::proof]
+fn check_contradictory_assumption() {
+    let x: u8 = kani::any();
+    kani::assume!(x > 10, "x is large");
+    kani::assume!(x < 5, "x is small");
+    kani::assume(x == 7);
+    assert!(x == 0);
+}
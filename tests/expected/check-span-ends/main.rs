@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --no-assertion-reach-checks --output-format json
+
+//! Check that the end of the source span of a check is reported even if the assertion
+//! reachability checks are disabled, and that the description of the check doesn't include the
+//! identifier used to find it.
+
+#[kani::proof]
+fn check_nested_overflow() {
+    let a: u8 = kani::any();
+    let b: u8 = kani::any();
+    let _ = a + b * 2;
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z complexity-lints
+
+//! Check that `-Z complexity-lints` warns about calls that reach code that is
+//! known to be expensive to verify.
+
+use std::collections::HashMap;
+
+#[kani::proof]
+fn check_hash_map() {
+    let mut map = HashMap::new();
+    map.insert(1u8, 2u8);
+    assert_eq!(map.len(), 1);
+}
+
+#[kani::proof]
+fn check_format() {
+    let s = format!("{}", 5u8);
+    assert!(!s.is_empty());
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_allocation() {
+    let mut v = Vec::new();
+    for i in 0..3u8 {
+        v.push(i);
+    }
+    let b = Box::new(1);
+    assert!(*b == 1);
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the harnesses generated by `#[kani::const_parity]` require
+//! `-Z const-fn-parity`.
+
+const fn square(x: u8) -> u8 {
+    x.wrapping_mul(x)
+}
+
+#[kani::const_parity]
+const SQUARE: u8 = square(3);
+
+#[kani::proof]
+fn check_square() {
+    assert_eq!(SQUARE, 9);
+}
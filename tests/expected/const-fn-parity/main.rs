@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z const-fn-parity
+
+//! Check that `-Z const-fn-parity` warns about the operations of a `const fn`
+//! whose result may differ between compile-time evaluation and runtime, and
+//! that it verifies the constants marked with `#[kani::const_parity]`.
+//! Floating-point operations are only reported in the `const fn`s that a
+//! constant evaluates.
+
+#![feature(const_fn_floating_point_arithmetic)]
+
+const fn scale(x: f32) -> f32 {
+    x * 2.0
+}
+
+/// No constant calls this function, so there's no warning.
+const fn halve(x: f32) -> f32 {
+    x / 2.0
+}
+
+const fn address(ptr: *const u8) -> usize {
+    unsafe { std::mem::transmute(ptr) }
+}
+
+/// Integer arithmetic behaves the same at compile time, so there's no warning.
+const fn square(x: u8) -> u8 {
+    x.wrapping_mul(x)
+}
+
+#[kani::const_parity]
+const TABLE: [u8; 3] = [square(1), square(2), square(3)];
+
+#[kani::const_parity]
+const SCALED: [f32; 2] = [scale(1.5), scale(-0.25)];
+
+#[kani::proof]
+fn check_const_fns() {
+    let x: u8 = kani::any();
+    kani::assume(x < 3);
+    assert_eq!(TABLE[x as usize], square(x + 1));
+    assert!(scale(1.0) == 2.0);
+    assert!(halve(scale(1.0)) == 1.0);
+    let byte = 0u8;
+    assert!(address(&byte) != 0);
+}
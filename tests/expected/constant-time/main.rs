@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z constant-time
+
+//! Check that `-Z constant-time` reports branches and memory accesses that depend
+//! on a secret, and accepts code that doesn't.
+
+fn leaky_eq(secret: &[u8; 4], guess: &[u8; 4]) -> bool {
+    for i in 0..4 {
+        if secret[i] != guess[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn constant_time_eq(secret: &[u8; 4], guess: &[u8; 4]) -> bool {
+    let mut diff = 0u8;
+    for i in 0..4 {
+        diff |= secret[i] ^ guess[i];
+    }
+    diff == 0
+}
+
+#[kani::proof]
+fn check_leaky_eq() {
+    let secret: [u8; 4] = kani::secret();
+    let guess: [u8; 4] = kani::any();
+    leaky_eq(&secret, &guess);
+}
+
+#[kani::proof]
+fn check_table_lookup() {
+    let table = [1u8, 2, 3, 4];
+    let secret: u8 = kani::secret();
+    let _ = table[(secret % 4) as usize];
+}
+
+#[kani::proof]
+fn check_constant_time_eq() {
+    let secret: [u8; 4] = kani::secret();
+    let guess: [u8; 4] = kani::any();
+    let _ = constant_time_eq(&secret, &guess);
+}
@@ -0,0 +1,55 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z doc-harnesses
+
+//! Check that the examples of the documentation fenced with ```kani are verified as harnesses.
+//!
+//! ```kani
+//! let x: u8 = kani::any();
+//! kani::assume(x < 100);
+//! assert_eq!(main::add_one(x), x + 1);
+//! ```
+
+/// Adds one to `x`.
+///
+/// ```
+/// // This example is not verified.
+/// assert_eq!(main::add_one(1), 3);
+/// ```
+///
+/// ```kani,should_panic
+/// # use main::add_one;
+/// add_one(u8::MAX);
+/// ```
+pub fn add_one(x: u8) -> u8 {
+    x + 1
+}
+
+/// Doubles `x`.
+///
+/// ```kani
+/// #[kani::proof]
+/// fn check_double() {
+///     let x: u8 = kani::any();
+///     assert_eq!(double(x), x.wrapping_mul(2));
+/// }
+/// ```
+pub fn double(x: u8) -> u8 {
+    x + x
+}
+
+/// Text that looks like an example, but isn't documentation.
+pub const NOT_AN_EXAMPLE: &str = r#"
+/// ```kani
+/// assert!(false);
+/// ```
+"#;
+
+mod private {
+    /// Examples in a private module can use the items of the crate through its name.
+    ///
+    /// ```kani
+    /// assert_eq!(main::add_one(1), 2);
+    /// ```
+    pub(crate) fn example() {}
+}
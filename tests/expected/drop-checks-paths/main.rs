@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z drop-checks -Z unwind
+
+//! Check that `-Z drop-checks` follows the drops of early-return and unwind
+//! paths: values dropped once on those paths pass, and values that they drop a
+//! second time are flagged.
+
+use std::mem;
+use std::ptr;
+
+fn len_or_zero(v: Vec<u8>, early: bool) -> usize {
+    if early {
+        return 0;
+    }
+    v.len()
+}
+
+#[kani::proof]
+fn check_early_return() {
+    let early: bool = kani::any();
+    let _ = len_or_zero(vec![1], early);
+}
+
+#[kani::proof]
+fn check_double_drop_on_early_return() {
+    let mut value = vec![1u8];
+    let early: bool = kani::any();
+    // SAFETY: the pointer is valid, but the early return drops the value again.
+    unsafe { ptr::drop_in_place(&mut value) };
+    if early {
+        return;
+    }
+    mem::forget(value);
+}
+
+#[kani::proof]
+fn check_double_drop_on_unwind() {
+    let mut value = vec![1u8];
+    let fail: bool = kani::any();
+    // SAFETY: the pointer is valid, but unwinding drops the value again.
+    unsafe { ptr::drop_in_place(&mut value) };
+    if fail {
+        panic!("failed after the drop");
+    }
+    mem::forget(value);
+}
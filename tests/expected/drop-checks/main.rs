@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z drop-checks
+
+//! Check that `-Z drop-checks` flags values that are dropped more than once.
+
+use std::mem::MaybeUninit;
+use std::ptr;
+
+#[kani::proof]
+fn check_double_drop() {
+    let mut value = Some(vec![1u8]);
+    let cond: bool = kani::any();
+    // SAFETY: the pointer is valid, but the value may be dropped twice.
+    unsafe {
+        ptr::drop_in_place(&mut value);
+        if cond {
+            ptr::drop_in_place(&mut value);
+        }
+        ptr::write(&mut value, None);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_reinit_after_drop() {
+    let mut slot: MaybeUninit<Vec<u8>> = MaybeUninit::uninit();
+    for i in 0..3 {
+        slot.write(vec![i]);
+        // SAFETY: the slot was initialized above and is only dropped once per iteration.
+        unsafe { slot.assume_init_drop() };
+    }
+}
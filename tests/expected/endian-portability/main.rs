@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z endian-portability
+
+//! Check that `-Z endian-portability` finds serialization code that assumes a
+//! little endian target.
+
+/// Writes a length prefix that is only portable if it uses an explicit byte order.
+fn encode(len: u16, portable: bool) -> [u8; 2] {
+    if portable { len.to_le_bytes() } else { len.to_ne_bytes() }
+}
+
+#[kani::proof]
+fn check_portable_encoding() {
+    let len: u16 = kani::any();
+    let bytes = encode(len, true);
+    assert_eq!(bytes[0], len as u8, "the first byte is the least significant");
+}
+
+#[kani::proof]
+fn check_native_encoding() {
+    let len: u16 = kani::any();
+    let bytes = encode(len, false);
+    assert_eq!(bytes[0], len as u8, "the first byte is the least significant");
+}
@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z enum-validity-checks
+
+//! Check that `-Z enum-validity-checks` flags enums created by unsafe code
+//! whose tag doesn't encode any variant, for both direct and niche encodings.
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Sparse {
+    A = 0,
+    B = 2,
+}
+
+#[kani::proof]
+fn check_direct_tag() {
+    let raw: u8 = kani::any();
+    kani::assume(raw <= 2);
+    // `1` is not the discriminant of any variant.
+    let value: Sparse = unsafe { std::mem::transmute(raw) };
+    assert_eq!(value == Sparse::A, raw == 0);
+}
+
+#[kani::proof]
+fn check_niche_tag() {
+    let raw: u8 = kani::any();
+    kani::assume(raw <= 3);
+    // `2` encodes `None`, and `3` is not valid.
+    let value: Option<bool> = unsafe { std::mem::transmute(raw) };
+    assert_eq!(value.is_some(), raw < 2);
+}
+
+#[kani::proof]
+fn check_pointer_write() {
+    let mut value = Sparse::A;
+    unsafe { *(&mut value as *mut Sparse as *mut u8) = 2 };
+    assert_eq!(value, Sparse::B);
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::expect_fail!` reports the failures of the wrapped expression as expected,
+//! and that it fails if the expected failure doesn't occur or has another class.
+
+#[kani::proof]
+fn check_expected() {
+    let x: u8 = kani::any();
+    let _ = kani::expect_fail!(x + 1);
+}
+
+#[kani::proof]
+fn check_missing() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    let _ = kani::expect_fail!("arithmetic_overflow", x + 1);
+}
+
+#[kani::proof]
+fn check_other_class() {
+    let x: u8 = kani::any();
+    let _ = kani::expect_fail!("assertion", x + 1);
+}
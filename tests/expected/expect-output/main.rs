@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::expect_output]` compares the results of a harness with the lines of a
+//! file, and that the harness fails if a line is missing.
+
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[kani::proof]
+#[kani::expect_output("overflow.expected")]
+fn check_matching() {
+    let _ = add(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::expect_output("overflow.expected")]
+fn check_mismatch() {
+    let a: u8 = kani::any();
+    kani::assume(a < 10);
+    let _ = add(a, 1);
+}
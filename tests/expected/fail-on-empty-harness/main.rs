@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --fail-on-empty-harness
+
+//! Check that harnesses without any check are reported, and that they fail with
+//! `--fail-on-empty-harness`.
+
+#[kani::proof]
+fn check_nothing() {
+    let x: u8 = kani::any();
+    let _ = x.wrapping_add(1);
+}
+
+#[kani::proof]
+fn check_something() {
+    let x: u8 = kani::any();
+    assert!(x.checked_add(1).is_some() || x == u8::MAX);
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --contract div
+
+//! Check that `--contract` only verifies the harnesses that check the
+//! contract of the given function.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+// The contract is deliberately wrong, so `max_harness` would fail if it was
+// selected by `--contract div`.
+#[kani::ensures(result == x)]
+fn max(x: u32, y: u32) -> u32 {
+    if x > y { x } else { y }
+}
+
+#[kani::proof_for_contract(div)]
+fn div_harness() {
+    div(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(max)]
+fn max_harness() {
+    max(7, 9);
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `should_panic` is rejected on contract harnesses when it's written before
+//! `proof_for_contract`, in which case it's only visible to the compiler.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[kani::should_panic]
+#[kani::proof_for_contract(increment)]
+fn check_before() {
+    increment(kani::any());
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `should_panic` is rejected on contract harnesses by the `proof_for_contract` macro.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[kani::proof_for_contract(increment)]
+#[kani::should_panic]
+fn check_after() {
+    increment(kani::any());
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts -Zstubbing
+
+//! Check that a contract harness cannot stub its target with `stub` or `stub_verified` when the
+//! attributes are written before `proof_for_contract`, in which case they're only visible to the
+//! compiler.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+fn stub_increment(x: u8) -> u8 {
+    x + 2
+}
+
+#[kani::stub(increment, stub_increment)]
+#[kani::proof_for_contract(increment)]
+fn check_stub() {
+    increment(kani::any());
+}
+
+#[kani::stub_verified(increment)]
+#[kani::proof_for_contract(increment)]
+fn check_stub_verified() {
+    increment(kani::any());
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z harness-lints
+
+//! Check that `-Z harness-lints` warns about symbolic values that are never used, including
+//! values that are only copied to variables that are never read.
+
+#[kani::proof]
+fn check_unused() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    let _ = kani::any::<u16>();
+    let z: u32 = kani::any_where(|z| *z < 10);
+    let w = <i8 as kani::Arbitrary>::any();
+    assert!(y < u8::MAX || z < 10);
+}
+
+#[kani::proof]
+fn check_only_copied() {
+    let a: u8 = kani::any();
+    let b = a;
+    let _ = b;
+    let c: u8 = kani::any();
+    let d = c;
+    assert!(d.checked_add(0).is_some());
+}
+
+#[kani::proof]
+fn check_used_in_closure() {
+    let x: u8 = kani::any();
+    let f = |y: u8| y.wrapping_add(x);
+    assert_eq!(f(0), x);
+}
@@ -0,0 +1,76 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z interrupts
+
+//! Check that `-Z interrupts` may invoke the attached interrupt handlers
+//! between memory accesses, and that a critical section protects the data.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static mut QUEUE: [u8; 4] = [0; 4];
+static mut LEN: usize = 0;
+static mut PUSHED: usize = 0;
+static IRQ_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// The handler of a receive interrupt that pushes a byte to the queue.
+fn on_receive() {
+    if IRQ_ENABLED.load(Ordering::SeqCst) {
+        unsafe {
+            if LEN < 4 {
+                QUEUE[LEN] = 0xFF;
+                LEN += 1;
+                PUSHED += 1;
+            }
+        }
+    }
+}
+
+/// Remove the last byte from the queue without masking interrupts.
+fn pop_racy() -> Option<u8> {
+    unsafe {
+        if LEN == 0 {
+            return None;
+        }
+        let idx = LEN - 1;
+        let byte = QUEUE[idx];
+        LEN = idx;
+        Some(byte)
+    }
+}
+
+/// Remove the last byte from the queue inside a critical section.
+fn pop_critical() -> Option<u8> {
+    IRQ_ENABLED.store(false, Ordering::SeqCst);
+    let byte = pop_racy();
+    IRQ_ENABLED.store(true, Ordering::SeqCst);
+    byte
+}
+
+fn setup() {
+    unsafe {
+        QUEUE = [1, 2, 3, 4];
+        LEN = 2;
+    }
+    kani::interrupt::attach(on_receive);
+}
+
+/// Check that every byte is either in the queue or was popped.
+fn check_no_loss(popped: Option<u8>) {
+    IRQ_ENABLED.store(false, Ordering::SeqCst);
+    assert!(popped.is_some());
+    assert!(unsafe { LEN + 1 == 2 + PUSHED }, "byte was lost");
+}
+
+#[kani::proof]
+fn check_pop_racy() {
+    setup();
+    let byte = pop_racy();
+    check_no_loss(byte);
+}
+
+#[kani::proof]
+fn check_pop_critical() {
+    setup();
+    let byte = pop_critical();
+    check_no_loss(byte);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z mmio --mmio-region 0x4000_0000:0x100
+
+//! Check that volatile accesses to the regions given by `--mmio-region` are modeled as
+//! memory-mapped I/O: reads return any value and writes are ignored.
+
+use std::ptr;
+
+const UART_BASE: usize = 0x4000_0000;
+const UART_STATUS: *mut u32 = UART_BASE as *mut u32;
+const UART_DATA: *mut u32 = (UART_BASE + 4) as *mut u32;
+const TX_READY: u32 = 0x1;
+
+/// A minimal driver that waits for the device to be ready before sending a byte.
+fn send(byte: u8) -> bool {
+    // SAFETY: the UART registers are memory-mapped.
+    unsafe {
+        if ptr::read_volatile(UART_STATUS) & TX_READY == 0 {
+            return false;
+        }
+        ptr::write_volatile(UART_DATA, byte as u32);
+    }
+    true
+}
+
+#[kani::proof]
+fn check_send() {
+    let sent = send(kani::any());
+    kani::cover!(sent, "device was ready");
+    kani::cover!(!sent, "device was busy");
+}
+
+#[kani::proof]
+fn check_status_is_nondet() {
+    // SAFETY: the UART registers are memory-mapped.
+    let status = unsafe { ptr::read_volatile(UART_STATUS) };
+    assert_eq!(status & TX_READY, 0, "device may be ready");
+}
+
+#[kani::proof]
+fn check_outside_region() {
+    let mut value = 10u32;
+    // SAFETY: the pointer is valid and aligned.
+    unsafe {
+        ptr::write_volatile(&mut value, 20);
+        assert_eq!(ptr::read_volatile(&value), 20);
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the number and size of the nondeterministic values of each harness are reported,
+//! and that harnesses with large inputs are flagged.
+
+#[kani::proof]
+fn check_small() {
+    let x: u32 = kani::any();
+    let b: bool = kani::any();
+    assert!(b || x == x);
+}
+
+#[kani::proof]
+fn check_large() {
+    let data: [u8; 65536] = kani::any();
+    assert!(data.len() == 65536);
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::assert_noninterference!` proves that an output doesn't depend
+//! on a secret, and reports a failure when it does.
+
+fn checksum(secret: u32, public: u32) -> u32 {
+    // The secret is masked out, so it never reaches the output.
+    public.wrapping_mul(31) ^ (secret & 0)
+}
+
+fn leaky_checksum(secret: u32, public: u32) -> u32 {
+    if public == 0 { secret } else { public.wrapping_mul(31) }
+}
+
+#[kani::proof]
+fn check_checksum() {
+    let public: u32 = kani::any();
+    kani::assert_noninterference!(secret: u32 => checksum(secret, public));
+}
+
+#[kani::proof]
+fn check_leaky_checksum() {
+    let public: u32 = kani::any();
+    kani::assert_noninterference!(secret: u32 => leaky_checksum(secret, public));
+}
+
+#[kani::proof]
+fn check_multiple_secrets() {
+    let public: [u8; 2] = kani::any();
+    kani::assert_noninterference!(a: u8, b: bool => public[0].wrapping_add(public[1]) | (a & 0) | (b as u8 & 0));
+}
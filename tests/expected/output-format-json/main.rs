@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --output-format json
+
+//! Check that `--output-format json` prints the results as a JSON document.
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(x + 1 < 10, "x is too large");
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --overflow-semantics wrap
+
+//! Check that `--overflow-semantics wrap` models integer overflow as two's-complement
+//! wrapping, without any overflow check.
+
+#[kani::proof]
+fn check_wrapping_add() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    assert_eq!(x + y, x.wrapping_add(y));
+}
+
+#[kani::proof]
+fn check_wrapping_neg() {
+    let x: i32 = kani::any();
+    assert_eq!(-x, x.wrapping_neg());
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the description of a panic whose message is formatted at runtime
+//! shows the format string of the message.
+
+#[kani::proof]
+fn check_unreachable() {
+    let x: u8 = kani::any();
+    if x > 10 {
+        unreachable!("unexpected value {}", x);
+    }
+}
+
+#[kani::proof]
+fn check_core_assert() {
+    let s = if kani::any() { "a" } else { "b" };
+    core::assert!(s == "a", "unexpected string {s}");
+}
+
+macro_rules! check_even {
+    ($val:expr) => {
+        if $val % 2 != 0 {
+            panic!("odd value {}", $val);
+        }
+    };
+}
+
+#[kani::proof]
+fn check_nested_macro() {
+    let x: u8 = kani::any();
+    check_even!(x);
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z provenance-checks
+
+//! Check that `-Z provenance-checks` flags integer-to-pointer casts that do not
+//! point to any live allocation, while allowing round trips through `usize`.
+
+#[kani::proof]
+fn check_arbitrary_address() {
+    let addr: usize = kani::any();
+    let ptr = addr as *const u8;
+    let _ = ptr;
+}
+
+#[kani::proof]
+fn check_round_trip() {
+    let val = 10u32;
+    let addr = &val as *const u32 as usize;
+    let ptr = addr as *const u32;
+    assert_eq!(unsafe { *ptr }, 10);
+}
+
+#[kani::proof]
+fn check_null() {
+    let ptr = 0usize as *const u8;
+    assert!(ptr.is_null());
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --verbose
+//
+//! Check that Kani reports how long it took to generate the code of each harness, both as a
+//! progress message during compilation and next to the verification time.
+
+#[kani::proof]
+fn check_first() {
+    let x: u8 = kani::any();
+    assert!(x.checked_add(1).is_some() || x == u8::MAX);
+}
+
+#[kani::proof]
+fn check_second() {
+    let y: bool = kani::any();
+    assert!(y || !y);
+}
@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the models of `kani::sync` report the calls that would block forever with the
+//! `self_blocking` property class.
+
+extern crate kani;
+
+use kani::sync::{mpsc, Mutex, RwLock};
+
+#[kani::proof]
+fn check_mutex_relock() {
+    let counter = Mutex::new(0u8);
+    let mut guard = counter.lock().unwrap();
+    *guard += 1;
+    if kani::any() {
+        drop(guard);
+    }
+    // Blocks forever if the guard is still alive.
+    let total = *counter.lock().unwrap();
+    assert_eq!(total, 1);
+}
+
+#[kani::proof]
+fn check_rwlock() {
+    let lock = RwLock::new(vec![1u8]);
+    {
+        let first = lock.read().unwrap();
+        let second = lock.read().unwrap();
+        assert_eq!(first.len(), second.len());
+        assert!(lock.try_write().is_err());
+    }
+    lock.write().unwrap().push(2);
+    let reader = lock.read().unwrap();
+    // Blocks forever, since the read guard is alive.
+    lock.write().unwrap().clear();
+    drop(reader);
+}
+
+#[kani::proof]
+fn check_recv_empty() {
+    let (_tx, rx) = mpsc::channel::<u8>();
+    let _ = rx.recv();
+}
@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z send-sync-lints
+
+//! Check that `-Z send-sync-lints` warns about the fields that an
+//! `unsafe impl Send` or `unsafe impl Sync` makes assumptions about.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+/// Missing `T: Send` bound.
+struct Wrapper<T> {
+    inner: T,
+}
+
+unsafe impl<T> Send for Wrapper<T> {}
+
+/// The bound makes the impl safe, so there's no warning.
+struct Bounded<T> {
+    inner: T,
+}
+
+unsafe impl<T: Sync> Sync for Bounded<T> {}
+
+/// Interior mutability without synchronization.
+struct Counter {
+    count: Cell<u32>,
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl Sync for Counter {}
+
+struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+unsafe impl Send for Buffer {}
+
+#[kani::proof]
+fn check_types() {
+    let w = Wrapper { inner: 1u8 };
+    let b = Bounded { inner: 2u8 };
+    let c = Counter { count: Cell::new(0), _marker: PhantomData };
+    c.count.set(u32::from(w.inner + b.inner));
+    let mut byte = 0u8;
+    let buf = Buffer { ptr: &mut byte, len: 1 };
+    assert_eq!(c.count.get() as usize, buf.len + 2);
+    assert!(!buf.ptr.is_null());
+}
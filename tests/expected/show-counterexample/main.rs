@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --show-counterexample
+
+//! Check that `--show-counterexample` prints the values of `kani::any()` that make a check fail.
+
+#[kani::proof]
+fn check_counterexample() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    kani::assume(y == 0);
+    assert!(x < 200 || y > 1);
+}
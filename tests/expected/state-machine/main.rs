@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::state_machine` explores bounded sequences of actions and
+//! checks the invariant after each step.
+
+extern crate kani;
+use kani::state_machine::StateMachine;
+
+#[derive(kani::Arbitrary)]
+enum Event {
+    Open,
+    Send,
+    Close,
+}
+
+/// A connection that allows at most `LIMIT` messages before it must be closed.
+#[derive(Clone, Copy)]
+enum Connection<const LIMIT: u8> {
+    Closed,
+    Open { sent: u8 },
+}
+
+impl<const LIMIT: u8> StateMachine for Connection<LIMIT> {
+    type Action = Event;
+
+    fn is_enabled(&self, event: &Event) -> bool {
+        match self {
+            Connection::Closed => matches!(event, Event::Open),
+            Connection::Open { .. } => !matches!(event, Event::Open),
+        }
+    }
+
+    fn step(&mut self, event: Event) {
+        *self = match (*self, event) {
+            (Connection::Open { sent }, Event::Send) => Connection::Open { sent: sent + 1 },
+            (_, Event::Close) => Connection::Closed,
+            _ => Connection::Open { sent: 0 },
+        }
+    }
+
+    fn invariant(&self) -> bool {
+        match self {
+            Connection::Closed => true,
+            Connection::Open { sent } => *sent <= LIMIT,
+        }
+    }
+}
+
+kani::state_machine_harness!(check_short_session, Connection::<2>::Closed, 3, unwind = 4);
+kani::state_machine_harness!(check_long_session, Connection::<2>::Closed, 5, unwind = 6);
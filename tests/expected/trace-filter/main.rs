@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --enable-unstable --trace-filter user-crate --trace-filter var=x --trace-max-steps 1
+
+//! Check that `--trace-filter` and `--trace-max-steps` print the filtered trace of failed checks.
+
+#[kani::proof]
+fn check_trace() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    assert!(x < 200 || y > 1);
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z trusted-code --trusted main::vendor
+
+//! Check that harnesses inside of a `--trusted` path are rejected, since
+//! their own checks would be disabled.
+
+mod vendor {
+    pub fn get(data: &[u8; 4], idx: usize) -> u8 {
+        data[idx]
+    }
+
+    #[kani::proof]
+    fn check_get() {
+        let data = [1, 2, 3, 4];
+        assert_eq!(get(&data, kani::any()), 1);
+    }
+}
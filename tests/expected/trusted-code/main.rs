@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z trusted-code --trusted main::vendor
+
+//! Check that the checks of the code inside a `--trusted` path are disabled,
+//! that its panics and the checks of the harness are still reported, and that
+//! the trusted functions reached by a harness are reported.
+
+mod vendor {
+    /// Vendored code that indexes out of bounds when `idx` is too large.
+    pub fn get(data: &[u8; 4], idx: usize) -> u8 {
+        data[idx]
+    }
+
+    /// Vendored code that panics when `idx` is too large.
+    pub fn get_or_panic(data: &[u8; 4], idx: usize) -> u8 {
+        if idx >= data.len() {
+            panic!("vendored panic");
+        }
+        data[idx]
+    }
+}
+
+fn get_checked(data: &[u8; 4], idx: usize) -> u8 {
+    data[idx]
+}
+
+#[kani::proof]
+fn check_trusted() {
+    let data = [1, 2, 3, 4];
+    let idx: usize = kani::any();
+    let _ = vendor::get(&data, idx);
+}
+
+#[kani::proof]
+fn check_untrusted() {
+    let data = [1, 2, 3, 4];
+    let idx: usize = kani::any();
+    let _ = get_checked(&data, idx);
+}
+
+#[kani::proof]
+fn check_after_trusted() {
+    let data = [1, 2, 3, 4];
+    let idx: usize = kani::any();
+    let value = vendor::get(&data, idx);
+    // The execution only continues if the index was in bounds, like at runtime.
+    assert!(idx < data.len());
+    assert!(value != 3, "the harness is checked after the trusted call");
+}
+
+#[kani::proof]
+fn check_trusted_panic() {
+    let data = [1, 2, 3, 4];
+    let idx: usize = kani::any();
+    let _ = vendor::get_or_panic(&data, idx);
+}
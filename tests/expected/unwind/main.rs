@@ -0,0 +1,60 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unwind
+
+//! Check that `-Z unwind` runs destructors while the stack unwinds after a panic,
+//! so bugs in drop-on-panic paths are found.
+
+use std::cell::Cell;
+
+/// Guard that restores a counter when it goes out of scope.
+struct Restore<'a> {
+    counter: &'a Cell<u8>,
+    value: u8,
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        self.counter.set(self.value);
+    }
+}
+
+fn update(counter: &Cell<u8>, fail: bool) {
+    let _restore = Restore { counter, value: counter.get() };
+    counter.set(counter.get() + 1);
+    if fail {
+        panic!("update failed");
+    }
+}
+
+/// Guard whose destructor has a bug that is only reachable on the panic path.
+struct Buggy {
+    armed: bool,
+}
+
+impl Drop for Buggy {
+    fn drop(&mut self) {
+        assert!(!self.armed, "dropped an armed guard");
+    }
+}
+
+fn arm_and_fail(guard: &mut Buggy) {
+    guard.armed = true;
+    let _x: u8 = [1u8, 2, 3][kani::any::<usize>() % 4];
+    guard.armed = false;
+}
+
+#[kani::proof]
+fn check_guard_restores_on_panic() {
+    let counter = Cell::new(0);
+    let outer = Restore { counter: &counter, value: 10 };
+    update(&counter, kani::any());
+    drop(outer);
+    assert_eq!(counter.get(), 10);
+}
+
+#[kani::proof]
+fn check_drop_on_panic_path() {
+    let mut guard = Buggy { armed: false };
+    arm_and_fail(&mut guard);
+}
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any_fn` behaves like a function, and that it covers every behavior.
+
+use std::cmp::Ordering;
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_same_input_same_output() {
+    let f = kani::any_fn::<u8, u16, 3>();
+    let x: u8 = kani::any();
+    let first = f.call(x);
+    let _ = f.call(x.wrapping_add(1));
+    assert_eq!(f.call(x), first);
+    assert_eq!(f.table().len(), 2);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_all_behaviors() {
+    let cmp = kani::any_fn::<(u8, u8), Ordering, 2>();
+    let (a, b) = (cmp.call((1, 2)), cmp.call((2, 1)));
+    kani::cover!(a == Ordering::Less && b == Ordering::Greater);
+    kani::cover!(a == Ordering::Less && b == Ordering::Less);
+    kani::cover!(a == Ordering::Equal && b == Ordering::Greater);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_as_fn() {
+    let f = kani::any_fn::<u8, bool, 2>();
+    let g = f.as_fn();
+    let input = [7u8, 7u8];
+    assert_eq!(input.iter().filter(|x| g(**x)).count() % 2, 0);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+#[kani::should_panic]
+fn check_table_bound() {
+    let f = kani::any_fn::<u8, u8, 1>();
+    f.call(1);
+    f.call(2);
+}
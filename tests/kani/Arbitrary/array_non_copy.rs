@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 11
+//
+//! Check that `kani::any()` can generate arrays of types that are neither `Copy` nor `Default`.
+
+#[derive(kani::Arbitrary, PartialEq, Debug)]
+struct Handle {
+    id: u8,
+    valid: bool,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {}
+}
+
+#[kani::proof]
+fn check_any_array_of_non_copy() {
+    let handles: [Handle; 10] = kani::any();
+    kani::assume(handles[0].valid);
+    assert!(handles[0].valid);
+    kani::cover!(handles[9].id == 42 && !handles[9].valid);
+}
+
+#[kani::proof]
+fn check_any_array_of_options() {
+    let arr: [Option<Handle>; 3] = kani::any();
+    kani::cover!(arr[0].is_none() && arr[2].is_some());
+}
+
+#[kani::proof]
+fn check_any_nested_array() {
+    let arr: [[Handle; 2]; 2] = kani::any();
+    kani::cover!(arr[0][0] != arr[1][1]);
+}
+
+#[kani::proof]
+fn check_any_empty_array() {
+    let arr: [Handle; 0] = kani::any();
+    assert!(arr.is_empty());
+}
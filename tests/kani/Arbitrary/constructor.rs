@@ -0,0 +1,63 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `#[kani(constructor = "...")]` creates symbolic values by calling the given
+//! constructor, so they satisfy the invariants it enforces.
+
+extern crate kani;
+
+mod sorted {
+    /// A pair whose elements are always in order, which is enforced by its constructor.
+    #[derive(kani::Arbitrary)]
+    #[kani(constructor = "new")]
+    pub struct SortedPair {
+        low: u8,
+        high: u8,
+    }
+
+    impl SortedPair {
+        pub fn new(a: u8, b: u8) -> Self {
+            if a <= b { SortedPair { low: a, high: b } } else { SortedPair { low: b, high: a } }
+        }
+
+        pub fn low(&self) -> u8 {
+            self.low
+        }
+
+        pub fn high(&self) -> u8 {
+            self.high
+        }
+    }
+}
+
+fn percentage(value: u8) -> Percentage {
+    Percentage { value: value % 101 }
+}
+
+#[derive(kani::Arbitrary)]
+#[kani(constructor = "crate::percentage")]
+struct Percentage {
+    #[safety_constraint(*value != 50)]
+    value: u8,
+}
+
+#[kani::proof]
+fn check_constructor() {
+    let pair: sorted::SortedPair = kani::any();
+    assert!(pair.low() <= pair.high());
+    kani::cover!(pair.low() < pair.high());
+}
+
+#[kani::proof]
+fn check_constructor_with_constraint() {
+    let percentage: Percentage = kani::any();
+    assert!(percentage.value <= 100);
+    assert!(percentage.value != 50);
+    kani::cover!(percentage.value == 100);
+}
+
+#[kani::proof]
+fn check_call_with_any() {
+    let (a, b): (u8, bool) = kani::arbitrary::call_with_any(|a: u8, b: bool| (a, b));
+    kani::cover!(a == 10 && b);
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the Arbitrary implementations for `std::net` types cover all kinds of addresses.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
+
+#[kani::proof]
+fn check_any_ip_addr() {
+    let addr: IpAddr = kani::any();
+    kani::cover!(addr.is_ipv4());
+    kani::cover!(addr.is_ipv6());
+    kani::cover!(addr.is_loopback());
+    if let IpAddr::V4(v4) = addr {
+        assert_eq!(Ipv4Addr::from(u32::from(v4)), v4);
+    }
+}
+
+#[kani::proof]
+fn check_any_ipv6_addr() {
+    let addr: Ipv6Addr = kani::any();
+    assert_eq!(Ipv6Addr::from(addr.octets()), addr);
+    kani::cover!(addr.to_ipv4_mapped().is_some());
+}
+
+#[kani::proof]
+fn check_any_socket_addr() {
+    let addr: SocketAddr = kani::any();
+    kani::cover!(addr.port() == 443);
+    match addr {
+        SocketAddr::V4(v4) => assert_eq!(addr.ip(), IpAddr::V4(*v4.ip())),
+        SocketAddr::V6(v6) => assert_eq!(addr.ip(), IpAddr::V6(*v6.ip())),
+    }
+}
+
+#[kani::proof]
+fn check_any_shutdown() {
+    let how: Shutdown = kani::any();
+    kani::cover!(how == Shutdown::Read);
+    kani::cover!(how == Shutdown::Write);
+    kani::cover!(how == Shutdown::Both);
+}
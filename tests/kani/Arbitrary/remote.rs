@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any_remote` creates symbolic values of types that are mirrored with
+//! `#[kani::arbitrary_remote]`.
+
+extern crate kani;
+
+use std::ops::Range;
+
+/// Represent types from a crate that we cannot modify.
+mod foreign {
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub struct Wrapper<T>(pub T, pub bool);
+
+    pub enum Shape {
+        Empty,
+        Circle(u32),
+        Rect { width: u32, height: u32 },
+    }
+}
+
+#[kani::arbitrary_remote(foreign::Point)]
+struct PointDef {
+    #[safety_constraint(*x >= 0)]
+    x: i32,
+    y: i32,
+}
+
+#[kani::arbitrary_remote(foreign::Wrapper<T>)]
+struct WrapperDef<T>(T, bool);
+
+#[kani::arbitrary_remote(foreign::Shape)]
+enum ShapeDef {
+    Empty,
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+}
+
+#[kani::arbitrary_remote(Range<u8>)]
+struct RangeDef {
+    #[safety_constraint(*start <= *end)]
+    start: u8,
+    end: u8,
+}
+
+#[kani::proof]
+fn check_remote_struct() {
+    let point = kani::any_remote::<PointDef>();
+    assert!(point.x >= 0);
+    kani::cover!(point.y < 0);
+}
+
+#[kani::proof]
+fn check_remote_generic() {
+    let wrapper = kani::any_remote::<WrapperDef<u8>>();
+    kani::cover!(wrapper.0 == 42 && wrapper.1);
+}
+
+#[kani::proof]
+fn check_remote_enum() {
+    let shape = kani::any_remote::<ShapeDef>();
+    kani::cover!(matches!(shape, foreign::Shape::Empty));
+    kani::cover!(matches!(shape, foreign::Shape::Circle(10)));
+    kani::cover!(matches!(shape, foreign::Shape::Rect { width: 1, height: 2 }));
+}
+
+#[kani::proof]
+fn check_remote_std() {
+    let range = kani::any_remote::<RangeDef>();
+    assert!(range.start <= range.end);
+    assert_eq!(range.len(), (range.end - range.start) as usize);
+}
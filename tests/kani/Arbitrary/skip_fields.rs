@@ -0,0 +1,82 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that fields annotated with `#[kani(..)]` are not symbolic, while the other fields are.
+
+extern crate kani;
+
+use std::marker::PhantomData;
+
+#[derive(kani::Arbitrary)]
+struct Counter<T> {
+    value: u8,
+    #[kani(skip)]
+    history: Vec<u8>,
+    #[kani(default = 10)]
+    limit: u8,
+    #[kani(default = PhantomData)]
+    _unit: PhantomData<T>,
+}
+
+#[derive(kani::Arbitrary)]
+struct Stats {
+    #[kani(default)]
+    count: usize,
+    #[kani(skip)]
+    cache: Option<u32>,
+    #[kani(default = 100)]
+    capacity: u32,
+    current: u32,
+}
+
+#[derive(kani::Arbitrary)]
+enum Event {
+    Tick(u8, #[kani(default = true)] bool),
+    Reset {
+        #[kani(skip)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(kani::Arbitrary, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    High = 10,
+}
+
+#[kani::proof]
+fn check_skip_fields() {
+    let counter: Counter<char> = kani::any();
+    assert!(counter.history.is_empty());
+    assert_eq!(counter.limit, 10);
+    kani::cover!(counter.value == 42);
+}
+
+#[kani::proof]
+fn check_arbitrary_fields() {
+    let stats: Stats = kani::any();
+    assert_eq!(stats.count, 0);
+    assert!(stats.cache.is_none());
+    assert_eq!(stats.capacity, 100);
+    kani::cover!(stats.current > stats.capacity);
+}
+
+#[kani::proof]
+fn check_skip_variant_fields() {
+    match kani::any() {
+        Event::Tick(value, flag) => {
+            assert!(flag);
+            kani::cover!(value == 42);
+        }
+        Event::Reset { reason } => assert!(reason.is_none()),
+    }
+}
+
+#[kani::proof]
+fn check_explicit_discriminants() {
+    let level: Level = kani::any();
+    let discriminant = level as u8;
+    assert!(discriminant == 1 || discriminant == 10);
+    kani::cover!(level == Level::High);
+}
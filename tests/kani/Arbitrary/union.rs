@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the derived `Arbitrary` for a union creates a symbolic value of its active field.
+
+extern crate kani;
+
+#[derive(kani::Arbitrary, Clone, Copy)]
+#[kani(active = bits)]
+#[repr(C)]
+union Number {
+    bits: u32,
+    float: f32,
+    bytes: [u8; 4],
+}
+
+#[kani::proof]
+fn check_active_field() {
+    let number: Number = kani::any();
+    let bits = unsafe { number.bits };
+    let bytes = unsafe { number.bytes };
+    assert_eq!(u32::from_ne_bytes(bytes), bits);
+    kani::cover!(bits == 0xdead_beef);
+}
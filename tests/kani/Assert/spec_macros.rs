@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the specification helper macros `implies!`, `iff!` and `result_of!`.
+
+fn checked_div(dividend: u32, divisor: u32) -> Option<u32> {
+    if divisor == 0 { None } else { Some(dividend / divisor) }
+}
+
+#[kani::proof]
+fn check_implies() {
+    let x: u8 = kani::any();
+    assert!(kani::implies!(x > 10 => x > 5));
+    assert!(kani::implies!(x > 10, x < 20 => x != 0));
+    // The conclusion is not evaluated if the premise is false.
+    let v: Vec<u8> = Vec::new();
+    assert!(kani::implies!(!v.is_empty() => v[0] == 0));
+}
+
+#[kani::proof]
+fn check_iff() {
+    let x: u8 = kani::any();
+    assert!(kani::iff!(x == 0, x < 1));
+    assert!(!kani::iff!(x == 0, x != 0));
+}
+
+#[kani::proof]
+fn check_result_of() {
+    let dividend: u32 = kani::any();
+    let divisor: u32 = kani::any();
+    let result = checked_div(dividend, divisor);
+    assert!(kani::result_of!(result, Some(quotient) => quotient <= dividend));
+    assert!(kani::result_of!(result, None => divisor == 0));
+    let ok: Result<u32, ()> = Ok(5);
+    assert!(kani::result_of!(ok, Err(_) => false));
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::cfg!` tells that the code is being verified, and that it forwards the
+//! remaining predicates to `cfg!`.
+extern crate kani;
+
+const LIMIT: usize = if kani::cfg!(verification) { 4 } else { 1024 };
+
+#[kani::proof]
+fn check_verification_mode() {
+    assert!(kani::cfg!(verification));
+    assert!(!kani::cfg!(playback));
+    assert_eq!(LIMIT, 4);
+}
+
+#[kani::proof]
+fn check_forwarded_predicates() {
+    assert!(kani::cfg!(kani));
+    assert!(kani::cfg!(all(kani, not(feature = "unknown"))));
+    assert_eq!(kani::cfg!(debug_assertions), cfg!(debug_assertions));
+}
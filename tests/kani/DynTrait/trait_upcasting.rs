@@ -0,0 +1,99 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check trait upcasting, including traits with multiple supertraits whose
+//! vtables store pointers to the vtable of each supertrait.
+
+#![feature(trait_upcasting)]
+
+trait Name {
+    fn name(&self) -> &'static str;
+}
+
+trait Id {
+    fn id(&self) -> u32;
+}
+
+trait Plugin: Name + Id {
+    fn run(&self, input: u32) -> u32;
+}
+
+struct Doubler {
+    id: u32,
+}
+
+impl Name for Doubler {
+    fn name(&self) -> &'static str {
+        "doubler"
+    }
+}
+
+impl Id for Doubler {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Plugin for Doubler {
+    fn run(&self, input: u32) -> u32 {
+        input.wrapping_mul(2)
+    }
+}
+
+struct Identity;
+
+impl Name for Identity {
+    fn name(&self) -> &'static str {
+        "identity"
+    }
+}
+
+impl Id for Identity {
+    fn id(&self) -> u32 {
+        0
+    }
+}
+
+impl Plugin for Identity {
+    fn run(&self, input: u32) -> u32 {
+        input
+    }
+}
+
+#[kani::proof]
+fn check_upcast_to_first_supertrait() {
+    let plugin: &dyn Plugin = &Doubler { id: 1 };
+    let name: &dyn Name = plugin;
+    assert_eq!(name.name(), "doubler");
+}
+
+#[kani::proof]
+fn check_upcast_to_second_supertrait() {
+    let id: u32 = kani::any();
+    let plugin: &dyn Plugin = &Doubler { id };
+    let ident: &dyn Id = plugin;
+    assert_eq!(ident.id(), id);
+}
+
+#[kani::proof]
+fn check_upcast_boxed_plugins() {
+    let input: u32 = kani::any();
+    let plugins: [Box<dyn Plugin>; 2] = [Box::new(Doubler { id: 7 }), Box::new(Identity)];
+    let idx: usize = kani::any();
+    kani::assume(idx < plugins.len());
+    let plugin = &plugins[idx];
+    let output = plugin.run(input);
+    let ident: &dyn Id = plugin.as_ref();
+    if ident.id() == 7 {
+        assert_eq!(output, input.wrapping_mul(2));
+    } else {
+        assert_eq!(output, input);
+    }
+}
+
+#[kani::proof]
+fn check_upcast_to_send_supertrait() {
+    let plugin: Box<dyn Plugin + Send> = Box::new(Identity);
+    let ident: Box<dyn Id + Send> = plugin;
+    assert_eq!(ident.id(), 0);
+}
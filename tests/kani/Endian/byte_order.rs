@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the byte-order helpers of `kani::endian` for both byte orders.
+
+extern crate kani;
+
+use kani::endian::{ByteOrder, Endian};
+
+#[kani::proof]
+fn check_explicit_bytes() {
+    let value = 0x0102_0304u32;
+    assert_eq!(value.to_bytes(Endian::Little), [4, 3, 2, 1]);
+    assert_eq!(value.to_bytes(Endian::Big), [1, 2, 3, 4]);
+    assert_eq!(i16::from_bytes([0xff, 0xfe], Endian::Big), -2);
+    assert_eq!(i16::from_bytes([0xfe, 0xff], Endian::Little), -2);
+}
+
+#[kani::proof]
+fn check_round_trip() {
+    let endian: Endian = kani::any();
+    let int: u64 = kani::any();
+    assert_eq!(u64::from_bytes(int.to_bytes(endian), endian), int);
+    let float: f32 = kani::any();
+    kani::assume(!float.is_nan());
+    assert_eq!(f32::from_bytes(float.to_bytes(endian), endian), float);
+}
+
+#[kani::proof]
+fn check_byte_orders_are_reversed() {
+    let value: u32 = kani::any();
+    let mut big = value.to_bytes(Endian::Big);
+    big.reverse();
+    assert_eq!(big, value.to_bytes(Endian::Little));
+}
+
+#[kani::proof]
+fn check_native_target() {
+    assert_eq!(Endian::target(), Endian::NATIVE);
+    assert_eq!(Endian::NATIVE, Endian::Little);
+}
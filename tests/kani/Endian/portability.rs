@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z endian-portability
+//
+//! Check that `-Z endian-portability` verifies the native byte-order
+//! conversions for both byte orders, while the conversions with an explicit
+//! byte order keep their semantics.
+
+extern crate kani;
+
+use kani::endian::{ByteOrder, Endian};
+
+#[kani::proof]
+fn check_explicit_conversions() {
+    let value: u32 = kani::any();
+    assert_eq!(value.to_le_bytes()[0], value as u8);
+    assert_eq!(value.to_be_bytes()[3], value as u8);
+    assert_eq!(u32::from_le_bytes(value.to_le_bytes()), value);
+    assert_eq!(u32::from_be_bytes(value.to_be_bytes()), value);
+    assert_eq!(i64::from_be(i64::to_be(-5)), -5);
+    let float: f64 = kani::any();
+    kani::assume(!float.is_nan());
+    assert_eq!(f64::from_le_bytes(float.to_le_bytes()), float);
+}
+
+#[kani::proof]
+fn check_native_conversions() {
+    let value: u16 = kani::any();
+    let bytes = value.to_ne_bytes();
+    assert_eq!(bytes, value.to_bytes(Endian::target()));
+    assert_eq!(u16::from_ne_bytes(bytes), value);
+    let le = if Endian::target() == Endian::Big { value.swap_bytes() } else { value };
+    assert_eq!(value.to_le(), le);
+}
+
+#[kani::proof]
+fn check_both_targets_are_covered() {
+    let target = Endian::target();
+    kani::cover!(target == Endian::Little);
+    kani::cover!(target == Endian::Big);
+    // The byte order doesn't change during the execution.
+    assert_eq!(Endian::target(), target);
+}
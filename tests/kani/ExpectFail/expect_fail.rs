@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the failures of the checks wrapped by `kani::expect_fail!` don't make verification
+//! fail.
+#![feature(repr_simd, platform_intrinsics)]
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i32x2(i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_shr<T>(x: T, y: T) -> T;
+}
+
+#[kani::proof]
+fn check_overflow() {
+    let x: u8 = kani::any();
+    kani::assume(x > 200);
+    let _ = kani::expect_fail!("arithmetic_overflow", x * 2);
+    assert!(x.checked_add(50).is_some());
+}
+
+#[kani::proof]
+fn check_negative_shift() {
+    let value = kani::any();
+    let values = i32x2(value, value);
+    let shift: i32 = kani::any();
+    kani::assume(shift < 0);
+    let _result = kani::expect_fail!(unsafe { simd_shr(values, i32x2(shift, shift)) });
+}
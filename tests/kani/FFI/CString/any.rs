@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::ffi` generates valid C strings.
+
+use std::ffi::{CStr, CString};
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_any_c_string() {
+    let s: CString = kani::ffi::any_c_string::<4>();
+    let c_str: &CStr = s.as_c_str();
+    let bytes = c_str.to_bytes_with_nul();
+    assert!(bytes.len() <= 5);
+    assert_eq!(bytes[bytes.len() - 1], 0);
+    assert!(c_str.to_bytes().iter().all(|b| *b != 0));
+    assert_eq!(CStr::from_bytes_with_nul(bytes), Ok(c_str));
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_any_c_string_bytes() {
+    let bytes = kani::ffi::any_c_string_bytes::<4>();
+    assert!(!bytes.is_empty() && bytes.len() <= 5);
+    let c_str = CStr::from_bytes_with_nul(&bytes).unwrap();
+    assert_eq!(c_str.to_bytes().len(), bytes.len() - 1);
+}
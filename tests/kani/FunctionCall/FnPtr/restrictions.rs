@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Test function pointer restrictions for calls through plain function pointers.
+
+// kani-flags: --enable-unstable --restrict-fn-ptrs
+
+fn double(x: u32) -> u32 {
+    x.wrapping_mul(2)
+}
+
+fn square(x: u32) -> u32 {
+    x.wrapping_mul(x)
+}
+
+fn negate(x: i8) -> i8 {
+    x.wrapping_neg()
+}
+
+const CALLBACKS: [fn(u32) -> u32; 2] = [double, square];
+
+fn apply(callback: fn(u32) -> u32, input: u32) -> u32 {
+    callback(input)
+}
+
+#[kani::proof]
+fn check_callback_table() {
+    let idx: usize = kani::any();
+    kani::assume(idx < CALLBACKS.len());
+    let input: u32 = kani::any();
+    let output = apply(CALLBACKS[idx], input);
+    assert!(output == double(input) || output == square(input));
+}
+
+#[kani::proof]
+fn check_reified_and_closure_callbacks() {
+    let callback: fn(u32) -> u32 = if kani::any() { double } else { |x| x.wrapping_add(1) };
+    let input: u32 = kani::any();
+    let output = apply(callback, input);
+    assert!(output == double(input) || output == input.wrapping_add(1));
+    let other: fn(i8) -> i8 = negate;
+    assert_eq!(other(1), -1);
+}
+
+#[kani::proof]
+fn check_unsafe_fn_ptr() {
+    let callback: unsafe fn(u32) -> u32 = double;
+    assert_eq!(unsafe { callback(2) }, 4);
+}
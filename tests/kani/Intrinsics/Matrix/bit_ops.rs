@@ -0,0 +1,74 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check the bit manipulation intrinsics (`rotate_*`, `ctpop`, `bitreverse`
+// and `bswap`) against reference implementations that inspect one bit or one
+// byte at a time.
+#![feature(core_intrinsics)]
+use std::intrinsics::{bitreverse, bswap, ctpop, rotate_left, rotate_right};
+
+/// Return bit `i` of `x`.
+fn bit(x: u64, i: u32) -> bool {
+    (x >> i) & 1 == 1
+}
+
+/// Return byte `i` of `x`.
+fn byte(x: u64, i: u32) -> u64 {
+    (x >> (i * 8)) & 0xFF
+}
+
+macro_rules! check_bit_ops {
+    ($ty:ty, $uty:ty) => {{
+        const BITS: u32 = <$ty>::BITS;
+        let x: $ty = kani::any();
+        let n: u32 = kani::any();
+        kani::assume(n < BITS);
+        let i: u32 = kani::any();
+        kani::assume(i < BITS);
+        let raw = x as $uty as u64;
+
+        // Bit `i` moves to bit `i + n` when rotating left, and the other way
+        // around when rotating right.
+        let rol = rotate_left(x, n as $ty) as $uty as u64;
+        assert_eq!(bit(rol, (i + n) % BITS), bit(raw, i));
+        let ror = rotate_right(x, n as $ty) as $uty as u64;
+        assert_eq!(bit(ror, i), bit(raw, (i + n) % BITS));
+
+        // Bit `i` moves to bit `BITS - 1 - i` when reversing bits.
+        let rev = bitreverse(x) as $uty as u64;
+        assert_eq!(bit(rev, BITS - 1 - i), bit(raw, i));
+
+        // Byte `j` moves to byte `BYTES - 1 - j` when swapping bytes.
+        let bytes = BITS / 8;
+        let j = i % bytes;
+        let swapped = bswap(x) as $uty as u64;
+        assert_eq!(byte(swapped, bytes - 1 - j), byte(raw, j));
+
+        // `ctpop` counts the bits that are set.
+        let mut count = 0;
+        for k in 0..BITS {
+            if bit(raw, k) {
+                count += 1;
+            }
+        }
+        assert_eq!(ctpop(x) as u32, count);
+    }};
+}
+
+#[kani::proof]
+#[kani::unwind(65)]
+fn check_bit_ops_unsigned() {
+    check_bit_ops!(u8, u8);
+    check_bit_ops!(u16, u16);
+    check_bit_ops!(u32, u32);
+    check_bit_ops!(u64, u64);
+}
+
+#[kani::proof]
+#[kani::unwind(65)]
+fn check_bit_ops_signed() {
+    check_bit_ops!(i8, u8);
+    check_bit_ops!(i16, u16);
+    check_bit_ops!(i32, u32);
+    check_bit_ops!(i64, u64);
+}
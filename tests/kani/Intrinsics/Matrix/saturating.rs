@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check `saturating_<op>` against a reference implementation that clamps the
+// exact result, computed in `i128`, to the bounds of the type.
+#![feature(core_intrinsics)]
+use std::intrinsics::{saturating_add, saturating_sub};
+
+macro_rules! check_saturating {
+    ($ty:ty, $intrinsic:ident, $op:tt) => {{
+        let a: $ty = kani::any();
+        let b: $ty = kani::any();
+        let exact = (a as i128) $op (b as i128);
+        let expected = exact.clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty;
+        assert_eq!($intrinsic(a, b), expected);
+    }};
+}
+
+macro_rules! check_all_types {
+    ($intrinsic:ident, $op:tt) => {
+        check_saturating!(u8, $intrinsic, $op);
+        check_saturating!(u16, $intrinsic, $op);
+        check_saturating!(u32, $intrinsic, $op);
+        check_saturating!(u64, $intrinsic, $op);
+        check_saturating!(usize, $intrinsic, $op);
+        check_saturating!(i8, $intrinsic, $op);
+        check_saturating!(i16, $intrinsic, $op);
+        check_saturating!(i32, $intrinsic, $op);
+        check_saturating!(i64, $intrinsic, $op);
+        check_saturating!(isize, $intrinsic, $op);
+    };
+}
+
+#[kani::proof]
+fn check_saturating_add() {
+    check_all_types!(saturating_add, +);
+}
+
+#[kani::proof]
+fn check_saturating_sub() {
+    check_all_types!(saturating_sub, -);
+}
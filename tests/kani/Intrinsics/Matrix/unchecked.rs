@@ -0,0 +1,93 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check `unchecked_<op>` against a reference implementation that computes the
+// exact result in `i128`, for every input where the operation is defined.
+#![feature(core_intrinsics)]
+use std::convert::TryFrom;
+use std::intrinsics::{
+    unchecked_add, unchecked_div, unchecked_mul, unchecked_rem, unchecked_shl, unchecked_shr,
+    unchecked_sub,
+};
+
+fn fits<T: TryFrom<i128>>(exact: i128) -> bool {
+    T::try_from(exact).is_ok()
+}
+
+macro_rules! check_unchecked_arith {
+    ($ty:ty, $intrinsic:ident, $op:tt) => {{
+        let a: $ty = kani::any();
+        let b: $ty = kani::any();
+        let exact = (a as i128) $op (b as i128);
+        kani::assume(fits::<$ty>(exact));
+        assert_eq!(unsafe { $intrinsic(a, b) }, exact as $ty);
+    }};
+}
+
+macro_rules! check_unchecked_div {
+    ($ty:ty, $intrinsic:ident, $op:tt) => {{
+        let a: $ty = kani::any();
+        let b: $ty = kani::any();
+        kani::assume(b != 0);
+        let exact = (a as i128) $op (b as i128);
+        // `MIN / -1` overflows.
+        kani::assume(fits::<$ty>(exact));
+        assert_eq!(unsafe { $intrinsic(a, b) }, exact as $ty);
+    }};
+}
+
+macro_rules! check_unchecked_shift {
+    ($ty:ty, $intrinsic:ident, $op:tt) => {{
+        let a: $ty = kani::any();
+        let b: $ty = kani::any();
+        kani::assume(b >= 0 as $ty && (b as u32) < <$ty>::BITS);
+        let exact = (a as i128) $op (b as u32);
+        assert_eq!(unsafe { $intrinsic(a, b) }, exact as $ty);
+    }};
+}
+
+macro_rules! check_all_types {
+    ($check:ident, $intrinsic:ident, $op:tt) => {
+        $check!(u8, $intrinsic, $op);
+        $check!(u16, $intrinsic, $op);
+        $check!(u32, $intrinsic, $op);
+        $check!(i8, $intrinsic, $op);
+        $check!(i16, $intrinsic, $op);
+        $check!(i32, $intrinsic, $op);
+    };
+}
+
+#[kani::proof]
+fn check_unchecked_add() {
+    check_all_types!(check_unchecked_arith, unchecked_add, +);
+}
+
+#[kani::proof]
+fn check_unchecked_sub() {
+    check_all_types!(check_unchecked_arith, unchecked_sub, -);
+}
+
+#[kani::proof]
+fn check_unchecked_mul() {
+    check_all_types!(check_unchecked_arith, unchecked_mul, *);
+}
+
+#[kani::proof]
+fn check_unchecked_div() {
+    check_all_types!(check_unchecked_div, unchecked_div, /);
+}
+
+#[kani::proof]
+fn check_unchecked_rem() {
+    check_all_types!(check_unchecked_div, unchecked_rem, %);
+}
+
+#[kani::proof]
+fn check_unchecked_shl() {
+    check_all_types!(check_unchecked_shift, unchecked_shl, <<);
+}
+
+#[kani::proof]
+fn check_unchecked_shr() {
+    check_all_types!(check_unchecked_shift, unchecked_shr, >>);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check `<op>_with_overflow` against a reference implementation that computes
+// the exact result in `i128` and then truncates it.
+#![feature(core_intrinsics)]
+use std::intrinsics::{add_with_overflow, mul_with_overflow, sub_with_overflow};
+
+macro_rules! check_with_overflow {
+    ($ty:ty, $intrinsic:ident, $op:tt) => {{
+        let a: $ty = kani::any();
+        let b: $ty = kani::any();
+        let (res, overflow) = $intrinsic(a, b);
+        let exact = (a as i128) $op (b as i128);
+        assert_eq!(overflow, exact < <$ty>::MIN as i128 || exact > <$ty>::MAX as i128);
+        assert_eq!(res, exact as $ty);
+    }};
+}
+
+macro_rules! check_all_types {
+    ($intrinsic:ident, $op:tt) => {
+        check_with_overflow!(u8, $intrinsic, $op);
+        check_with_overflow!(u16, $intrinsic, $op);
+        check_with_overflow!(u32, $intrinsic, $op);
+        check_with_overflow!(i8, $intrinsic, $op);
+        check_with_overflow!(i16, $intrinsic, $op);
+        check_with_overflow!(i32, $intrinsic, $op);
+    };
+}
+
+#[kani::proof]
+fn check_add_with_overflow() {
+    check_all_types!(add_with_overflow, +);
+    check_with_overflow!(u64, add_with_overflow, +);
+    check_with_overflow!(i64, add_with_overflow, +);
+}
+
+#[kani::proof]
+fn check_sub_with_overflow() {
+    check_all_types!(sub_with_overflow, -);
+    check_with_overflow!(u64, sub_with_overflow, -);
+    check_with_overflow!(i64, sub_with_overflow, -);
+}
+
+#[kani::proof]
+fn check_mul_with_overflow() {
+    // The exact product of two 64-bit values doesn't always fit in `i128`.
+    check_all_types!(mul_with_overflow, *);
+}
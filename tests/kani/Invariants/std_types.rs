@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check the `Invariant` implementations of std types and that they can be used by types that
+//! derive `Invariant`.
+
+extern crate kani;
+use kani::Invariant;
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Positive {
+    #[safety_constraint(*val > 0)]
+    val: i32,
+}
+
+#[derive(kani::Invariant)]
+struct Composite {
+    single: Positive,
+    array: [Positive; 2],
+    pair: (Positive, u8),
+    maybe: Option<Positive>,
+    result: Result<Positive, char>,
+    list: Vec<Positive>,
+}
+
+#[kani::proof]
+fn check_primitives_are_safe() {
+    assert!(kani::any::<u64>().is_safe());
+    assert!(kani::any::<char>().is_safe());
+    assert!(kani::any::<f32>().is_safe());
+    assert!(kani::any::<(bool, i8)>().is_safe());
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_composite() {
+    let composite = Composite {
+        single: kani::any(),
+        array: kani::any(),
+        pair: kani::any(),
+        maybe: kani::any(),
+        result: kani::any(),
+        list: vec![kani::any()],
+    };
+    assert!(composite.is_safe());
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_unsafe_element() {
+    let val: i32 = kani::any();
+    let list = vec![Positive { val: 1 }, Positive { val }];
+    assert_eq!(list.is_safe(), val > 0);
+    assert_eq!(Some(Positive { val }).is_safe(), val > 0);
+    assert!(None::<Positive>.is_safe());
+    assert_eq!(Err::<u8, _>(Positive { val }).is_safe(), val > 0);
+}
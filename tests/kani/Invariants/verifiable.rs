@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the values created by `kani::any()` for a `#[kani::verifiable]` type always
+//! satisfy its invariant.
+
+extern crate kani;
+
+#[kani::verifiable]
+struct Percentage {
+    #[safety_constraint(*value <= 100)]
+    value: u8,
+}
+
+#[kani::verifiable]
+struct Budget {
+    #[safety_constraint(*spent <= *limit)]
+    spent: u32,
+    limit: u32,
+    share: Percentage,
+}
+
+#[kani::verifiable]
+enum Speed {
+    Stopped,
+    Kmh(#[safety_constraint(*0 <= 300)] u16),
+}
+
+#[kani::proof]
+fn check_verifiable_struct() {
+    let budget: Budget = kani::any();
+    assert!(kani::Invariant::is_safe(&budget));
+    assert!(budget.spent <= budget.limit);
+    assert!(budget.share.value <= 100);
+    kani::cover!(budget.spent == budget.limit && budget.share.value == 100);
+}
+
+#[kani::proof]
+fn check_verifiable_enum() {
+    let speed: Speed = kani::any();
+    assert!(kani::Invariant::is_safe(&speed));
+    if let Speed::Kmh(kmh) = speed {
+        assert!(kmh <= 300);
+    }
+}
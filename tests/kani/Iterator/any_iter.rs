@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any_iter` yields a symbolic number of symbolic items, up to its bound.
+
+/// Return the largest element, like `Iterator::max`, written against a generic iterator.
+fn largest<I: Iterator<Item = u8>>(iter: I) -> Option<u8> {
+    let mut result = None;
+    for item in iter {
+        if result.map_or(true, |max| item > max) {
+            result = Some(item);
+        }
+    }
+    result
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_length() {
+    let iter = kani::any_iter::<u8, 4>();
+    let len = iter.len();
+    assert!(len <= 4);
+    assert_eq!(iter.count(), len);
+    kani::cover!(len == 0);
+    kani::cover!(len == 4);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_items() {
+    let mut iter = kani::any_iter::<u8, 3>();
+    let first = iter.next();
+    if let Some(item) = first {
+        kani::cover!(item == 42);
+    }
+    let max = largest(iter);
+    kani::cover!(max.is_some() && first.is_none());
+    if let (Some(first), Some(max)) = (first, max) {
+        kani::cover!(first > max);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_fused() {
+    let mut iter = kani::any_iter::<bool, 2>();
+    while iter.next().is_some() {}
+    assert!(iter.next().is_none());
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
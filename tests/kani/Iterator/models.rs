@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 5
+
+//! Check that the iterator models in `kani::iter` match their `std` counterparts.
+
+fn is_even(x: &u8) -> bool {
+    x % 2 == 0
+}
+
+#[kani::proof]
+fn check_map_filter() {
+    let input: [u8; 3] = kani::any();
+    assert_eq!(kani::iter::map(&input, |x| x / 2), input.iter().map(|x| x / 2).collect::<Vec<_>>());
+    assert_eq!(
+        kani::iter::filter(&input, is_even),
+        input.iter().filter(|x| is_even(x)).cloned().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        kani::iter::filter_map(&input, |x| x.checked_sub(10)),
+        input.iter().filter_map(|x| x.checked_sub(10)).collect::<Vec<_>>()
+    );
+}
+
+#[kani::proof]
+fn check_reductions() {
+    let input: [u8; 3] = kani::any();
+    let sum = kani::iter::fold(&input, 0u32, |acc, x| acc + *x as u32);
+    assert_eq!(sum, input.iter().map(|x| *x as u32).sum());
+    assert_eq!(kani::iter::position(&input, is_even), input.iter().position(is_even));
+    assert_eq!(kani::iter::any(&input, is_even), input.iter().any(is_even));
+    assert_eq!(kani::iter::all(&input, is_even), input.iter().all(is_even));
+    assert_eq!(kani::iter::count(&input, is_even), input.iter().filter(|x| is_even(x)).count());
+}
@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that an intrusive doubly linked list, which recovers each node from the
+//! address of its link with `offset_of!`, can be verified.
+
+#![feature(offset_of)]
+
+use std::mem::offset_of;
+use std::ptr::{addr_of_mut, null_mut};
+
+struct Link {
+    next: *mut Link,
+    prev: *mut Link,
+}
+
+struct Node {
+    value: u32,
+    tag: u8,
+    link: Link,
+}
+
+impl Node {
+    fn new(value: u32) -> Node {
+        Node { value, tag: 0, link: Link { next: null_mut(), prev: null_mut() } }
+    }
+}
+
+/// Recover the node that contains the given link.
+unsafe fn container_of(link: *mut Link) -> *mut Node {
+    (link as *mut u8).sub(offset_of!(Node, link)) as *mut Node
+}
+
+/// Insert `new` after `pos`.
+unsafe fn insert_after(pos: *mut Link, new: *mut Link) {
+    (*new).prev = pos;
+    (*new).next = (*pos).next;
+    if !(*pos).next.is_null() {
+        (*(*pos).next).prev = new;
+    }
+    (*pos).next = new;
+}
+
+#[kani::proof]
+fn check_container_of() {
+    let mut node = Node::new(kani::any());
+    let link = addr_of_mut!(node.link);
+    let recovered = unsafe { container_of(link) };
+    assert_eq!(recovered, addr_of_mut!(node));
+    unsafe { (*recovered).tag = 7 };
+    assert_eq!(node.tag, 7);
+}
+
+#[kani::proof]
+fn check_traversal() {
+    let mut first = Node::new(kani::any());
+    let mut second = Node::new(kani::any());
+    let mut third = Node::new(kani::any());
+    unsafe {
+        insert_after(addr_of_mut!(first.link), addr_of_mut!(third.link));
+        insert_after(addr_of_mut!(first.link), addr_of_mut!(second.link));
+        let mid = container_of(first.link.next);
+        assert_eq!((*mid).value, second.value);
+        let last = container_of((*mid).link.next);
+        assert_eq!((*last).value, third.value);
+        assert!((*last).link.next.is_null());
+        assert_eq!(container_of((*last).link.prev), mid);
+    }
+}
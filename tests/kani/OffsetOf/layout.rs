@@ -0,0 +1,94 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `offset_of!` returns the offsets of the layout that Kani uses for
+//! the type, so field projections through raw pointers read the right bytes.
+
+#![feature(offset_of)]
+
+use std::mem::{align_of_val, offset_of, size_of, size_of_val};
+
+#[repr(C)]
+struct Header {
+    kind: u8,
+    len: u32,
+    flags: u16,
+}
+
+#[repr(C, packed)]
+struct Packed {
+    kind: u8,
+    len: u32,
+}
+
+#[repr(C, align(16))]
+struct Aligned {
+    a: u8,
+    b: u64,
+}
+
+struct Nested {
+    id: u16,
+    pair: (u8, u64),
+}
+
+struct Generic<T> {
+    flag: bool,
+    value: T,
+}
+
+/// Read the field at the given offset through a pointer to the whole value.
+unsafe fn read_at<T, F: Copy>(value: &T, offset: usize) -> F {
+    ((value as *const T as *const u8).add(offset) as *const F).read_unaligned()
+}
+
+fn check_generic<T: Copy + PartialEq + kani::Arbitrary>() {
+    let value = Generic { flag: kani::any(), value: kani::any::<T>() };
+    let offset = offset_of!(Generic<T>, value);
+    assert!(offset + size_of::<T>() <= size_of::<Generic<T>>());
+    assert!(unsafe { read_at::<_, T>(&value, offset) } == value.value);
+    let flag = offset_of!(Generic<T>, flag);
+    assert_eq!(unsafe { read_at::<_, bool>(&value, flag) }, value.flag);
+}
+
+#[kani::proof]
+fn check_repr_c() {
+    assert_eq!(offset_of!(Header, kind), 0);
+    assert_eq!(offset_of!(Header, len), 4);
+    assert_eq!(offset_of!(Header, flags), 8);
+    let header = Header { kind: kani::any(), len: kani::any(), flags: kani::any() };
+    assert_eq!(unsafe { read_at::<_, u32>(&header, offset_of!(Header, len)) }, header.len);
+    assert_eq!(unsafe { read_at::<_, u16>(&header, offset_of!(Header, flags)) }, header.flags);
+}
+
+#[kani::proof]
+fn check_packed_and_aligned() {
+    assert_eq!(offset_of!(Packed, len), 1);
+    let packed = Packed { kind: 1, len: kani::any() };
+    let len = packed.len;
+    assert_eq!(unsafe { read_at::<_, u32>(&packed, offset_of!(Packed, len)) }, len);
+    assert_eq!(offset_of!(Aligned, b), 8);
+    let aligned = Aligned { a: 2, b: kani::any() };
+    assert_eq!(align_of_val(&aligned), 16);
+    assert_eq!(size_of_val(&aligned), 16);
+    assert_eq!(unsafe { read_at::<_, u64>(&aligned, offset_of!(Aligned, b)) }, aligned.b);
+}
+
+#[kani::proof]
+fn check_reordered_and_nested() {
+    let nested = Nested { id: kani::any(), pair: (kani::any(), kani::any()) };
+    let id = offset_of!(Nested, id);
+    let pair = offset_of!(Nested, pair);
+    assert_eq!(unsafe { read_at::<_, u16>(&nested, id) }, nested.id);
+    let first = pair + offset_of!((u8, u64), 0);
+    let second = pair + offset_of!((u8, u64), 1);
+    assert_eq!(unsafe { read_at::<_, u8>(&nested, first) }, nested.pair.0);
+    assert_eq!(unsafe { read_at::<_, u64>(&nested, second) }, nested.pair.1);
+}
+
+#[kani::proof]
+fn check_generic_offsets() {
+    check_generic::<u8>();
+    check_generic::<u64>();
+    check_generic::<[u16; 3]>();
+}
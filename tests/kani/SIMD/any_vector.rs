@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::simd::Vector` can be used to build symbolic inputs for SIMD code.
+#![feature(portable_simd)]
+
+extern crate kani;
+
+use kani::simd::{any_vector, Vector};
+use std::simd::i32x8;
+
+#[kani::proof]
+fn check_portable_add() {
+    let a = any_vector::<i32, 8>();
+    let b = any_vector::<i32, 8>();
+    let sum: i32x8 = unsafe { a.to_simd::<i32x8>() + b.to_simd::<i32x8>() };
+    let sum = unsafe { Vector::<i32, 8>::from_simd(sum) };
+    for i in 0..Vector::<i32, 8>::LANES {
+        assert_eq!(sum.extract(i), a.extract(i).wrapping_add(b.extract(i)));
+    }
+}
+
+#[kani::proof]
+fn check_lane_access() {
+    let mut v = Vector::<u8, 16>::splat(0);
+    let index: usize = kani::any_where(|i| *i < 16);
+    let value: u8 = kani::any();
+    v.insert(index, value);
+    assert_eq!(v.extract(index), value);
+    assert_eq!(v.to_array().iter().filter(|lane| **lane != 0).count(), (value != 0) as usize);
+    assert_eq!(Vector::from_array(v.to_array()), v);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[kani::proof]
+fn check_arch_max() {
+    use std::arch::x86_64::{__m128i, _mm_max_epi16};
+    let a = any_vector::<i16, 8>();
+    let b = any_vector::<i16, 8>();
+    let max: __m128i = unsafe { _mm_max_epi16(a.to_simd(), b.to_simd()) };
+    let max = unsafe { Vector::<i16, 8>::from_simd(max) };
+    for i in 0..8 {
+        assert_eq!(max.extract(i), a.extract(i).max(b.extract(i)));
+    }
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_size_mismatch() {
+    let v = any_vector::<u32, 4>();
+    let _: [u32; 8] = unsafe { v.to_simd() };
+}
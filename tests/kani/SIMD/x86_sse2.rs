@@ -0,0 +1,119 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check a subset of the SSE2 integer intrinsics from `core::arch::x86_64` against their scalar
+//! semantics.
+#![cfg(target_arch = "x86_64")]
+
+use std::arch::x86_64::*;
+
+fn to_bytes(v: __m128i) -> [u8; 16] {
+    unsafe { std::mem::transmute(v) }
+}
+
+fn to_i16s(v: __m128i) -> [i16; 8] {
+    unsafe { std::mem::transmute(v) }
+}
+
+fn from_bytes(bytes: [u8; 16]) -> __m128i {
+    unsafe { std::mem::transmute(bytes) }
+}
+
+fn from_i16s(values: [i16; 8]) -> __m128i {
+    unsafe { std::mem::transmute(values) }
+}
+
+/// The fast path of a `memchr`-like search.
+#[kani::proof]
+fn check_find_byte() {
+    let haystack: [u8; 16] = kani::any();
+    let needle: u8 = kani::any();
+    let mask = unsafe {
+        let eq = _mm_cmpeq_epi8(from_bytes(haystack), _mm_set1_epi8(needle as i8));
+        _mm_movemask_epi8(eq)
+    };
+    let expected = haystack.iter().position(|b| *b == needle);
+    let found = if mask == 0 { None } else { Some(mask.trailing_zeros() as usize) };
+    assert_eq!(found, expected);
+}
+
+#[kani::proof]
+fn check_saturating_u8() {
+    let a: [u8; 16] = kani::any();
+    let b: [u8; 16] = kani::any();
+    let (adds, subs) = unsafe {
+        (
+            to_bytes(_mm_adds_epu8(from_bytes(a), from_bytes(b))),
+            to_bytes(_mm_subs_epu8(from_bytes(a), from_bytes(b))),
+        )
+    };
+    for i in 0..16 {
+        assert_eq!(adds[i], a[i].saturating_add(b[i]));
+        assert_eq!(subs[i], a[i].saturating_sub(b[i]));
+    }
+}
+
+#[kani::proof]
+fn check_saturating_i16() {
+    let a: [i16; 8] = kani::any();
+    let b: [i16; 8] = kani::any();
+    let (adds, subs) = unsafe {
+        (
+            to_i16s(_mm_adds_epi16(from_i16s(a), from_i16s(b))),
+            to_i16s(_mm_subs_epi16(from_i16s(a), from_i16s(b))),
+        )
+    };
+    for i in 0..8 {
+        assert_eq!(adds[i], a[i].saturating_add(b[i]));
+        assert_eq!(subs[i], a[i].saturating_sub(b[i]));
+    }
+}
+
+#[kani::proof]
+fn check_min_max_avg() {
+    let a: [u8; 16] = kani::any();
+    let b: [u8; 16] = kani::any();
+    let (min, max, avg) = unsafe {
+        (
+            to_bytes(_mm_min_epu8(from_bytes(a), from_bytes(b))),
+            to_bytes(_mm_max_epu8(from_bytes(a), from_bytes(b))),
+            to_bytes(_mm_avg_epu8(from_bytes(a), from_bytes(b))),
+        )
+    };
+    for i in 0..16 {
+        assert_eq!(min[i], a[i].min(b[i]));
+        assert_eq!(max[i], a[i].max(b[i]));
+        assert_eq!(avg[i] as u16, (a[i] as u16 + b[i] as u16 + 1) / 2);
+    }
+}
+
+#[kani::proof]
+fn check_mul() {
+    let a: [i16; 8] = kani::any();
+    let b: [i16; 8] = kani::any();
+    let (lo, hi) = unsafe {
+        (
+            to_i16s(_mm_mullo_epi16(from_i16s(a), from_i16s(b))),
+            to_i16s(_mm_mulhi_epi16(from_i16s(a), from_i16s(b))),
+        )
+    };
+    for i in 0..8 {
+        let product = a[i] as i32 * b[i] as i32;
+        assert_eq!(lo[i], product as i16);
+        assert_eq!(hi[i], (product >> 16) as i16);
+    }
+}
+
+#[kani::proof]
+fn check_shuffle_and_shift() {
+    let a: [i16; 8] = kani::any();
+    let (shuffled, shifted) = unsafe {
+        let v = from_i16s(a);
+        (to_i16s(_mm_shuffle_epi32(v, 0b00_01_10_11)), to_i16s(_mm_srai_epi16(v, 3)))
+    };
+    // Reversing the 32-bit lanes keeps the order of the 16-bit halves within each lane.
+    assert_eq!(shuffled, [a[6], a[7], a[4], a[5], a[2], a[3], a[0], a[1]]);
+    for i in 0..8 {
+        assert_eq!(shifted[i], a[i] >> 3);
+    }
+}
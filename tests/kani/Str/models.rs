@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 5
+
+//! Check that the models in `kani::strings` match their `std` counterparts.
+
+/// Generate an ASCII string of length up to 3 with characters that are relevant for parsing.
+fn any_ascii(buf: &mut [u8; 3]) -> &str {
+    let len: usize = kani::any_where(|len| *len <= 3);
+    for byte in buf.iter_mut() {
+        *byte = kani::any_where(|b: &u8| b.is_ascii_digit() || *b == b'-' || *b == b'+' || *b == b'x');
+    }
+    std::str::from_utf8(&buf[..len]).unwrap()
+}
+
+#[kani::proof]
+fn check_parse_unsigned() {
+    let mut buf = [0; 3];
+    let s = any_ascii(&mut buf);
+    assert_eq!(kani::strings::parse::<u8>(s), s.parse::<u8>());
+}
+
+#[kani::proof]
+fn check_parse_signed() {
+    let mut buf = [0; 3];
+    let s = any_ascii(&mut buf);
+    assert_eq!(kani::strings::parse::<i8>(s), s.parse::<i8>());
+}
+
+#[kani::proof]
+fn check_parse_overflow() {
+    assert_eq!(kani::strings::parse::<u8>("256"), "256".parse::<u8>());
+    assert_eq!(kani::strings::parse::<i8>("-129"), "-129".parse::<i8>());
+    assert_eq!(kani::strings::parse::<i8>("-128"), Ok(i8::MIN));
+}
+
+#[kani::proof]
+fn check_split() {
+    let mut buf = [0; 3];
+    let s = any_ascii(&mut buf);
+    assert_eq!(kani::strings::find(s, "-1"), s.find("-1"));
+    assert_eq!(kani::strings::find_char(s, '+'), s.find('+'));
+    assert_eq!(kani::strings::split_once(s, '-'), s.split_once('-'));
+    assert_eq!(kani::strings::split(s, '-'), s.split('-').collect::<Vec<_>>());
+}
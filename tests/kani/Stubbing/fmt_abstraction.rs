@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z fmt-abstraction
+//
+//! This tests that `-Z fmt-abstraction` replaces the formatting machinery with
+//! a model that doesn't write anything, and that user stubs take precedence.
+
+use std::fmt::{Arguments, Result, Write};
+
+struct Loud;
+
+impl std::fmt::Display for Loud {
+    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> Result {
+        panic!("formatting traits should not be called");
+    }
+}
+
+#[kani::proof]
+fn check_format_is_empty() {
+    let x: u8 = kani::any();
+    let s = format!("value: {x} {}", Loud);
+    assert!(s.is_empty());
+}
+
+fn write_marker(output: &mut dyn Write, _args: Arguments<'_>) -> Result {
+    output.write_str("marker")
+}
+
+#[kani::proof]
+#[kani::stub(std::fmt::write, write_marker)]
+fn check_user_stub() {
+    let s = format!("{}", 10);
+    assert_eq!(s, "marker");
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//! Check that harnesses whose stubs don't conflict can be compiled together, and that a harness
+//! is not affected by the stubs of the other harnesses compiled with it.
+
+pub fn magic_number() -> u32 {
+    0
+}
+
+pub fn magic_number_stub() -> u32 {
+    1
+}
+
+pub fn lucky_number() -> u32 {
+    7
+}
+
+pub fn lucky_number_stub() -> u32 {
+    8
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    #[kani::proof]
+    #[kani::stub(magic_number, magic_number_stub)]
+    fn check_magic_stub() {
+        assert_eq!(magic_number(), 1);
+    }
+
+    /// This harness reaches `magic_number`, which is only stubbed by `check_magic_stub`.
+    #[kani::proof]
+    #[kani::stub(lucky_number, lucky_number_stub)]
+    fn check_lucky_stub() {
+        assert_eq!(lucky_number(), 8);
+        assert_eq!(magic_number(), 0);
+    }
+
+    #[kani::proof]
+    #[kani::stub(magic_number, magic_number_stub)]
+    #[kani::stub(lucky_number, lucky_number_stub)]
+    fn check_both_stubs() {
+        assert_eq!(magic_number() + lucky_number(), 9);
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness check_elapsed -Z stubbing
+//
+//! This tests stubbing an associated function of a `std` type, `std::time::SystemTime::now`,
+//! with a model that returns a fixed point in time.
+
+use std::time::{Duration, SystemTime};
+
+fn mock_now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_000)
+}
+
+fn seconds_since_epoch() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+}
+
+#[kani::proof]
+#[kani::stub(std::time::SystemTime::now, mock_now)]
+fn check_elapsed() {
+    assert_eq!(seconds_since_epoch(), 1_000);
+}
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check the model of `std::sync::mpsc` in `kani::sync::mpsc`.
+
+extern crate kani;
+
+use kani::sync::mpsc::{self, RecvTimeoutError, TryRecvError, TrySendError};
+use std::time::Duration;
+
+fn producer(tx: mpsc::Sender<u8>, count: u8) {
+    for i in 0..count {
+        tx.send(i).unwrap();
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_producer_consumer() {
+    let count: u8 = kani::any_where(|count| *count <= 3);
+    let (tx, rx) = mpsc::channel();
+    producer(tx.clone(), count);
+    producer(tx, 1);
+    let received: Vec<u8> = rx.iter().collect();
+    assert_eq!(received.len(), count as usize + 1);
+    assert_eq!(received.last(), Some(&0));
+}
+
+#[kani::proof]
+fn check_disconnected() {
+    let (tx, rx) = mpsc::channel::<u8>();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(rx.recv_timeout(Duration::from_secs(1)), Err(RecvTimeoutError::Timeout));
+    drop(rx);
+    assert!(tx.send(1).is_err());
+}
+
+#[kani::proof]
+fn check_sync_channel_bound() {
+    let (tx, rx) = mpsc::sync_channel(1);
+    assert_eq!(tx.try_send(1u8), Ok(()));
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(rx.recv(), Ok(1));
+    drop(tx);
+    assert!(rx.recv().is_err());
+}
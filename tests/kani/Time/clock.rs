@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the model of the system clocks used by `Instant` and `SystemTime`.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A rate limiter that allows one request per `interval`.
+struct RateLimiter {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        match self.last {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+#[kani::proof]
+fn check_instant_is_monotonic() {
+    let first = Instant::now();
+    let second = Instant::now();
+    assert!(second >= first);
+    assert_eq!(second.checked_duration_since(first), Some(second - first));
+}
+
+#[kani::proof]
+fn check_rate_limiter() {
+    let mut limiter = RateLimiter { interval: Duration::from_secs(1), last: None };
+    assert!(limiter.try_acquire());
+    let start = limiter.last.unwrap();
+    if limiter.try_acquire() {
+        assert!(limiter.last.unwrap().duration_since(start) >= Duration::from_secs(1));
+    }
+    kani::cover!(limiter.last.unwrap() == start, "request was throttled");
+}
+
+#[kani::proof]
+fn check_system_time() {
+    let now = SystemTime::now();
+    match now.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => assert_eq!(UNIX_EPOCH + elapsed, now),
+        Err(err) => assert_eq!(now + err.duration(), UNIX_EPOCH),
+    }
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `cargo kani check-arbitrary` reports the types whose safety constraints can never
+# be satisfied.
+
+set -u
+
+TMP_DIR=$(mktemp -d)
+cp -r types ${TMP_DIR}
+pushd ${TMP_DIR}/types > /dev/null
+
+cargo kani check-arbitrary
+echo "[TEST] Exit code: $?"
+
+popd > /dev/null
+rm -rf ${TMP_DIR}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Types with satisfiable and unsatisfiable safety constraints.
+
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+pub struct Rating {
+    #[cfg_attr(kani, safety_constraint(*stars >= 1 && *stars <= 5))]
+    pub stars: u8,
+}
+
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+pub struct Empty {
+    #[cfg_attr(kani, safety_constraint(*len > 10 && *len < 5))]
+    pub len: usize,
+}
+
+#[cfg(kani)]
+#[kani::proof]
+fn check_rating() {
+    let rating: Rating = kani::any();
+    assert!(rating.stars > 0);
+}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Types whose `Arbitrary` and `Invariant` implementations are consistent or not.
+
+/// Both derives use the same safety constraint.
+#[cfg_attr(kani, derive(kani::Arbitrary, kani::Invariant))]
+pub struct Rating {
+    #[cfg_attr(kani, safety_constraint(*stars >= 1 && *stars <= 5))]
+    pub stars: u8,
+}
+
+/// `Arbitrary` is implemented by hand and can produce unsafe values.
+#[cfg_attr(kani, derive(kani::Invariant))]
+pub struct Percentage {
+    #[cfg_attr(kani, safety_constraint(*value <= 100))]
+    pub value: u8,
+}
+
+#[cfg(kani)]
+impl kani::Arbitrary for Percentage {
+    fn any() -> Self {
+        Percentage { value: kani::any() }
+    }
+}
+
+/// Types that don't implement `Arbitrary` are not checked.
+#[cfg_attr(kani, derive(kani::Invariant))]
+pub struct Unchecked {
+    pub value: u8,
+}
@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Run two Kani sessions on the same file at the same time and check that they don't interfere
+# with each other.
+
+set -eu
+
+TMP_DIR=$(mktemp -d)
+cp harnesses.rs ${TMP_DIR}
+pushd ${TMP_DIR} > /dev/null
+
+kani harnesses.rs --harness check_first > first.log 2>&1 &
+FIRST=$!
+kani harnesses.rs --harness check_second > second.log 2>&1 &
+SECOND=$!
+
+wait ${FIRST} && echo "[TEST] First session succeeded"
+wait ${SECOND} && echo "[TEST] Second session succeeded"
+grep "Complete -" first.log second.log
+
+# No goto binaries should be left behind by either session.
+if ls *.out 2> /dev/null | grep -v symtab.out; then
+    echo "[TEST] Found leftover goto binaries"
+else
+    echo "[TEST] No leftover goto binaries"
+fi
+
+# Nor the lock that protects the compiler outputs.
+if ls *.lock 2> /dev/null; then
+    echo "[TEST] Found leftover lock files"
+else
+    echo "[TEST] No leftover lock files"
+fi
+
+popd > /dev/null
+rm -rf ${TMP_DIR}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Harnesses used to check that concurrent sessions can verify the same file.
+
+#[kani::proof]
+fn check_first() {
+    let x: u32 = kani::any();
+    kani::assume(x < 100);
+    assert!(x + 1 > x);
+}
+
+#[kani::proof]
+fn check_second() {
+    let y: u8 = kani::any();
+    assert_eq!(y.wrapping_add(0), y);
+}
@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `cargo kani daemon` only removes sockets: it must refuse to listen on a path that
+# is a regular file, and it must remove its socket when it shuts down.
+
+set -u
+
+OUT_DIR=$(mktemp -d)
+SOCKET=${OUT_DIR}/daemon.sock
+pushd lib > /dev/null
+
+echo "fn main() {}" > ${SOCKET}
+cargo kani daemon --socket ${SOCKET} 2>&1 | grep "is not a socket"
+if [ "$(cat ${SOCKET})" == "fn main() {}" ]; then
+    echo "[TEST] The file was not removed"
+fi
+rm ${SOCKET}
+
+cargo kani daemon --socket ${SOCKET} > ${OUT_DIR}/daemon.log 2>&1 &
+DAEMON=$!
+while [ ! -S ${SOCKET} ] && kill -0 ${DAEMON} 2> /dev/null; do
+    sleep 1
+done
+python3 - ${SOCKET} <<'PY'
+import socket, sys
+client = socket.socket(socket.AF_UNIX)
+client.connect(sys.argv[1])
+client.sendall(b'{"command": "shutdown"}\n')
+print(client.makefile().readline().strip())
+PY
+wait ${DAEMON}
+if [ ! -e ${SOCKET} ]; then
+    echo "[TEST] The socket was removed"
+fi
+
+popd > /dev/null
+rm -rf ${OUT_DIR} lib/target
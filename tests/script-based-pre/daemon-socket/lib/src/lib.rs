@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_add(0), x);
+}
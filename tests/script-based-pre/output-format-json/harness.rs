@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A failing harness, whose results are printed with `--output-format json`.
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(x + 1 < 10, "x is too large");
+}
@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that the standard output of `--output-format json` is a single JSON document, with
+# nothing else printed around it, even when Kani prints a warning.
+
+set -u
+
+OUT_DIR=$(mktemp -d)
+
+check_json_stdout() {
+    python3 - ${OUT_DIR}/stdout.json <<'PY'
+import json, sys
+with open(sys.argv[1]) as f:
+    results = json.load(f)
+print("[TEST] Standard output is a JSON document")
+print(f"[TEST] Status: {results['status']}")
+for harness in results["harnesses"]:
+    for check in harness["checks"]:
+        if check["status"] == "failure":
+            print(f"[TEST] Failed check: {check['description']}")
+PY
+}
+
+kani harness.rs --output-format json --target-dir ${OUT_DIR} > ${OUT_DIR}/stdout.json 2> /dev/null
+echo "[TEST] Exit status: $?"
+check_json_stdout
+
+# `--mir-linker` is obsolete, so Kani warns about it.
+kani harness.rs --output-format json --mir-linker --target-dir ${OUT_DIR} \
+    > ${OUT_DIR}/stdout.json 2> ${OUT_DIR}/stderr.txt
+echo "[TEST] Exit status with a warning: $?"
+if grep -q "The \`--mir-linker\` option is obsolete" ${OUT_DIR}/stderr.txt; then
+    echo "[TEST] The warning is printed to standard error"
+fi
+check_json_stdout
+
+rm -rf ${OUT_DIR}
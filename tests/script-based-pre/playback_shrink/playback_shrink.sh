@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Test that `playback --shrink` shrinks the concrete values of a failing test
+set -o pipefail
+set -o nounset
+
+echo "[TEST] Run test without shrinking..."
+kani playback -Z concrete-playback shrink.rs -- kani_concrete_playback
+
+echo "[TEST] Run test with shrinking..."
+kani playback -Z concrete-playback --shrink shrink.rs -- kani_concrete_playback
+
+echo "[TEST] Run test with two kani::assert with shrinking..."
+kani playback -Z concrete-playback --shrink two_asserts.rs -- kani_concrete_playback
+
+# Cleanup
+rm -f kani_concrete_playback
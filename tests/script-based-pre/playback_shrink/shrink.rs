@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that shrinking reduces numbers toward zero, enum variants toward the first one, and
+// collections toward empty, while the harness keeps failing at the same assertion.
+
+#[derive(kani::Arbitrary, Debug)]
+enum Op {
+    Nop,
+    Push(u8),
+    Pop,
+}
+
+#[kani::proof]
+pub fn harness() {
+    let offset: i32 = kani::any();
+    let size: u8 = kani::any();
+    let ops = kani::vec::any_vec::<u8, 2>();
+    let op: Op = kani::any();
+    assert!(offset >= -3 || size <= 100, "Found {} {} {:?} {:?}", offset, size, ops, op);
+}
+
+#[test]
+fn kani_concrete_playback_harness() {
+    let concrete_vals: Vec<Vec<u8>> = vec![
+        // -1000
+        vec![24, 252, 255, 255],
+        // 250
+        vec![250],
+        // 2
+        vec![2, 0, 0, 0, 0, 0, 0, 0],
+        vec![7, 9],
+        // 1
+        vec![1, 0, 0, 0],
+        // 42
+        vec![42],
+    ];
+    kani::concrete_playback_run(concrete_vals, harness);
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that shrinking keeps the harness failing at the same `kani::assert`, instead of moving to
+// another check that fails for simpler values.
+
+#[kani::proof]
+pub fn harness() {
+    let x: u8 = kani::any();
+    kani::assert(x < 10 || x > 200, "x is small or large");
+    kani::assert(x != 0, "x is not zero");
+}
+
+#[test]
+fn kani_concrete_playback_harness() {
+    let concrete_vals: Vec<Vec<u8>> = vec![
+        // 50
+        vec![50],
+    ];
+    kani::concrete_playback_run(concrete_vals, harness);
+}
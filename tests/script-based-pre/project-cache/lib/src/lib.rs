@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! The value is read from a file outside of `src`, so only the dep-info file of the crate tells
+//! the project cache about it.
+const VALUE: u8 = include!("../value.txt");
+
+#[kani::proof]
+fn check_value() {
+    let unused = 0;
+    assert_eq!(VALUE, 1);
+}
@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks when the project cached with `-Z project-cache` is reused:
+# - The same command loads the project from the cache, and shows the warnings again.
+# - Changing a file that is only read with `include!` invalidates the cache.
+# - Changing an environment variable that configures the build invalidates the cache.
+# - `--force-build` bypasses the cache.
+
+MANIFEST=lib/Cargo.toml
+OUT_DIR=target
+
+# Expects the name of the log file and, optionally, extra environment variables
+function check_kani {
+    local log_file="${OUT_DIR}/$1"
+    env $2 cargo kani --manifest-path "${MANIFEST}" --target-dir "${OUT_DIR}" --verbose \
+        -Z project-cache ${FORCE_BUILD} > "${log_file}" 2>&1
+
+    local cached=$(grep -c "Using the cached build" ${log_file})
+    echo "${log_file}:Loaded ${cached} cached projects"
+    local warnings=$(grep -c "unused variable: \`unused\`" ${log_file})
+    echo "${log_file}:Found ${warnings} warnings"
+    grep "successfully verified harnesses" -H ${log_file} || true
+}
+
+# Ensure output folder is clean
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+
+echo "Initial build"
+check_kani initial.log
+
+echo "Re-execute the same command"
+check_kani same.log
+
+echo "Change the included file"
+echo "2" > lib/value.txt
+check_kani include.log
+
+echo "Change the build environment"
+check_kani env.log "RUSTFLAGS=--cfg=project_cache"
+
+echo "Force the build"
+FORCE_BUILD=--force-build check_kani force.log "RUSTFLAGS=--cfg=project_cache"
+
+# Try to leave a clean output folder at the end
+rm -rf ${OUT_DIR}
+echo "1" > lib/value.txt
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+mod parser {
+    pub fn parse(input: u8) -> u8 {
+        assert!(input < 100, "input too large");
+        input
+    }
+
+    #[kani::proof]
+    fn check_parse() {
+        parse(kani::any());
+    }
+}
+
+mod lexer {
+    pub fn lex(input: u8) -> u8 {
+        input + 1
+    }
+
+    #[kani::proof]
+    fn check_lex() {
+        lex(kani::any());
+    }
+}
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `kani results query` can inspect the results of the last run without running
+# verification again, from the directory where `kani` ran.
+
+set -eu
+
+TMP_DIR=$(mktemp -d)
+mkdir ${TMP_DIR}/src
+cp harnesses.rs ${TMP_DIR}/src
+pushd ${TMP_DIR} > /dev/null
+
+kani src/harnesses.rs > /dev/null 2>&1 || true
+
+echo "[TEST] Failures in module parser:"
+kani results query --module parser --status failure
+echo "[TEST] Overflow checks:"
+kani results query --class overflow --format json | grep '"harness"'
+
+popd > /dev/null
+rm -rf ${TMP_DIR}
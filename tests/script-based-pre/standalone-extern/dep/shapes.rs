@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A dependency of `main.rs`, which is built before the crate that uses it.
+
+pub struct Square {
+    pub side: u16,
+}
+
+impl Square {
+    pub fn area(&self) -> u32 {
+        self.side as u32 * self.side as u32
+    }
+}
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `kani` can verify a single file that depends on a crate built beforehand, which is
+# given with `--extern`.
+
+set -eu
+
+TMP_DIR=$(mktemp -d)
+cp -r main.rs dep ${TMP_DIR}
+pushd ${TMP_DIR} > /dev/null
+
+echo "[TEST] Build the dependency:"
+kani dep/shapes.rs --only-codegen --keep-temps > /dev/null
+ls dep/libshapes.rlib
+
+echo "[TEST] Verify the crate with the dependency:"
+kani main.rs --extern shapes=dep/libshapes.rlib
+
+popd > /dev/null
+rm -rf ${TMP_DIR}
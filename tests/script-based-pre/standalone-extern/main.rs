@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Uses a dependency given with `--extern shapes=<PATH>`.
+
+extern crate shapes;
+
+use shapes::Square;
+
+#[kani::proof]
+fn check_area() {
+    let square = Square { side: kani::any() };
+    assert!(square.area() >= square.side as u32);
+}
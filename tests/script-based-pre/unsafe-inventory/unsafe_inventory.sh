@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `cargo kani unsafe-inventory` lists every unsafe block and function, whether a
+# harness reaches it, and the status of its pointer checks.
+
+set -u
+
+TMP_DIR=$(mktemp -d)
+cp -r unsafe_pkg ${TMP_DIR}
+pushd ${TMP_DIR}/unsafe_pkg > /dev/null
+
+cargo kani unsafe-inventory
+echo "[TEST] Exit code: $?"
+
+popd > /dev/null
+rm -rf ${TMP_DIR}
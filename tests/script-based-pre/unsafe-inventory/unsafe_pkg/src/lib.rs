@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+pub fn first(bytes: &[u8]) -> u8 {
+    unsafe { read(bytes.as_ptr()) }
+}
+
+pub fn at(bytes: &[u8], idx: usize) -> u8 {
+    unsafe { *bytes.as_ptr().add(idx) }
+}
+
+pub fn increment(ptr: *mut u32) {
+    unsafe { *ptr += 1 }
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    #[kani::proof]
+    fn check_first() {
+        assert_eq!(first(&[1, 2]), 1);
+    }
+
+    #[kani::proof]
+    fn check_at() {
+        let idx: usize = kani::any();
+        kani::assume(idx < 3);
+        // Reading at index 2 is out of bounds.
+        at(&[1, 2], idx);
+    }
+}
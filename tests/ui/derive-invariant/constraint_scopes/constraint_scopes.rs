@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints can use lowercase constants and functions, variables bound by
+//! closures, and reflexive comparisons of floats without errors or warnings.
+
+#![allow(non_upper_case_globals)]
+
+const limit: u8 = 100;
+
+const fn max_len() -> usize {
+    4
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Sample {
+    /// `NaN` is the only value that is not equal to itself.
+    #[safety_constraint(*ratio == *ratio)]
+    ratio: f32,
+    #[safety_constraint(*level < limit)]
+    level: u8,
+    #[safety_constraint(data.iter().all(|v| *v < limit) && data.len() <= max_len())]
+    data: [u8; 4],
+}
+
+#[kani::proof]
+fn check_sample() {
+    let sample: Sample = kani::any();
+    assert!(!sample.ratio.is_nan());
+    assert!(sample.level < limit);
+    assert!(sample.data.iter().all(|v| *v < limit));
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the derived `Invariant` for enums checks the fields of the active variant.
+
+#[derive(kani::Invariant)]
+struct Even {
+    #[safety_constraint(*val % 2 == 0)]
+    val: u32,
+}
+
+#[derive(kani::Invariant)]
+enum Shape {
+    Empty,
+    Line(Even),
+    Rect { width: Even, height: u32 },
+}
+
+#[kani::proof]
+fn check_enum_invariant() {
+    let val: u32 = kani::any();
+    let shape = match kani::any::<u8>() {
+        0 => Shape::Empty,
+        1 => Shape::Line(Even { val }),
+        _ => Shape::Rect { width: Even { val }, height: kani::any() },
+    };
+    let expected = matches!(shape, Shape::Empty) || val % 2 == 0;
+    assert_eq!(kani::Invariant::is_safe(&shape), expected);
+}
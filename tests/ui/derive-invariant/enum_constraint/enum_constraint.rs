@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the safety constraints of enum variants restrict the values created by the derived
+//! `Arbitrary`, and are checked by the derived `Invariant` for the active variant only.
+
+extern crate kani;
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+enum Speed {
+    Stopped,
+    Kmh(#[safety_constraint(*0 <= 300)] u16),
+    Moving {
+        #[safety_constraint(*kmh > 0 && *kmh <= *limit)]
+        kmh: u16,
+        limit: u16,
+    },
+}
+
+#[kani::proof]
+fn check_arbitrary_constraint() {
+    let speed: Speed = kani::any();
+    match speed {
+        Speed::Stopped => {}
+        Speed::Kmh(kmh) => {
+            assert!(kmh <= 300);
+            kani::cover!(kmh == 300);
+        }
+        Speed::Moving { kmh, limit } => {
+            assert!(kmh > 0 && kmh <= limit);
+            kani::cover!(kmh == limit);
+        }
+    }
+}
+
+#[kani::proof]
+fn check_invariant_constraint() {
+    let val: u16 = kani::any();
+    let speed = match kani::any::<u8>() {
+        0 => Speed::Stopped,
+        1 => Speed::Kmh(val),
+        _ => Speed::Moving { kmh: val, limit: 100 },
+    };
+    let expected = match speed {
+        Speed::Stopped => true,
+        Speed::Kmh(_) => val <= 300,
+        Speed::Moving { .. } => val > 0 && val <= 100,
+    };
+    assert_eq!(kani::Invariant::is_safe(&speed), expected);
+}
@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the safety constraints of struct fields are assumed by the derived `Arbitrary`
+//! and checked by the derived `Invariant`. The unnamed fields of a tuple struct are referred to
+//! by their index.
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Range {
+    #[safety_constraint(*start <= *end)]
+    start: u8,
+    #[safety_constraint(*end < 100)]
+    end: u8,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Ordered(#[safety_constraint(*0 < *1)] u8, u8);
+
+#[kani::proof]
+fn check_arbitrary_range() {
+    let range: Range = kani::any();
+    assert!(range.start <= range.end);
+    assert!(range.end < 100);
+    assert!(kani::Invariant::is_safe(&range));
+}
+
+#[kani::proof]
+fn check_invalid_range() {
+    let range = Range { start: kani::any(), end: kani::any() };
+    kani::cover!(!kani::Invariant::is_safe(&range));
+    kani::cover!(kani::Invariant::is_safe(&range));
+}
+
+#[kani::proof]
+fn check_arbitrary_tuple() {
+    let ordered: Ordered = kani::any();
+    assert!(ordered.0 < ordered.1);
+    assert!(kani::Invariant::is_safe(&ordered));
+    assert!(!kani::Invariant::is_safe(&Ordered(1, 1)));
+}
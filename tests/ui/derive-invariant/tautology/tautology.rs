@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that safety constraints that are always true produce a warning.
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Config {
+    #[safety_constraint(true)]
+    retries: u8,
+    #[safety_constraint(*timeout == *timeout)]
+    timeout: u32,
+}
+
+#[kani::proof]
+fn check_config() {
+    let config: Config = kani::any();
+    assert!(kani::Invariant::is_safe(&config));
+}
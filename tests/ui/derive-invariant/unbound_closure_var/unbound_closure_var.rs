@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that a variable bound by a closure of a safety constraint cannot be used outside of the
+//! closure.
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+struct Data {
+    #[safety_constraint(values.iter().any(|v| *v > 0) && *v < 10)]
+    values: [u8; 2],
+}
+
+#[kani::proof]
+fn dead_harness() {
+    let data: Data = kani::any();
+    assert!(kani::Invariant::is_safe(&data));
+}
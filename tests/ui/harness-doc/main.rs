@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --verbose
+//
+//! Checks that the documentation of a harness is printed when we pass `--verbose`
+
+/// Adding zero to a value never changes it.
+///
+/// This line is part of the documentation but it is not the summary.
+#[kani::proof]
+fn check_add_zero() {
+    let x: u32 = kani::any();
+    assert_eq!(x + 0, x);
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the `unwind` and `solver` options of `#[kani::proof]` are honored per harness.
+
+#[kani::proof(unwind = 4, solver = "cadical")]
+fn check_options() {
+    let mut count = 0;
+    for _ in 0..3 {
+        count += 1;
+    }
+    assert_eq!(count, 3);
+}
+
+#[kani::proof(unwind = 2, solver = minisat)]
+fn check_insufficient_unwind() {
+    let mut count = 0;
+    for _ in 0..3 {
+        count += 1;
+    }
+    assert_eq!(count, 3);
+}
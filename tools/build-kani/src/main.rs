@@ -13,6 +13,7 @@
 use crate::sysroot::{build_bin, build_lib, kani_playback_lib, kani_sysroot_lib};
 use anyhow::{bail, Result};
 use clap::Parser;
+use std::env::consts::EXE_SUFFIX;
 use std::{ffi::OsString, path::Path, process::Command};
 
 fn main() -> Result<()> {
@@ -78,8 +79,8 @@ fn bundle_kani(dir: &Path) -> Result<()> {
 
     // 1. Kani binaries
     let release = Path::new("./target/release");
-    cp(&release.join("kani-driver"), &bin)?;
-    cp(&release.join("kani-compiler"), &bin)?;
+    cp(&release.join(format!("kani-driver{EXE_SUFFIX}")), &bin)?;
+    cp(&release.join(format!("kani-compiler{EXE_SUFFIX}")), &bin)?;
 
     // 2. Kani scripts
     let scripts = dir.join("scripts");
@@ -14,6 +14,7 @@
 use crate::{cp, AutoRun};
 use anyhow::{bail, format_err, Result};
 use cargo_metadata::{Artifact, Message};
+use std::env::consts::EXE_SUFFIX;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::BufReader;
@@ -39,6 +40,11 @@ fn lib_extension() -> &'static str {
     "dylib"
 }
 
+#[cfg(target_os = "windows")]
+fn lib_extension() -> &'static str {
+    "dll"
+}
+
 /// Returns the path to Kani sysroot. I.e.: folder where we store pre-compiled binaries and
 /// libraries.
 pub fn kani_sysroot() -> PathBuf {
@@ -64,7 +70,7 @@ fn kani_sysroot_bin() -> PathBuf {
 /// - The `lib/` folder contains the sysroot for verification.
 /// - The `lib-playback/` folder contains the sysroot used for playback.
 pub fn build_lib(bin_folder: &Path) -> Result<()> {
-    let compiler_path = bin_folder.join("kani-compiler");
+    let compiler_path = bin_folder.join(format!("kani-compiler{}", EXE_SUFFIX));
     build_verification_lib(&compiler_path)?;
     build_playback_lib(&compiler_path)
 }